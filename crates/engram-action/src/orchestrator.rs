@@ -16,6 +16,7 @@ pub struct Orchestrator {
     task_store: Arc<TaskStore>,
     config: ActionConfig,
     event_tx: Option<tokio::sync::broadcast::Sender<serde_json::Value>>,
+    event_log: Option<Arc<engram_storage::Database>>,
 }
 
 impl Orchestrator {
@@ -26,6 +27,7 @@ impl Orchestrator {
             task_store,
             config,
             event_tx: None,
+            event_log: None,
         }
     }
 
@@ -35,10 +37,26 @@ impl Orchestrator {
         self
     }
 
+    /// Append published domain events to the durable event log in `database`
+    /// before broadcasting them.
+    pub fn with_event_log(mut self, database: Arc<engram_storage::Database>) -> Self {
+        self.event_log = Some(database);
+        self
+    }
+
     /// Publish a domain event if an event sender is configured.
     fn publish_event(&self, event: engram_core::events::DomainEvent) {
         if let Some(ref tx) = self.event_tx {
-            let _ = tx.send(event.to_json());
+            let mut payload = event.to_json();
+            if let Some(ref database) = self.event_log {
+                match database
+                    .with_conn(|conn| engram_storage::append_event_log_json(conn, &payload))
+                {
+                    Ok(log_id) => payload[engram_storage::LOG_ID_FIELD] = log_id.into(),
+                    Err(e) => tracing::warn!(error = %e, "Failed to log action event"),
+                }
+            }
+            let _ = tx.send(payload);
         }
     }
 
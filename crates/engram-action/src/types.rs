@@ -175,6 +175,24 @@ pub struct Task {
     pub source_chunk_id: Option<Uuid>,
 }
 
+impl Task {
+    /// The task as a row of the SQLite `tasks` table.
+    pub fn to_row(&self) -> engram_storage::TaskRow {
+        engram_storage::TaskRow {
+            id: self.id.to_string(),
+            title: self.title.clone(),
+            status: self.status.to_string(),
+            intent_id: self.intent_id.map(|id| id.to_string()),
+            action_type: self.action_type.to_string(),
+            action_payload: self.action_payload.clone(),
+            scheduled_at: self.scheduled_at.map(|t| t.0.to_string()),
+            completed_at: self.completed_at.map(|t| t.0.to_string()),
+            created_at: self.created_at.0.to_string(),
+            source_chunk_id: self.source_chunk_id.map(|id| id.to_string()),
+        }
+    }
+}
+
 /// Payload passed to action handlers.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActionPayload {
@@ -2,6 +2,14 @@
 //!
 //! Provides token generation, persistence, and middleware for validating
 //! `Authorization: Bearer <token>` headers on protected endpoints.
+//!
+//! Two kinds of token are accepted: the primary token from `.api_token`,
+//! which has every scope, and named tokens stored hashed in the
+//! `api_tokens` table, which carry an explicit set of [`Scope`]s and an
+//! optional expiry. Routes declare the scope they need with the
+//! `require_*` middleware in this module.
+
+use std::fmt;
 
 use axum::extract::{Request, State};
 use axum::http::StatusCode;
@@ -9,9 +17,191 @@ use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
+use engram_core::error::EngramError;
+use engram_storage::{ApiTokenRow, Database};
+
+use crate::error::ApiError;
 use crate::state::AppState;
 
+/// A permission that can be granted to an API token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Scope {
+    /// Search, browse and read chat history over captured data.
+    #[serde(rename = "search:read")]
+    SearchRead,
+    /// Create, change, delete and run saved searches.
+    #[serde(rename = "search:write")]
+    SearchWrite,
+    /// Ask chat questions and change chat history: sessions, voice queries
+    /// and answer feedback.
+    #[serde(rename = "chat:write")]
+    ChatWrite,
+    /// Ingest new content and control dictation.
+    #[serde(rename = "ingest:write")]
+    IngestWrite,
+    /// Create, update, approve and dismiss action engine tasks.
+    #[serde(rename = "actions:approve")]
+    ActionsApprove,
+    /// Configuration, purging and exports. Implies every other scope.
+    #[serde(rename = "admin")]
+    Admin,
+}
+
+impl Scope {
+    /// All scopes, in display order.
+    pub const ALL: [Scope; 6] = [
+        Scope::SearchRead,
+        Scope::SearchWrite,
+        Scope::ChatWrite,
+        Scope::IngestWrite,
+        Scope::ActionsApprove,
+        Scope::Admin,
+    ];
+
+    /// The wire name of this scope (e.g. `search:read`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::SearchRead => "search:read",
+            Scope::SearchWrite => "search:write",
+            Scope::ChatWrite => "chat:write",
+            Scope::IngestWrite => "ingest:write",
+            Scope::ActionsApprove => "actions:approve",
+            Scope::Admin => "admin",
+        }
+    }
+
+    /// Parse a scope from its wire name.
+    pub fn parse(s: &str) -> Option<Scope> {
+        Scope::ALL.into_iter().find(|scope| scope.as_str() == s)
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The authenticated caller, attached to request extensions by [`require_auth`].
+#[derive(Debug, Clone)]
+pub struct AuthContext {
+    /// Token name (`primary` for the `.api_token` token).
+    pub token_name: String,
+    pub scopes: Vec<Scope>,
+}
+
+impl AuthContext {
+    /// Whether the caller holds `scope`, either directly or via `admin`.
+    pub fn allows(&self, scope: Scope) -> bool {
+        self.scopes.contains(&Scope::Admin) || self.scopes.contains(&scope)
+    }
+}
+
+/// A freshly minted token. The plaintext is only available here.
+#[derive(Debug, Clone)]
+pub struct MintedToken {
+    pub token: String,
+    pub record: ApiTokenRow,
+}
+
+/// Hex-encoded SHA-256 of a bearer token, as stored in `api_tokens`.
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Create a named token with the given scopes and store its hash.
+///
+/// `expires_at` is a Unix epoch (seconds); `None` means the token never expires.
+pub fn mint_token(
+    db: &Database,
+    name: &str,
+    scopes: &[Scope],
+    expires_at: Option<i64>,
+) -> Result<MintedToken, EngramError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(EngramError::Config(
+            "Token name must not be empty".to_string(),
+        ));
+    }
+    if scopes.is_empty() {
+        return Err(EngramError::Config(
+            "At least one scope is required".to_string(),
+        ));
+    }
+
+    let mut scope_names: Vec<String> = Vec::new();
+    for scope in scopes {
+        if !scope_names.iter().any(|s| s == scope.as_str()) {
+            scope_names.push(scope.as_str().to_string());
+        }
+    }
+
+    let token = generate_token();
+    let record = ApiTokenRow {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        token_hash: hash_token(&token),
+        scopes: scope_names,
+        expires_at,
+        last_used_at: None,
+        revoked: false,
+        created_at: chrono::Utc::now().timestamp(),
+    };
+    db.with_conn(|conn| engram_storage::store_api_token(conn, &record))?;
+
+    Ok(MintedToken { token, record })
+}
+
+/// Resolve a presented bearer token to the caller's identity.
+///
+/// Returns the rejection message on failure.
+fn authenticate(state: &AppState, token: &str) -> Result<AuthContext, &'static str> {
+    if token == state.api_token {
+        return Ok(AuthContext {
+            token_name: "primary".to_string(),
+            scopes: vec![Scope::Admin],
+        });
+    }
+
+    let hash = hash_token(token);
+    let record = state
+        .database
+        .with_conn(|conn| engram_storage::get_api_token_by_hash(conn, &hash))
+        .map_err(|e| {
+            tracing::warn!(error = %e, "API token lookup failed");
+            "Invalid bearer token"
+        })?
+        .ok_or("Invalid bearer token")?;
+
+    if record.revoked {
+        return Err("Token has been revoked");
+    }
+    let now = chrono::Utc::now().timestamp();
+    if record.expires_at.is_some_and(|exp| exp <= now) {
+        return Err("Token has expired");
+    }
+
+    if let Err(e) = state
+        .database
+        .with_conn(|conn| engram_storage::touch_api_token(conn, &record.id, now))
+    {
+        tracing::warn!(error = %e, token = %record.name, "Failed to record API token use");
+    }
+
+    Ok(AuthContext {
+        token_name: record.name,
+        scopes: record
+            .scopes
+            .iter()
+            .filter_map(|s| Scope::parse(s))
+            .collect(),
+    })
+}
+
 /// Generate a random 32-character hex token.
 pub fn generate_token() -> String {
     let mut rng = rand::rng();
@@ -54,9 +244,11 @@ pub fn load_or_generate_token(token_path: &std::path::Path) -> String {
 
 /// Middleware that validates Bearer token authentication.
 ///
-/// Extracts the token from `Authorization: Bearer <token>` and compares
-/// against `AppState.api_token`. Returns 401 if missing or invalid.
-pub async fn require_auth(State(state): State<AppState>, req: Request, next: Next) -> Response {
+/// Extracts the token from `Authorization: Bearer <token>` and checks it
+/// against `AppState.api_token` and the `api_tokens` table. On success the
+/// caller's [`AuthContext`] is attached to the request. Returns 401 if the
+/// token is missing, unknown, revoked or expired.
+pub async fn require_auth(State(state): State<AppState>, mut req: Request, next: Next) -> Response {
     let auth_header = req.headers().get("authorization");
 
     match auth_header {
@@ -75,17 +267,22 @@ pub async fn require_auth(State(state): State<AppState>, req: Request, next: Nex
                 }
             };
 
-            if let Some(token) = value_str.strip_prefix("Bearer ") {
-                if token == state.api_token {
-                    return next.run(req).await;
-                }
-            }
+            let message = match value_str.strip_prefix("Bearer ") {
+                Some(token) => match authenticate(&state, token) {
+                    Ok(ctx) => {
+                        req.extensions_mut().insert(ctx);
+                        return next.run(req).await;
+                    }
+                    Err(message) => message,
+                },
+                None => "Invalid bearer token",
+            };
 
             (
                 StatusCode::UNAUTHORIZED,
                 Json(serde_json::json!({
                     "error": "unauthorized",
-                    "message": "Invalid bearer token"
+                    "message": message
                 })),
             )
                 .into_response()
@@ -100,3 +297,46 @@ pub async fn require_auth(State(state): State<AppState>, req: Request, next: Nex
             .into_response(),
     }
 }
+
+/// Reject the request with 403 unless the authenticated caller holds `scope`.
+async fn check_scope(scope: Scope, req: Request, next: Next) -> Response {
+    let allowed = req
+        .extensions()
+        .get::<AuthContext>()
+        .is_some_and(|ctx| ctx.allows(scope));
+    if allowed {
+        next.run(req).await
+    } else {
+        ApiError::Forbidden(format!("Token lacks required scope '{}'", scope)).into_response()
+    }
+}
+
+/// Middleware requiring the `search:read` scope.
+pub async fn require_search_read(req: Request, next: Next) -> Response {
+    check_scope(Scope::SearchRead, req, next).await
+}
+
+/// Middleware requiring the `search:write` scope.
+pub async fn require_search_write(req: Request, next: Next) -> Response {
+    check_scope(Scope::SearchWrite, req, next).await
+}
+
+/// Middleware requiring the `chat:write` scope.
+pub async fn require_chat_write(req: Request, next: Next) -> Response {
+    check_scope(Scope::ChatWrite, req, next).await
+}
+
+/// Middleware requiring the `ingest:write` scope.
+pub async fn require_ingest_write(req: Request, next: Next) -> Response {
+    check_scope(Scope::IngestWrite, req, next).await
+}
+
+/// Middleware requiring the `actions:approve` scope.
+pub async fn require_actions_approve(req: Request, next: Next) -> Response {
+    check_scope(Scope::ActionsApprove, req, next).await
+}
+
+/// Middleware requiring the `admin` scope.
+pub async fn require_admin(req: Request, next: Next) -> Response {
+    check_scope(Scope::Admin, req, next).await
+}
@@ -0,0 +1,197 @@
+//! Hot-reload of the config file.
+//!
+//! Polls `AppState.config_path` for modification and applies a changed file
+//! through `AppState::apply_config`, so edits made on disk reach running
+//! subsystems the same way `PUT /config` does. A file that fails to parse or
+//! validate is logged and ignored; the running configuration is kept.
+
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use tracing::{info, warn};
+
+use engram_core::config::{ApplyMode, ConfigChange, EngramConfig};
+use engram_core::error::EngramError;
+
+use crate::state::AppState;
+
+/// Load the config file, apply `overrides` and apply the result as
+/// `AppState::apply_config` does.
+///
+/// `overrides` re-applies settings that take precedence over the file
+/// (CLI flags, environment) so a reload does not undo them. The file is
+/// read under the config lock, so a reload cannot apply a file that a
+/// concurrent `PUT /config` has since rewritten.
+pub fn reload_config_file(
+    state: &AppState,
+    overrides: &dyn Fn(&mut EngramConfig),
+) -> Result<Vec<ConfigChange>, EngramError> {
+    state
+        .update_config(
+            |_| {
+                let mut config = EngramConfig::load(&state.config_path)?;
+                overrides(&mut config);
+                Ok(config)
+            },
+            false,
+        )
+        .map(|(_, changes)| changes)
+}
+
+/// Watch the config file and reload it whenever its modification time
+/// changes, checking every `poll_interval`.
+pub fn watch_config_file<F>(state: AppState, poll_interval: Duration, overrides: F)
+where
+    F: Fn(&mut EngramConfig) + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut last_modified = modified_at(&state.config_path);
+        let mut interval = tokio::time::interval(poll_interval);
+        loop {
+            interval.tick().await;
+            let current = modified_at(&state.config_path);
+            if current.is_none() || current == last_modified {
+                continue;
+            }
+            last_modified = current;
+
+            match reload_config_file(&state, &overrides) {
+                Ok(changes) => log_changes(&changes),
+                Err(e) => warn!(
+                    error = %e,
+                    path = %state.config_path.display(),
+                    "Config reload rejected; keeping the running configuration"
+                ),
+            }
+        }
+    });
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn log_changes(changes: &[ConfigChange]) {
+    for change in changes {
+        match change.apply {
+            ApplyMode::Live => info!(field = %change.field, "Config reloaded: applied live"),
+            ApplyMode::Restart => {
+                warn!(field = %change.field, "Config reloaded: restart required to apply")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use engram_core::config::SafetyConfig;
+    use engram_storage::Database;
+    use engram_vector::embedding::MockEmbedding;
+    use engram_vector::{EngramPipeline, VectorIndex};
+
+    fn make_state(config_path: std::path::PathBuf) -> AppState {
+        let index = Arc::new(VectorIndex::new());
+        let pipeline = EngramPipeline::new(
+            Arc::clone(&index),
+            MockEmbedding::new(),
+            SafetyConfig::default(),
+            0.95,
+        );
+        AppState::with_config_path(
+            EngramConfig::default(),
+            index,
+            Database::in_memory().unwrap(),
+            pipeline,
+            config_path,
+        )
+    }
+
+    fn temp_config(content: &str) -> std::path::PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("engram-config-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_reload_applies_valid_file() {
+        let path = temp_config("[screen]\nfps = 2.0\n\n[general]\nport = 4040\n");
+        let state = make_state(path.clone());
+        let mut config_rx = state.subscribe_config();
+
+        let changes = reload_config_file(&state, &|_| {}).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let apply = |field: &str| changes.iter().find(|c| c.field == field).unwrap().apply;
+        assert_eq!(changes.len(), 2);
+        assert_eq!(apply("screen.fps"), ApplyMode::Live);
+        assert_eq!(apply("general.port"), ApplyMode::Restart);
+        assert!(config_rx.has_changed().unwrap());
+        assert_eq!(config_rx.borrow_and_update().screen.fps, 2.0);
+        assert_eq!(state.config.lock().unwrap().general.port, 4040);
+    }
+
+    #[test]
+    fn test_reload_rejects_invalid_file() {
+        let path = temp_config("[screen]\nfps = 0.0\n");
+        let state = make_state(path.clone());
+        let config_rx = state.subscribe_config();
+
+        let result = reload_config_file(&state, &|_| {});
+        std::fs::remove_file(&path).ok();
+
+        assert!(result.is_err());
+        assert!(!config_rx.has_changed().unwrap());
+        assert_eq!(state.config.lock().unwrap().screen.fps, 1.0);
+    }
+
+    #[test]
+    fn test_reload_keeps_overrides() {
+        let path = temp_config("[general]\nport = 4040\n");
+        let state = make_state(path.clone());
+
+        let changes = reload_config_file(&state, &|c| c.general.port = 3030).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert!(changes.is_empty());
+        assert_eq!(state.config.lock().unwrap().general.port, 3030);
+    }
+
+    #[test]
+    fn test_concurrent_updates_each_build_on_the_last() {
+        let path = temp_config("");
+        let state = make_state(path.clone());
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let state = state.clone();
+                std::thread::spawn(move || {
+                    state
+                        .update_config(
+                            |current| {
+                                let mut config = current.clone();
+                                config.general.stream_replay_events += 1;
+                                Ok(config)
+                            },
+                            true,
+                        )
+                        .unwrap()
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        let saved = EngramConfig::load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(
+            state.config.lock().unwrap().general.stream_replay_events,
+            1008
+        );
+        assert_eq!(saved.general.stream_replay_events, 1008);
+    }
+}
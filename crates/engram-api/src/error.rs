@@ -9,7 +9,7 @@ use axum::Json;
 use serde::Serialize;
 
 /// JSON error response body.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct ErrorBody {
     /// Machine-readable error code (e.g., "bad_request", "not_found").
     pub error: String,
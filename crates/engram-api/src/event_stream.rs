@@ -0,0 +1,481 @@
+//! Typed, filterable and resumable SSE event stream.
+//!
+//! The durable event log is the stream: each event log entry is sent to
+//! `/stream` subscribers with its log ID as the SSE event ID. A message on
+//! `AppState.event_tx` only wakes the recorder, which then fans out every
+//! entry logged after the last one it sent, so a recorder that lags behind
+//! the channel, or a publish that never reached it, leaves no gap. Events
+//! are appended to the log by whoever publishes them.
+//!
+//! Clients that reconnect with `Last-Event-ID` are replayed the entries
+//! they missed from the event log, and subscribers that fall behind the
+//! live channel catch up the same way. Replay reaches back over the newest
+//! `replay_capacity` entries; a client whose last event is older than that
+//! first receives a `reset` event naming the oldest ID it will get.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use axum::response::sse::Event;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc};
+use tracing::warn;
+
+use engram_core::error::EngramError;
+use engram_storage::Database;
+
+/// Capacity of the live fan-out channel.
+const LIVE_CHANNEL_CAPACITY: usize = 256;
+
+/// Payload keys that carry an application name, checked by the `app` filter.
+const APP_FIELDS: &[&str] = &["app_name", "target_app", "source_app"];
+
+/// Event log entries fanned out per outbox read.
+const OUTBOX_BATCH: u32 = 256;
+
+/// SSE event name telling a client that events it missed are no longer
+/// available for replay.
+pub const RESET_EVENT: &str = "reset";
+
+pub use engram_storage::LOG_ID_FIELD;
+
+/// A domain event with its stream ID.
+#[derive(Debug, Clone)]
+pub struct StreamEvent {
+    /// Event log ID.
+    pub id: i64,
+    /// Event name from `DomainEvent::event_name()`.
+    pub name: String,
+    /// The event JSON as produced by `DomainEvent::to_json()`.
+    pub payload: serde_json::Value,
+}
+
+impl StreamEvent {
+    fn from_log(row: engram_storage::EventLogRow) -> Self {
+        let mut payload: serde_json::Value = serde_json::from_str(&row.payload).unwrap_or_default();
+        payload[LOG_ID_FIELD] = row.id.into();
+        Self {
+            id: row.id,
+            name: row.event_type,
+            payload,
+        }
+    }
+
+    fn to_sse(&self) -> Event {
+        Event::default()
+            .id(self.id.to_string())
+            .event(&self.name)
+            .data(self.payload.to_string())
+    }
+}
+
+/// Events replayed to a client resuming after a given event ID.
+#[derive(Debug, Clone, Default)]
+pub struct Replay {
+    /// Set to the oldest ID still available when events after the
+    /// requested ID have already left the replay window.
+    pub reset_to: Option<i64>,
+    /// Missed events, oldest first.
+    pub events: Vec<StreamEvent>,
+}
+
+/// The `reset` event sent before a replay with a gap.
+fn reset_sse(last_event_id: i64, oldest_id: i64) -> Event {
+    Event::default().event(RESET_EVENT).data(
+        serde_json::json!({ "last_event_id": last_event_id, "oldest_id": oldest_id }).to_string(),
+    )
+}
+
+/// Server-side filter applied to a `/stream` subscription.
+#[derive(Debug, Clone, Default)]
+pub struct StreamFilter {
+    /// Event names to include; `None` includes every event.
+    pub events: Option<HashSet<String>>,
+    /// Only include events whose payload names this application.
+    pub app: Option<String>,
+}
+
+impl StreamFilter {
+    /// Build a filter from the comma-separated `events` and `app` query values.
+    pub fn new(events: Option<&str>, app: Option<&str>) -> Self {
+        let events = events
+            .map(|list| {
+                list.split(',')
+                    .map(|e| e.trim().to_string())
+                    .filter(|e| !e.is_empty())
+                    .collect::<HashSet<_>>()
+            })
+            .filter(|set| !set.is_empty());
+        let app = app.map(|a| a.trim().to_string()).filter(|a| !a.is_empty());
+        Self { events, app }
+    }
+
+    /// Whether `event` passes this filter.
+    pub fn matches(&self, event: &StreamEvent) -> bool {
+        if let Some(ref names) = self.events {
+            if !names.contains(&event.name) {
+                return false;
+            }
+        }
+        match self.app {
+            Some(ref app) => {
+                event_app_names(&event.payload).any(|name| name.eq_ignore_ascii_case(app))
+            }
+            None => true,
+        }
+    }
+}
+
+/// Application names mentioned in an event's variant fields.
+fn event_app_names(payload: &serde_json::Value) -> impl Iterator<Item = &str> {
+    payload["data"]
+        .as_object()
+        .and_then(|variant| variant.values().next())
+        .and_then(|fields| fields.as_object())
+        .into_iter()
+        .flat_map(|fields| {
+            APP_FIELDS
+                .iter()
+                .filter_map(|key| fields.get(*key).and_then(|v| v.as_str()))
+        })
+}
+
+/// Fans event log entries out to `/stream` subscribers and replays them.
+pub struct EventStream {
+    database: Arc<Database>,
+    replay_capacity: usize,
+    /// ID of the last event log entry sent to live subscribers.
+    last_id: AtomicI64,
+    /// Serializes reads of the outbox so entries go out once, in order.
+    outbox: Mutex<()>,
+    live_tx: broadcast::Sender<Arc<StreamEvent>>,
+    started: AtomicBool,
+}
+
+impl EventStream {
+    /// Create an event stream over the event log in `database`, replaying
+    /// at most the newest `replay_capacity` entries.
+    ///
+    /// Only entries logged from now on are sent live; earlier ones are
+    /// available through replay.
+    pub fn new(database: Arc<Database>, replay_capacity: usize) -> Self {
+        let last_id = database
+            .with_conn(engram_storage::max_event_log_id)
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Event stream: failed to read event log position");
+                0
+            });
+        let (live_tx, _) = broadcast::channel(LIVE_CHANNEL_CAPACITY);
+        Self {
+            database,
+            replay_capacity: replay_capacity.max(1),
+            last_id: AtomicI64::new(last_id),
+            outbox: Mutex::new(()),
+            live_tx,
+            started: AtomicBool::new(false),
+        }
+    }
+
+    /// ID of the most recently sent event (0 if none).
+    pub fn last_id(&self) -> i64 {
+        self.last_id.load(Ordering::Acquire)
+    }
+
+    /// Send every event log entry after the last one sent, in order.
+    fn record_from_log(&self) {
+        let _outbox = self.outbox.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            let rows = match self.database.with_conn(|conn| {
+                engram_storage::query_event_log(
+                    conn,
+                    &engram_storage::EventLogFilter {
+                        after_id: Some(self.last_id()),
+                        limit: OUTBOX_BATCH,
+                        ..Default::default()
+                    },
+                )
+            }) {
+                Ok(rows) => rows,
+                Err(e) => {
+                    warn!(error = %e, "Event stream: failed to read event log");
+                    return;
+                }
+            };
+            if rows.is_empty() {
+                return;
+            }
+            for row in rows {
+                let event = Arc::new(StreamEvent::from_log(row));
+                self.last_id.store(event.id, Ordering::Release);
+                let _ = self.live_tx.send(event);
+            }
+        }
+    }
+
+    /// Logged events with an ID greater than `after_id`, oldest first,
+    /// limited to the replay window.
+    pub fn replay_after(&self, after_id: i64) -> Result<Replay, EngramError> {
+        self.database.with_conn(|conn| {
+            let Some(oldest) = engram_storage::event_log_window_start(conn, self.replay_capacity)?
+            else {
+                return Ok(Replay::default());
+            };
+            let reset_to = (after_id < oldest - 1).then_some(oldest);
+            let rows = engram_storage::query_event_log(
+                conn,
+                &engram_storage::EventLogFilter {
+                    after_id: Some(after_id.max(oldest - 1)),
+                    limit: self.replay_capacity as u32,
+                    ..Default::default()
+                },
+            )?;
+            Ok(Replay {
+                reset_to,
+                events: rows.into_iter().map(StreamEvent::from_log).collect(),
+            })
+        })
+    }
+
+    /// Start fanning out the event log, woken by each event published on
+    /// `source`.
+    ///
+    /// Entries logged while the recorder was not running are sent first.
+    /// Idempotent; does nothing outside a Tokio runtime.
+    pub fn start(self: &Arc<Self>, source: &broadcast::Sender<serde_json::Value>) {
+        if tokio::runtime::Handle::try_current().is_err() {
+            return;
+        }
+        if self.started.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let stream = Arc::clone(self);
+        let mut rx = source.subscribe();
+        tokio::spawn(async move {
+            stream.record_from_log();
+            loop {
+                match rx.recv().await {
+                    // Every published event is already in the log, so a
+                    // missed wake-up loses nothing.
+                    Ok(_) | Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => break,
+                }
+                stream.record_from_log();
+            }
+        });
+    }
+
+    /// Subscribe a client, replaying logged events after `last_event_id`
+    /// before following the live stream.
+    ///
+    /// Without `last_event_id` only events sent from now on are delivered.
+    pub fn subscribe(
+        self: &Arc<Self>,
+        filter: StreamFilter,
+        last_event_id: Option<i64>,
+    ) -> mpsc::Receiver<Event> {
+        let (tx, rx) = mpsc::channel(64);
+        let mut live = self.live_tx.subscribe();
+        let mut cursor = last_event_id.unwrap_or_else(|| self.last_id());
+        let stream = Arc::clone(self);
+
+        tokio::spawn(async move {
+            // Catch up from the log, then follow the live channel. Lagging
+            // behind the live channel falls back to the log again.
+            let mut needs_replay = true;
+            loop {
+                if needs_replay {
+                    needs_replay = false;
+                    let replay = match stream.replay_after(cursor) {
+                        Ok(replay) => replay,
+                        Err(e) => {
+                            warn!(error = %e, "Event stream: replay failed");
+                            Replay::default()
+                        }
+                    };
+                    if let Some(oldest) = replay.reset_to {
+                        if tx.send(reset_sse(cursor, oldest)).await.is_err() {
+                            return;
+                        }
+                    }
+                    for event in replay.events {
+                        cursor = event.id;
+                        if filter.matches(&event) && tx.send(event.to_sse()).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+
+                match live.recv().await {
+                    Ok(event) => {
+                        if event.id <= cursor {
+                            continue;
+                        }
+                        cursor = event.id;
+                        if filter.matches(&event) && tx.send(event.to_sse()).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(RecvError::Lagged(_)) => needs_replay = true,
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        });
+
+        rx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(name: &str, data: serde_json::Value) -> StreamEvent {
+        StreamEvent {
+            id: 1,
+            name: name.to_string(),
+            payload: serde_json::json!({ "event": name, "timestamp": 0, "data": data }),
+        }
+    }
+
+    /// Append an event to the log, as a publisher would.
+    fn log(db: &Database, name: &str) -> i64 {
+        db.with_conn(|conn| {
+            engram_storage::append_event_log_json(
+                conn,
+                &serde_json::json!({ "event": name, "timestamp": 0 }),
+            )
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_filter_by_event_name() {
+        let filter = StreamFilter::new(Some("task_created, intent_detected"), None);
+        assert!(filter.matches(&event("task_created", serde_json::json!({}))));
+        assert!(filter.matches(&event("intent_detected", serde_json::json!({}))));
+        assert!(!filter.matches(&event("text_extracted", serde_json::json!({}))));
+
+        let all = StreamFilter::new(Some(" , "), None);
+        assert!(all.events.is_none());
+        assert!(all.matches(&event("text_extracted", serde_json::json!({}))));
+    }
+
+    #[test]
+    fn test_filter_by_app() {
+        let filter = StreamFilter::new(None, Some("slack"));
+        let slack = event(
+            "text_extracted",
+            serde_json::json!({ "TextExtracted": { "app_name": "Slack" } }),
+        );
+        let dictated = event(
+            "dictation_completed",
+            serde_json::json!({ "DictationCompleted": { "target_app": "Slack" } }),
+        );
+        let chrome = event(
+            "text_extracted",
+            serde_json::json!({ "TextExtracted": { "app_name": "Chrome" } }),
+        );
+        let no_app = event("task_created", serde_json::json!({ "TaskCreated": {} }));
+        assert!(filter.matches(&slack));
+        assert!(filter.matches(&dictated));
+        assert!(!filter.matches(&chrome));
+        assert!(!filter.matches(&no_app));
+    }
+
+    #[test]
+    fn test_replay_uses_log_ids_within_window() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let ids: Vec<i64> = (0..3).map(|_| log(&db, "task_created")).collect();
+        let stream = EventStream::new(Arc::clone(&db), 2);
+        assert_eq!(stream.last_id(), ids[2]);
+
+        let replay = stream.replay_after(ids[0]).unwrap();
+        assert_eq!(replay.reset_to, None);
+        let replayed: Vec<i64> = replay.events.iter().map(|e| e.id).collect();
+        assert_eq!(replayed, vec![ids[1], ids[2]]);
+        assert_eq!(replay.events[0].name, "task_created");
+        assert_eq!(replay.events[0].payload[LOG_ID_FIELD], ids[1]);
+
+        // The first entry has left the window, so resuming before it resets.
+        let replay = stream.replay_after(0).unwrap();
+        assert_eq!(replay.reset_to, Some(ids[1]));
+        assert_eq!(replay.events.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_replays_then_follows_live() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let stream = Arc::new(EventStream::new(Arc::clone(&db), 100));
+        let (tx, _) = broadcast::channel(16);
+        stream.start(&tx);
+        let first = log(&db, "task_created");
+        log(&db, "intent_detected");
+
+        let mut rx = stream.subscribe(StreamFilter::new(Some("task_created"), None), Some(0));
+        let third = log(&db, "task_created");
+        tx.send(serde_json::json!({})).unwrap();
+
+        let replayed = rx.recv().await.unwrap();
+        let live = rx.recv().await.unwrap();
+        // Events are opaque; compare their wire form.
+        let replayed = format!("{:?}", replayed);
+        let live = format!("{:?}", live);
+        assert!(replayed.contains(&format!("id: {}", first)), "{}", replayed);
+        assert!(replayed.contains("event: task_created"), "{}", replayed);
+        assert!(live.contains(&format!("id: {}", third)), "{}", live);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_resets_when_last_event_id_left_the_window() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let ids: Vec<i64> = (0..3).map(|_| log(&db, "task_created")).collect();
+        let stream = Arc::new(EventStream::new(Arc::clone(&db), 1));
+
+        let mut rx = stream.subscribe(StreamFilter::default(), Some(ids[0]));
+        let reset = format!("{:?}", rx.recv().await.unwrap());
+        assert!(reset.contains("event: reset"), "{}", reset);
+        let data = format!(
+            r#"{{\"last_event_id\":{},\"oldest_id\":{}}}"#,
+            ids[0], ids[2]
+        );
+        assert!(reset.contains(&data), "{}", reset);
+        let replayed = format!("{:?}", rx.recv().await.unwrap());
+        assert!(
+            replayed.contains(&format!("id: {}", ids[2])),
+            "{}",
+            replayed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lagging_recorder_leaves_no_gap() {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let stream = Arc::new(EventStream::new(Arc::clone(&db), 100));
+        let (tx, _) = broadcast::channel(2);
+        stream.start(&tx);
+
+        // The recorder task does not run until this task yields, so all but
+        // the last two wake-ups are dropped from the channel.
+        let mut live = stream.live_tx.subscribe();
+        for timestamp in 0..20 {
+            let mut payload =
+                serde_json::json!({ "event": "task_created", "timestamp": timestamp });
+            let log_id = db
+                .with_conn(|conn| engram_storage::append_event_log_json(conn, &payload))
+                .unwrap();
+            payload[LOG_ID_FIELD] = log_id.into();
+            tx.send(payload).unwrap();
+        }
+        let mut sent = Vec::new();
+        for _ in 0..20 {
+            sent.push(live.recv().await.unwrap());
+        }
+
+        let ids: Vec<i64> = sent.iter().map(|e| e.id).collect();
+        assert_eq!(ids, (1..=20).collect::<Vec<_>>());
+        assert_eq!(sent[19].payload["timestamp"], 19);
+        assert_eq!(stream.last_id(), 20);
+    }
+}
@@ -5,12 +5,16 @@
 //! configuration, and health checks.
 
 pub mod auth;
+pub mod config_watch;
 pub mod error;
+pub mod event_stream;
 pub mod handlers;
+pub mod openapi;
 pub mod rate_limit;
 pub mod routes;
+pub mod saved_search;
 pub mod state;
 
 pub use error::ApiError;
 pub use routes::create_router;
-pub use state::AppState;
+pub use state::{AppState, PendingEvent};
@@ -0,0 +1,247 @@
+//! OpenAPI 3 document for the HTTP API.
+//!
+//! The spec is assembled by `utoipa` from the `#[utoipa::path]` attributes
+//! on the handlers in [`crate::handlers`] and the schemas derived on their
+//! request and response types. It is served unauthenticated at
+//! `GET /openapi.json`.
+
+use axum::Json;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::handlers;
+
+/// The Engram API specification.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Engram API",
+        description = "Local-first screen, audio and dictation memory. Protected endpoints take \
+            `Authorization: Bearer <token>`; the scopes listed on each operation are the \
+            token scopes it requires (`admin` implies all scopes)."
+    ),
+    paths(
+        openapi_json,
+        handlers::health,
+        handlers::ui,
+        handlers::stream,
+        handlers::list_events,
+        handlers::search,
+        handlers::recent,
+        handlers::search_semantic,
+        handlers::search_hybrid,
+        handlers::search_raw,
+        handlers::search_analytics,
+        handlers::search_click,
+        handlers::purge_search_log,
+        handlers::list_saved_searches,
+        handlers::create_saved_search,
+        handlers::get_saved_search,
+        handlers::update_saved_search,
+        handlers::delete_saved_search,
+        handlers::run_saved_search,
+        handlers::apps,
+        handlers::app_activity,
+        handlers::audio_status,
+        handlers::audio_device,
+        handlers::similar_captures,
+        handlers::capture_context,
+        handlers::ingest,
+        handlers::ingest_batch,
+        handlers::start_import,
+        handlers::list_imports,
+        handlers::get_import,
+        handlers::dictation_status,
+        handlers::dictation_history,
+        handlers::dictation_start,
+        handlers::dictation_stop,
+        handlers::storage_stats,
+        handlers::storage_purge,
+        handlers::purge_dry_run,
+        handlers::export_data,
+        handlers::get_config,
+        handlers::update_config,
+        handlers::get_config_schema,
+        handlers::get_daily_digest,
+        handlers::get_daily_digest_by_date,
+        handlers::get_topics,
+        handlers::get_entities,
+        handlers::get_summaries,
+        handlers::trigger_export,
+        handlers::list_tasks,
+        handlers::create_task,
+        handlers::get_task,
+        handlers::update_task,
+        handlers::delete_task,
+        handlers::get_action_history,
+        handlers::list_intents,
+        handlers::approve_action,
+        handlers::dismiss_action,
+        handlers::chat_handler,
+        handlers::chat_history_handler,
+        handlers::chat_sessions_handler,
+        handlers::chat_session_update_handler,
+        handlers::chat_session_delete_handler,
+        handlers::chat_session_export_handler,
+        handlers::chat_search_handler,
+        handlers::chat_feedback_handler,
+        handlers::chat_feedback_report_handler,
+        handlers::chat_stream_start_handler,
+        handlers::chat_stream_handler,
+        handlers::chat_stream_cancel_handler,
+        handlers::chat_voice_status_handler,
+        handlers::chat_voice_start_handler,
+        handlers::chat_voice_stop_handler,
+    ),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "core", description = "Health, dashboard and event stream"),
+        (name = "search", description = "Keyword, semantic and hybrid search"),
+        (name = "capture", description = "Captured applications, audio and ingest"),
+        (name = "dictation", description = "Hotkey dictation"),
+        (name = "storage", description = "Storage and configuration"),
+        (name = "insights", description = "Digests, topics, entities and summaries"),
+        (name = "actions", description = "Action engine tasks and intents"),
+        (name = "chat", description = "Conversational queries over captured data"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Registers the `bearer_auth` security scheme referenced by the handlers.
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+/// GET /openapi.json - OpenAPI 3 specification for this API.
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    tag = "core",
+    responses(
+        (status = 200, description = "OpenAPI document", content_type = "application/json", body = serde_json::Value),
+    ),
+    security(())
+)]
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METHODS: [&str; 5] = ["get", "post", "put", "delete", "patch"];
+
+    /// Extract `(method, path)` pairs from the `.route(...)` calls in `routes.rs`.
+    fn router_routes() -> Vec<(String, String)> {
+        let source = include_str!("routes.rs");
+        let mut routes = Vec::new();
+        for (start, _) in source.match_indices(".route(") {
+            // Find the balanced argument list of this `.route(` call.
+            let args_start = start + ".route(".len();
+            let mut depth = 1;
+            let mut end = args_start;
+            for (i, c) in source[args_start..].char_indices() {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    end = args_start + i;
+                    break;
+                }
+            }
+            let args = &source[args_start..end];
+            let path = args.split('"').nth(1).expect("route path literal");
+            for method in METHODS {
+                let call = format!("{}(", method);
+                let has_method = args.match_indices(&call).any(|(i, _)| {
+                    i == 0
+                        || !args.as_bytes()[i - 1].is_ascii_alphanumeric()
+                            && args.as_bytes()[i - 1] != b'_'
+                });
+                if has_method {
+                    routes.push((method.to_string(), path.to_string()));
+                }
+            }
+        }
+        routes
+    }
+
+    #[test]
+    fn test_router_routes_are_parsed() {
+        let routes = router_routes();
+        assert!(routes.contains(&("get".to_string(), "/search".to_string())));
+        assert!(routes.contains(&("put".to_string(), "/config".to_string())));
+        assert!(routes.contains(&("delete".to_string(), "/tasks/{id}".to_string())));
+        assert!(routes.len() > 50);
+    }
+
+    #[test]
+    fn test_every_route_is_documented() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let paths = spec["paths"].as_object().unwrap();
+
+        let missing: Vec<String> = router_routes()
+            .into_iter()
+            .filter(|(method, path)| paths.get(path).and_then(|p| p.get(method)).is_none())
+            .map(|(method, path)| format!("{} {}", method.to_uppercase(), path))
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "Routes missing from the OpenAPI spec: {:?}",
+            missing
+        );
+    }
+
+    #[test]
+    fn test_spec_declares_bearer_auth() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        assert_eq!(
+            spec["components"]["securitySchemes"]["bearer_auth"]["scheme"],
+            "bearer"
+        );
+        assert_eq!(
+            spec["paths"]["/config"]["put"]["security"][0]["bearer_auth"][0],
+            "admin"
+        );
+        assert!(spec["components"]["schemas"]["SearchParams"].is_null());
+        assert!(spec["components"]["schemas"]["PaginatedResults"].is_object());
+    }
+
+    #[test]
+    fn test_chat_bodies_reference_schemas() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let schemas = &spec["components"]["schemas"];
+        for name in [
+            "ChatFeedback",
+            "FeedbackRecorded",
+            "FeedbackReport",
+            "ChatMessageHit",
+            "ChatSessionUpdate",
+            "VoiceStatus",
+            "VoiceAnswer",
+            "ChatResponse",
+        ] {
+            assert!(schemas[name].is_object(), "missing schema {}", name);
+        }
+        assert_eq!(
+            spec["paths"]["/chat/feedback"]["post"]["requestBody"]["content"]["application/json"]
+                ["schema"]["$ref"],
+            "#/components/schemas/ChatFeedback"
+        );
+        assert_eq!(
+            schemas["FeedbackRating"]["enum"],
+            serde_json::json!(["up", "down", "irrelevant"])
+        );
+    }
+}
@@ -1,87 +1,251 @@
-//! Simple token-bucket rate limiter middleware.
+//! Per-client token-bucket rate limiter middleware.
 //!
-//! Limits requests to a configurable number per second using an atomic
-//! counter that resets each second. Applied as an axum middleware.
+//! Every API token gets one bucket per [`RouteClass`], sized from the
+//! matching `[rate_limit]` budget: `burst` requests up front, refilled at
+//! `per_sec`. A client polling search endpoints cannot drain another
+//! client's budget, or its own ingest budget. Rejected requests get a 429
+//! with a `Retry-After` header. Budgets follow `[rate_limit]` on config
+//! reload.
 
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
-use axum::extract::Request;
-use axum::http::StatusCode;
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderValue};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
-use axum::Json;
+use engram_core::config::{EngramConfig, RateLimitConfig, RouteBudget};
+use tokio::sync::watch;
+
+use crate::auth::AuthContext;
+use crate::error::ApiError;
+
+/// Group of routes sharing one budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteClass {
+    Search,
+    Ingest,
+    Admin,
+    Chat,
+}
+
+impl RouteClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RouteClass::Search => "search",
+            RouteClass::Ingest => "ingest",
+            RouteClass::Admin => "admin",
+            RouteClass::Chat => "chat",
+        }
+    }
+
+    fn budget(&self, config: &RateLimitConfig) -> RouteBudget {
+        match self {
+            RouteClass::Search => config.search,
+            RouteClass::Ingest => config.ingest,
+            RouteClass::Admin => config.admin,
+            RouteClass::Chat => config.chat,
+        }
+    }
+}
+
+/// A single token bucket.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
 
 /// Shared state for the rate limiter.
 #[derive(Clone)]
 pub struct RateLimiter {
-    /// Maximum requests allowed per second.
-    max_per_sec: u64,
-    /// Current count of requests in the active window.
-    count: Arc<AtomicU64>,
-    /// The epoch second of the current window.
-    window: Arc<AtomicU64>,
+    /// Budgets per route class.
+    budgets: Arc<RwLock<RateLimitConfig>>,
+    /// Buckets keyed by token name and route class.
+    buckets: Arc<Mutex<HashMap<(String, RouteClass), Bucket>>>,
 }
 
 impl RateLimiter {
-    /// Create a new rate limiter allowing `max_per_sec` requests per second.
-    pub fn new(max_per_sec: u64) -> Self {
+    /// Create a rate limiter with the given budgets.
+    pub fn new(budgets: RateLimitConfig) -> Self {
         Self {
-            max_per_sec,
-            count: Arc::new(AtomicU64::new(0)),
-            window: Arc::new(AtomicU64::new(0)),
+            budgets: Arc::new(RwLock::new(budgets)),
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Replace the budgets; takes effect on the next request.
+    pub fn set_budgets(&self, budgets: RateLimitConfig) {
+        if let Ok(mut current) = self.budgets.write() {
+            *current = budgets;
         }
     }
 
-    /// Try to acquire a permit. Returns true if the request is allowed.
-    fn try_acquire(&self) -> bool {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-
-        loop {
-            let current_window = self.window.load(Ordering::Acquire);
-
-            if now != current_window {
-                // New second window — atomically transition via compare_exchange.
-                match self.window.compare_exchange(
-                    current_window,
-                    now,
-                    Ordering::AcqRel,
-                    Ordering::Acquire,
-                ) {
-                    Ok(_) => {
-                        self.count.store(1, Ordering::Release);
-                        return true;
-                    }
-                    Err(_) => continue, // Another thread won the race; retry.
-                }
-            } else {
-                let prev = self.count.fetch_add(1, Ordering::AcqRel);
-                return prev < self.max_per_sec;
+    /// Keep the budgets in sync with `[rate_limit]`.
+    ///
+    /// Does nothing outside a Tokio runtime.
+    pub fn follow(&self, mut config_rx: watch::Receiver<EngramConfig>) {
+        if tokio::runtime::Handle::try_current().is_err() {
+            return;
+        }
+        let limiter = self.clone();
+        tokio::spawn(async move {
+            while config_rx.changed().await.is_ok() {
+                let budgets = config_rx.borrow_and_update().rate_limit.clone();
+                limiter.set_budgets(budgets);
             }
+        });
+    }
+
+    /// Try to take a token from `client`'s bucket for `class`.
+    ///
+    /// Returns how long to wait before retrying when the bucket is empty.
+    pub fn try_acquire(&self, client: &str, class: RouteClass) -> Result<(), Duration> {
+        self.try_acquire_at(client, class, Instant::now())
+    }
+
+    fn try_acquire_at(
+        &self,
+        client: &str,
+        class: RouteClass,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let budget = self
+            .budgets
+            .read()
+            .map(|b| class.budget(&b))
+            .unwrap_or_else(|_| class.budget(&RateLimitConfig::default()));
+        let capacity = f64::from(budget.burst.max(1));
+        let rate = f64::from(budget.per_sec.max(1));
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let bucket = buckets
+            .entry((client.to_string(), class))
+            .or_insert(Bucket {
+                tokens: capacity,
+                refilled_at: now,
+            });
+
+        let elapsed = now
+            .saturating_duration_since(bucket.refilled_at)
+            .as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
         }
     }
 }
 
-/// Axum middleware that enforces the rate limit.
+/// Axum middleware that enforces the budget for one route class.
+///
+/// Runs after `require_auth`, so the bucket is keyed by the caller's token.
 pub async fn rate_limit_middleware(
-    axum::extract::Extension(limiter): axum::extract::Extension<RateLimiter>,
+    State((limiter, class)): State<(RateLimiter, RouteClass)>,
     req: Request,
     next: Next,
 ) -> Response {
-    if limiter.try_acquire() {
-        next.run(req).await
-    } else {
-        (
-            StatusCode::TOO_MANY_REQUESTS,
-            Json(serde_json::json!({
-                "error": "too_many_requests",
-                "message": "Rate limit exceeded"
-            })),
-        )
-            .into_response()
+    let client = req
+        .extensions()
+        .get::<AuthContext>()
+        .map(|ctx| ctx.token_name.clone())
+        .unwrap_or_default();
+
+    match limiter.try_acquire(&client, class) {
+        Ok(()) => next.run(req).await,
+        Err(wait) => {
+            // Retry-After is whole seconds; round up so a retry succeeds.
+            let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+            let mut resp = ApiError::TooManyRequests(format!(
+                "Rate limit exceeded for {} requests; retry in {}s",
+                class.as_str(),
+                retry_after
+            ))
+            .into_response();
+            resp.headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+            resp
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(per_sec: u32, burst: u32) -> RateLimiter {
+        let budget = RouteBudget { per_sec, burst };
+        RateLimiter::new(RateLimitConfig {
+            search: budget,
+            ingest: budget,
+            admin: budget,
+            chat: budget,
+        })
+    }
+
+    #[test]
+    fn test_bucket_allows_burst_then_refills() {
+        let limiter = limiter(2, 3);
+        let start = Instant::now();
+        for _ in 0..3 {
+            assert!(limiter
+                .try_acquire_at("a", RouteClass::Search, start)
+                .is_ok());
+        }
+        let wait = limiter
+            .try_acquire_at("a", RouteClass::Search, start)
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_millis(500));
+
+        let later = start + Duration::from_millis(500);
+        assert!(limiter
+            .try_acquire_at("a", RouteClass::Search, later)
+            .is_ok());
+        assert!(limiter
+            .try_acquire_at("a", RouteClass::Search, later)
+            .is_err());
+    }
+
+    #[test]
+    fn test_buckets_are_per_client_and_class() {
+        let limiter = limiter(1, 1);
+        let now = Instant::now();
+        assert!(limiter
+            .try_acquire_at("dashboard", RouteClass::Search, now)
+            .is_ok());
+        assert!(limiter
+            .try_acquire_at("dashboard", RouteClass::Search, now)
+            .is_err());
+
+        assert!(limiter
+            .try_acquire_at("dashboard", RouteClass::Ingest, now)
+            .is_ok());
+        assert!(limiter
+            .try_acquire_at("script", RouteClass::Search, now)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_set_budgets_applies_to_existing_buckets() {
+        let limiter = limiter(1, 1);
+        let now = Instant::now();
+        assert!(limiter.try_acquire_at("a", RouteClass::Chat, now).is_ok());
+
+        let budgets = RateLimitConfig {
+            chat: RouteBudget {
+                per_sec: 10,
+                burst: 10,
+            },
+            ..RateLimitConfig::default()
+        };
+        limiter.set_budgets(budgets);
+
+        let later = now + Duration::from_millis(100);
+        assert!(limiter.try_acquire_at("a", RouteClass::Chat, later).is_ok());
     }
 }
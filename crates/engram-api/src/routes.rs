@@ -5,13 +5,13 @@
 
 use axum::extract::DefaultBodyLimit;
 use axum::http::{header, HeaderValue, Method};
-use axum::routing::{delete, get, post};
+use axum::routing::{delete, get, post, put};
 use axum::Router;
 use tower_http::compression::CompressionLayer;
 use tower_http::cors::{AllowOrigin, CorsLayer};
 use tower_http::trace::TraceLayer;
 
-use crate::rate_limit::RateLimiter;
+use crate::rate_limit::{RateLimiter, RouteClass};
 
 use crate::handlers;
 use crate::state::AppState;
@@ -24,6 +24,9 @@ use crate::state::AppState;
 /// # Returns
 /// A fully configured axum Router ready to serve requests.
 pub fn create_router(state: AppState) -> Router {
+    // Record published domain events for `/stream` replay.
+    state.event_stream.start(&state.event_tx);
+
     // CORS middleware: allow localhost origins for dashboard access.
     // Use the configured port (from CLI/env/config) plus port+1 for dev server.
     let port = state.config.lock().map(|c| c.general.port).unwrap_or(3030);
@@ -55,13 +58,27 @@ pub fn create_router(state: AppState) -> Router {
     // Routes that do NOT require authentication.
     let public_routes = Router::new()
         .route("/health", get(handlers::health))
-        .route("/ui", get(handlers::ui));
+        .route("/ui", get(handlers::ui))
+        .route("/openapi.json", get(crate::openapi::openapi_json));
 
-    // Rate limiter: 100 requests per second.
-    let limiter = RateLimiter::new(100);
+    // Per-token rate limiter with one budget per route class, updated on
+    // config reload.
+    let budgets = state
+        .config
+        .lock()
+        .map(|c| c.rate_limit.clone())
+        .unwrap_or_default();
+    let limiter = RateLimiter::new(budgets);
+    limiter.follow(state.subscribe_config());
+    let rate_limit = |class: RouteClass| {
+        axum::middleware::from_fn_with_state(
+            (limiter.clone(), class),
+            crate::rate_limit::rate_limit_middleware,
+        )
+    };
 
-    // Rate-limited protected routes.
-    let rate_limited_routes = Router::new()
+    // Protected routes, grouped by the token scope they require.
+    let read_routes = Router::new()
         .route("/search", get(handlers::search))
         .route("/recent", get(handlers::recent))
         .route("/apps", get(handlers::apps))
@@ -69,22 +86,16 @@ pub fn create_router(state: AppState) -> Router {
         .route("/audio/status", get(handlers::audio_status))
         .route("/dictation/status", get(handlers::dictation_status))
         .route("/dictation/history", get(handlers::dictation_history))
-        .route("/dictation/start", post(handlers::dictation_start))
-        .route("/dictation/stop", post(handlers::dictation_stop))
         .route("/storage/stats", get(handlers::storage_stats))
-        .route("/storage/purge", post(handlers::storage_purge))
-        .route(
-            "/config",
-            get(handlers::get_config)
-                .put(handlers::update_config)
-                .layer(DefaultBodyLimit::max(64 * 1024)), // 64KB for config
-        )
         .route("/audio/device", get(handlers::audio_device))
-        .route("/storage/purge/dry-run", post(handlers::purge_dry_run))
         .route("/search/semantic", get(handlers::search_semantic))
         .route("/search/hybrid", get(handlers::search_hybrid))
         .route("/search/raw", get(handlers::search_raw))
-        .route("/ingest", post(handlers::ingest))
+        .route("/search/analytics", get(handlers::search_analytics))
+        .route("/search/saved", get(handlers::list_saved_searches))
+        .route("/search/saved/{id}", get(handlers::get_saved_search))
+        .route("/captures/{id}/similar", get(handlers::similar_captures))
+        .route("/captures/{id}/context", get(handlers::capture_context))
         .route("/insights/daily", get(handlers::get_daily_digest))
         .route(
             "/insights/daily/{date}",
@@ -93,39 +104,122 @@ pub fn create_router(state: AppState) -> Router {
         .route("/insights/topics", get(handlers::get_topics))
         .route("/entities", get(handlers::get_entities))
         .route("/summaries", get(handlers::get_summaries))
-        .route("/insights/export", post(handlers::trigger_export))
-        // Action engine routes
+        .route("/tasks", get(handlers::list_tasks))
+        .route("/tasks/{id}", get(handlers::get_task))
+        .route("/actions/history", get(handlers::get_action_history))
+        .route("/intents", get(handlers::list_intents))
+        .route("/events", get(handlers::list_events))
+        .route_layer(axum::middleware::from_fn(crate::auth::require_search_read))
+        .layer(rate_limit(RouteClass::Search));
+
+    let search_write_routes = Router::new()
+        .route("/search/saved", post(handlers::create_saved_search))
         .route(
-            "/tasks",
-            get(handlers::list_tasks).post(handlers::create_task),
+            "/search/saved/{id}",
+            put(handlers::update_saved_search).delete(handlers::delete_saved_search),
         )
+        .route("/search/saved/{id}/run", post(handlers::run_saved_search))
+        .route("/search/log/{id}/click", post(handlers::search_click))
+        .route_layer(axum::middleware::from_fn(crate::auth::require_search_write))
+        .layer(rate_limit(RouteClass::Search));
+
+    let chat_read_routes = Router::new()
+        .route("/chat/history", get(handlers::chat_history_handler))
+        .route("/chat/sessions", get(handlers::chat_sessions_handler))
+        .route("/chat/search", get(handlers::chat_search_handler))
         .route(
-            "/tasks/{id}",
-            get(handlers::get_task)
-                .put(handlers::update_task)
-                .delete(handlers::delete_task),
+            "/chat/feedback/report",
+            get(handlers::chat_feedback_report_handler),
         )
-        .route("/actions/history", get(handlers::get_action_history))
-        .route("/intents", get(handlers::list_intents))
-        .route("/actions/{task_id}/approve", post(handlers::approve_action))
-        .route("/actions/{task_id}/dismiss", post(handlers::dismiss_action))
-        // Chat routes
+        .route(
+            "/chat/sessions/{id}/export",
+            get(handlers::chat_session_export_handler),
+        )
+        .route("/chat/voice", get(handlers::chat_voice_status_handler))
+        .route_layer(axum::middleware::from_fn(crate::auth::require_search_read))
+        .layer(rate_limit(RouteClass::Chat));
+
+    let chat_write_routes = Router::new()
         .route("/chat", post(handlers::chat_handler))
-        .route("/chat/history", get(handlers::chat_history_handler))
-        .route("/chat/sessions", get(handlers::chat_sessions_handler))
+        .route("/chat/feedback", post(handlers::chat_feedback_handler))
         .route(
             "/chat/sessions/{id}",
-            delete(handlers::chat_session_delete_handler),
+            put(handlers::chat_session_update_handler)
+                .delete(handlers::chat_session_delete_handler),
         )
-        .layer(axum::middleware::from_fn(
-            crate::rate_limit::rate_limit_middleware,
+        .route("/chat/stream", post(handlers::chat_stream_start_handler))
+        .route(
+            "/chat/stream/{message_id}",
+            delete(handlers::chat_stream_cancel_handler),
+        )
+        .route(
+            "/chat/voice/start",
+            post(handlers::chat_voice_start_handler),
+        )
+        .route("/chat/voice/stop", post(handlers::chat_voice_stop_handler))
+        .route_layer(axum::middleware::from_fn(crate::auth::require_chat_write))
+        .layer(rate_limit(RouteClass::Chat));
+
+    let ingest_routes = Router::new()
+        .route("/ingest", post(handlers::ingest))
+        .route(
+            "/ingest/batch",
+            post(handlers::ingest_batch).layer(DefaultBodyLimit::max(8 * 1024 * 1024)), // 8MB for batches
+        )
+        .route("/dictation/start", post(handlers::dictation_start))
+        .route("/dictation/stop", post(handlers::dictation_stop))
+        .route_layer(axum::middleware::from_fn(crate::auth::require_ingest_write))
+        .layer(rate_limit(RouteClass::Ingest));
+
+    // Action engine routes
+    let action_routes = Router::new()
+        .route("/tasks", post(handlers::create_task))
+        .route(
+            "/tasks/{id}",
+            put(handlers::update_task).delete(handlers::delete_task),
+        )
+        .route("/actions/{task_id}/approve", post(handlers::approve_action))
+        .route("/actions/{task_id}/dismiss", post(handlers::dismiss_action))
+        .route_layer(axum::middleware::from_fn(
+            crate::auth::require_actions_approve,
         ))
-        .layer(axum::Extension(limiter));
+        .layer(rate_limit(RouteClass::Admin));
+
+    let admin_routes = Router::new()
+        .route(
+            "/config",
+            get(handlers::get_config)
+                .put(handlers::update_config)
+                .layer(DefaultBodyLimit::max(64 * 1024)), // 64KB for config
+        )
+        .route("/config/schema", get(handlers::get_config_schema))
+        .route("/storage/purge", post(handlers::storage_purge))
+        .route("/storage/purge/dry-run", post(handlers::purge_dry_run))
+        .route("/search/log", delete(handlers::purge_search_log))
+        .route("/insights/export", post(handlers::trigger_export))
+        .route(
+            "/import",
+            get(handlers::list_imports).post(handlers::start_import),
+        )
+        .route("/import/{id}", get(handlers::get_import))
+        .route("/export", post(handlers::export_data))
+        .route_layer(axum::middleware::from_fn(crate::auth::require_admin))
+        .layer(rate_limit(RouteClass::Admin));
+
+    // Rate-limited protected routes.
+    let rate_limited_routes = read_routes
+        .merge(search_write_routes)
+        .merge(chat_read_routes)
+        .merge(chat_write_routes)
+        .merge(ingest_routes)
+        .merge(action_routes)
+        .merge(admin_routes);
 
-    // SSE stream and WebSocket routes exempt from rate limiting.
+    // SSE streams exempt from rate limiting.
     let stream_routes = Router::new()
         .route("/stream", get(handlers::stream))
-        .route("/chat/stream", get(handlers::chat_stream_handler));
+        .route("/chat/stream", get(handlers::chat_stream_handler))
+        .route_layer(axum::middleware::from_fn(crate::auth::require_search_read));
 
     // Combine all protected routes behind auth.
     let protected_routes =
@@ -0,0 +1,310 @@
+//! Saved search execution.
+//!
+//! Re-runs persisted saved searches against the FTS5 and vector indexes,
+//! works out which matching captures were stored since the previous run,
+//! and announces them as a `SavedSearchMatched` domain event. Searches with
+//! `notify` set also create a Notification task in the action engine.
+//!
+//! New matches are searched for among the captures stored since the last
+//! run, with the search's filters applied in SQL, so they are found however
+//! they rank against older matches.
+
+use serde::Serialize;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use engram_core::events::DomainEvent;
+use engram_core::types::{ContentType, Embedding, Timestamp};
+use engram_storage::{FtsFilters, SavedSearchRow};
+use engram_vector::SearchFilters;
+
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// Maximum number of results considered per saved search run.
+const RUN_RESULT_LIMIT: u64 = 100;
+
+/// Maximum number of newly stored captures scored against a semantic query
+/// per run.
+const NEW_CAPTURE_SCAN_LIMIT: u64 = 10_000;
+
+/// Newly stored captures below this cosine similarity to the query are not
+/// semantic matches.
+const MIN_SEMANTIC_SIMILARITY: f64 = 0.35;
+
+/// Valid saved search modes.
+pub const SAVED_SEARCH_MODES: &[&str] = &["keyword", "semantic", "hybrid"];
+
+/// Outcome of a single saved search run.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct SavedSearchRun {
+    pub search_id: String,
+    /// Unix epoch (seconds) at which the run started.
+    pub ran_at: i64,
+    /// Total number of captures currently matching the query.
+    pub match_count: usize,
+    /// Matching captures that were stored since the previous run.
+    pub new_capture_ids: Vec<Uuid>,
+    /// Notification task created for the new matches, if any.
+    pub task_id: Option<Uuid>,
+}
+
+/// Run a saved search once and record the run.
+///
+/// The first run of a search establishes a baseline: existing matches are
+/// counted but not reported as new.
+pub async fn run_saved_search(
+    state: &AppState,
+    search: &SavedSearchRow,
+) -> Result<SavedSearchRun, ApiError> {
+    let search_id = Uuid::parse_str(&search.id).map_err(|e| {
+        ApiError::Internal(format!(
+            "Saved search {} has an invalid ID: {}",
+            search.id, e
+        ))
+    })?;
+    let ran_at = chrono::Utc::now().timestamp();
+    // Captures stored while the query runs are left for the next run, so
+    // each new match is reported once.
+    let watermark = state
+        .database
+        .with_conn(engram_storage::max_capture_rowid)?;
+    let filters = FtsFilters {
+        content_type: search.content_type.clone(),
+        app_name: search.app_name.clone(),
+        ..Default::default()
+    };
+    let match_ids = execute(state, search, &filters).await?;
+
+    let new_capture_ids = match search.last_capture_rowid {
+        Some(after) => {
+            let stored = FtsFilters {
+                stored_after: Some(after),
+                stored_up_to: Some(watermark),
+                ..filters
+            };
+            new_matches(state, search, &stored).await?
+        }
+        None => Vec::new(),
+    };
+
+    let record_run = |conn: &rusqlite::Connection| {
+        engram_storage::record_saved_search_run(
+            conn,
+            &search.id,
+            ran_at,
+            match_ids.len() as i64,
+            watermark,
+        )
+    };
+    if new_capture_ids.is_empty() {
+        state.database.with_conn(record_run)?;
+    } else {
+        let matched = DomainEvent::SavedSearchMatched {
+            search_id,
+            name: search.name.clone(),
+            query: search.query.clone(),
+            new_match_count: new_capture_ids.len(),
+            capture_ids: new_capture_ids.clone(),
+            timestamp: Timestamp::now(),
+        };
+        state.commit_and_publish(matched, record_run)?;
+    }
+
+    let mut task_id = None;
+    if !new_capture_ids.is_empty() && search.notify {
+        task_id = create_notification(state, search, &new_capture_ids);
+    }
+
+    Ok(SavedSearchRun {
+        search_id: search.id.clone(),
+        ran_at,
+        match_count: match_ids.len(),
+        new_capture_ids,
+        task_id,
+    })
+}
+
+/// Run every enabled saved search whose interval has elapsed.
+///
+/// Failures are logged per search and do not stop the remaining searches.
+/// Returns the number of searches that ran successfully.
+pub async fn run_due_saved_searches(state: &AppState) -> usize {
+    let now = chrono::Utc::now().timestamp();
+    let due = match state
+        .database
+        .with_conn(|conn| engram_storage::list_due_saved_searches(conn, now))
+    {
+        Ok(d) => d,
+        Err(e) => {
+            warn!(error = %e, "Saved searches: failed to list due searches");
+            return 0;
+        }
+    };
+
+    let mut ran = 0;
+    for search in &due {
+        match run_saved_search(state, search).await {
+            Ok(run) => {
+                debug!(
+                    search_id = %search.id,
+                    matches = run.match_count,
+                    new_matches = run.new_capture_ids.len(),
+                    "Saved search run complete"
+                );
+                ran += 1;
+            }
+            Err(e) => {
+                warn!(search_id = %search.id, error = ?e, "Saved search run failed");
+            }
+        }
+    }
+    ran
+}
+
+/// Execute the query for a saved search and return matching capture IDs,
+/// best match first.
+async fn execute(
+    state: &AppState,
+    search: &SavedSearchRow,
+    filters: &FtsFilters,
+) -> Result<Vec<Uuid>, ApiError> {
+    let mut ids = keyword_matches(state, search, filters)?;
+
+    if search.mode == "semantic" || search.mode == "hybrid" {
+        let filters = SearchFilters {
+            content_type: search.content_type.as_deref().and_then(|ct| match ct {
+                "screen" => Some(ContentType::Screen),
+                "audio" => Some(ContentType::Audio),
+                "dictation" => Some(ContentType::Dictation),
+                "document" => Some(ContentType::Document),
+                _ => None,
+            }),
+            app_name: search.app_name.clone(),
+            start: None,
+            end: None,
+        };
+        let vector_results = state
+            .search_engine
+            .hybrid_search(&search.query, filters, RUN_RESULT_LIMIT as usize)
+            .await?;
+        for vr in vector_results {
+            if !ids.contains(&vr.id) {
+                ids.push(vr.id);
+            }
+        }
+    }
+
+    Ok(ids)
+}
+
+/// Matching captures among those passing `filters`, which select the
+/// captures stored since the previous run, best match first.
+///
+/// Semantic matches are scored from the stored embeddings of those
+/// captures rather than found by a nearest-neighbour search over the whole
+/// index.
+async fn new_matches(
+    state: &AppState,
+    search: &SavedSearchRow,
+    filters: &FtsFilters,
+) -> Result<Vec<Uuid>, ApiError> {
+    let mut ids = keyword_matches(state, search, filters)?;
+
+    if search.mode == "semantic" || search.mode == "hybrid" {
+        let query = Embedding(state.search_engine.embed_query(&search.query).await?);
+        let candidates = state
+            .fts_search
+            .search_filtered("", filters, NEW_CAPTURE_SCAN_LIMIT)?;
+        let mut scored: Vec<(f64, Uuid)> = Vec::new();
+        for candidate in candidates {
+            let Some(stored) = state.search_engine.index().get_embedding(candidate.id)? else {
+                continue;
+            };
+            let similarity = query.cosine_similarity(&Embedding(stored));
+            if similarity >= MIN_SEMANTIC_SIMILARITY {
+                scored.push((similarity, candidate.id));
+            }
+        }
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        for (_, id) in scored.into_iter().take(RUN_RESULT_LIMIT as usize) {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+
+    Ok(ids)
+}
+
+/// Full-text matches for a keyword or hybrid search, best first, with
+/// `filters` applied before the result limit.
+fn keyword_matches(
+    state: &AppState,
+    search: &SavedSearchRow,
+    filters: &FtsFilters,
+) -> Result<Vec<Uuid>, ApiError> {
+    if search.mode != "keyword" && search.mode != "hybrid" {
+        return Ok(Vec::new());
+    }
+    // An empty query would list every capture passing the filters.
+    if engram_storage::sanitize_fts5_query(&search.query).is_empty() {
+        return Ok(Vec::new());
+    }
+    let results = state
+        .fts_search
+        .search_filtered(&search.query, filters, RUN_RESULT_LIMIT)?;
+    Ok(results.into_iter().map(|r| r.id).collect())
+}
+
+/// Create a Notification task for new saved search matches.
+///
+/// Skipped when the action engine is disabled.
+fn create_notification(
+    state: &AppState,
+    search: &SavedSearchRow,
+    new_capture_ids: &[Uuid],
+) -> Option<Uuid> {
+    if !state.action_config.enabled {
+        return None;
+    }
+
+    let title = format!(
+        "Saved search \"{}\": {} new match{}",
+        search.name,
+        new_capture_ids.len(),
+        if new_capture_ids.len() == 1 { "" } else { "es" }
+    );
+    let payload = serde_json::json!({
+        "title": title,
+        "body": format!("New captures matching \"{}\"", search.query),
+    });
+
+    match state.task_store.create(
+        title,
+        engram_action::ActionType::Notification,
+        payload.to_string(),
+        None,
+        new_capture_ids.first().copied(),
+        None,
+    ) {
+        Ok(task) => {
+            let created = DomainEvent::TaskCreated {
+                task_id: task.id,
+                action_type: task.action_type.to_string(),
+                source: format!("saved_search:{}", search.id),
+                timestamp: Timestamp::now(),
+            };
+            if let Err(e) = state.commit_and_publish(created, |conn| {
+                engram_storage::store_task(conn, &task.to_row())
+            }) {
+                warn!(search_id = %search.id, error = %e, "Failed to persist saved search notification");
+            }
+            Some(task.id)
+        }
+        Err(e) => {
+            warn!(search_id = %search.id, error = %e, "Failed to create saved search notification");
+            None
+        }
+    }
+}
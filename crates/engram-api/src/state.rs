@@ -8,12 +8,42 @@ use std::sync::atomic::{AtomicBool, AtomicU64};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
-use engram_core::config::EngramConfig;
+use engram_core::config::{ConfigChange, EngramConfig};
+use engram_core::error::EngramError;
 use engram_dictation::DictationEngine;
 use engram_storage::{Database, FtsSearch, QueryService};
 use engram_vector::embedding::{DynEmbeddingService, MockEmbedding};
 use engram_vector::{EngramPipeline, SearchEngine, VectorIndex};
 
+use tokio::sync::watch;
+use tracing::warn;
+
+use crate::event_stream::{EventStream, LOG_ID_FIELD};
+
+/// A domain event appended to the event log in the transaction of the write
+/// it describes, then published by [`AppState::publish_committed`] once that
+/// write commits.
+pub struct PendingEvent {
+    payload: serde_json::Value,
+    log_id: Option<i64>,
+}
+
+impl PendingEvent {
+    pub fn new(event: engram_core::events::DomainEvent) -> Self {
+        Self {
+            payload: event.to_json(),
+            log_id: None,
+        }
+    }
+
+    /// Append the event to the event log on `conn`, the connection of the
+    /// write's transaction.
+    pub fn append(&mut self, conn: &rusqlite::Connection) -> Result<(), EngramError> {
+        self.log_id = Some(engram_storage::append_event_log_json(conn, &self.payload)?);
+        Ok(())
+    }
+}
+
 /// Shared application state.
 ///
 /// All fields use `Arc` for cheap cloning across handler tasks.
@@ -22,6 +52,8 @@ use engram_vector::{EngramPipeline, SearchEngine, VectorIndex};
 pub struct AppState {
     /// Application configuration.
     pub config: Arc<Mutex<EngramConfig>>,
+    /// Latest applied configuration, for subsystems that reload live.
+    pub config_tx: watch::Sender<EngramConfig>,
     /// In-memory vector index for semantic search.
     pub vector_index: Arc<VectorIndex>,
     /// SQLite database for persistent storage.
@@ -36,6 +68,8 @@ pub struct AppState {
     pub query_service: Arc<QueryService>,
     /// Broadcast sender for SSE events.
     pub event_tx: tokio::sync::broadcast::Sender<serde_json::Value>,
+    /// ID assignment, replay buffer and fan-out for `/stream`.
+    pub event_stream: Arc<EventStream>,
     /// Server start time for uptime calculation.
     pub start_time: Instant,
     /// Path to the config file for persistence.
@@ -58,6 +92,8 @@ pub struct AppState {
     pub action_config: engram_action::ActionConfig,
     /// Chat orchestrator for the conversational interface.
     pub chat: Option<Arc<engram_chat::ChatOrchestrator>>,
+    /// Running and recently finished imports.
+    pub imports: Arc<engram_import::ImportJobs>,
 }
 
 impl AppState {
@@ -86,6 +122,7 @@ impl AppState {
         config_path: PathBuf,
     ) -> Self {
         let (event_tx, _) = tokio::sync::broadcast::channel(256);
+        let (config_tx, _) = watch::channel(config.clone());
         let db_arc = Arc::new(database);
         let index_arc = vector_index;
 
@@ -96,6 +133,10 @@ impl AppState {
         ));
         let fts_search = Arc::new(FtsSearch::new(Arc::clone(&db_arc)));
         let query_service = Arc::new(QueryService::new(Arc::clone(&db_arc)));
+        let event_stream = Arc::new(EventStream::new(
+            Arc::clone(&db_arc),
+            config.general.stream_replay_events,
+        ));
 
         let action_config = engram_action::ActionConfig::default();
         let task_store = Arc::new(engram_action::TaskStore::new());
@@ -113,6 +154,7 @@ impl AppState {
 
         Self {
             config: Arc::new(Mutex::new(config)),
+            config_tx,
             vector_index: index_arc,
             database: db_arc,
             pipeline: Arc::new(pipeline),
@@ -120,6 +162,7 @@ impl AppState {
             fts_search,
             query_service,
             event_tx,
+            event_stream,
             start_time: Instant::now(),
             config_path,
             api_token: String::new(),
@@ -131,6 +174,7 @@ impl AppState {
             orchestrator,
             action_config,
             chat: None,
+            imports: Arc::new(engram_import::ImportJobs::new()),
         }
     }
 
@@ -149,11 +193,135 @@ impl AppState {
         self
     }
 
-    /// Publish a domain event to the SSE broadcast channel.
+    /// Append a domain event to the durable event log and publish it to the
+    /// SSE broadcast channel.
     ///
-    /// Errors are silently ignored (expected when there are no active subscribers).
+    /// A failed log write is logged and the event is still broadcast. Send
+    /// errors are silently ignored (expected when there are no active
+    /// subscribers). Use [`AppState::commit_and_publish`] when the event
+    /// describes a database write.
     pub fn publish_event(&self, event: engram_core::events::DomainEvent) {
-        let _ = self.event_tx.send(event.to_json());
+        self.publish_committed(PendingEvent::new(event));
+    }
+
+    /// Publish an event whose write has committed.
+    ///
+    /// An event that was never appended to the event log, because its write
+    /// ran without a database, is appended now as by
+    /// [`AppState::publish_event`].
+    pub fn publish_committed(&self, event: PendingEvent) {
+        let PendingEvent {
+            mut payload,
+            log_id,
+        } = event;
+        let log_id = match log_id {
+            Some(log_id) => Some(log_id),
+            None => self
+                .database
+                .with_conn(|conn| engram_storage::append_event_log_json(conn, &payload))
+                .map_err(
+                    |e| warn!(error = %e, event = %payload["event"], "Failed to log domain event"),
+                )
+                .ok(),
+        };
+        if let Some(log_id) = log_id {
+            payload[LOG_ID_FIELD] = log_id.into();
+        }
+        let _ = self.event_tx.send(payload);
+    }
+
+    /// Run a database write and append `event` to the event log in the same
+    /// transaction, then publish the event.
+    ///
+    /// Either both the state change and its event are persisted or neither
+    /// is, so log consumers never miss or see phantom events. The event
+    /// reaches `/stream` from the log even if publishing it is lost.
+    pub fn commit_and_publish<T, F>(
+        &self,
+        event: engram_core::events::DomainEvent,
+        write: F,
+    ) -> Result<T, EngramError>
+    where
+        F: FnOnce(&rusqlite::Connection) -> Result<T, EngramError>,
+    {
+        let mut event = PendingEvent::new(event);
+        let value = self.database.with_transaction(|conn| {
+            let value = write(conn)?;
+            event.append(conn)?;
+            Ok(value)
+        })?;
+        self.publish_committed(event);
+        Ok(value)
+    }
+
+    /// Subscribe to configuration changes.
+    ///
+    /// The receiver starts at the current config; `changed()` resolves
+    /// each time [`AppState::apply_config`] applies a new one.
+    pub fn subscribe_config(&self) -> watch::Receiver<EngramConfig> {
+        self.config_tx.subscribe()
+    }
+
+    /// Validate and apply a new configuration.
+    ///
+    /// Replaces the shared config, notifies `subscribe_config()` receivers
+    /// and publishes `ConfigUpdated`. Returns each changed field and whether
+    /// it took effect live or needs a restart; an identical config is a
+    /// no-op. Does not write the config file.
+    ///
+    /// The `ConfigUpdated` event is committed to the event log before the
+    /// config is replaced, so a config that cannot be logged is not applied.
+    pub fn apply_config(&self, updated: EngramConfig) -> Result<Vec<ConfigChange>, EngramError> {
+        self.update_config(|_| Ok(updated), false)
+            .map(|(_, changes)| changes)
+    }
+
+    /// Build a new configuration from the current one and apply it as
+    /// [`AppState::apply_config`] does, returning it with its changes.
+    ///
+    /// `build` runs under the config lock, so concurrent updates (`PUT
+    /// /config`, file reloads) each start from the previous one's result
+    /// instead of undoing it. With `save`, a changed config is written to
+    /// `config_path` before the lock is released, so the file always holds
+    /// the last config applied; a failed write is logged.
+    pub fn update_config<F>(
+        &self,
+        build: F,
+        save: bool,
+    ) -> Result<(EngramConfig, Vec<ConfigChange>), EngramError>
+    where
+        F: FnOnce(&EngramConfig) -> Result<EngramConfig, EngramError>,
+    {
+        let (updated, changes, event) = {
+            let mut config = self.config.lock().unwrap_or_else(|e| e.into_inner());
+            let updated = build(&config)?;
+            updated.validate()?;
+            let changes = config.diff(&updated);
+            if changes.is_empty() {
+                return Ok((updated, changes));
+            }
+
+            let mut changed_sections: Vec<String> =
+                changes.iter().map(|c| c.section().to_string()).collect();
+            changed_sections.dedup();
+            let mut event = PendingEvent::new(engram_core::events::DomainEvent::ConfigUpdated {
+                changed_sections,
+                timestamp: engram_core::types::Timestamp::now(),
+            });
+            self.database.with_transaction(|conn| event.append(conn))?;
+
+            *config = updated.clone();
+            self.config_tx.send_replace(updated.clone());
+            if save {
+                if let Err(e) = updated.save(&self.config_path) {
+                    warn!(error = %e, path = %self.config_path.display(), "Failed to save config to disk");
+                }
+            }
+            (updated, changes, event)
+        };
+
+        self.publish_committed(event);
+        Ok((updated, changes))
     }
 
     /// Set shared audio_active flag and dictation engine.
@@ -12,6 +12,7 @@ use serde_json::Value;
 use tower::ServiceExt;
 use uuid::Uuid;
 
+use engram_api::auth::{mint_token, Scope};
 use engram_api::create_router;
 use engram_api::handlers::{
     AppsResponse, AudioDeviceResponse, HealthResponse, PaginatedResults, PurgeDryRunResponse,
@@ -164,6 +165,22 @@ async fn test_ui_no_auth_required() {
     assert_eq!(resp.status(), StatusCode::OK);
 }
 
+#[tokio::test]
+async fn test_openapi_spec_no_auth_required() {
+    let app = make_app();
+    let resp = app
+        .oneshot(Request::get("/openapi.json").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let bytes = body_bytes(resp).await;
+    let spec: Value = serde_json::from_slice(&bytes).unwrap();
+    assert!(spec["openapi"].as_str().unwrap().starts_with("3."));
+    assert!(spec["paths"]["/ingest"]["post"]["requestBody"].is_object());
+    assert!(spec["components"]["schemas"]["IngestRequest"].is_object());
+}
+
 // =============================================================================
 // Auth scenarios (applied to protected endpoints)
 // =============================================================================
@@ -265,6 +282,9 @@ async fn test_auth_required_on_all_protected_endpoints() {
         "/dictation/stop",
         "/storage/purge",
         "/ingest",
+        "/ingest/batch",
+        "/import",
+        "/export",
     ];
 
     for path in post_endpoints {
@@ -318,6 +338,215 @@ async fn test_auth_required_on_all_protected_endpoints() {
     );
 }
 
+// =============================================================================
+// Scoped API tokens
+// =============================================================================
+
+/// Send a request to `uri` with the given bearer token.
+async fn send_with_token(state: &AppState, method: &str, uri: &str, token: &str) -> StatusCode {
+    let app = create_router(state.clone());
+    app.oneshot(
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", format!("Bearer {}", token))
+            .header("content-type", "application/json")
+            .body(Body::from("{}"))
+            .unwrap(),
+    )
+    .await
+    .unwrap()
+    .status()
+}
+
+#[tokio::test]
+async fn test_scoped_token_enforces_scopes() {
+    let state = make_state();
+    let minted = mint_token(&state.database, "reader", &[Scope::SearchRead], None).unwrap();
+    assert_ne!(minted.record.token_hash, minted.token);
+
+    assert_eq!(
+        send_with_token(&state, "GET", "/recent", &minted.token).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        send_with_token(&state, "GET", "/tasks", &minted.token).await,
+        StatusCode::OK
+    );
+
+    for (method, uri) in [
+        ("GET", "/config"),
+        ("PUT", "/config"),
+        ("POST", "/storage/purge"),
+        ("POST", "/ingest"),
+        ("POST", "/ingest/batch"),
+        ("POST", "/import"),
+        ("POST", "/export"),
+        ("POST", "/tasks"),
+        (
+            "POST",
+            "/actions/00000000-0000-0000-0000-000000000000/approve",
+        ),
+    ] {
+        let status = send_with_token(&state, method, uri, &minted.token).await;
+        assert_eq!(
+            status,
+            StatusCode::FORBIDDEN,
+            "Expected 403 for {} {}",
+            method,
+            uri
+        );
+    }
+}
+
+const SEARCH_WRITES: [(&str, &str); 5] = [
+    ("POST", "/search/saved"),
+    ("PUT", "/search/saved/1"),
+    ("DELETE", "/search/saved/1"),
+    ("POST", "/search/saved/1/run"),
+    ("POST", "/search/log/1/click"),
+];
+
+const CHAT_WRITES: [(&str, &str); 8] = [
+    ("POST", "/chat"),
+    ("POST", "/chat/stream"),
+    (
+        "DELETE",
+        "/chat/stream/00000000-0000-0000-0000-000000000000",
+    ),
+    ("PUT", "/chat/sessions/00000000-0000-0000-0000-000000000000"),
+    (
+        "DELETE",
+        "/chat/sessions/00000000-0000-0000-0000-000000000000",
+    ),
+    ("POST", "/chat/voice/start"),
+    ("POST", "/chat/voice/stop"),
+    ("POST", "/chat/feedback"),
+];
+
+#[tokio::test]
+async fn test_search_read_token_cannot_write() {
+    let state = make_state();
+    let reader = mint_token(&state.database, "reader", &[Scope::SearchRead], None).unwrap();
+
+    for (method, uri) in SEARCH_WRITES.iter().chain(CHAT_WRITES.iter()) {
+        let status = send_with_token(&state, method, uri, &reader.token).await;
+        assert_eq!(
+            status,
+            StatusCode::FORBIDDEN,
+            "Expected 403 for {} {}",
+            method,
+            uri
+        );
+    }
+
+    // Reads on the same paths stay open to `search:read`.
+    for uri in ["/search/saved", "/chat/sessions", "/chat/history"] {
+        assert_ne!(
+            send_with_token(&state, "GET", uri, &reader.token).await,
+            StatusCode::FORBIDDEN,
+            "Expected GET {} to be allowed",
+            uri
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_write_scopes_grant_only_their_routes() {
+    let state = make_state();
+    let searcher = mint_token(&state.database, "searcher", &[Scope::SearchWrite], None).unwrap();
+    let chatter = mint_token(&state.database, "chatter", &[Scope::ChatWrite], None).unwrap();
+
+    for (method, uri) in SEARCH_WRITES {
+        assert_ne!(
+            send_with_token(&state, method, uri, &searcher.token).await,
+            StatusCode::FORBIDDEN,
+            "Expected search:write to allow {} {}",
+            method,
+            uri
+        );
+        assert_eq!(
+            send_with_token(&state, method, uri, &chatter.token).await,
+            StatusCode::FORBIDDEN,
+            "Expected chat:write to be refused {} {}",
+            method,
+            uri
+        );
+    }
+    for (method, uri) in CHAT_WRITES {
+        assert_ne!(
+            send_with_token(&state, method, uri, &chatter.token).await,
+            StatusCode::FORBIDDEN,
+            "Expected chat:write to allow {} {}",
+            method,
+            uri
+        );
+        assert_eq!(
+            send_with_token(&state, method, uri, &searcher.token).await,
+            StatusCode::FORBIDDEN,
+            "Expected search:write to be refused {} {}",
+            method,
+            uri
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_admin_token_implies_all_scopes() {
+    let state = make_state();
+    let minted = mint_token(&state.database, "ops", &[Scope::Admin], None).unwrap();
+    assert_eq!(
+        send_with_token(&state, "GET", "/config", &minted.token).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        send_with_token(&state, "GET", "/recent", &minted.token).await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn test_revoked_and_expired_tokens_rejected() {
+    let state = make_state();
+    let revoked = mint_token(&state.database, "old", &[Scope::SearchRead], None).unwrap();
+    state
+        .database
+        .with_conn(|conn| engram_storage::revoke_api_token(conn, "old"))
+        .unwrap();
+    assert_eq!(
+        send_with_token(&state, "GET", "/recent", &revoked.token).await,
+        StatusCode::UNAUTHORIZED
+    );
+
+    let expired = mint_token(
+        &state.database,
+        "expired",
+        &[Scope::SearchRead],
+        Some(chrono::Utc::now().timestamp() - 1),
+    )
+    .unwrap();
+    assert_eq!(
+        send_with_token(&state, "GET", "/recent", &expired.token).await,
+        StatusCode::UNAUTHORIZED
+    );
+}
+
+#[tokio::test]
+async fn test_scoped_token_records_last_used() {
+    let state = make_state();
+    let minted = mint_token(&state.database, "tracked", &[Scope::SearchRead], None).unwrap();
+    assert_eq!(
+        send_with_token(&state, "GET", "/recent", &minted.token).await,
+        StatusCode::OK
+    );
+
+    let tokens = state
+        .database
+        .with_conn(engram_storage::list_api_tokens)
+        .unwrap();
+    assert!(tokens[0].last_used_at.is_some());
+}
+
 // =============================================================================
 // GET /search
 // =============================================================================
@@ -802,6 +1031,43 @@ async fn test_get_config_happy_path() {
 
 #[tokio::test]
 async fn test_put_config_happy_path() {
+    let app = make_app();
+    let resp = app
+        .oneshot(authed_put_json("/config", r#"{"screen":{"fps":2.0}}"#))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_put_config_deep_merges_nested_sections() {
+    let state = make_state();
+    let before = state.config.lock().unwrap().screen.clone();
+    let app = create_router(state.clone());
+    let resp = app
+        .oneshot(authed_put_json(
+            "/config",
+            r#"{"screen":{"screenshot_storage":{"quality":50}}}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let after = state.config.lock().unwrap().screen.clone();
+    assert_eq!(after.screenshot_storage.quality, 50);
+    assert_eq!(
+        after.screenshot_storage.format,
+        before.screenshot_storage.format
+    );
+    assert_eq!(
+        after.screenshot_storage.max_count,
+        before.screenshot_storage.max_count
+    );
+    assert_eq!(after.fps, before.fps);
+}
+
+#[tokio::test]
+async fn test_put_config_rejects_unknown_nested_field() {
     let app = make_app();
     let resp = app
         .oneshot(authed_put_json(
@@ -810,7 +1076,85 @@ async fn test_put_config_happy_path() {
         ))
         .await
         .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let text = String::from_utf8_lossy(&body_bytes(resp).await).to_string();
+    assert!(text.contains("screen.capture_interval_ms"), "{}", text);
+}
+
+#[tokio::test]
+async fn test_put_config_rejects_out_of_enum_value() {
+    let app = make_app();
+    let resp = app
+        .oneshot(authed_put_json(
+            "/config",
+            r#"{"audio":{"vad_sensitivity":"banana"}}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let text = String::from_utf8_lossy(&body_bytes(resp).await).to_string();
+    assert!(
+        text.contains("audio.vad_sensitivity must be one of"),
+        "{}",
+        text
+    );
+}
+
+#[tokio::test]
+async fn test_get_config_schema() {
+    let app = make_app();
+    let resp = app.oneshot(authed_get("/config/schema")).await.unwrap();
     assert_eq!(resp.status(), StatusCode::OK);
+
+    let json: Value = serde_json::from_slice(&body_bytes(resp).await).unwrap();
+    assert_eq!(json["title"], "EngramConfig");
+    assert!(json["properties"]["screen"].is_object());
+    assert_eq!(
+        json["$defs"]["AudioConfig"]["properties"]["chunk_duration_secs"]["maximum"],
+        300
+    );
+}
+
+#[tokio::test]
+async fn test_put_config_reports_live_and_restart_changes() {
+    let state = make_state();
+    let mut config_rx = state.subscribe_config();
+    let app = create_router(state.clone());
+    let resp = app
+        .oneshot(authed_put_json(
+            "/config",
+            r#"{"screen":{"fps":2.0},"chat":{"voice_hotkey":"Ctrl+Shift+V"}}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let json: Value = serde_json::from_slice(&body_bytes(resp).await).unwrap();
+    assert_eq!(json["screen"]["fps"], 2.0);
+    let changes = json["changes"].as_array().unwrap();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0]["field"], "chat.voice_hotkey");
+    assert_eq!(changes[0]["apply"], "restart");
+    assert_eq!(changes[1]["field"], "screen.fps");
+    assert_eq!(changes[1]["apply"], "live");
+
+    // Subscribed subsystems see the new config.
+    assert!(config_rx.has_changed().unwrap());
+    assert_eq!(config_rx.borrow_and_update().screen.fps, 2.0);
+}
+
+#[tokio::test]
+async fn test_put_config_rejects_invalid_value() {
+    let state = make_state();
+    let app = create_router(state.clone());
+    let resp = app
+        .oneshot(authed_put_json("/config", r#"{"screen":{"fps":0.0}}"#))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let text = String::from_utf8_lossy(&body_bytes(resp).await).to_string();
+    assert!(text.contains("screen.fps"), "{}", text);
+    assert_eq!(state.config.lock().unwrap().screen.fps, 1.0);
 }
 
 #[tokio::test]
@@ -868,6 +1212,60 @@ async fn test_stream_happy_path() {
     assert_eq!(resp.status(), StatusCode::OK);
 }
 
+#[tokio::test]
+async fn test_stream_replays_named_filtered_events() {
+    use engram_core::events::DomainEvent;
+    use engram_core::types::Timestamp;
+    use tokio_stream::StreamExt;
+
+    let state = make_state();
+    let app = create_router(state.clone());
+
+    state.publish_event(DomainEvent::TaskCreated {
+        task_id: Uuid::new_v4(),
+        action_type: "reminder".to_string(),
+        source: "test".to_string(),
+        timestamp: Timestamp::now(),
+    });
+    state.publish_event(DomainEvent::SearchPerformed {
+        query: Some("q".to_string()),
+        result_count: 0,
+        route: "search".to_string(),
+        latency_ms: 1,
+        timestamp: Timestamp::now(),
+    });
+    // Wait for the recorder to buffer both events.
+    for _ in 0..100 {
+        if state.event_stream.last_id() >= 2 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+
+    let resp = app
+        .oneshot(
+            Request::get("/stream?events=task_created")
+                .header("authorization", format!("Bearer {}", TEST_TOKEN))
+                .header("last-event-id", "0")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let mut body = resp.into_body().into_data_stream();
+    let chunk = tokio::time::timeout(std::time::Duration::from_secs(2), body.next())
+        .await
+        .expect("replayed event")
+        .unwrap()
+        .unwrap();
+    let text = String::from_utf8_lossy(&chunk);
+    assert!(text.contains("event: task_created"), "{}", text);
+    assert!(text.contains("id: 1"), "{}", text);
+    assert!(!text.contains("search_performed"), "{}", text);
+}
+
 // =============================================================================
 // GET /search/semantic
 // =============================================================================
@@ -1035,6 +1433,208 @@ async fn test_ingest_missing_text_field_returns_error() {
     );
 }
 
+#[tokio::test]
+async fn test_ingest_batch_mixed_items_and_idempotency() {
+    let app = create_router(make_state());
+    let body = r#"{"items":[
+        {"type":"screen","text":"Quarterly roadmap review","app_name":"Slides"},
+        {"type":"audio","text":"Let's ship on Friday","speaker":"Dana","duration_secs":3.5,
+         "timestamp":"2026-01-05T10:00:00Z","idempotency_key":"call-1"},
+        {"type":"dictation","text":"Remember to email the vendor"},
+        {"type":"document","text":"Meeting notes: budget approved","title":"notes.md",
+         "source_path":"/home/u/notes.md","passage_index":2},
+        {"type":"document","text":"   "}
+    ]}"#;
+
+    let resp = app
+        .clone()
+        .oneshot(authed_post_json("/ingest/batch", body))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json: Value = serde_json::from_slice(&body_bytes(resp).await).unwrap();
+    let results = json["results"].as_array().unwrap();
+    assert_eq!(results.len(), 5);
+    for (i, result) in results.iter().take(4).enumerate() {
+        assert_eq!(result["status"], "stored", "item {}: {}", i, result);
+        assert_eq!(result["index"], i);
+    }
+    assert_eq!(results[4]["status"], "invalid");
+    assert_eq!(json["stored"], 4);
+    assert_eq!(json["failed"], 1);
+    let first_id = results[1]["id"].clone();
+    assert!(first_id.is_string());
+
+    // Replaying a key returns the recorded outcome without ingesting again.
+    let replay =
+        r#"{"items":[{"type":"audio","text":"Let's ship on Friday","idempotency_key":"call-1"}]}"#;
+    let resp = app
+        .oneshot(authed_post_json("/ingest/batch", replay))
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body_bytes(resp).await).unwrap();
+    assert_eq!(json["results"][0]["status"], "duplicate");
+    assert_eq!(json["results"][0]["id"], first_id);
+    assert_eq!(json["results"][0]["idempotency_key"], "call-1");
+    assert_eq!(json["stored"], 0);
+}
+
+#[tokio::test]
+async fn test_ingest_batch_rejects_empty_and_unknown_type() {
+    let resp = make_app()
+        .oneshot(authed_post_json("/ingest/batch", r#"{"items":[]}"#))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = make_app()
+        .oneshot(authed_post_json(
+            "/ingest/batch",
+            r#"{"items":[{"type":"video","text":"x"}]}"#,
+        ))
+        .await
+        .unwrap();
+    assert!(resp.status().is_client_error());
+}
+
+#[tokio::test]
+async fn test_import_runs_in_background_and_reports_progress() {
+    let path = std::env::temp_dir().join(format!("engram-import-{}.jsonl", Uuid::new_v4()));
+    std::fs::write(
+        &path,
+        [
+            r#"{"type":"OCR","content":{"text":"Quarterly roadmap review","timestamp":"2026-01-05T10:00:00Z","app_name":"Keynote"}}"#,
+            r#"{"type":"Audio","content":{"transcription":"Let's ship on Friday","timestamp":"2026-01-05T10:01:00Z"}}"#,
+            r#"{"type":"OCR","content":{"text":"no timestamp here"}}"#,
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+    let app = create_router(make_state());
+
+    let body = serde_json::json!({"path": path, "format": "screenpipe"}).to_string();
+    let resp = app
+        .clone()
+        .oneshot(authed_post_json("/import", &body))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    let job: Value = serde_json::from_slice(&body_bytes(resp).await).unwrap();
+    let id = job["id"].as_str().unwrap().to_string();
+    assert_eq!(job["format"], "screenpipe");
+
+    // Poll below the admin rate limit until the import finishes.
+    let mut job = job;
+    for _ in 0..50 {
+        let resp = app
+            .clone()
+            .oneshot(authed_get(&format!("/import/{}", id)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        job = serde_json::from_slice(&body_bytes(resp).await).unwrap();
+        if job["status"] != "running" {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(job["status"], "completed", "{}", job);
+    assert_eq!(job["progress"]["records"], 3);
+    assert_eq!(job["progress"]["imported"], 2);
+    assert_eq!(job["progress"]["failed"], 1);
+    assert!(job["progress"]["errors"][0]
+        .as_str()
+        .unwrap()
+        .starts_with("line 3:"));
+    assert!(job["finished_at"].is_string());
+
+    let resp = app.oneshot(authed_get("/import")).await.unwrap();
+    let list: Value = serde_json::from_slice(&body_bytes(resp).await).unwrap();
+    assert_eq!(list["total"], 1);
+    assert_eq!(list["jobs"][0]["id"], id.as_str());
+}
+
+#[tokio::test]
+async fn test_import_rejects_bad_requests() {
+    for body in [
+        r#"{"path":"/tmp/export.jsonl","format":"notion"}"#,
+        r#"{"path":"relative/export.jsonl","format":"engram"}"#,
+        r#"{"path":"/nonexistent/export.jsonl","format":"engram"}"#,
+    ] {
+        let resp = make_app()
+            .oneshot(authed_post_json("/import", body))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{}", body);
+    }
+
+    let resp = make_app()
+        .oneshot(authed_get(&format!("/import/{}", Uuid::new_v4())))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_export_writes_tables_and_manifest() {
+    let state = make_state();
+    state
+        .database
+        .with_conn(|conn| {
+            conn.execute_batch(
+                "INSERT INTO captures (id, content_type, timestamp, text)
+                     VALUES ('c1', 'screen', 1767607200, 'Quarterly roadmap review'),
+                            ('c2', 'audio', 1767607260, 'Let''s ship on Friday');",
+            )
+            .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))
+        })
+        .unwrap();
+    let dir = std::env::temp_dir().join(format!("engram-export-{}", Uuid::new_v4()));
+
+    let body = serde_json::json!({
+        "dir": dir,
+        "start": "2026-01-05",
+        "content_types": ["screen"],
+        "tables": ["captures", "tasks"],
+    })
+    .to_string();
+    let resp = create_router(state)
+        .oneshot(authed_post_json("/export", &body))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let manifest: Value = serde_json::from_slice(&body_bytes(resp).await).unwrap();
+    assert_eq!(manifest["total_rows"], 1);
+    assert_eq!(manifest["formats"], serde_json::json!(["jsonl"]));
+    assert_eq!(manifest["tables"][0]["name"], "captures");
+    assert_eq!(manifest["tables"][0]["rows"], 1);
+    assert_eq!(manifest["tables"][1]["rows"], 0);
+
+    let captures = std::fs::read_to_string(dir.join("captures.jsonl")).unwrap();
+    assert!(captures.contains("Quarterly roadmap review"));
+    assert!(!captures.contains("ship on Friday"));
+    assert!(dir.join("manifest.json").is_file());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_export_rejects_bad_requests() {
+    for body in [
+        r#"{"dir":"relative/export"}"#,
+        r#"{"dir":"/tmp/engram-export","formats":["csv"]}"#,
+        r#"{"dir":"/tmp/engram-export","tables":["api_tokens"]}"#,
+        r#"{"dir":"/tmp/engram-export","start":"yesterday"}"#,
+    ] {
+        let resp = make_app()
+            .oneshot(authed_post_json("/export", body))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{}", body);
+    }
+}
+
 #[tokio::test]
 async fn test_ingest_with_optional_fields() {
     let app = make_app();
@@ -1075,6 +1675,41 @@ async fn test_unknown_route_returns_404() {
 // Error response types (API error mapping)
 // =============================================================================
 
+#[tokio::test]
+async fn test_rate_limit_is_per_token_and_route_class() {
+    let state = make_state();
+    state.config.lock().unwrap().rate_limit.search = engram_core::config::RouteBudget {
+        per_sec: 1,
+        burst: 2,
+    };
+    let other = mint_token(&state.database, "script", &[Scope::SearchRead], None).unwrap();
+    let app = create_router(state.clone());
+
+    for _ in 0..2 {
+        let resp = app.clone().oneshot(authed_get("/recent")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+    let resp = app.clone().oneshot(authed_get("/recent")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(resp.headers()["retry-after"], "1");
+    let text = String::from_utf8_lossy(&body_bytes(resp).await).to_string();
+    assert!(text.contains("search"), "{}", text);
+
+    // Another route class and another token keep their own budgets.
+    let resp = app.clone().oneshot(authed_get("/config")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = app
+        .oneshot(
+            Request::get("/recent")
+                .header("authorization", format!("Bearer {}", other.token))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
 #[tokio::test]
 async fn test_error_rate_limited_maps_to_429() {
     let err: engram_api::ApiError = engram_core::error::EngramError::RateLimited.into();
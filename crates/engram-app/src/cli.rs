@@ -3,11 +3,11 @@
 //! Uses `clap` with derive macros for ergonomic argument parsing.
 //! Priority resolution: CLI args > env vars > config file > defaults.
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
 /// Engram — a personal memory engine that captures screen, audio, and dictation.
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[command(name = "engram", version, about)]
 pub struct CliArgs {
     /// Path to the configuration file.
@@ -29,6 +29,88 @@ pub struct CliArgs {
     /// Run without system tray UI.
     #[arg(long = "headless")]
     pub headless: bool,
+
+    /// Administrative command to run instead of starting Engram.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Administrative subcommands.
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Manage scoped API tokens.
+    Token {
+        #[command(subcommand)]
+        action: TokenCommand,
+    },
+    /// Import captures exported from screenpipe, a Rewind-style archive or
+    /// another Engram.
+    Import {
+        /// Line-delimited JSON export to import.
+        path: PathBuf,
+
+        /// Export format: engram, screenpipe or rewind.
+        #[arg(short = 'f', long = "format", default_value = "engram")]
+        format: String,
+    },
+    /// Export captures, tasks, chat history and insights to JSONL/Parquet
+    /// files with a schema manifest.
+    Export {
+        /// Directory to write into; must be empty or not exist yet.
+        dir: PathBuf,
+
+        /// Formats to write: jsonl, parquet (comma-separated).
+        #[arg(
+            short = 'f',
+            long = "format",
+            value_delimiter = ',',
+            default_value = "jsonl"
+        )]
+        formats: Vec<String>,
+
+        /// Only rows from this date on (YYYY-MM-DD or RFC 3339).
+        #[arg(long)]
+        start: Option<String>,
+
+        /// Only rows up to this date, inclusive (YYYY-MM-DD or RFC 3339).
+        #[arg(long)]
+        end: Option<String>,
+
+        /// Only captures of these content types (comma-separated).
+        #[arg(long = "content-type", value_delimiter = ',')]
+        content_types: Vec<String>,
+
+        /// Only these tables (comma-separated). Defaults to all.
+        #[arg(long = "table", value_delimiter = ',')]
+        tables: Vec<String>,
+    },
+}
+
+/// `engram token` subcommands.
+#[derive(Subcommand, Debug, Clone)]
+pub enum TokenCommand {
+    /// Mint a new named token. The token is printed once and stored hashed.
+    Mint {
+        /// Unique name for the token.
+        name: String,
+
+        /// Scope to grant: search:read, search:write, chat:write, ingest:write,
+        /// actions:approve or admin.
+        /// Repeat the flag or separate with commas.
+        #[arg(short = 's', long = "scope", value_delimiter = ',', required = true)]
+        scopes: Vec<String>,
+
+        /// Expire the token after this many days.
+        #[arg(long = "expires-in-days")]
+        expires_in_days: Option<u32>,
+    },
+    /// Revoke a token by name or ID.
+    Revoke {
+        /// Token name or ID.
+        token: String,
+    },
+    /// List tokens with their scopes, expiry and last use.
+    List,
 }
 
 impl CliArgs {
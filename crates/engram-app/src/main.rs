@@ -12,6 +12,9 @@
 //! - Screen capture and OCR (via engram-capture + engram-ocr)
 //! - Audio capture (via engram-audio)
 //! - Dictation hotkey listener (via engram-dictation)
+//!
+//! With `[replay]` enabled, a recorded session from disk stands in for
+//! screen and audio capture on any platform.
 
 mod cli;
 
@@ -46,24 +49,52 @@ fn set_directory_permissions(_path: &std::path::Path) -> Result<(), std::io::Err
 use engram_capture::{CaptureConfig, CaptureService, WindowsCaptureService};
 use engram_ocr::{OcrConfig, OcrService, WindowsOcrService};
 
+/// Seconds between screen captures for the configured FPS.
+fn capture_interval_secs(fps: f64) -> u64 {
+    (1.0 / fps).max(1.0) as u64
+}
+
 /// Run the screen capture + OCR loop as a background task.
+///
+/// Follows `screen.enabled`, `screen.fps` and `screen.save_screenshots`
+/// from the config watch channel.
 async fn screen_capture_loop(
     pipeline: Arc<EngramPipeline>,
-    interval_secs: u64,
-    capture_config: CaptureConfig,
+    mut capture_config: CaptureConfig,
+    mut config_rx: tokio::sync::watch::Receiver<EngramConfig>,
 ) {
-    let capture_service = WindowsCaptureService::new(capture_config);
+    let mut enabled = config_rx.borrow_and_update().screen.enabled;
+    let mut capture_service = WindowsCaptureService::new(capture_config.clone());
     let ocr_service = WindowsOcrService::new(OcrConfig::default());
 
-    tracing::info!(interval_secs, "Screen capture loop started");
+    let interval_secs = capture_interval_secs(capture_config.fps);
+    tracing::info!(interval_secs, enabled, "Screen capture loop started");
 
     let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(interval_secs));
 
     loop {
         interval.tick().await;
 
+        if config_rx.has_changed().unwrap_or(false) {
+            let screen = config_rx.borrow_and_update().screen.clone();
+            enabled = screen.enabled;
+            if screen.fps != capture_config.fps
+                || screen.save_screenshots != capture_config.save_screenshots
+            {
+                capture_config.fps = screen.fps;
+                capture_config.save_screenshots = screen.save_screenshots;
+                capture_service = WindowsCaptureService::new(capture_config.clone());
+                let interval_secs = capture_interval_secs(screen.fps);
+                interval = tokio::time::interval(tokio::time::Duration::from_secs(interval_secs));
+                tracing::info!(interval_secs, "Screen capture settings reloaded");
+            }
+        }
+        if !enabled {
+            continue;
+        }
+
         // Step 1: Capture screenshot.
-        let mut frame = match capture_service.capture_frame().await {
+        let frame = match capture_service.capture_frame().await {
             Ok(f) => f,
             Err(e) => {
                 tracing::debug!(error = %e, "Screen capture skipped (expected on non-Windows)");
@@ -73,37 +104,76 @@ async fn screen_capture_loop(
             }
         };
 
-        // Step 2: If capture returned empty text, run OCR on the screenshot bytes.
-        if frame.text.is_empty() && !frame.image_data.is_empty() {
-            match ocr_service.extract_text(&frame.image_data).await {
-                Ok(text) => frame.text = text,
-                Err(e) => {
-                    tracing::debug!(error = %e, "OCR failed");
-                    continue;
-                }
+        // Steps 2-3: OCR if needed, then ingest.
+        ocr_and_ingest_frame(&pipeline, &ocr_service, frame).await;
+    }
+}
+
+/// OCR a captured frame if it has no text yet, then ingest it.
+async fn ocr_and_ingest_frame(
+    pipeline: &EngramPipeline,
+    ocr_service: &WindowsOcrService,
+    mut frame: engram_core::types::ScreenFrame,
+) {
+    // Step 2: If capture returned empty text, run OCR on the screenshot bytes.
+    if frame.text.is_empty() && !frame.image_data.is_empty() {
+        match ocr_service.extract_text(&frame.image_data).await {
+            Ok(text) => frame.text = text,
+            Err(e) => {
+                tracing::debug!(error = %e, "OCR failed");
+                return;
             }
-            // Drop BMP bytes after OCR to free memory.
-            frame.image_data = Vec::new();
         }
+        // Drop BMP bytes after OCR to free memory.
+        frame.image_data = Vec::new();
+    }
 
-        if frame.text.trim().is_empty() {
-            continue;
-        }
+    if frame.text.trim().is_empty() {
+        return;
+    }
+
+    // Step 3: Ingest (safety -> dedup -> embed -> store).
+    match pipeline.ingest_screen(frame).await {
+        Ok(result) => tracing::debug!(result = ?result, "Screen frame ingested"),
+        Err(e) => tracing::warn!(error = %e, "Screen ingest failed"),
+    }
+}
+
+/// Replay recorded screen frames through OCR and the pipeline.
+async fn replay_screen_loop(
+    pipeline: Arc<EngramPipeline>,
+    capture_service: engram_capture::ReplayCaptureService,
+) {
+    let ocr_service = WindowsOcrService::new(OcrConfig::default());
+    tracing::info!(frames = capture_service.len(), "Screen replay started");
 
-        // Step 3: Ingest (safety -> dedup -> embed -> store).
-        match pipeline.ingest_screen(frame).await {
-            Ok(result) => tracing::debug!(result = ?result, "Screen frame ingested"),
-            Err(e) => tracing::warn!(error = %e, "Screen ingest failed"),
+    while capture_service.remaining() > 0 {
+        match capture_service.capture_frame().await {
+            Ok(frame) => ocr_and_ingest_frame(&pipeline, &ocr_service, frame).await,
+            Err(e) => tracing::warn!(error = %e, "Replay frame skipped"),
         }
     }
+    tracing::info!("Screen replay finished");
 }
 
+/// How often the audio capture loop drains the capture buffer.
+#[cfg(target_os = "windows")]
+const AUDIO_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
 /// Run the audio capture loop as a background task.
+///
+/// Microphone audio is captured into `capture_buffer`, which is drained
+/// every `AUDIO_POLL_INTERVAL`: into the dictation engine while a
+/// dictation session or voice query is listening, otherwise towards the
+/// next `chunk_secs` chunk of ambient audio.
+#[allow(clippy::too_many_arguments)]
 async fn audio_capture_loop(
     pipeline: Arc<EngramPipeline>,
     enabled: bool,
     chunk_secs: u64,
     audio_active: Arc<AtomicBool>,
+    dictation: Arc<engram_dictation::DictationEngine>,
+    capture_buffer: engram_audio::AudioBuffer,
     whisper_model_path: String,
     vad_model_path: String,
 ) {
@@ -120,7 +190,14 @@ async fn audio_capture_loop(
     #[cfg(not(target_os = "windows"))]
     {
         tracing::info!("Audio capture requires Windows WASAPI — skipping on this platform");
-        let _ = (&pipeline, &audio_active, &whisper_model_path, &vad_model_path); // suppress unused warnings
+        let _ = (
+            &pipeline,
+            &audio_active,
+            &dictation,
+            &capture_buffer,
+            &whisper_model_path,
+            &vad_model_path,
+        ); // suppress unused warnings
     }
 
     #[cfg(target_os = "windows")]
@@ -132,7 +209,8 @@ async fn audio_capture_loop(
         use engram_whisper::whisper_service::WhisperService;
         use engram_whisper::{TranscriptionService, WhisperConfig};
 
-        let audio_service = WindowsAudioService::new(WinAudioConfig::default());
+        let audio_service =
+            WindowsAudioService::with_buffer(WinAudioConfig::default(), capture_buffer);
 
         if let Err(e) = audio_service.start().await {
             tracing::warn!(error = %e, "Failed to start audio capture");
@@ -175,6 +253,8 @@ async fn audio_capture_loop(
         };
 
         let sample_rate = audio_service.config().sample_rate;
+        let chunk_samples = sample_rate as usize * chunk_secs as usize;
+        let mut pending: Vec<f32> = Vec::new();
         let mut speech_buffer: Vec<f32> = Vec::new();
 
         tracing::info!(
@@ -183,19 +263,34 @@ async fn audio_capture_loop(
             "Audio processing loop entering — waiting for first tick"
         );
 
-        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(chunk_secs));
+        let mut interval = tokio::time::interval(AUDIO_POLL_INTERVAL);
         loop {
             interval.tick().await;
 
-            // Step 1: Drain the audio buffer accumulated since last tick.
-            let buf_len = audio_service.buffer().len();
-            tracing::debug!(buf_len, "Audio tick — draining buffer");
-            let samples = audio_service.buffer().take();
-            if samples.is_empty() {
-                tracing::debug!("Audio tick — no samples buffered");
+            // Step 1: Drain the audio buffer accumulated since last poll.
+            let polled = audio_service.buffer().take();
+            if polled.is_empty() {
                 continue;
             }
 
+            // While a dictation session or voice query is listening, the
+            // microphone audio is recorded there instead of transcribed as
+            // ambient audio.
+            if dictation.push_audio(&polled).is_ok() {
+                tracing::trace!(
+                    samples = polled.len(),
+                    "Audio poll — recorded for dictation"
+                );
+                continue;
+            }
+
+            pending.extend_from_slice(&polled);
+            if pending.len() < chunk_samples {
+                continue;
+            }
+            let samples = std::mem::take(&mut pending);
+            tracing::debug!(samples = samples.len(), "Audio tick — ambient chunk ready");
+
             // Compute RMS and peak to verify signal is present.
             let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
             let peak = samples.iter().map(|s| s.abs()).fold(0.0f32, f32::max);
@@ -269,6 +364,171 @@ async fn audio_capture_loop(
     }
 }
 
+/// Replay recorded audio through VAD, Whisper and the pipeline.
+///
+/// Each segment is checked with VAD in `chunk_secs` windows, like live
+/// audio; the speech windows are transcribed unless the recording carries
+/// its own transcript.
+async fn replay_audio_loop(
+    pipeline: Arc<EngramPipeline>,
+    audio_service: engram_audio::ReplayAudioService,
+    chunk_secs: u64,
+    audio_active: Arc<AtomicBool>,
+    whisper_model_path: String,
+    vad_model_path: String,
+) {
+    use engram_audio::{
+        AudioCaptureService, SileroVad, SileroVadConfig, VadResult, VoiceActivityDetector,
+    };
+    use engram_whisper::whisper_service::WhisperService;
+    use engram_whisper::{TranscriptionService, WhisperConfig};
+
+    let vad = match SileroVad::new(SileroVadConfig {
+        model_path: vad_model_path,
+        ..SileroVadConfig::default()
+    }) {
+        Ok(v) => Some(v),
+        Err(e) => {
+            tracing::warn!(error = %e, "VAD unavailable — processing all replayed audio");
+            None
+        }
+    };
+    let whisper = match WhisperService::new(WhisperConfig {
+        model_path: whisper_model_path,
+        ..WhisperConfig::default()
+    }) {
+        Ok(w) => Some(w),
+        Err(e) => {
+            tracing::warn!(
+                error = %e,
+                "Whisper unavailable — only replayed audio with a recorded transcript is ingested"
+            );
+            None
+        }
+    };
+
+    if let Err(e) = audio_service.start().await {
+        tracing::warn!(error = %e, "Failed to start audio replay");
+        return;
+    }
+    audio_active.store(true, Ordering::Relaxed);
+
+    while let Some(segment) = audio_service.next_segment().await {
+        let segment = match segment {
+            Ok(s) => s,
+            Err(e) => {
+                tracing::warn!(error = %e, "Replay audio segment skipped");
+                continue;
+            }
+        };
+
+        // Step 1: Voice Activity Detection over chunk-sized windows.
+        let window = (segment.sample_rate as usize * chunk_secs.max(1) as usize).max(1);
+        let speech: Vec<f32> = segment
+            .samples
+            .chunks(window)
+            .filter(|samples| {
+                vad.as_ref()
+                    .is_none_or(|v| v.detect(samples) != VadResult::Silence)
+            })
+            .flatten()
+            .copied()
+            .collect();
+        if speech.is_empty() {
+            tracing::debug!("Replay audio segment is silent");
+            continue;
+        }
+        let duration_secs = speech.len() as f32 / segment.sample_rate.max(1) as f32;
+
+        // Step 2: Use the recorded transcript, or run Whisper.
+        let (text, confidence) = match (segment.transcript, &whisper) {
+            (Some(transcript), _) => (transcript, 1.0),
+            (None, Some(whisper)) => match whisper.transcribe(&speech, segment.sample_rate).await {
+                Ok(result) => {
+                    let confidence = if result.segments.is_empty() {
+                        0.0f32
+                    } else {
+                        result.segments.iter().map(|s| s.confidence).sum::<f32>()
+                            / result.segments.len() as f32
+                    };
+                    (result.text, confidence)
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Whisper transcription failed");
+                    continue;
+                }
+            },
+            (None, None) => continue,
+        };
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        // Step 3: Ingest.
+        let or_unknown = |s: String| {
+            if s.is_empty() {
+                "unknown".to_string()
+            } else {
+                s
+            }
+        };
+        let chunk = engram_core::types::AudioChunk {
+            id: uuid::Uuid::new_v4(),
+            content_type: engram_core::types::ContentType::Audio,
+            timestamp: segment.timestamp,
+            duration_secs,
+            transcription: text,
+            speaker: or_unknown(segment.speaker),
+            source_device: segment.source_device,
+            app_in_focus: or_unknown(segment.app_name),
+            confidence,
+        };
+        match pipeline.ingest_audio(chunk).await {
+            Ok(result) => tracing::debug!(result = ?result, "Replay audio ingested"),
+            Err(e) => tracing::warn!(error = %e, "Audio ingest failed"),
+        }
+    }
+
+    let _ = audio_service.stop().await;
+    audio_active.store(false, Ordering::Relaxed);
+    tracing::info!("Audio replay finished");
+}
+
+/// Scan watched document folders as a background task.
+///
+/// Follows `[documents]` from the config watch channel; a config change
+/// triggers an immediate rescan.
+async fn document_watch_loop(
+    watcher: engram_document::DocumentWatcher,
+    mut config_rx: tokio::sync::watch::Receiver<EngramConfig>,
+) {
+    let mut documents = config_rx.borrow_and_update().documents.clone();
+    tracing::info!(
+        enabled = documents.enabled,
+        dirs = documents.watch_dirs.len(),
+        "Document watch loop started"
+    );
+
+    loop {
+        if documents.enabled {
+            if let Err(e) = watcher.scan(&documents).await {
+                tracing::warn!(error = %e, "Document scan failed");
+            }
+        }
+
+        let wait = tokio::time::Duration::from_secs(documents.scan_interval_secs.max(5));
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            changed = config_rx.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+            }
+        }
+        documents = config_rx.borrow_and_update().documents.clone();
+    }
+}
+
 /// Start the dictation hotkey listener as a background task.
 async fn dictation_listener(
     hotkey: String,
@@ -375,6 +635,108 @@ async fn dictation_listener(
     }
 }
 
+/// Start the voice query hotkey listener as a background task.
+///
+/// The hotkey toggles recording; a recording is also stopped once it
+/// reaches `max_duration_seconds`. Answers are published through the chat
+/// orchestrator for the tray panel.
+async fn voice_query_listener(
+    hotkey: String,
+    chat: Arc<engram_chat::ChatOrchestrator>,
+    max_duration_seconds: u32,
+) {
+    tracing::info!(hotkey = %hotkey, "Voice query listener started");
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        tracing::info!("Voice query hotkey requires Windows — skipping on this platform");
+        let _ = (&chat, max_duration_seconds); // suppress unused warnings
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use engram_dictation::{HotkeyConfig, HotkeyService};
+
+        // HotkeyService contains a raw pointer (!Send), so run on a blocking thread.
+        let _ = tokio::task::spawn_blocking(move || {
+            let hotkey_service = match HotkeyService::new(HotkeyConfig { key: hotkey }) {
+                Ok(s) => {
+                    tracing::info!("Voice query hotkey registered");
+                    s
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to register voice query hotkey");
+                    return;
+                }
+            };
+
+            let rt = tokio::runtime::Handle::current();
+            let max_duration = std::time::Duration::from_secs(max_duration_seconds as u64);
+            let mut started_at: Option<std::time::Instant> = None;
+
+            // Poll for hotkey presses and toggle recording on/off.
+            loop {
+                let pressed = hotkey_service.was_pressed();
+                let timed_out = started_at
+                    .is_some_and(|t| max_duration_seconds > 0 && t.elapsed() >= max_duration);
+                if started_at.is_some() && (pressed || timed_out) {
+                    started_at = None;
+                    match rt.block_on(chat.finish_voice_query()) {
+                        Ok(answer) => tracing::info!(
+                            transcript_len = answer.transcript.len(),
+                            "Voice query answered"
+                        ),
+                        Err(e) => tracing::warn!(error = %e, "Voice query failed"),
+                    }
+                } else if pressed {
+                    match rt.block_on(chat.start_voice_query(None)) {
+                        Ok(_) => {
+                            tracing::info!("Voice query recording via hotkey");
+                            started_at = Some(std::time::Instant::now());
+                        }
+                        Err(e) => tracing::warn!(error = %e, "Failed to start voice query"),
+                    }
+                }
+                std::thread::sleep(std::time::Duration::from_millis(50));
+            }
+        })
+        .await;
+    }
+}
+
+/// Apply CLI flag and environment overrides on top of the config file.
+///
+/// Also used when `config.toml` is reloaded so the overrides keep winning.
+fn apply_cli_overrides(config: &mut EngramConfig, cli_args: &cli::CliArgs) {
+    if let Some(dir) = cli_args.resolve_data_dir() {
+        config.general.data_dir = dir;
+    }
+    if let Some(level) = cli_args.resolve_log_level() {
+        config.general.log_level = level;
+    }
+    // Store the resolved port in config for downstream use.
+    config.general.port = cli_args.resolve_port(config.general.port);
+}
+
+/// Map the `[chat]` config section onto the chat crate's configuration.
+fn chat_config(config: &engram_core::config::ChatConfig) -> engram_chat::ChatConfig {
+    engram_chat::ChatConfig {
+        enabled: config.enabled,
+        voice_hotkey: config.voice_hotkey.clone(),
+        context_turns: config.context_turns,
+        session_timeout_minutes: config.session_timeout_minutes,
+        max_voice_duration_seconds: config.max_voice_duration_seconds,
+        default_search_days: config.default_search_days,
+        max_results_per_query: config.max_results_per_query,
+        llm: engram_chat::ChatLlmConfig {
+            enabled: config.llm.enabled,
+            model_path: config.llm.model_path.clone(),
+            max_tokens: config.llm.max_tokens,
+            temperature: config.llm.temperature,
+        },
+    }
+}
+
 /// Detect and load an ONNX embedding model, falling back to MockEmbedding.
 ///
 /// Checks for `model.onnx` + `tokenizer.json` in the configured model directory
@@ -420,6 +782,33 @@ fn create_embedding_service(
     Box::new(MockEmbedding::new())
 }
 
+/// Load the local GGUF model for chat answers when `[chat.llm]` is enabled.
+///
+/// Returns `None` (extractive answers) when disabled or when the model fails
+/// to load.
+fn create_chat_response_backend(
+    config: &engram_core::config::ChatLlmConfig,
+) -> Option<Arc<dyn engram_chat::ResponseBackend>> {
+    if !config.enabled {
+        return None;
+    }
+    let model_path = std::path::Path::new(&config.model_path);
+    match engram_chat::GgufModel::load(model_path) {
+        Ok(model) => {
+            tracing::info!(model = %model_path.display(), "Chat answers: local LLM");
+            Some(Arc::new(engram_chat::LlmBackend::new(model)))
+        }
+        Err(e) => {
+            tracing::warn!(
+                error = %e,
+                model = %model_path.display(),
+                "Failed to load chat LLM — using extractive answers"
+            );
+            None
+        }
+    }
+}
+
 /// Expand ~ to home directory in a path string.
 fn resolve_data_dir(data_dir: &str) -> std::path::PathBuf {
     if data_dir.starts_with("~/") || data_dir.starts_with("~\\") {
@@ -433,6 +822,169 @@ fn resolve_data_dir(data_dir: &str) -> std::path::PathBuf {
     }
 }
 
+/// Run an `engram token` subcommand against the database and exit.
+fn run_token_command(
+    action: &cli::TokenCommand,
+    db: &Database,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use engram_api::auth::Scope;
+
+    match action {
+        cli::TokenCommand::Mint {
+            name,
+            scopes,
+            expires_in_days,
+        } => {
+            let mut parsed = Vec::new();
+            for s in scopes {
+                match Scope::parse(s.trim()) {
+                    Some(scope) => parsed.push(scope),
+                    None => {
+                        let valid: Vec<&str> = Scope::ALL.iter().map(|s| s.as_str()).collect();
+                        return Err(format!(
+                            "unknown scope '{}' (expected one of: {})",
+                            s,
+                            valid.join(", ")
+                        )
+                        .into());
+                    }
+                }
+            }
+            let expires_at = expires_in_days
+                .map(|days| chrono::Utc::now().timestamp() + i64::from(days) * 86_400);
+            let minted = engram_api::auth::mint_token(db, name, &parsed, expires_at)?;
+            println!("Token '{}' ({})", minted.record.name, minted.record.id);
+            println!("Scopes: {}", minted.record.scopes.join(", "));
+            println!("{}", minted.token);
+            eprintln!("Store this token now; it cannot be shown again.");
+        }
+        cli::TokenCommand::Revoke { token } => {
+            if db.with_conn(|conn| engram_storage::revoke_api_token(conn, token))? {
+                println!("Revoked token '{}'", token);
+            } else {
+                return Err(format!("no active token named '{}'", token).into());
+            }
+        }
+        cli::TokenCommand::List => {
+            let now = chrono::Utc::now().timestamp();
+            let format_epoch = |epoch: Option<i64>| {
+                epoch
+                    .and_then(|e| chrono::DateTime::from_timestamp(e, 0))
+                    .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| "-".to_string())
+            };
+            for t in db.with_conn(engram_storage::list_api_tokens)? {
+                let status = if t.revoked {
+                    "revoked"
+                } else if t.expires_at.is_some_and(|e| e <= now) {
+                    "expired"
+                } else {
+                    "active"
+                };
+                println!(
+                    "{}\t{}\t{}\t{}\texpires={}\tlast_used={}",
+                    t.name,
+                    t.id,
+                    status,
+                    t.scopes.join(","),
+                    format_epoch(t.expires_at),
+                    format_epoch(t.last_used_at),
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run `engram import`: stream an export file into the database.
+///
+/// Progress is written to stderr and the final counts to stdout.
+async fn run_import_command(
+    path: &std::path::Path,
+    format: &str,
+    config: &EngramConfig,
+    data_dir: &std::path::Path,
+    db: Database,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let format: engram_import::ImportFormat = format.parse()?;
+    let db = Arc::new(db);
+    let pipeline = EngramPipeline::new_dyn(
+        Arc::new(VectorIndex::new()),
+        create_embedding_service(config, data_dir),
+        config.safety.clone(),
+        config.search.dedup_threshold,
+    )
+    .with_database(Arc::clone(&db));
+    let importer = engram_import::Importer::new(Arc::new(pipeline), db);
+
+    let progress = importer
+        .import_file(path, format, |p| {
+            let percent = (p.bytes_read * 100)
+                .checked_div(p.total_bytes)
+                .unwrap_or(100);
+            eprint!(
+                "\r{}%  {} records, {} imported, {} duplicates, {} failed",
+                percent, p.records, p.imported, p.duplicates, p.failed
+            );
+        })
+        .await?;
+    eprintln!();
+
+    println!(
+        "Imported {} of {} records from {} ({} duplicates, {} skipped, {} denied, {} failed)",
+        progress.imported,
+        progress.records,
+        path.display(),
+        progress.duplicates,
+        progress.skipped,
+        progress.denied,
+        progress.failed,
+    );
+    for error in &progress.errors {
+        eprintln!("  {}", error);
+    }
+    Ok(())
+}
+
+/// Handle `engram export`: write the selected tables to `dir`.
+fn run_export_command(
+    dir: &std::path::Path,
+    formats: &[String],
+    start: Option<&str>,
+    end: Option<&str>,
+    content_types: &[String],
+    tables: &[String],
+    db: Database,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let options = engram_export::ExportOptions {
+        formats: formats
+            .iter()
+            .map(|f| f.parse())
+            .collect::<Result<Vec<_>, _>>()?,
+        start: start
+            .map(|s| engram_export::ExportOptions::parse_time(s, false))
+            .transpose()?,
+        end: end
+            .map(|s| engram_export::ExportOptions::parse_time(s, true))
+            .transpose()?,
+        content_types: content_types.to_vec(),
+        tables: tables.to_vec(),
+    };
+    let dir = std::path::absolute(dir)?;
+    let manifest = engram_export::DataExporter::new(Arc::new(db)).export(&dir, &options)?;
+
+    for table in &manifest.tables {
+        println!("{:<16} {:>8} rows", table.name, table.rows);
+    }
+    println!(
+        "Exported {} rows to {} (schema v{})",
+        manifest.total_rows(),
+        dir.display(),
+        manifest.schema_version
+    );
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Parse CLI arguments first (before tracing, so --log-level can influence it).
@@ -451,14 +1003,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut config = EngramConfig::load_or_default(&config_file);
 
     // Apply CLI overrides (CLI > env > config > defaults).
-    if let Some(ref dir) = cli_args.resolve_data_dir() {
-        config.general.data_dir = dir.clone();
-    }
-    if let Some(ref level) = cli_args.resolve_log_level() {
-        config.general.log_level = level.clone();
-    }
-    // Store the resolved port in config for downstream use.
-    config.general.port = cli_args.resolve_port(config.general.port);
+    apply_cli_overrides(&mut config, &cli_args);
 
     // Tracing — use the resolved log level.
     let log_filter = &config.general.log_level;
@@ -511,6 +1056,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let db = Database::new(&db_path)?;
     tracing::info!(path = %db_path.display(), "SQLite database opened");
 
+    if let Some(cli::Command::Token { ref action }) = cli_args.command {
+        return run_token_command(action, &db);
+    }
+    if let Some(cli::Command::Import {
+        ref path,
+        ref format,
+    }) = cli_args.command
+    {
+        return run_import_command(path, format, &config, &data_dir, db).await;
+    }
+
+    if let Some(cli::Command::Export {
+        ref dir,
+        ref formats,
+        ref start,
+        ref end,
+        ref content_types,
+        ref tables,
+    }) = cli_args.command
+    {
+        return run_export_command(
+            dir,
+            formats,
+            start.as_deref(),
+            end.as_deref(),
+            content_types,
+            tables,
+            db,
+        );
+    }
+
     // Vector index (single shared instance).
     let index = Arc::new(VectorIndex::new());
     tracing::info!("HNSW vector index initialized");
@@ -539,7 +1115,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         api_embedding,
         config.safety.clone(),
         config.search.dedup_threshold,
-    );
+    )
+    .with_database(Arc::clone(&db_arc));
 
     // Generate or load API authentication token.
     let token_path = data_dir.join(".api_token");
@@ -548,9 +1125,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Shared state for audio and dictation control.
     let audio_active = Arc::new(AtomicBool::new(false));
-
-    // Create dictation engine with Whisper transcription if available.
-    let dictation_engine = {
+    // Microphone capture buffer, drained by the audio loop and by voice
+    // queries when they start and stop.
+    let capture_buffer =
+        engram_audio::AudioBuffer::for_config(&engram_audio::AudioConfig::default());
+
+    // Create dictation engine with Whisper transcription if available. The
+    // same Whisper model transcribes voice queries.
+    let (dictation_engine, speech_whisper) = {
         use engram_whisper::{TranscriptionService, WhisperConfig, WhisperService};
         let dictation_whisper_config = WhisperConfig {
             model_path: data_dir
@@ -563,6 +1145,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         match WhisperService::new(dictation_whisper_config) {
             Ok(whisper) => {
                 let whisper = Arc::new(whisper);
+                let speech_whisper: Arc<dyn engram_whisper::DynTranscriptionService> =
+                    Arc::clone(&whisper) as _;
                 let transcription_fn: engram_dictation::TranscriptionFn =
                     Box::new(move |samples, sample_rate| {
                         let whisper = Arc::clone(&whisper);
@@ -574,16 +1158,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         Ok(result.text)
                     });
                 tracing::info!("Dictation engine initialized with Whisper transcription");
-                Arc::new(engram_dictation::DictationEngine::with_transcription(
-                    transcription_fn,
-                ))
+                (
+                    Arc::new(engram_dictation::DictationEngine::with_transcription(
+                        transcription_fn,
+                    )),
+                    Some(speech_whisper),
+                )
             }
             Err(e) => {
                 tracing::warn!(
                     error = %e,
                     "Whisper unavailable — dictation will use placeholder text"
                 );
-                Arc::new(engram_dictation::DictationEngine::new())
+                (Arc::new(engram_dictation::DictationEngine::new()), None)
             }
         }
     };
@@ -623,7 +1210,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             Arc::clone(&action_task_store),
             action_config.clone(),
         )
-        .with_event_tx(state.event_tx.clone()),
+        .with_event_tx(state.event_tx.clone())
+        .with_event_log(Arc::clone(&state.database)),
     );
 
     let state = state.with_action_engine(
@@ -641,7 +1229,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let intent_event_rx = state.event_tx.subscribe();
         let intent_task_store = Arc::clone(&action_task_store);
         let intent_state = state.clone();
-        let intent_db = Arc::clone(&state.database);
         tokio::spawn(async move {
             use tokio_stream::wrappers::BroadcastStream;
             use tokio_stream::StreamExt as _;
@@ -687,23 +1274,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 detected_at: intent.detected_at.0.to_string(),
                                 acted_on: false,
                             };
-                            if let Err(e) = intent_db
-                                .with_conn(|conn| engram_storage::store_intent(conn, &intent_row))
-                            {
+                            // Persist the intent and its IntentDetected event together
+                            let detected = engram_core::events::DomainEvent::IntentDetected {
+                                intent_id: intent.id,
+                                intent_type: intent.intent_type.to_string(),
+                                confidence: intent.confidence,
+                                source_chunk_id: intent.source_chunk_id,
+                                timestamp: engram_core::types::Timestamp::now(),
+                            };
+                            if let Err(e) = intent_state.commit_and_publish(detected, |conn| {
+                                engram_storage::store_intent(conn, &intent_row)
+                            }) {
                                 tracing::warn!(error = %e, "Failed to persist intent");
                             }
 
-                            // Emit IntentDetected event
-                            intent_state.publish_event(
-                                engram_core::events::DomainEvent::IntentDetected {
-                                    intent_id: intent.id,
-                                    intent_type: intent.intent_type.to_string(),
-                                    confidence: intent.confidence,
-                                    source_chunk_id: intent.source_chunk_id,
-                                    timestamp: engram_core::types::Timestamp::now(),
-                                },
-                            );
-
                             // Create task from intent
                             let action_type = match intent.intent_type {
                                 engram_action::IntentType::Reminder => {
@@ -744,14 +1328,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 Some(intent.source_chunk_id),
                                 intent.extracted_time,
                             ) {
-                                intent_state.publish_event(
-                                    engram_core::events::DomainEvent::TaskCreated {
-                                        task_id: task.id,
-                                        action_type: task.action_type.to_string(),
-                                        source: format!("intent:{}", intent.id),
-                                        timestamp: engram_core::types::Timestamp::now(),
-                                    },
-                                );
+                                let created = engram_core::events::DomainEvent::TaskCreated {
+                                    task_id: task.id,
+                                    action_type: task.action_type.to_string(),
+                                    source: format!("intent:{}", intent.id),
+                                    timestamp: engram_core::types::Timestamp::now(),
+                                };
+                                if let Err(e) = intent_state.commit_and_publish(created, |conn| {
+                                    engram_storage::store_task(conn, &task.to_row())
+                                }) {
+                                    tracing::warn!(error = %e, "Failed to persist task");
+                                }
                             }
                         }
                     }
@@ -775,32 +1362,40 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // === Chat Interface ===
     let state = if config.chat.enabled {
-        let chat_config = engram_chat::ChatConfig {
-            enabled: config.chat.enabled,
-            voice_hotkey: config.chat.voice_hotkey.clone(),
-            context_turns: config.chat.context_turns,
-            session_timeout_minutes: config.chat.session_timeout_minutes,
-            max_voice_duration_seconds: config.chat.max_voice_duration_seconds,
-            default_search_days: config.chat.default_search_days,
-            max_results_per_query: config.chat.max_results_per_query,
-            llm: engram_chat::ChatLlmConfig {
-                enabled: config.chat.llm.enabled,
-                model_path: config.chat.llm.model_path.clone(),
-                max_tokens: config.chat.llm.max_tokens,
-                temperature: config.chat.llm.temperature,
-            },
-        };
+        let chat_config = chat_config(&config.chat);
         // Wire real backends for search, action, analytics, persistence, and events
         let chat_backends = engram_chat::ChatBackends {
             database: Arc::clone(&state.database),
             fts_search: Arc::clone(&state.fts_search),
+            search_engine: Arc::clone(&state.search_engine),
             query_service: Arc::clone(&state.query_service),
             task_store: Arc::clone(&state.task_store),
             intent_detector: engram_action::intent::IntentDetector::new(action_config.clone()),
             event_tx: state.event_tx.clone(),
         };
-        let chat_orchestrator =
-            Arc::new(engram_chat::ChatOrchestrator::new(chat_config).with_backends(chat_backends));
+        let mut chat_orchestrator =
+            engram_chat::ChatOrchestrator::new(chat_config).with_backends(chat_backends);
+        if let Some(backend) = create_chat_response_backend(&config.chat.llm) {
+            chat_orchestrator = chat_orchestrator.with_response_backend(backend);
+        }
+        // Voice queries record through the dictation engine, which the audio
+        // capture loop feeds, and are transcribed with Whisper.
+        if let Some(whisper) = speech_whisper {
+            let voice = engram_chat::VoiceInterface::new(
+                config.chat.voice_hotkey.clone(),
+                config.chat.max_voice_duration_seconds,
+            )
+            .with_source(
+                engram_chat::DictationAudioSource::new(
+                    Arc::clone(&dictation_engine),
+                    engram_audio::AudioConfig::default().sample_rate,
+                )
+                .with_capture_buffer(capture_buffer.clone()),
+            )
+            .with_transcription(whisper);
+            chat_orchestrator = chat_orchestrator.with_voice(voice);
+        }
+        let chat_orchestrator = Arc::new(chat_orchestrator);
         tracing::info!("Chat interface: enabled (with real backends)");
         state.with_chat(chat_orchestrator)
     } else {
@@ -808,6 +1403,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         state
     };
 
+    // === Config hot-reload ===
+    // PUT /config and edits to config.toml both go through
+    // AppState::apply_config; subsystems below follow the config watch channel.
+    let overrides_args = cli_args.clone();
+    engram_api::config_watch::watch_config_file(
+        state.clone(),
+        std::time::Duration::from_secs(2),
+        move |c| apply_cli_overrides(c, &overrides_args),
+    );
+
+    let mut reload_rx = state.subscribe_config();
+    let reload_pipelines = [Arc::clone(&pipeline), Arc::clone(&state.pipeline)];
+    let reload_chat = state.chat.clone();
+    tokio::spawn(async move {
+        while reload_rx.changed().await.is_ok() {
+            let config = reload_rx.borrow_and_update().clone();
+            for p in &reload_pipelines {
+                p.set_safety_config(config.safety.clone());
+            }
+            if let Some(ref chat) = reload_chat {
+                chat.update_config(chat_config(&config.chat));
+            }
+        }
+    });
+
     // === System Tray ===
     // The tray icon must be created AND its event loop run on the same
     // dedicated thread (Win32 requires a message pump for tray events).
@@ -889,8 +1509,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // === Background tasks ===
 
+    // A recorded session replaces live screen and audio capture.
+    let (replay_screen, replay_audio) = if config.replay.enabled {
+        let session_dir = PathBuf::from(&config.replay.session_dir);
+        let screen = engram_capture::ReplayCaptureService::open(&session_dir, config.replay.speed)?;
+        let audio = engram_audio::ReplayAudioService::open(&session_dir, config.replay.speed)?;
+        tracing::info!(
+            dir = %session_dir.display(),
+            frames = screen.len(),
+            audio_segments = audio.len(),
+            "Replaying recorded capture session"
+        );
+        (Some(screen), Some(audio))
+    } else {
+        (None, None)
+    };
+
     // Screen capture + OCR loop.
-    let capture_interval = (1.0 / config.screen.fps).max(1.0) as u64;
     let pipeline_capture = Arc::clone(&pipeline);
 
     // Build CaptureConfig from the loaded TOML config.
@@ -935,12 +1570,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         tracing::info!(dir = %screenshot_dir.display(), "Screenshot saving enabled");
     }
 
-    if config.screen.enabled {
+    if let Some(capture_service) = replay_screen {
         tokio::spawn(async move {
-            screen_capture_loop(pipeline_capture, capture_interval, capture_cfg).await;
+            replay_screen_loop(pipeline_capture, capture_service).await;
         });
     } else {
-        tracing::info!("Screen capture disabled in config");
+        // The loop always runs so `screen.enabled` can be toggled live.
+        if !config.screen.enabled {
+            tracing::info!("Screen capture disabled in config");
+        }
+        let capture_config_rx = state.subscribe_config();
+        tokio::spawn(async move {
+            screen_capture_loop(pipeline_capture, capture_cfg, capture_config_rx).await;
+        });
     }
 
     // Audio capture loop.
@@ -948,6 +1590,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let audio_enabled = config.audio.enabled;
     let audio_chunk_secs = config.audio.chunk_duration_secs as u64;
     let audio_active_clone = Arc::clone(&audio_active);
+    let dictation_audio = Arc::clone(&dictation_engine);
     let whisper_model_path = data_dir
         .join("models")
         .join(format!("ggml-{}.en.bin", config.audio.whisper_model))
@@ -958,17 +1601,33 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .join("silero_vad.onnx")
         .to_string_lossy()
         .to_string();
-    tokio::spawn(async move {
-        audio_capture_loop(
-            pipeline_audio,
-            audio_enabled,
-            audio_chunk_secs,
-            audio_active_clone,
-            whisper_model_path,
-            vad_model_path,
-        )
-        .await;
-    });
+    if let Some(audio_service) = replay_audio {
+        tokio::spawn(async move {
+            replay_audio_loop(
+                pipeline_audio,
+                audio_service,
+                audio_chunk_secs,
+                audio_active_clone,
+                whisper_model_path,
+                vad_model_path,
+            )
+            .await;
+        });
+    } else {
+        tokio::spawn(async move {
+            audio_capture_loop(
+                pipeline_audio,
+                audio_enabled,
+                audio_chunk_secs,
+                audio_active_clone,
+                dictation_audio,
+                capture_buffer,
+                whisper_model_path,
+                vad_model_path,
+            )
+            .await;
+        });
+    }
 
     // Dictation hotkey listener.
     let dictation_hotkey = config.dictation.hotkey.clone();
@@ -978,17 +1637,36 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         dictation_listener(dictation_hotkey, dictation_engine_clone, pipeline_dictation).await;
     });
 
+    // Voice query hotkey listener.
+    if let Some(chat) = state.chat.clone() {
+        let voice_hotkey = config.chat.voice_hotkey.clone();
+        let max_voice_secs = config.chat.max_voice_duration_seconds;
+        tokio::spawn(async move {
+            voice_query_listener(voice_hotkey, chat, max_voice_secs).await;
+        });
+    }
+
+    // Watched document folders. The loop always runs so `documents.enabled`
+    // can be toggled live.
+    let document_watcher =
+        engram_document::DocumentWatcher::new(Arc::clone(&pipeline), Arc::clone(&db_arc));
+    let documents_config_rx = state.subscribe_config();
+    tokio::spawn(async move {
+        document_watch_loop(document_watcher, documents_config_rx).await;
+    });
+
     // Periodic summarization + entity extraction loop.
     if config.insight.enabled {
-        let insight_config = config.insight.clone();
+        let mut insight_config_rx = state.subscribe_config();
         let insight_query_service = state.query_service.clone();
         let insight_state = state.clone();
         tokio::spawn(async move {
-            let interval_mins = insight_config.summary_interval_minutes.max(1) as u64;
-            let min_chunks = insight_config.min_chunks_for_summary as usize;
+            let mut insight_config = insight_config_rx.borrow_and_update().insight.clone();
+            let mut interval_mins = insight_config.summary_interval_minutes.max(1) as u64;
+            let mut min_chunks = insight_config.min_chunks_for_summary as usize;
             let max_bullets = insight_config.max_bullet_points as usize;
 
-            let summarizer = engram_insight::SummarizationService::new(max_bullets, min_chunks);
+            let mut summarizer = engram_insight::SummarizationService::new(max_bullets, min_chunks);
             let entity_extractor = engram_insight::EntityExtractor::new();
 
             let mut interval =
@@ -1002,6 +1680,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 interval.tick().await;
                 tracing::debug!("Insight summarization tick");
 
+                // Pick up summarization settings changed since the last run.
+                if insight_config_rx.has_changed().unwrap_or(false) {
+                    insight_config = insight_config_rx.borrow_and_update().insight.clone();
+                    min_chunks = insight_config.min_chunks_for_summary as usize;
+                    summarizer = engram_insight::SummarizationService::new(
+                        insight_config.max_bullet_points as usize,
+                        min_chunks,
+                    );
+                    let mins = insight_config.summary_interval_minutes.max(1) as u64;
+                    if mins != interval_mins {
+                        interval_mins = mins;
+                        let period = tokio::time::Duration::from_secs(interval_mins * 60);
+                        interval =
+                            tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+                    }
+                }
+
                 let chunks = match insight_query_service.get_chunks_since(last_run_epoch) {
                     Ok(c) => c,
                     Err(e) => {
@@ -1064,24 +1759,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 .collect::<Vec<_>>(),
                         )?;
 
-                        insight_query_service.store_summary(
-                            &summary.id.to_string(),
-                            &summary.title,
-                            &bullet_json,
-                            &chunk_ids_json,
-                            Some(app.as_str()),
-                            Some(&summary.time_range_start.to_string()),
-                            Some(&summary.time_range_end.to_string()),
-                        )?;
-
-                        insight_state.publish_event(
-                            engram_core::events::DomainEvent::SummaryGenerated {
-                                summary_id: summary.id,
-                                chunk_count: app_chunks.len() as u32,
-                                source_app: Some(app.clone()),
-                                timestamp: engram_core::types::Timestamp::now(),
-                            },
-                        );
+                        let generated = engram_core::events::DomainEvent::SummaryGenerated {
+                            summary_id: summary.id,
+                            chunk_count: app_chunks.len() as u32,
+                            source_app: Some(app.clone()),
+                            timestamp: engram_core::types::Timestamp::now(),
+                        };
+                        insight_state.commit_and_publish(generated, |conn| {
+                            engram_storage::store_summary(
+                                conn,
+                                &summary.id.to_string(),
+                                &summary.title,
+                                &bullet_json,
+                                &chunk_ids_json,
+                                Some(app.as_str()),
+                                Some(&summary.time_range_start.to_string()),
+                                Some(&summary.time_range_end.to_string()),
+                            )
+                        })?;
 
                         tracing::info!(
                             app = %app,
@@ -1098,29 +1793,33 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     let first_chunk_id = app_chunks.first().map(|c| c.id).unwrap_or_default();
                     let entities = entity_extractor.extract(&combined_text, first_chunk_id);
                     if !entities.is_empty() {
-                        let mut entity_types_set = std::collections::HashSet::new();
-                        for entity in &entities {
-                            entity_types_set.insert(entity.entity_type.as_str().to_string());
-                            if let Err(e) = insight_query_service.store_entity(
-                                &entity.id.to_string(),
-                                entity.entity_type.as_str(),
-                                &entity.value,
-                                Some(&entity.source_chunk_id.to_string()),
-                                None,
-                                entity.confidence as f64,
-                            ) {
-                                tracing::warn!(error = %e, "Insight: failed to store entity");
+                        let entity_types_set: std::collections::HashSet<String> = entities
+                            .iter()
+                            .map(|entity| entity.entity_type.as_str().to_string())
+                            .collect();
+                        let extracted = engram_core::events::DomainEvent::EntitiesExtracted {
+                            entity_count: entities.len() as u32,
+                            entity_types: entity_types_set.into_iter().collect(),
+                            timestamp: engram_core::types::Timestamp::now(),
+                        };
+                        if let Err(e) = insight_state.commit_and_publish(extracted, |conn| {
+                            for entity in &entities {
+                                engram_storage::store_entity(
+                                    conn,
+                                    &entity.id.to_string(),
+                                    entity.entity_type.as_str(),
+                                    &entity.value,
+                                    Some(&entity.source_chunk_id.to_string()),
+                                    None,
+                                    entity.confidence as f64,
+                                )?;
                             }
+                            Ok(())
+                        }) {
+                            tracing::warn!(error = %e, app = %app, "Insight: failed to store entities");
+                            continue;
                         }
 
-                        insight_state.publish_event(
-                            engram_core::events::DomainEvent::EntitiesExtracted {
-                                entity_count: entities.len() as u32,
-                                entity_types: entity_types_set.into_iter().collect(),
-                                timestamp: engram_core::types::Timestamp::now(),
-                            },
-                        );
-
                         tracing::info!(
                             app = %app,
                             entity_count = entities.len(),
@@ -1132,22 +1831,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         });
 
         // Daily digest scheduler.
-        let digest_config = config.insight.clone();
+        let digest_config_rx = state.subscribe_config();
         let digest_query_service = state.query_service.clone();
         let digest_state = state.clone();
         tokio::spawn(async move {
-            let digest_time_str = digest_config.digest_time.clone();
             let mut last_digest_date = String::new();
 
             loop {
                 tokio::time::sleep(tokio::time::Duration::from_secs(60)).await;
 
+                // Digest time and export settings follow config reloads.
+                let digest_config = digest_config_rx.borrow().insight.clone();
+                let digest_time_str = &digest_config.digest_time;
+
                 let now = chrono::Utc::now();
                 let today = now.format("%Y-%m-%d").to_string();
                 let current_time = now.format("%H:%M").to_string();
 
                 // Only run once per day at the configured time.
-                if current_time != digest_time_str || last_digest_date == today {
+                if &current_time != digest_time_str || last_digest_date == today {
                     continue;
                 }
 
@@ -1233,27 +1935,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                 let content_json = serde_json::to_string(&digest.content).unwrap_or_default();
 
-                if let Err(e) = digest_query_service.store_digest(
-                    &digest.id.to_string(),
-                    &digest.digest_date,
-                    &content_json,
-                    digest.summary_count,
-                    digest.entity_count,
-                    digest.chunk_count,
-                ) {
+                let generated = engram_core::events::DomainEvent::DailyDigestGenerated {
+                    date: today.clone(),
+                    summary_count: digest.summary_count,
+                    entity_count: digest.entity_count,
+                    timestamp: engram_core::types::Timestamp::now(),
+                };
+                if let Err(e) = digest_state.commit_and_publish(generated, |conn| {
+                    engram_storage::store_digest(
+                        conn,
+                        &digest.id.to_string(),
+                        &digest.digest_date,
+                        &content_json,
+                        digest.summary_count,
+                        digest.entity_count,
+                        digest.chunk_count,
+                    )
+                }) {
                     tracing::warn!(error = %e, "Insight: failed to store daily digest");
                     continue;
                 }
 
-                digest_state.publish_event(
-                    engram_core::events::DomainEvent::DailyDigestGenerated {
-                        date: today.clone(),
-                        summary_count: digest.summary_count,
-                        entity_count: digest.entity_count,
-                        timestamp: engram_core::types::Timestamp::now(),
-                    },
-                );
-
                 tracing::info!(
                     date = %today,
                     summaries = digest.summary_count,
@@ -1314,6 +2016,49 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         tracing::info!("Insight pipeline disabled in config");
     }
 
+    // Saved search re-run loop. Each search carries its own interval; this
+    // tick only decides how often we check for searches that are due.
+    let saved_search_state = state.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            let ran = engram_api::saved_search::run_due_saved_searches(&saved_search_state).await;
+            if ran > 0 {
+                tracing::debug!(count = ran, "Saved searches re-run");
+            }
+        }
+    });
+
+    // Event log retention. Runs on the storage purge cadence; the retention
+    // period follows config reloads.
+    let event_log_state = state.clone();
+    let event_log_config_rx = state.subscribe_config();
+    let purge_hours = config.storage.purge_interval_hours.max(1) as u64;
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(purge_hours * 3600));
+        loop {
+            interval.tick().await;
+            let retention_days = event_log_config_rx
+                .borrow()
+                .storage
+                .event_log_retention_days;
+            if retention_days == 0 {
+                continue;
+            }
+            let cutoff = chrono::Utc::now().timestamp() - retention_days as i64 * 86_400;
+            match event_log_state
+                .database
+                .with_conn(|conn| engram_storage::purge_event_log(conn, cutoff))
+            {
+                Ok(0) => {}
+                Ok(deleted) => tracing::info!(deleted, "Event log: purged expired events"),
+                Err(e) => tracing::warn!(error = %e, "Event log: purge failed"),
+            }
+        }
+    });
+
     // === API server ===
 
     let port = config.general.port;
@@ -2,11 +2,14 @@
 //!
 //! Provides trait-based abstractions for audio capture, chunk processing,
 //! and voice activity detection. Includes a mock implementation for testing
-//! without real audio hardware.
+//! without real audio hardware, and a replay source that reads recorded
+//! WAV files from disk.
 
+pub mod replay;
 pub mod silero_vad;
 pub mod windows_audio;
 
+pub use replay::{read_wav, ReplayAudioSegment, ReplayAudioService};
 pub use silero_vad::{SileroVad, SileroVadConfig};
 pub use windows_audio::{AudioBuffer, AudioConfig, WindowsAudioService};
 
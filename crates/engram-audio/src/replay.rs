@@ -0,0 +1,329 @@
+//! Replay of recorded audio from a capture session on disk.
+//!
+//! Reads the `audio` entries of a session's `session.json`, each pointing
+//! at a WAV file relative to the session directory:
+//!
+//! ```json
+//! {
+//!   "started_at": "2026-01-05T10:00:00Z",
+//!   "audio": [
+//!     {
+//!       "timestamp": "2026-01-05T10:00:05Z",
+//!       "wav": "audio/0001.wav",
+//!       "speaker": "Dana",
+//!       "app_name": "Zoom"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Segments are returned in timestamp order, paced like the screen replay.
+//! An entry with `transcript` carries its own transcription so the session
+//! can be replayed where Whisper is unavailable.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tokio::time::Instant;
+
+use engram_core::error::EngramError;
+
+use crate::AudioCaptureService;
+
+/// File name of the session manifest inside a session directory.
+const SESSION_MANIFEST: &str = "session.json";
+
+/// One recorded audio entry.
+#[derive(Debug, Clone, Deserialize)]
+struct ReplayAudioEntry {
+    timestamp: DateTime<Utc>,
+    wav: String,
+    #[serde(default)]
+    speaker: String,
+    #[serde(default)]
+    app_name: String,
+    #[serde(default = "default_source_device")]
+    source_device: String,
+    transcript: Option<String>,
+}
+
+fn default_source_device() -> String {
+    "replay".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+struct AudioManifest {
+    started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    audio: Vec<ReplayAudioEntry>,
+}
+
+/// A decoded audio segment from a replayed session.
+#[derive(Debug, Clone)]
+pub struct ReplayAudioSegment {
+    /// When the segment was recorded.
+    pub timestamp: DateTime<Utc>,
+    /// Mono PCM samples in [-1.0, 1.0].
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+    pub speaker: String,
+    pub app_name: String,
+    pub source_device: String,
+    /// Recorded transcription, used instead of running Whisper.
+    pub transcript: Option<String>,
+}
+
+impl ReplayAudioSegment {
+    /// Length of the segment in seconds.
+    pub fn duration_secs(&self) -> f32 {
+        self.samples.len() as f32 / self.sample_rate.max(1) as f32
+    }
+}
+
+#[derive(Debug, Default)]
+struct ReplayState {
+    next: usize,
+    clock_start: Option<Instant>,
+}
+
+/// Audio source that replays recorded WAV files from a session directory.
+#[derive(Debug)]
+pub struct ReplayAudioService {
+    dir: PathBuf,
+    entries: Vec<ReplayAudioEntry>,
+    started_at: DateTime<Utc>,
+    speed: f64,
+    active: AtomicBool,
+    state: Mutex<ReplayState>,
+}
+
+impl ReplayAudioService {
+    /// Load the audio entries of the session in `dir`.
+    ///
+    /// `speed` scales the delays between segments; 0 replays without delays.
+    pub fn open(dir: &Path, speed: f64) -> Result<Self, EngramError> {
+        let manifest_path = dir.join(SESSION_MANIFEST);
+        let raw = std::fs::read_to_string(&manifest_path).map_err(|e| {
+            EngramError::Audio(format!(
+                "Failed to read replay manifest {}: {}",
+                manifest_path.display(),
+                e
+            ))
+        })?;
+        let manifest: AudioManifest = serde_json::from_str(&raw).map_err(|e| {
+            EngramError::Audio(format!(
+                "Invalid replay manifest {}: {}",
+                manifest_path.display(),
+                e
+            ))
+        })?;
+
+        let mut entries = manifest.audio;
+        entries.sort_by_key(|e| e.timestamp);
+        let started_at = manifest
+            .started_at
+            .or_else(|| entries.first().map(|e| e.timestamp))
+            .unwrap_or_else(Utc::now);
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            entries,
+            started_at,
+            speed,
+            active: AtomicBool::new(false),
+            state: Mutex::new(ReplayState::default()),
+        })
+    }
+
+    /// Total number of recorded segments.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the session has no audio.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Wait for the next segment and decode it.
+    ///
+    /// Returns `None` once every segment has been replayed.
+    pub async fn next_segment(&self) -> Option<Result<ReplayAudioSegment, EngramError>> {
+        let (entry, due) = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let entry = self.entries.get(state.next).cloned()?;
+            state.next += 1;
+            let clock_start = *state.clock_start.get_or_insert_with(Instant::now);
+            let due = clock_start + replay_delay(self.started_at, entry.timestamp, self.speed);
+            (entry, due)
+        };
+        tokio::time::sleep_until(due).await;
+
+        let path = self.dir.join(&entry.wav);
+        let decoded = tokio::task::spawn_blocking(move || read_wav(&path))
+            .await
+            .map_err(|e| EngramError::Audio(format!("WAV decode task failed: {}", e)));
+        Some(
+            decoded
+                .and_then(|r| r)
+                .map(|(samples, sample_rate)| ReplayAudioSegment {
+                    timestamp: entry.timestamp,
+                    samples,
+                    sample_rate,
+                    speaker: entry.speaker,
+                    app_name: entry.app_name,
+                    source_device: entry.source_device,
+                    transcript: entry.transcript,
+                }),
+        )
+    }
+}
+
+impl AudioCaptureService for ReplayAudioService {
+    async fn start(&self) -> Result<(), EngramError> {
+        self.active.store(true, Ordering::Relaxed);
+        tracing::info!(segments = self.entries.len(), "Audio replay started");
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<(), EngramError> {
+        self.active.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+}
+
+/// Delay from the start of replay until an entry recorded at `timestamp` is due.
+fn replay_delay(started_at: DateTime<Utc>, timestamp: DateTime<Utc>, speed: f64) -> Duration {
+    if speed <= 0.0 {
+        return Duration::ZERO;
+    }
+    let offset = (timestamp - started_at).to_std().unwrap_or_default();
+    offset.div_f64(speed)
+}
+
+/// Decode a WAV file into mono f32 samples and its sample rate.
+///
+/// Integer samples are scaled to [-1.0, 1.0]; multi-channel audio is
+/// averaged down to mono.
+pub fn read_wav(path: &Path) -> Result<(Vec<f32>, u32), EngramError> {
+    let reader = hound::WavReader::open(path)
+        .map_err(|e| EngramError::Audio(format!("Failed to open WAV {}: {}", path.display(), e)))?;
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as usize;
+
+    let interleaved: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .into_samples::<f32>()
+            .collect::<Result<_, _>>()
+            .map_err(|e| {
+                EngramError::Audio(format!("Failed to decode WAV {}: {}", path.display(), e))
+            })?,
+        hound::SampleFormat::Int => {
+            let scale = (1_i64 << (spec.bits_per_sample.saturating_sub(1))) as f32;
+            reader
+                .into_samples::<i32>()
+                .map(|s| s.map(|s| s as f32 / scale))
+                .collect::<Result<_, _>>()
+                .map_err(|e| {
+                    EngramError::Audio(format!("Failed to decode WAV {}: {}", path.display(), e))
+                })?
+        }
+    };
+
+    let samples = if channels == 1 {
+        interleaved
+    } else {
+        interleaved
+            .chunks(channels)
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect()
+    };
+    Ok((samples, spec.sample_rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_wav(path: &Path, channels: u16, samples: &[i16]) {
+        let spec = hound::WavSpec {
+            channels,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for &s in samples {
+            writer.write_sample(s).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    #[test]
+    fn test_read_wav_scales_and_downmixes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stereo.wav");
+        write_wav(&path, 2, &[16384, 0, -32768, -32768]);
+
+        let (samples, rate) = read_wav(&path).unwrap();
+        assert_eq!(rate, 16000);
+        assert_eq!(samples, vec![0.25, -1.0]);
+    }
+
+    #[tokio::test]
+    async fn test_replays_segments_in_timestamp_order() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("audio")).unwrap();
+        write_wav(&dir.path().join("audio").join("1.wav"), 1, &[0; 8000]);
+        write_wav(&dir.path().join("audio").join("2.wav"), 1, &[100; 16000]);
+        std::fs::write(
+            dir.path().join(SESSION_MANIFEST),
+            r#"{"frames": [], "audio": [
+                {"timestamp": "2026-01-05T10:00:09Z", "wav": "audio/2.wav", "speaker": "Dana",
+                 "app_name": "Zoom", "transcript": "Ship it Friday"},
+                {"timestamp": "2026-01-05T10:00:01Z", "wav": "audio/1.wav"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let service = ReplayAudioService::open(dir.path(), 0.0).unwrap();
+        assert_eq!(service.len(), 2);
+        service.start().await.unwrap();
+        assert!(service.is_active());
+
+        let first = service.next_segment().await.unwrap().unwrap();
+        assert_eq!(first.duration_secs(), 0.5);
+        assert_eq!(first.source_device, "replay");
+        assert!(first.transcript.is_none());
+
+        let second = service.next_segment().await.unwrap().unwrap();
+        assert_eq!(second.speaker, "Dana");
+        assert_eq!(second.app_name, "Zoom");
+        assert_eq!(second.transcript.as_deref(), Some("Ship it Friday"));
+        assert_eq!(second.duration_secs(), 1.0);
+
+        assert!(service.next_segment().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_missing_wav_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(SESSION_MANIFEST),
+            r#"{"audio": [{"timestamp": "2026-01-05T10:00:00Z", "wav": "gone.wav"}]}"#,
+        )
+        .unwrap();
+
+        let service = ReplayAudioService::open(dir.path(), 0.0).unwrap();
+        let err = service.next_segment().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("gone.wav"), "{}", err);
+    }
+}
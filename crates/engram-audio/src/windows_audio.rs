@@ -63,6 +63,11 @@ impl AudioBuffer {
         }
     }
 
+    /// Create a buffer holding 30 seconds of audio in `config`'s format.
+    pub fn for_config(config: &AudioConfig) -> Self {
+        Self::new((config.sample_rate as usize) * (config.channels as usize) * 30)
+    }
+
     /// Push samples into the buffer. Drops oldest samples if buffer is full.
     pub fn push(&self, data: &[f32]) {
         if let Ok(mut buf) = self.samples.lock() {
@@ -130,12 +135,17 @@ pub struct WindowsAudioService {
 impl WindowsAudioService {
     /// Create a new audio capture service with the given configuration.
     pub fn new(config: AudioConfig) -> Self {
-        // Buffer 30 seconds of audio at the configured sample rate.
-        let max_samples = (config.sample_rate as usize) * (config.channels as usize) * 30;
+        let buffer = AudioBuffer::for_config(&config);
+        Self::with_buffer(config, buffer)
+    }
+
+    /// Create a service that captures into `buffer`, which other consumers
+    /// can hold a clone of.
+    pub fn with_buffer(config: AudioConfig, buffer: AudioBuffer) -> Self {
         Self {
             config,
             active: Arc::new(AtomicBool::new(false)),
-            buffer: AudioBuffer::new(max_samples),
+            buffer,
             #[cfg(target_os = "windows")]
             stream: Mutex::new(None),
         }
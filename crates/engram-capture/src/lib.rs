@@ -1,9 +1,11 @@
 //! Engram Capture crate - Screen capture, window detection, capture session management.
 //!
 //! Provides the CaptureService trait for screen frame capture, a MockCaptureService
-//! for testing, a CaptureSession struct for managing capture lifecycle, and
-//! a WindowsCaptureService for real screen capture on Windows via Win32 GDI.
+//! for testing, a CaptureSession struct for managing capture lifecycle,
+//! a WindowsCaptureService for real screen capture on Windows via Win32 GDI,
+//! and a ReplayCaptureService that replays a recorded session from disk.
 
+pub mod replay;
 pub mod windows_capture;
 
 use chrono::Utc;
@@ -12,6 +14,7 @@ use uuid::Uuid;
 use engram_core::error::EngramError;
 use engram_core::types::{CaptureStatus, ContentType, ScreenFrame};
 
+pub use replay::{ReplayCaptureService, ReplayFrame, SESSION_MANIFEST};
 pub use windows_capture::{
     enumerate_monitors, CaptureConfig, MonitorInfo, MonitorSelectionMode, MonitorSelector,
     WindowsCaptureService,
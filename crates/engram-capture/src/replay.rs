@@ -0,0 +1,278 @@
+//! Replay of a recorded capture session from disk.
+//!
+//! A session directory holds a `session.json` manifest plus the image files
+//! it references:
+//!
+//! ```json
+//! {
+//!   "started_at": "2026-01-05T10:00:00Z",
+//!   "frames": [
+//!     {
+//!       "timestamp": "2026-01-05T10:00:02Z",
+//!       "app_name": "Code",
+//!       "window_title": "main.rs - engram",
+//!       "image": "frames/0001.png"
+//!     },
+//!     {
+//!       "timestamp": "2026-01-05T10:00:07Z",
+//!       "app_name": "Slack",
+//!       "window_title": "#release",
+//!       "text": "Ship it Friday"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Frames are returned in timestamp order, paced by their offset from
+//! `started_at` (or the first frame) divided by the replay speed. A frame
+//! with `text` skips OCR, like a live frame whose capture already had text.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use engram_core::error::EngramError;
+use engram_core::types::{ContentType, ScreenFrame};
+
+use crate::CaptureService;
+
+/// File name of the session manifest inside a session directory.
+pub const SESSION_MANIFEST: &str = "session.json";
+
+/// One recorded screen frame.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReplayFrame {
+    pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub app_name: String,
+    #[serde(default)]
+    pub window_title: String,
+    #[serde(default = "default_monitor_id")]
+    pub monitor_id: String,
+    /// Image file, relative to the session directory.
+    pub image: Option<String>,
+    /// Text already extracted from the frame.
+    pub text: Option<String>,
+    #[serde(default = "default_focused")]
+    pub focused: bool,
+}
+
+fn default_monitor_id() -> String {
+    "replay".to_string()
+}
+
+fn default_focused() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+struct FrameManifest {
+    started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    frames: Vec<ReplayFrame>,
+}
+
+#[derive(Debug, Default)]
+struct ReplayState {
+    next: usize,
+    clock_start: Option<Instant>,
+}
+
+/// Capture service that replays recorded frames from a session directory.
+///
+/// Returns an error once every frame has been replayed.
+#[derive(Debug)]
+pub struct ReplayCaptureService {
+    dir: PathBuf,
+    frames: Vec<ReplayFrame>,
+    started_at: DateTime<Utc>,
+    speed: f64,
+    state: Mutex<ReplayState>,
+}
+
+impl ReplayCaptureService {
+    /// Load the frames of the session in `dir`.
+    ///
+    /// `speed` scales the delays between frames; 0 replays without delays.
+    pub fn open(dir: &Path, speed: f64) -> Result<Self, EngramError> {
+        let manifest_path = dir.join(SESSION_MANIFEST);
+        let raw = std::fs::read_to_string(&manifest_path).map_err(|e| {
+            EngramError::Capture(format!(
+                "Failed to read replay manifest {}: {}",
+                manifest_path.display(),
+                e
+            ))
+        })?;
+        let manifest: FrameManifest = serde_json::from_str(&raw).map_err(|e| {
+            EngramError::Capture(format!(
+                "Invalid replay manifest {}: {}",
+                manifest_path.display(),
+                e
+            ))
+        })?;
+
+        let mut frames = manifest.frames;
+        frames.sort_by_key(|f| f.timestamp);
+        let started_at = manifest
+            .started_at
+            .or_else(|| frames.first().map(|f| f.timestamp))
+            .unwrap_or_else(Utc::now);
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            frames,
+            started_at,
+            speed,
+            state: Mutex::new(ReplayState::default()),
+        })
+    }
+
+    /// Total number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether the session has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Number of frames not yet replayed.
+    pub fn remaining(&self) -> usize {
+        let next = self.state.lock().unwrap_or_else(|e| e.into_inner()).next;
+        self.frames.len().saturating_sub(next)
+    }
+}
+
+/// Delay from the start of replay until an entry recorded at `timestamp` is due.
+pub(crate) fn replay_delay(
+    started_at: DateTime<Utc>,
+    timestamp: DateTime<Utc>,
+    speed: f64,
+) -> Duration {
+    if speed <= 0.0 {
+        return Duration::ZERO;
+    }
+    let offset = (timestamp - started_at).to_std().unwrap_or_default();
+    offset.div_f64(speed)
+}
+
+impl CaptureService for ReplayCaptureService {
+    async fn capture_frame(&self) -> Result<ScreenFrame, EngramError> {
+        let (frame, due) = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(frame) = self.frames.get(state.next).cloned() else {
+                return Err(EngramError::Capture("Replay session finished".into()));
+            };
+            state.next += 1;
+            let clock_start = *state.clock_start.get_or_insert_with(Instant::now);
+            let due = clock_start + replay_delay(self.started_at, frame.timestamp, self.speed);
+            (frame, due)
+        };
+        tokio::time::sleep_until(due).await;
+
+        let image_data = match frame.image {
+            Some(ref image) => {
+                let path = self.dir.join(image);
+                tokio::fs::read(&path).await.map_err(|e| {
+                    EngramError::Capture(format!(
+                        "Failed to read replay image {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?
+            }
+            None => Vec::new(),
+        };
+
+        Ok(ScreenFrame {
+            id: Uuid::new_v4(),
+            content_type: ContentType::Screen,
+            timestamp: frame.timestamp,
+            app_name: frame.app_name,
+            window_title: frame.window_title,
+            monitor_id: frame.monitor_id,
+            text: frame.text.unwrap_or_default(),
+            focused: frame.focused,
+            image_data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_session(dir: &Path, manifest: &str) {
+        std::fs::write(dir.join(SESSION_MANIFEST), manifest).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replays_frames_in_timestamp_order() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("frames")).unwrap();
+        std::fs::write(dir.path().join("frames").join("1.png"), b"PNGDATA").unwrap();
+        write_session(
+            dir.path(),
+            r#"{"frames": [
+                {"timestamp": "2026-01-05T10:00:07Z", "app_name": "Slack", "text": "Ship it Friday"},
+                {"timestamp": "2026-01-05T10:00:02Z", "app_name": "Code",
+                 "window_title": "main.rs", "image": "frames/1.png"}
+            ]}"#,
+        );
+
+        let service = ReplayCaptureService::open(dir.path(), 0.0).unwrap();
+        assert_eq!(service.len(), 2);
+
+        let first = service.capture_frame().await.unwrap();
+        assert_eq!(first.app_name, "Code");
+        assert_eq!(first.window_title, "main.rs");
+        assert_eq!(first.image_data, b"PNGDATA");
+        assert!(first.text.is_empty());
+        assert_eq!(first.monitor_id, "replay");
+        assert_eq!(first.timestamp.to_rfc3339(), "2026-01-05T10:00:02+00:00");
+
+        let second = service.capture_frame().await.unwrap();
+        assert_eq!(second.text, "Ship it Friday");
+        assert!(second.image_data.is_empty());
+
+        assert_eq!(service.remaining(), 0);
+        assert!(service.capture_frame().await.is_err());
+    }
+
+    #[test]
+    fn test_replay_delay_scales_with_speed() {
+        let start: DateTime<Utc> = "2026-01-05T10:00:00Z".parse().unwrap();
+        let at: DateTime<Utc> = "2026-01-05T10:00:04Z".parse().unwrap();
+        assert_eq!(replay_delay(start, at, 1.0), Duration::from_secs(4));
+        assert_eq!(replay_delay(start, at, 2.0), Duration::from_secs(2));
+        assert_eq!(replay_delay(start, at, 0.0), Duration::ZERO);
+        // Entries recorded before `started_at` are due immediately.
+        assert_eq!(replay_delay(at, start, 1.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_missing_manifest_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = ReplayCaptureService::open(dir.path(), 1.0).unwrap_err();
+        assert!(err.to_string().contains("session.json"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_missing_image_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        write_session(
+            dir.path(),
+            r#"{"frames": [{"timestamp": "2026-01-05T10:00:00Z", "image": "gone.png"}]}"#,
+        );
+
+        let service = ReplayCaptureService::open(dir.path(), 0.0).unwrap();
+        let err = service.capture_frame().await.unwrap_err();
+        assert!(err.to_string().contains("gone.png"), "{}", err);
+    }
+}
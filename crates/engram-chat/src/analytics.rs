@@ -0,0 +1,751 @@
+//! Analytics over capture timestamps.
+//!
+//! Focused time is measured from the gaps between consecutive focused
+//! screen captures: a gap of at most [`SESSION_GAP_SECS`] is credited to
+//! the app and window of the earlier capture, and a longer gap ends the
+//! session. Usage is aggregated in SQL with window functions, so every
+//! capture in range counts however many there are. Answers come back as
+//! prose plus an [`AnalyticsTable`] the dashboard can chart.
+
+use std::collections::BTreeMap;
+
+use chrono::{Duration, Local, NaiveDate, TimeZone};
+use serde_json::json;
+
+use engram_core::error::EngramError;
+use engram_storage::Database;
+
+use crate::response::{describe_range, format_timestamp};
+use crate::types::{AnalyticsMetric, AnalyticsTable, ChatResponse, StructuredQuery, TimeRange};
+
+/// Longest gap between captures still counted as continuous use.
+pub const SESSION_GAP_SECS: i64 = 300;
+
+/// Days shown by a trend when the question names no range.
+const TREND_DAYS: i64 = 7;
+
+/// Confidence of answers computed from capture timestamps.
+const ANALYTICS_CONFIDENCE: f32 = 0.9;
+
+/// Totals for one app, window or day.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Usage {
+    focused_secs: i64,
+    captures: u64,
+    sessions: u64,
+    first: Option<i64>,
+    last: Option<i64>,
+}
+
+/// What [`AnalyticsEngine::usage`] groups captures by.
+#[derive(Debug, Clone, Copy)]
+enum Group {
+    App,
+    Window,
+    /// Local calendar day, as `YYYY-MM-DD`.
+    Day,
+    /// Every capture under one empty key.
+    All,
+}
+
+impl Group {
+    fn key_sql(self) -> &'static str {
+        match self {
+            Group::App => "app_name",
+            Group::Window => {
+                "CASE WHEN trim(window_title) = '' THEN '(untitled)' ELSE window_title END"
+            }
+            Group::Day => "date(timestamp, 'unixepoch', 'localtime')",
+            Group::All => "''",
+        }
+    }
+}
+
+/// Answers analytics questions from the `captures` and `app_activity`
+/// tables.
+pub(crate) struct AnalyticsEngine<'a> {
+    db: &'a Database,
+    /// Rows listed in prose; tables keep every row.
+    limit: usize,
+}
+
+impl<'a> AnalyticsEngine<'a> {
+    pub(crate) fn new(db: &'a Database, limit: usize) -> Self {
+        Self {
+            db,
+            limit: limit.max(1),
+        }
+    }
+
+    /// Answer `metric` for `query`. `explicit` is the time range the query
+    /// named, as opposed to the parser's default window.
+    pub(crate) fn answer(
+        &self,
+        metric: AnalyticsMetric,
+        query: &StructuredQuery,
+        explicit: Option<&TimeRange>,
+    ) -> ChatResponse {
+        let result = match metric {
+            AnalyticsMetric::TimeSpent => self.time_spent(query),
+            AnalyticsMetric::Count => self.count(query),
+            AnalyticsMetric::FirstLastSeen => self.first_last_seen(query, explicit),
+            AnalyticsMetric::Trend => self.trend(query, explicit),
+        };
+        result.unwrap_or_else(|e| {
+            tracing::warn!("Chat analytics query failed: {}", e);
+            ChatResponse {
+                answer: "I couldn't compute that from your captures right now.".to_string(),
+                sources: vec![],
+                confidence: 0.0,
+                suggestions: vec!["Try again".to_string()],
+                table: None,
+                sections: vec![],
+            }
+        })
+    }
+
+    /// Focused time per app, or per window of the named app.
+    fn time_spent(&self, query: &StructuredQuery) -> Result<ChatResponse, EngramError> {
+        let range = query.time_range.as_ref();
+        let by_window = query.app_filter.is_some();
+        let group = if by_window { Group::Window } else { Group::App };
+        let usage = self.usage(query, range, group)?;
+        let subject = subject(query);
+        let period = range.map(describe_range).unwrap_or_default();
+        let mut rows: Vec<(String, Usage)> = usage.into_iter().collect();
+        rows.sort_by(|a, b| {
+            b.1.focused_secs
+                .cmp(&a.1.focused_secs)
+                .then(b.1.captures.cmp(&a.1.captures))
+        });
+
+        let total: i64 = rows.iter().map(|(_, u)| u.focused_secs).sum();
+        if rows.is_empty() {
+            return Ok(nothing_found(&subject, &period, query));
+        }
+        let sessions = self
+            .usage(query, range, Group::All)?
+            .remove("")
+            .unwrap_or_default();
+        let mut answer = format!(
+            "You spent {} focused {}{} across {} {}.",
+            format_duration(total),
+            subject,
+            period,
+            sessions.sessions,
+            plural(sessions.sessions, "session", "sessions"),
+        );
+        let top: Vec<String> = rows
+            .iter()
+            .filter(|(_, u)| u.focused_secs > 0)
+            .take(self.limit)
+            .map(|(name, u)| {
+                format!(
+                    "{} {} ({}%)",
+                    name,
+                    format_duration(u.focused_secs),
+                    percent(u.focused_secs, total)
+                )
+            })
+            .collect();
+        if !top.is_empty() {
+            let label = if by_window { "Top windows" } else { "Top apps" };
+            answer.push_str(&format!(" {}: {}.", label, top.join(", ")));
+        }
+
+        let table = AnalyticsTable {
+            title: if by_window {
+                format!("Focused time by window{}", period)
+            } else {
+                format!("Focused time by app{}", period)
+            },
+            columns: vec![
+                if by_window { "Window" } else { "App" }.to_string(),
+                "Minutes".to_string(),
+                "Captures".to_string(),
+                "Sessions".to_string(),
+            ],
+            rows: rows
+                .iter()
+                .map(|(name, u)| {
+                    vec![
+                        json!(name),
+                        json!(minutes(u.focused_secs)),
+                        json!(u.captures),
+                        json!(u.sessions),
+                    ]
+                })
+                .collect(),
+            value_column: 1,
+        };
+        Ok(analytics_response(answer, table, follow_ups(query)))
+    }
+
+    /// Capture counts per app, or per day for the named app.
+    fn count(&self, query: &StructuredQuery) -> Result<ChatResponse, EngramError> {
+        let range = query.time_range.as_ref();
+        let subject = subject(query);
+        let period = range.map(describe_range).unwrap_or_default();
+        let by_day = query.app_filter.is_some() || !query.topics.is_empty();
+        let (days, apps) = if by_day {
+            (self.usage_by_day(query, range)?, BTreeMap::new())
+        } else {
+            (BTreeMap::new(), self.usage(query, range, Group::App)?)
+        };
+        let total: u64 = days.values().chain(apps.values()).map(|u| u.captures).sum();
+        if total == 0 {
+            return Ok(nothing_found(&subject, &period, query));
+        }
+
+        let (answer, table) = if by_day {
+            let sessions: u64 = days.values().map(|u| u.sessions).sum();
+            let answer = format!(
+                "You have {} {} {}{}, on {} {} and across {} {}.",
+                total,
+                plural(total, "capture", "captures"),
+                subject,
+                period,
+                days.len(),
+                plural(days.len() as u64, "day", "days"),
+                sessions,
+                plural(sessions, "session", "sessions"),
+            );
+            let table = AnalyticsTable {
+                title: format!("Captures {} by day{}", subject, period),
+                columns: vec!["Day".to_string(), "Captures".to_string()],
+                rows: days
+                    .iter()
+                    .map(|(day, u)| vec![json!(day.to_string()), json!(u.captures)])
+                    .collect(),
+                value_column: 1,
+            };
+            (answer, table)
+        } else {
+            let mut apps: Vec<(String, Usage)> = apps.into_iter().collect();
+            apps.sort_by(|a, b| b.1.captures.cmp(&a.1.captures).then(a.0.cmp(&b.0)));
+            let top: Vec<String> = apps
+                .iter()
+                .take(self.limit)
+                .map(|(app, u)| format!("{} {}", app, u.captures))
+                .collect();
+            let answer = format!(
+                "You have {} {}{}. By app: {}.",
+                total,
+                plural(total, "capture", "captures"),
+                period,
+                top.join(", ")
+            );
+            let table = AnalyticsTable {
+                title: format!("Captures by app{}", period),
+                columns: vec!["App".to_string(), "Captures".to_string()],
+                rows: apps
+                    .iter()
+                    .map(|(app, u)| vec![json!(app), json!(u.captures)])
+                    .collect(),
+                value_column: 1,
+            };
+            (answer, table)
+        };
+        Ok(analytics_response(answer, table, follow_ups(query)))
+    }
+
+    /// First and last capture per app. Without a named range this covers
+    /// all captures plus the `app_activity` aggregates.
+    fn first_last_seen(
+        &self,
+        query: &StructuredQuery,
+        explicit: Option<&TimeRange>,
+    ) -> Result<ChatResponse, EngramError> {
+        let mut apps = self.usage(query, explicit, Group::App)?;
+        if explicit.is_none() && query.topics.is_empty() {
+            for (app, first, last, captures) in self.app_activity()? {
+                if !app_matches(&app, query.app_filter.as_deref()) {
+                    continue;
+                }
+                let usage = apps.entry(app).or_default();
+                usage.first = Some(usage.first.map_or(first, |f| f.min(first)));
+                usage.last = Some(usage.last.map_or(last, |l| l.max(last)));
+                usage.captures = usage.captures.max(captures);
+            }
+        }
+        let subject = subject(query);
+        let period = explicit.map(describe_range).unwrap_or_default();
+        let mut rows: Vec<(String, Usage)> = apps
+            .into_iter()
+            .filter(|(_, u)| u.first.is_some())
+            .collect();
+        if rows.is_empty() {
+            return Ok(nothing_found(&subject, &period, query));
+        }
+        rows.sort_by_key(|(_, u)| std::cmp::Reverse(u.last));
+
+        let first = rows.iter().filter_map(|(_, u)| u.first).min().unwrap_or(0);
+        let last = rows.iter().filter_map(|(_, u)| u.last).max().unwrap_or(0);
+        let captures: u64 = rows.iter().map(|(_, u)| u.captures).sum();
+        let mut answer = format!(
+            "{} at {} and last at {}{} ({} {}).",
+            first_seen_phrase(query),
+            format_timestamp(first),
+            format_timestamp(last),
+            period,
+            captures,
+            plural(captures, "capture", "captures"),
+        );
+        if rows.len() > 1 {
+            let recent: Vec<String> = rows
+                .iter()
+                .take(self.limit)
+                .map(|(app, u)| format!("{} {}", app, format_timestamp(u.last.unwrap_or(0))))
+                .collect();
+            answer.push_str(&format!(" Most recent: {}.", recent.join(", ")));
+        }
+
+        let table = AnalyticsTable {
+            title: format!("First and last seen{}", period),
+            columns: vec![
+                "App".to_string(),
+                "First seen".to_string(),
+                "Last seen".to_string(),
+                "Captures".to_string(),
+            ],
+            rows: rows
+                .iter()
+                .map(|(app, u)| {
+                    vec![
+                        json!(app),
+                        json!(format_timestamp(u.first.unwrap_or(0))),
+                        json!(format_timestamp(u.last.unwrap_or(0))),
+                        json!(u.captures),
+                    ]
+                })
+                .collect(),
+            value_column: 3,
+        };
+        Ok(analytics_response(answer, table, follow_ups(query)))
+    }
+
+    /// Focused time per day, compared day over day, over the named range
+    /// (at least its last two days) or the last [`TREND_DAYS`] days.
+    fn trend(
+        &self,
+        query: &StructuredQuery,
+        explicit: Option<&TimeRange>,
+    ) -> Result<ChatResponse, EngramError> {
+        let now = Local::now().timestamp();
+        let mut range = explicit.cloned().unwrap_or(TimeRange {
+            start: day_start(local_day(now) - Duration::days(TREND_DAYS - 1)),
+            end: now,
+        });
+        let last_day = local_day(range.end);
+        if local_day(range.start) >= last_day {
+            range.start = day_start(last_day - Duration::days(1));
+        }
+        let usage = self.usage_by_day(query, Some(&range))?;
+        let subject = subject(query);
+        let period = describe_range(&range);
+
+        // Every day of the range, including days without captures.
+        let mut days: Vec<(NaiveDate, Usage)> = Vec::new();
+        let mut day = local_day(range.start);
+        while day <= last_day {
+            days.push((day, usage.get(&day).cloned().unwrap_or_default()));
+            day += Duration::days(1);
+        }
+        if days.iter().all(|(_, u)| u.captures == 0) {
+            return Ok(nothing_found(&subject, &period, query));
+        }
+
+        let (latest_day, latest) = &days[days.len() - 1];
+        let (_, previous) = &days[days.len() - 2];
+        let change = match previous.focused_secs {
+            0 => String::new(),
+            prev => {
+                let delta = (latest.focused_secs - prev) as f64 / prev as f64 * 100.0;
+                format!(" ({:+.0}%)", delta)
+            }
+        };
+        let average = days.iter().map(|(_, u)| u.focused_secs).sum::<i64>() / days.len() as i64;
+        let (busiest_day, busiest) = days
+            .iter()
+            .max_by_key(|(day, u)| (u.focused_secs, std::cmp::Reverse(*day)))
+            .expect("trend covers at least two days");
+        let answer = format!(
+            "Focused time {}: {} on {} vs {} the day before{}. Daily average {} over {} days{}; busiest day {} ({}).",
+            subject,
+            format_duration(latest.focused_secs),
+            latest_day,
+            format_duration(previous.focused_secs),
+            change,
+            format_duration(average),
+            days.len(),
+            period,
+            busiest_day,
+            format_duration(busiest.focused_secs),
+        );
+
+        let table = AnalyticsTable {
+            title: format!("Focused time {} by day{}", subject, period),
+            columns: vec![
+                "Day".to_string(),
+                "Minutes".to_string(),
+                "Captures".to_string(),
+                "Sessions".to_string(),
+            ],
+            rows: days
+                .iter()
+                .map(|(day, u)| {
+                    vec![
+                        json!(day.to_string()),
+                        json!(minutes(u.focused_secs)),
+                        json!(u.captures),
+                        json!(u.sessions),
+                    ]
+                })
+                .collect(),
+            value_column: 1,
+        };
+        Ok(analytics_response(answer, table, follow_ups(query)))
+    }
+
+    /// Usage per `group` of the captures in `range` that match the query's
+    /// app and topic filters.
+    ///
+    /// Gaps are taken with `LEAD` over every focused screen capture, so
+    /// switching to an app the query doesn't match stops the clock; only
+    /// gaps after a matching capture are credited. Sessions are counted
+    /// with `LAG` over each group's matching captures.
+    fn usage(
+        &self,
+        query: &StructuredQuery,
+        range: Option<&TimeRange>,
+        group: Group,
+    ) -> Result<BTreeMap<String, Usage>, EngramError> {
+        let (start, end) = range.map_or((i64::MIN, i64::MAX), |r| (r.start, r.end));
+        let mut params: Vec<rusqlite::types::Value> =
+            vec![start.into(), end.into(), SESSION_GAP_SECS.into()];
+        let mut conditions = vec!["1".to_string()];
+        // Case-insensitive substring matches, the same as the chat search
+        // filter.
+        if let Some(ref app) = query.app_filter {
+            params.push(app.to_lowercase().into());
+            conditions.push(format!("instr(lower(app_name), ?{}) > 0", params.len()));
+        }
+        if !query.topics.is_empty() {
+            let topics: Vec<String> = query
+                .topics
+                .iter()
+                .map(|topic| {
+                    params.push(topic.to_lowercase().into());
+                    let n = params.len();
+                    format!("instr(lower(window_title), ?{n}) > 0 OR instr(lower(text), ?{n}) > 0")
+                })
+                .collect();
+            conditions.push(format!("({})", topics.join(" OR ")));
+        }
+        let sql = format!(
+            "WITH points AS (
+                 SELECT rowid AS seq, timestamp, {key} AS key,
+                        content_type = 'screen' AND COALESCE(focused, 1) != 0 AS focused_screen,
+                        {matches} AS matches
+                 FROM captures
+                 WHERE timestamp >= ?1 AND timestamp <= ?2
+             ),
+             screens AS (
+                 SELECT key, matches,
+                        LEAD(timestamp) OVER (ORDER BY timestamp, seq) - timestamp AS gap
+                 FROM points
+                 WHERE focused_screen
+             ),
+             matched AS (
+                 SELECT key, timestamp,
+                        timestamp - LAG(timestamp) OVER (PARTITION BY key ORDER BY timestamp, seq)
+                            AS since_last
+                 FROM points
+                 WHERE matches
+             )
+             SELECT key, SUM(focused_secs), SUM(captures), SUM(new_session),
+                    MIN(first), MAX(last)
+             FROM (
+                 SELECT key, gap AS focused_secs, 0 AS captures, 0 AS new_session,
+                        NULL AS first, NULL AS last
+                 FROM screens
+                 WHERE matches AND gap <= ?3
+                 UNION ALL
+                 SELECT key, 0, 1, since_last IS NULL OR since_last > ?3, timestamp, timestamp
+                 FROM matched
+             )
+             GROUP BY key",
+            key = group.key_sql(),
+            matches = conditions.join(" AND "),
+        );
+
+        self.db.with_conn(|conn| {
+            let mut stmt = conn
+                .prepare(&sql)
+                .map_err(|e| EngramError::Storage(format!("Analytics prepare: {}", e)))?;
+            let rows = stmt
+                .query_map(rusqlite::params_from_iter(params.iter()), |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        Usage {
+                            focused_secs: row.get(1)?,
+                            captures: row.get::<_, i64>(2)? as u64,
+                            sessions: row.get::<_, i64>(3)? as u64,
+                            first: row.get(4)?,
+                            last: row.get(5)?,
+                        },
+                    ))
+                })
+                .map_err(|e| EngramError::Storage(format!("Analytics query: {}", e)))?;
+            rows.collect::<Result<BTreeMap<_, _>, _>>()
+                .map_err(|e| EngramError::Storage(e.to_string()))
+        })
+    }
+
+    /// [`AnalyticsEngine::usage`] per local day.
+    fn usage_by_day(
+        &self,
+        query: &StructuredQuery,
+        range: Option<&TimeRange>,
+    ) -> Result<BTreeMap<NaiveDate, Usage>, EngramError> {
+        Ok(self
+            .usage(query, range, Group::Day)?
+            .into_iter()
+            .filter_map(|(day, usage)| {
+                Some((NaiveDate::parse_from_str(&day, "%Y-%m-%d").ok()?, usage))
+            })
+            .collect())
+    }
+
+    /// `(app, first_seen, last_seen, capture_count)` from `app_activity`.
+    fn app_activity(&self) -> Result<Vec<(String, i64, i64, u64)>, EngramError> {
+        self.db.with_conn(|conn| {
+            let mut stmt = conn
+                .prepare("SELECT app_name, first_seen, last_seen, capture_count FROM app_activity")
+                .map_err(|e| EngramError::Storage(format!("App activity prepare: {}", e)))?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get::<_, i64>(3)? as u64,
+                    ))
+                })
+                .map_err(|e| EngramError::Storage(format!("App activity query: {}", e)))?;
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(|e| EngramError::Storage(e.to_string()))
+        })
+    }
+}
+
+fn analytics_response(
+    answer: String,
+    table: AnalyticsTable,
+    suggestions: Vec<String>,
+) -> ChatResponse {
+    ChatResponse {
+        answer,
+        sources: vec![],
+        confidence: ANALYTICS_CONFIDENCE,
+        suggestions,
+        table: Some(table),
+        sections: vec![],
+    }
+}
+
+fn nothing_found(subject: &str, period: &str, query: &StructuredQuery) -> ChatResponse {
+    let subject = if query.app_filter.is_some() || !query.topics.is_empty() {
+        format!(" {}", subject)
+    } else {
+        String::new()
+    };
+    ChatResponse {
+        answer: format!("I don't have any captures{}{}.", subject, period),
+        sources: vec![],
+        confidence: ANALYTICS_CONFIDENCE,
+        suggestions: vec![
+            "Which apps did I use most?".to_string(),
+            "What did I do today?".to_string(),
+        ],
+        table: None,
+        sections: vec![],
+    }
+}
+
+/// "You first used VS Code", "Standup first came up" or "Your first
+/// capture was".
+fn first_seen_phrase(query: &StructuredQuery) -> String {
+    match (&query.app_filter, query.topics.is_empty()) {
+        (Some(app), true) => format!("You first used {}", app),
+        (_, false) => format!("\"{}\" first came up", query.topics.join(" ")),
+        (None, true) => "Your first capture was".to_string(),
+    }
+}
+
+/// Follow-up questions about the same app, or screen time overall.
+fn follow_ups(query: &StructuredQuery) -> Vec<String> {
+    match &query.app_filter {
+        Some(app) => vec![
+            format!("How long was I in {} this week?", app),
+            format!("How is my {} time trending?", app),
+            format!("When did I last use {}?", app),
+        ],
+        None => vec![
+            "How long was I in each app this week?".to_string(),
+            "How is my screen time trending?".to_string(),
+            "How many captures do I have today?".to_string(),
+        ],
+    }
+}
+
+/// What the question is about: "in VS Code", "on standup", or "overall".
+fn subject(query: &StructuredQuery) -> String {
+    match (&query.app_filter, query.topics.is_empty()) {
+        (Some(app), true) => format!("in {}", app),
+        (Some(app), false) => format!("on {} in {}", query.topics.join(" "), app),
+        (None, false) => format!("on {}", query.topics.join(" ")),
+        (None, true) => "overall".to_string(),
+    }
+}
+
+/// Case-insensitive substring match, the same as the chat search filter.
+fn app_matches(app: &str, filter: Option<&str>) -> bool {
+    filter.is_none_or(|f| app.to_lowercase().contains(&f.to_lowercase()))
+}
+
+fn local_day(epoch: i64) -> NaiveDate {
+    Local
+        .timestamp_opt(epoch, 0)
+        .single()
+        .map(|dt| dt.date_naive())
+        .unwrap_or_default()
+}
+
+fn day_start(day: NaiveDate) -> i64 {
+    day.and_hms_opt(0, 0, 0)
+        .and_then(|dt| dt.and_local_timezone(Local).earliest())
+        .map(|dt| dt.timestamp())
+        .unwrap_or_default()
+}
+
+/// "2h 5m", "12m" or "40s".
+fn format_duration(secs: i64) -> String {
+    let (hours, mins) = (secs / 3600, secs % 3600 / 60);
+    match (hours, mins) {
+        (0, 0) => format!("{}s", secs),
+        (0, m) => format!("{}m", m),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+fn minutes(secs: i64) -> f64 {
+    (secs as f64 / 6.0).round() / 10.0
+}
+
+fn percent(part: i64, total: i64) -> i64 {
+    if total == 0 {
+        0
+    } else {
+        (part as f64 / total as f64 * 100.0).round() as i64
+    }
+}
+
+fn plural<'s>(n: u64, one: &'s str, many: &'s str) -> &'s str {
+    if n == 1 {
+        one
+    } else {
+        many
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insert(db: &Database, timestamp: i64, app: &str, content_type: &str) {
+        db.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO captures (id, content_type, timestamp, text, app_name)
+                 VALUES (?1, ?2, ?3, '', ?4)",
+                rusqlite::params![
+                    uuid::Uuid::new_v4().to_string(),
+                    content_type,
+                    timestamp,
+                    app
+                ],
+            )
+            .map_err(|e| EngramError::Storage(e.to_string()))
+        })
+        .unwrap();
+    }
+
+    fn query(app: Option<&str>) -> StructuredQuery {
+        StructuredQuery {
+            intent: crate::types::QueryIntent::Question,
+            topics: vec![],
+            people: vec![],
+            time_range: None,
+            content_type: None,
+            app_filter: app.map(str::to_string),
+            data_source: None,
+            analytics: Some(AnalyticsMetric::TimeSpent),
+            composition: None,
+            raw_query: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_usage_credits_gaps_up_to_session_gap() {
+        let db = Database::in_memory().unwrap();
+        for (timestamp, app) in [
+            (0, "Code"),
+            (60, "Code"),
+            (120, "Slack"),
+            // Slack -> Code gap is longer than a session gap.
+            (120 + SESSION_GAP_SECS + 1, "Code"),
+            (120 + SESSION_GAP_SECS + 31, "Code"),
+        ] {
+            insert(&db, timestamp, app, "screen");
+        }
+        // Audio counts as a capture but not towards focused time.
+        insert(&db, 90, "Code", "audio");
+
+        let engine = AnalyticsEngine::new(&db, 5);
+        let usage = engine.usage(&query(None), None, Group::App).unwrap();
+        assert_eq!(usage["Code"].focused_secs, 150);
+        assert_eq!(usage["Code"].captures, 5);
+        assert_eq!(usage["Code"].sessions, 2);
+        assert_eq!(usage["Slack"].focused_secs, 0);
+        assert_eq!(usage["Slack"].first, Some(120));
+
+        let all = engine.usage(&query(None), None, Group::All).unwrap();
+        assert_eq!(all[""].captures, 6);
+        assert_eq!(all[""].sessions, 2);
+    }
+
+    #[test]
+    fn test_usage_switching_to_unmatched_app_stops_clock() {
+        let db = Database::in_memory().unwrap();
+        insert(&db, 0, "Code", "screen");
+        insert(&db, 100, "Chrome", "screen");
+        insert(&db, 400, "Code", "screen");
+
+        let engine = AnalyticsEngine::new(&db, 5);
+        let usage = engine
+            .usage(&query(Some("code")), None, Group::App)
+            .unwrap();
+        assert_eq!(usage["Code"].focused_secs, 100);
+        assert!(!usage.contains_key("Chrome"));
+    }
+
+    #[test]
+    fn test_format_duration_and_minutes() {
+        assert_eq!(format_duration(40), "40s");
+        assert_eq!(format_duration(720), "12m");
+        assert_eq!(format_duration(7500), "2h 5m");
+        assert_eq!(minutes(90), 1.5);
+        assert_eq!(percent(1, 3), 33);
+        assert_eq!(percent(1, 0), 0);
+    }
+}
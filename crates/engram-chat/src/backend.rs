@@ -0,0 +1,624 @@
+//! Answer generation backends for search results.
+//!
+//! [`ExtractiveBackend`] lists the retrieved captures using templates.
+//! [`LlmBackend`] prompts a local language model with the same captures and
+//! asks it to cite them inline by chunk ID; when the model fails or cites
+//! nothing it was given, it falls back to the extractive answer. Model text
+//! can be passed on while it is written, cleaned the same way as the final
+//! answer.
+
+use std::sync::{Arc, LazyLock};
+
+use async_trait::async_trait;
+use regex::Regex;
+use uuid::Uuid;
+
+use crate::error::ChatError;
+use crate::response::{format_timestamp, source_ref, ResponseGenerator, SearchResult};
+use crate::types::{ChatResponse, StructuredQuery};
+
+/// Characters of each capture included in the model prompt.
+const MAX_SOURCE_CHARS: usize = 600;
+
+/// Characters of each capture the stub generator repeats back.
+const STUB_SNIPPET_CHARS: usize = 120;
+
+/// Shape of an inline citation: `h` is a hex digit, anything else literal.
+const CITATION_SHAPE: &str = "[hhhhhhhh-hhhh-hhhh-hhhh-hhhhhhhhhhhh]";
+
+/// `[chunk-id]` citations in answer text, capturing the chunk ID.
+pub(crate) static CITATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[([0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12})\]")
+        .unwrap()
+});
+
+/// How an answer was produced, as reported in `ChatResponseGenerated` events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseMode {
+    /// Composed from templates.
+    Template,
+    /// Written by a language model.
+    Llm,
+}
+
+impl ResponseMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResponseMode::Template => "template",
+            ResponseMode::Llm => "llm",
+        }
+    }
+}
+
+/// Inputs for composing an answer from search results.
+pub struct GenerationRequest<'a> {
+    /// The parsed question.
+    pub query: &'a StructuredQuery,
+    /// Retrieved captures, best first.
+    pub results: &'a [SearchResult],
+    /// Maximum number of results to use.
+    pub max_results: usize,
+    /// Token budget for model-written answers.
+    pub max_tokens: u32,
+    /// Sampling temperature for model-written answers.
+    pub temperature: f32,
+    /// Receives model-written answer text in pieces as it is produced.
+    /// Template answers are not sent here.
+    pub on_delta: Option<&'a (dyn Fn(&str) + Send + Sync)>,
+}
+
+/// A composed answer and how it was produced.
+#[derive(Debug, Clone)]
+pub struct GeneratedResponse {
+    pub response: ChatResponse,
+    pub mode: ResponseMode,
+}
+
+/// Composes chat answers from retrieved captures.
+#[async_trait]
+pub trait ResponseBackend: Send + Sync {
+    /// Compose an answer to `request.query` from `request.results`.
+    async fn generate(&self, request: &GenerationRequest<'_>) -> GeneratedResponse;
+}
+
+/// Template answers via [`ResponseGenerator::compose_extractive`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExtractiveBackend;
+
+impl ExtractiveBackend {
+    fn compose(&self, request: &GenerationRequest<'_>) -> GeneratedResponse {
+        GeneratedResponse {
+            response: ResponseGenerator::new(request.max_results)
+                .compose_extractive(request.results, request.query),
+            mode: ResponseMode::Template,
+        }
+    }
+}
+
+#[async_trait]
+impl ResponseBackend for ExtractiveBackend {
+    async fn generate(&self, request: &GenerationRequest<'_>) -> GeneratedResponse {
+        self.compose(request)
+    }
+}
+
+/// Completes a text prompt with a language model.
+///
+/// Implementations are blocking; [`LlmBackend`] runs them on the blocking
+/// thread pool.
+pub trait TextGenerator: Send + Sync {
+    /// Continue `prompt` with up to `max_tokens` tokens.
+    fn generate(
+        &self,
+        prompt: &str,
+        max_tokens: u32,
+        temperature: f32,
+    ) -> Result<String, ChatError>;
+
+    /// Like [`Self::generate`], passing each piece of text to `on_text` as
+    /// it is produced. Generation stops early when `on_text` returns
+    /// `false`. The default passes the whole completion as one piece.
+    fn generate_streaming(
+        &self,
+        prompt: &str,
+        max_tokens: u32,
+        temperature: f32,
+        on_text: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<String, ChatError> {
+        let text = self.generate(prompt, max_tokens, temperature)?;
+        on_text(&text);
+        Ok(text)
+    }
+}
+
+/// Retrieval-augmented answers from a local model, with inline citations.
+pub struct LlmBackend {
+    generator: Arc<dyn TextGenerator>,
+    fallback: ExtractiveBackend,
+}
+
+impl LlmBackend {
+    pub fn new(generator: impl TextGenerator + 'static) -> Self {
+        Self {
+            generator: Arc::new(generator),
+            fallback: ExtractiveBackend,
+        }
+    }
+}
+
+#[async_trait]
+impl ResponseBackend for LlmBackend {
+    async fn generate(&self, request: &GenerationRequest<'_>) -> GeneratedResponse {
+        let limited = &request.results[..request.results.len().min(request.max_results)];
+        if limited.is_empty() {
+            return self.fallback.compose(request);
+        }
+
+        let prompt = build_prompt(&request.query.raw_query, limited);
+        let generator = Arc::clone(&self.generator);
+        let (max_tokens, temperature) = (request.max_tokens, request.temperature);
+        // Pieces arrive over a channel whose receiver lives as long as this
+        // future, so dropping the future stops generation.
+        let (tx, mut pieces) = tokio::sync::mpsc::unbounded_channel::<String>();
+        let task = tokio::task::spawn_blocking(move || {
+            generator.generate_streaming(&prompt, max_tokens, temperature, &mut |text| {
+                tx.send(text.to_string()).is_ok()
+            })
+        });
+        if let Some(on_delta) = request.on_delta {
+            let mut filter = CitationFilter::new(limited);
+            while let Some(piece) = pieces.recv().await {
+                let text = filter.push(&piece);
+                if !text.is_empty() {
+                    on_delta(&text);
+                }
+            }
+            let (rest, _) = filter.finish();
+            if !rest.is_empty() {
+                on_delta(&rest);
+            }
+        }
+        let output = task.await.unwrap_or_else(|e| {
+            Err(ChatError::LlmError(format!(
+                "generation task failed: {}",
+                e
+            )))
+        });
+
+        let text = match output {
+            Ok(text) => text,
+            Err(e) => {
+                tracing::warn!("LLM generation failed, using extractive answer: {}", e);
+                return self.fallback.compose(request);
+            }
+        };
+        let Some((answer, cited)) = cite_sources(&text, limited) else {
+            tracing::warn!("LLM answer cited no sources, using extractive answer");
+            return self.fallback.compose(request);
+        };
+
+        let cited: Vec<&SearchResult> = cited.into_iter().map(|i| &limited[i]).collect();
+        let confidence = cited.iter().map(|r| r.relevance_score).sum::<f32>() / cited.len() as f32;
+        GeneratedResponse {
+            response: ChatResponse {
+                answer,
+                sources: cited.iter().map(|r| source_ref(r)).collect(),
+                confidence,
+                suggestions: ResponseGenerator::new(request.max_results)
+                    .generate_suggestions(request.query, limited),
+                table: None,
+                sections: vec![],
+            },
+            mode: ResponseMode::Llm,
+        }
+    }
+}
+
+/// Prompt a model to answer `question` from `results`, citing chunk IDs.
+fn build_prompt(question: &str, results: &[SearchResult]) -> String {
+    let mut prompt = String::from(
+        "You answer questions about the user's own screen, audio and dictation history \
+         using only the sources below. Cite every fact with its source ID in square \
+         brackets, like [id]. If the sources do not answer the question, say so.\n\nSources:\n",
+    );
+    for r in results {
+        let content: String = r
+            .content
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(MAX_SOURCE_CHARS)
+            .collect();
+        prompt.push_str(&format!(
+            "[{}] {} in {}: {}\n",
+            r.chunk_id,
+            format_timestamp(r.timestamp),
+            r.source_app,
+            content
+        ));
+    }
+    prompt.push_str(&format!("\nQuestion: {}\nAnswer:", question.trim()));
+    prompt
+}
+
+/// Drop citations of IDs that are not among `results` and return the
+/// cleaned answer with the cited result indices in order of first citation.
+/// Returns `None` when the answer is empty or cites none of `results`.
+fn cite_sources(text: &str, results: &[SearchResult]) -> Option<(String, Vec<usize>)> {
+    let mut filter = CitationFilter::new(results);
+    let mut answer = filter.push(text);
+    let (rest, cited) = filter.finish();
+    answer.push_str(&rest);
+    if answer.is_empty() || cited.is_empty() {
+        return None;
+    }
+    Some((answer, cited))
+}
+
+/// Cleans model text piece by piece: citations of `results` are
+/// lowercased, other citations dropped, runs of spaces collapsed and the
+/// ends trimmed. Splitting the text differently gives the same output.
+struct CitationFilter<'a> {
+    results: &'a [SearchResult],
+    /// Cited result indices in order of first citation.
+    cited: Vec<usize>,
+    /// Text that may still become a citation, starting with `[`.
+    candidate: String,
+    /// Whitespace held back until non-whitespace follows it.
+    space: String,
+    /// Whether any text was written yet.
+    started: bool,
+}
+
+impl<'a> CitationFilter<'a> {
+    fn new(results: &'a [SearchResult]) -> Self {
+        Self {
+            results,
+            cited: Vec::new(),
+            candidate: String::new(),
+            space: String::new(),
+            started: false,
+        }
+    }
+
+    /// Clean the next piece, returning the text that later pieces cannot
+    /// change.
+    fn push(&mut self, text: &str) -> String {
+        let mut out = String::new();
+        for c in text.chars() {
+            self.read(c, &mut out);
+        }
+        out
+    }
+
+    /// The text held back at the end, and the cited result indices.
+    fn finish(mut self) -> (String, Vec<usize>) {
+        let mut out = String::new();
+        for c in std::mem::take(&mut self.candidate).chars() {
+            self.write(c, &mut out);
+        }
+        (out, self.cited)
+    }
+
+    fn read(&mut self, c: char, out: &mut String) {
+        if self.candidate.is_empty() {
+            if c == '[' {
+                self.candidate.push(c);
+            } else {
+                self.write(c, out);
+            }
+            return;
+        }
+
+        let position = self.candidate.chars().count();
+        let fits = CITATION_SHAPE.chars().nth(position).is_some_and(|shape| {
+            if shape == 'h' {
+                c.is_ascii_hexdigit()
+            } else {
+                c == shape
+            }
+        });
+        if !fits {
+            // Not a citation: keep the `[` and read the rest again, since
+            // a citation may start later in it.
+            let rest: String = std::mem::take(&mut self.candidate)
+                .chars()
+                .skip(1)
+                .chain(std::iter::once(c))
+                .collect();
+            self.write('[', out);
+            for c in rest.chars() {
+                self.read(c, out);
+            }
+            return;
+        }
+
+        self.candidate.push(c);
+        if self.candidate.len() == CITATION_SHAPE.len() {
+            let citation = std::mem::take(&mut self.candidate);
+            let index = Uuid::parse_str(&citation[1..citation.len() - 1])
+                .ok()
+                .and_then(|id| self.results.iter().position(|r| r.chunk_id == id));
+            if let Some(i) = index {
+                if !self.cited.contains(&i) {
+                    self.cited.push(i);
+                }
+                for c in citation.to_lowercase().chars() {
+                    self.write(c, out);
+                }
+            }
+        }
+    }
+
+    fn write(&mut self, c: char, out: &mut String) {
+        if !c.is_whitespace() {
+            out.push_str(&std::mem::take(&mut self.space));
+            out.push(c);
+            self.started = true;
+        } else if self.started && !(c == ' ' && self.space.ends_with(' ')) {
+            self.space.push(c);
+        }
+    }
+}
+
+/// Deterministic stand-in for a local model.
+///
+/// Answers with the start of each source in the prompt followed by its
+/// citation, so retrieval-augmented answers can be tested without loading
+/// a model.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StubGenerator;
+
+impl TextGenerator for StubGenerator {
+    fn generate(
+        &self,
+        prompt: &str,
+        max_tokens: u32,
+        temperature: f32,
+    ) -> Result<String, ChatError> {
+        self.generate_streaming(prompt, max_tokens, temperature, &mut |_| true)
+    }
+
+    /// Produces the answer one word at a time.
+    fn generate_streaming(
+        &self,
+        prompt: &str,
+        max_tokens: u32,
+        _temperature: f32,
+        on_text: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<String, ChatError> {
+        let mut words: Vec<String> = Vec::new();
+        for line in prompt.lines() {
+            let Some(caps) = CITATION_RE.captures(line) else {
+                continue;
+            };
+            if !line.starts_with(&caps[0]) {
+                continue;
+            }
+            let content = line.split_once(": ").map(|(_, c)| c).unwrap_or_default();
+            let snippet: String = content.chars().take(STUB_SNIPPET_CHARS).collect();
+            words.extend(snippet.split_whitespace().map(str::to_string));
+            words.push(caps[0].to_string());
+        }
+        if words.is_empty() {
+            words = "The sources do not answer this question."
+                .split(' ')
+                .map(str::to_string)
+                .collect();
+        }
+        words.truncate(max_tokens as usize);
+
+        let mut text = String::new();
+        for word in words {
+            let piece = if text.is_empty() {
+                word
+            } else {
+                format!(" {}", word)
+            };
+            text.push_str(&piece);
+            if !on_text(&piece) {
+                break;
+            }
+        }
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::types::{QueryIntent, RetrievalPath};
+
+    struct FailingGenerator;
+
+    impl TextGenerator for FailingGenerator {
+        fn generate(&self, _: &str, _: u32, _: f32) -> Result<String, ChatError> {
+            Err(ChatError::LlmError("model crashed".to_string()))
+        }
+    }
+
+    struct FixedGenerator(String);
+
+    impl TextGenerator for FixedGenerator {
+        fn generate(&self, _: &str, _: u32, _: f32) -> Result<String, ChatError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn query() -> StructuredQuery {
+        StructuredQuery {
+            intent: QueryIntent::Search,
+            topics: vec!["deploy".to_string()],
+            people: vec![],
+            time_range: None,
+            content_type: None,
+            app_filter: None,
+            data_source: None,
+            analytics: None,
+            composition: None,
+            raw_query: "when did we deploy?".to_string(),
+        }
+    }
+
+    fn result(content: &str, score: f32) -> SearchResult {
+        SearchResult {
+            chunk_id: Uuid::new_v4(),
+            content: content.to_string(),
+            timestamp: 1_700_000_000,
+            source_app: "Slack".to_string(),
+            relevance_score: score,
+            person: None,
+            retrieval: RetrievalPath::Keyword,
+        }
+    }
+
+    fn request<'a>(
+        query: &'a StructuredQuery,
+        results: &'a [SearchResult],
+    ) -> GenerationRequest<'a> {
+        GenerationRequest {
+            query,
+            results,
+            max_results: 10,
+            max_tokens: 256,
+            temperature: 0.3,
+            on_delta: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stub_answer_cites_sources() {
+        let q = query();
+        let results = vec![
+            result("We deployed v2 on Friday.", 0.9),
+            result("Rollback plan is ready.", 0.5),
+        ];
+        let generated = LlmBackend::new(StubGenerator)
+            .generate(&request(&q, &results))
+            .await;
+
+        assert_eq!(generated.mode, ResponseMode::Llm);
+        let answer = &generated.response.answer;
+        assert!(
+            answer.contains(&format!("[{}]", results[0].chunk_id)),
+            "{}",
+            answer
+        );
+        assert!(answer.starts_with("We deployed v2 on Friday."));
+        assert_eq!(generated.response.sources.len(), 2);
+        assert_eq!(generated.response.sources[0].chunk_id, results[0].chunk_id);
+        assert!((generated.response.confidence - 0.7).abs() < 1e-6);
+        assert!(!generated.response.suggestions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_model_text_is_sent_as_it_is_written() {
+        let q = query();
+        let results = vec![result("We deployed v2 on Friday.", 0.9)];
+        let pieces = Mutex::new(Vec::new());
+        let on_delta = |text: &str| pieces.lock().unwrap().push(text.to_string());
+        let generated = LlmBackend::new(StubGenerator)
+            .generate(&GenerationRequest {
+                on_delta: Some(&on_delta),
+                ..request(&q, &results)
+            })
+            .await;
+
+        let pieces = pieces.into_inner().unwrap();
+        assert_eq!(generated.mode, ResponseMode::Llm);
+        assert!(pieces.len() > 1, "{:?}", pieces);
+        assert_eq!(pieces.concat(), generated.response.answer);
+    }
+
+    #[test]
+    fn test_citation_filter_ignores_how_text_is_split() {
+        let results = vec![result("a", 0.5), result("b", 0.5)];
+        let known = results[1].chunk_id;
+        let text = format!(
+            "  Second [[{}]  and made up [{}] and again [{}]. ",
+            known.to_string().to_uppercase(),
+            Uuid::new_v4(),
+            known
+        );
+        let whole = cite_sources(&text, &results).unwrap();
+        for size in [1, 2, 5, 17] {
+            let mut filter = CitationFilter::new(&results);
+            let chars: Vec<char> = text.chars().collect();
+            let mut answer: String = chars
+                .chunks(size)
+                .map(|piece| filter.push(&piece.iter().collect::<String>()))
+                .collect();
+            let (rest, cited) = filter.finish();
+            answer.push_str(&rest);
+            assert_eq!((answer, cited), whole, "split every {} chars", size);
+        }
+        assert_eq!(
+            whole.0,
+            format!("Second [[{}] and made up and again [{}].", known, known)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_model_failure_falls_back_to_extractive() {
+        let q = query();
+        let results = vec![result("We deployed v2 on Friday.", 0.9)];
+        let generated = LlmBackend::new(FailingGenerator)
+            .generate(&request(&q, &results))
+            .await;
+        let extractive = ExtractiveBackend.generate(&request(&q, &results)).await;
+
+        assert_eq!(generated.mode, ResponseMode::Template);
+        assert_eq!(generated.response.answer, extractive.response.answer);
+    }
+
+    #[tokio::test]
+    async fn test_uncited_answer_falls_back_to_extractive() {
+        let q = query();
+        let results = vec![result("We deployed v2 on Friday.", 0.9)];
+        let unknown = Uuid::new_v4();
+        let generated = LlmBackend::new(FixedGenerator(format!("It was Friday [{}].", unknown)))
+            .generate(&request(&q, &results))
+            .await;
+        assert_eq!(generated.mode, ResponseMode::Template);
+    }
+
+    #[tokio::test]
+    async fn test_no_results_skips_model() {
+        let q = query();
+        let generated = LlmBackend::new(FixedGenerator("made up".to_string()))
+            .generate(&request(&q, &[]))
+            .await;
+        assert_eq!(generated.mode, ResponseMode::Template);
+        assert!(generated.response.sources.is_empty());
+    }
+
+    #[test]
+    fn test_cite_sources_drops_unknown_ids() {
+        let results = vec![result("a", 0.5), result("b", 0.5)];
+        let known = results[1].chunk_id;
+        let text = format!(
+            "Second [{}] and made up [{}] and again [{}].",
+            known.to_string().to_uppercase(),
+            Uuid::new_v4(),
+            known
+        );
+        let (answer, cited) = cite_sources(&text, &results).unwrap();
+        assert_eq!(cited, vec![1]);
+        assert_eq!(
+            answer,
+            format!("Second [{}] and made up and again [{}].", known, known)
+        );
+    }
+
+    #[test]
+    fn test_prompt_lists_sources_and_question() {
+        let results = vec![result("line one\nline two", 0.5)];
+        let prompt = build_prompt("when did we deploy?", &results);
+        assert!(prompt.contains(&format!("[{}] ", results[0].chunk_id)));
+        assert!(prompt.contains("in Slack: line one line two\n"));
+        assert!(prompt.ends_with("Question: when did we deploy?\nAnswer:"));
+    }
+}
@@ -38,6 +38,8 @@ impl ConversationManager {
             last_message_at: now,
             context: SessionContext::default(),
             message_count: 0,
+            title: None,
+            pinned: false,
         }
     }
 
@@ -216,6 +218,9 @@ mod tests {
             time_range: None,
             content_type: None,
             app_filter: None,
+            data_source: None,
+            analytics: None,
+            composition: None,
             raw_query: raw.to_string(),
         }
     }
@@ -226,6 +231,8 @@ mod tests {
             sources: vec![],
             confidence: 0.8,
             suggestions: vec![],
+            table: None,
+            sections: vec![],
         }
     }
 
@@ -13,6 +13,12 @@ pub enum ChatError {
     MessageTooLong(usize),
     #[error("session not found: {0}")]
     SessionNotFound(uuid::Uuid),
+    #[error("invalid title: {0}")]
+    InvalidTitle(String),
+    #[error("message not found: {0}")]
+    MessageNotFound(uuid::Uuid),
+    #[error("invalid feedback: {0}")]
+    InvalidFeedback(String),
     #[error("parse error: {0}")]
     ParseError(String),
     #[error("search error: {0}")]
@@ -25,6 +31,8 @@ pub enum ChatError {
     VoiceError(String),
     #[error("storage error: {0}")]
     StorageError(String),
+    #[error("request cancelled")]
+    Cancelled,
 }
 
 impl From<EngramError> for ChatError {
@@ -56,6 +64,16 @@ mod tests {
         let err = ChatError::SessionNotFound(id);
         assert_eq!(err.to_string(), format!("session not found: {}", id));
 
+        let err = ChatError::InvalidTitle("title cannot be empty".to_string());
+        assert_eq!(err.to_string(), "invalid title: title cannot be empty");
+
+        let id = Uuid::new_v4();
+        let err = ChatError::MessageNotFound(id);
+        assert_eq!(err.to_string(), format!("message not found: {}", id));
+
+        let err = ChatError::InvalidFeedback("not an answer".to_string());
+        assert_eq!(err.to_string(), "invalid feedback: not an answer");
+
         let err = ChatError::ParseError("bad regex".to_string());
         assert_eq!(err.to_string(), "parse error: bad regex");
 
@@ -73,6 +91,9 @@ mod tests {
 
         let err = ChatError::StorageError("disk full".to_string());
         assert_eq!(err.to_string(), "storage error: disk full");
+
+        let err = ChatError::Cancelled;
+        assert_eq!(err.to_string(), "request cancelled");
     }
 
     #[test]
@@ -0,0 +1,217 @@
+//! Feedback on chat answers, and how it tunes retrieval.
+//!
+//! A thumbs-up raises each rated source's boost under the question's terms;
+//! a thumbs-down or "irrelevant" lowers it. Each rating also nudges the
+//! user's semantic weight toward the retrieval path that found the sources
+//! they liked and away from the one that found sources they did not.
+//! Boosts and weights are kept per user, and rating the same answer or
+//! source again replaces the earlier rating rather than adding to it.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::retrieval::RankingFeedback;
+use crate::types::{FeedbackRating, RankShift, RetrievalPath, SourceRef, StructuredQuery};
+
+/// User that feedback and tuning are recorded for when none is given; the
+/// name of the `.api_token` token.
+pub const DEFAULT_USER: &str = "primary";
+
+/// Boost change per rated source and term.
+const UP_STEP: f32 = 0.25;
+const DOWN_STEP: f32 = -0.25;
+const IRRELEVANT_STEP: f32 = -0.5;
+
+/// Semantic weight change per rating.
+const WEIGHT_STEP: f32 = 0.02;
+
+/// Bounds of a tuned semantic weight, so neither path is ever ignored.
+const MIN_SEMANTIC_WEIGHT: f32 = 0.2;
+const MAX_SEMANTIC_WEIGHT: f32 = 0.9;
+
+/// Terms feedback is learned under: the question's topics, lowercased.
+pub fn feedback_terms(query: &StructuredQuery) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for topic in &query.topics {
+        let term = topic.trim().to_lowercase();
+        if !term.is_empty() && !terms.contains(&term) {
+            terms.push(term);
+        }
+    }
+    terms
+}
+
+/// Boost change for each rated source under each term.
+pub fn boost_step(rating: FeedbackRating) -> f32 {
+    match rating {
+        FeedbackRating::Up => UP_STEP,
+        FeedbackRating::Down => DOWN_STEP,
+        FeedbackRating::Irrelevant => IRRELEVANT_STEP,
+    }
+}
+
+/// The semantic weight after rating `sources`.
+///
+/// Liked semantic-only sources and disliked keyword-only ones raise the
+/// weight, the reverse lowers it; sources both paths found say nothing
+/// about either.
+pub fn tune_semantic_weight(current: f32, rating: FeedbackRating, sources: &[SourceRef]) -> f32 {
+    (current + WEIGHT_STEP * weight_direction(rating, sources) as f32)
+        .clamp(MIN_SEMANTIC_WEIGHT, MAX_SEMANTIC_WEIGHT)
+}
+
+/// The semantic weight with an earlier rating of `sources` taken back,
+/// before a replacing rating is applied.
+pub fn untune_semantic_weight(current: f32, rating: FeedbackRating, sources: &[SourceRef]) -> f32 {
+    (current - WEIGHT_STEP * weight_direction(rating, sources) as f32)
+        .clamp(MIN_SEMANTIC_WEIGHT, MAX_SEMANTIC_WEIGHT)
+}
+
+fn weight_direction(rating: FeedbackRating, sources: &[SourceRef]) -> i32 {
+    let net: i32 = sources
+        .iter()
+        .map(|s| match s.retrieval {
+            RetrievalPath::Semantic => 1,
+            RetrievalPath::Keyword => -1,
+            RetrievalPath::Hybrid => 0,
+        })
+        .sum();
+    match rating {
+        FeedbackRating::Up => net.signum(),
+        FeedbackRating::Down | FeedbackRating::Irrelevant => -net.signum(),
+    }
+}
+
+/// Ranking feedback from a user's semantic weight and learned
+/// `(term, chunk_id, score)` boosts.
+pub fn ranking_feedback(
+    semantic_weight: f32,
+    boosts: impl IntoIterator<Item = (String, Uuid, f32)>,
+) -> RankingFeedback {
+    let mut by_term: HashMap<String, HashMap<Uuid, f32>> = HashMap::new();
+    for (term, chunk_id, score) in boosts {
+        by_term.entry(term).or_default().insert(chunk_id, score);
+    }
+    RankingFeedback {
+        semantic_weight,
+        boosts: by_term,
+    }
+}
+
+/// Compare two rankings of source IDs, best first. Sources are listed in
+/// their `adjusted` order, then those only in `baseline`.
+pub fn rank_shifts(baseline: &[Uuid], adjusted: &[Uuid]) -> Vec<RankShift> {
+    let rank_in = |ranking: &[Uuid], id: Uuid| ranking.iter().position(|r| *r == id).map(|i| i + 1);
+    let mut shifts: Vec<RankShift> = adjusted
+        .iter()
+        .map(|&id| RankShift {
+            chunk_id: id,
+            baseline_rank: rank_in(baseline, id),
+            rank: rank_in(adjusted, id),
+        })
+        .collect();
+    shifts.extend(
+        baseline
+            .iter()
+            .filter(|id| !adjusted.contains(id))
+            .map(|&id| RankShift {
+                chunk_id: id,
+                baseline_rank: rank_in(baseline, id),
+                rank: None,
+            }),
+    );
+    shifts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::QueryParser;
+
+    fn source(retrieval: RetrievalPath) -> SourceRef {
+        SourceRef {
+            chunk_id: Uuid::new_v4(),
+            timestamp: "2026-01-05 09:00".to_string(),
+            source_app: "Slack".to_string(),
+            relevance_score: 0.8,
+            retrieval,
+        }
+    }
+
+    #[test]
+    fn test_feedback_terms_are_lowercased_topics() {
+        let mut query = QueryParser::new(7).parse("find pricing notes", &[]);
+        query.topics = vec![
+            "Pricing".to_string(),
+            "pricing".to_string(),
+            " ".to_string(),
+        ];
+        assert_eq!(feedback_terms(&query), vec!["pricing"]);
+    }
+
+    #[test]
+    fn test_tune_semantic_weight_follows_liked_path() {
+        let semantic = [source(RetrievalPath::Semantic)];
+        let keyword = [source(RetrievalPath::Keyword)];
+        let hybrid = [source(RetrievalPath::Hybrid)];
+
+        let up = tune_semantic_weight(0.6, FeedbackRating::Up, &semantic);
+        assert!((up - 0.62).abs() < 1e-6);
+        let down = tune_semantic_weight(0.6, FeedbackRating::Down, &semantic);
+        assert!((down - 0.58).abs() < 1e-6);
+        let irrelevant = tune_semantic_weight(0.6, FeedbackRating::Irrelevant, &keyword);
+        assert!((irrelevant - 0.62).abs() < 1e-6);
+        assert_eq!(tune_semantic_weight(0.6, FeedbackRating::Up, &hybrid), 0.6);
+
+        assert_eq!(
+            tune_semantic_weight(MAX_SEMANTIC_WEIGHT, FeedbackRating::Up, &semantic),
+            MAX_SEMANTIC_WEIGHT
+        );
+        assert_eq!(
+            tune_semantic_weight(MIN_SEMANTIC_WEIGHT, FeedbackRating::Up, &keyword),
+            MIN_SEMANTIC_WEIGHT
+        );
+    }
+
+    #[test]
+    fn test_ranking_feedback_groups_boosts_by_term() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let feedback = ranking_feedback(
+            0.7,
+            vec![
+                ("pricing".to_string(), a, 0.5),
+                ("pricing".to_string(), b, -1.0),
+            ],
+        );
+        assert_eq!(feedback.semantic_weight, 0.7);
+        assert_eq!(feedback.boost(a, &["pricing".to_string()]), 0.5);
+        assert_eq!(feedback.boost(b, &["pricing".to_string()]), -1.0);
+    }
+
+    #[test]
+    fn test_rank_shifts() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let shifts = rank_shifts(&[a, b], &[b, c]);
+        assert_eq!(
+            shifts,
+            vec![
+                RankShift {
+                    chunk_id: b,
+                    baseline_rank: Some(2),
+                    rank: Some(1),
+                },
+                RankShift {
+                    chunk_id: c,
+                    baseline_rank: None,
+                    rank: Some(2),
+                },
+                RankShift {
+                    chunk_id: a,
+                    baseline_rank: Some(1),
+                    rank: None,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Local GGUF model runner via candle (pure Rust, CPU).
+//!
+//! When compiled with the `llm` feature, loads a quantized Llama-family
+//! GGUF model plus the `tokenizer.json` next to it and runs greedy or
+//! temperature sampling. Without the feature, loading always fails so
+//! callers keep the extractive backend.
+
+use std::path::Path;
+
+use crate::backend::TextGenerator;
+use crate::error::ChatError;
+
+/// A quantized GGUF language model loaded into memory.
+pub struct GgufModel {
+    #[cfg(feature = "llm")]
+    inner: std::sync::Mutex<inner::Loaded>,
+}
+
+impl GgufModel {
+    /// Load the model at `model_path` and the `tokenizer.json` in the same
+    /// directory.
+    ///
+    /// # Errors
+    /// Returns `ChatError::LlmError` if either file is missing or fails to
+    /// load.
+    #[cfg(feature = "llm")]
+    pub fn load(model_path: &Path) -> Result<Self, ChatError> {
+        let tokenizer_path = model_path.with_file_name("tokenizer.json");
+        for path in [model_path, tokenizer_path.as_path()] {
+            if !path.exists() {
+                return Err(ChatError::LlmError(format!(
+                    "model file not found: {}",
+                    path.display()
+                )));
+            }
+        }
+
+        tracing::info!(model = %model_path.display(), "Loading GGUF model");
+        let loaded = inner::Loaded::load(model_path, &tokenizer_path)?;
+        tracing::info!("GGUF model loaded");
+        Ok(Self {
+            inner: std::sync::Mutex::new(loaded),
+        })
+    }
+
+    /// Stub loader when the `llm` feature is disabled.
+    #[cfg(not(feature = "llm"))]
+    pub fn load(model_path: &Path) -> Result<Self, ChatError> {
+        Err(ChatError::LlmError(format!(
+            "cannot load {}: local LLM answers require the `llm` feature",
+            model_path.display()
+        )))
+    }
+}
+
+#[cfg(feature = "llm")]
+impl TextGenerator for GgufModel {
+    fn generate(
+        &self,
+        prompt: &str,
+        max_tokens: u32,
+        temperature: f32,
+    ) -> Result<String, ChatError> {
+        self.generate_streaming(prompt, max_tokens, temperature, &mut |_| true)
+    }
+
+    fn generate_streaming(
+        &self,
+        prompt: &str,
+        max_tokens: u32,
+        temperature: f32,
+        on_text: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<String, ChatError> {
+        self.inner
+            .lock()
+            .map_err(|e| ChatError::LlmError(format!("model lock poisoned: {}", e)))?
+            .generate(prompt, max_tokens, temperature, on_text)
+    }
+}
+
+#[cfg(not(feature = "llm"))]
+impl TextGenerator for GgufModel {
+    fn generate(
+        &self,
+        _prompt: &str,
+        _max_tokens: u32,
+        _temperature: f32,
+    ) -> Result<String, ChatError> {
+        Err(ChatError::LlmError(
+            "local LLM answers require the `llm` feature".to_string(),
+        ))
+    }
+}
+
+#[cfg(feature = "llm")]
+mod inner {
+    use std::path::Path;
+
+    use candle_core::quantized::gguf_file;
+    use candle_core::{Device, Tensor};
+    use candle_transformers::generation::LogitsProcessor;
+    use candle_transformers::models::quantized_llama::ModelWeights;
+    use tokenizers::Tokenizer;
+
+    use crate::error::ChatError;
+
+    /// Fixed sampling seed, so equal prompts and settings give equal answers.
+    const SEED: u64 = 299_792_458;
+
+    /// Token IDs tried as end of sequence when the GGUF metadata has none.
+    const EOS_TOKENS: &[&str] = &["</s>", "<|eot_id|>", "<|end_of_text|>", "<|im_end|>"];
+
+    fn llm_error(context: &str, e: impl std::fmt::Display) -> ChatError {
+        ChatError::LlmError(format!("{}: {}", context, e))
+    }
+
+    pub(super) struct Loaded {
+        model: ModelWeights,
+        tokenizer: Tokenizer,
+        eos_token: Option<u32>,
+        device: Device,
+    }
+
+    impl Loaded {
+        pub(super) fn load(model_path: &Path, tokenizer_path: &Path) -> Result<Self, ChatError> {
+            let device = Device::Cpu;
+            let mut file =
+                std::fs::File::open(model_path).map_err(|e| llm_error("open GGUF model", e))?;
+            let content = gguf_file::Content::read(&mut file)
+                .map_err(|e| llm_error("read GGUF header", e))?;
+            let eos_from_metadata = content
+                .metadata
+                .get("tokenizer.ggml.eos_token_id")
+                .and_then(|v| v.to_u32().ok());
+            let model = ModelWeights::from_gguf(content, &mut file, &device)
+                .map_err(|e| llm_error("load GGUF weights", e))?;
+            let tokenizer =
+                Tokenizer::from_file(tokenizer_path).map_err(|e| llm_error("load tokenizer", e))?;
+            let eos_token = eos_from_metadata
+                .or_else(|| EOS_TOKENS.iter().find_map(|t| tokenizer.token_to_id(t)));
+
+            Ok(Self {
+                model,
+                tokenizer,
+                eos_token,
+                device,
+            })
+        }
+
+        pub(super) fn generate(
+            &mut self,
+            prompt: &str,
+            max_tokens: u32,
+            temperature: f32,
+            on_text: &mut dyn FnMut(&str) -> bool,
+        ) -> Result<String, ChatError> {
+            let encoding = self
+                .tokenizer
+                .encode(prompt, true)
+                .map_err(|e| llm_error("tokenize prompt", e))?;
+            let prompt_tokens = encoding.get_ids();
+            if prompt_tokens.is_empty() {
+                return Err(ChatError::LlmError("empty prompt".to_string()));
+            }
+
+            let temperature = (temperature > 0.0).then_some(temperature as f64);
+            let mut sampler = LogitsProcessor::new(SEED, temperature, None);
+            let mut generated: Vec<u32> = Vec::new();
+            // Decoded text already passed to `on_text`.
+            let mut sent = String::new();
+
+            // Index 0 resets the model's KV cache, then tokens are fed one
+            // at a time after the prompt.
+            let mut input = prompt_tokens.to_vec();
+            let mut position = 0;
+            for _ in 0..max_tokens {
+                let tensor = Tensor::new(input.as_slice(), &self.device)
+                    .and_then(|t| t.unsqueeze(0))
+                    .map_err(|e| llm_error("build input tensor", e))?;
+                let logits = self
+                    .model
+                    .forward(&tensor, position)
+                    .and_then(|l| l.squeeze(0))
+                    .map_err(|e| llm_error("model forward", e))?;
+                let next = sampler
+                    .sample(&logits)
+                    .map_err(|e| llm_error("sample token", e))?;
+                if Some(next) == self.eos_token {
+                    break;
+                }
+                position += input.len();
+                generated.push(next);
+                input = vec![next];
+
+                // A token can end partway through a character, and decoding
+                // more tokens can change earlier text; only text that still
+                // follows what was sent is passed on.
+                let text = self.decode(&generated)?;
+                if let Some(new) = text
+                    .strip_prefix(sent.as_str())
+                    .filter(|new| !new.is_empty() && !new.ends_with('\u{FFFD}'))
+                {
+                    let more = on_text(new);
+                    sent = text;
+                    if !more {
+                        break;
+                    }
+                }
+            }
+
+            let text = self.decode(&generated)?;
+            if let Some(rest) = text.strip_prefix(sent.as_str()).filter(|r| !r.is_empty()) {
+                on_text(rest);
+            }
+            Ok(text)
+        }
+
+        fn decode(&self, tokens: &[u32]) -> Result<String, ChatError> {
+            self.tokenizer
+                .decode(tokens, true)
+                .map_err(|e| llm_error("decode output", e))
+        }
+    }
+}
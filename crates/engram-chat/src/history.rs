@@ -0,0 +1,257 @@
+//! Chat session history: titles and Markdown export.
+//!
+//! Sessions are titled from the topics of their first query, and can be
+//! exported as Markdown with each `[chunk-id]` citation turned into a
+//! footnote naming the cited capture's time and app.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use uuid::Uuid;
+
+use crate::backend::CITATION_RE;
+use crate::error::ChatError;
+use crate::response::format_timestamp;
+use crate::types::{ChatMessage, ChatSessionSummary, SourceRef, StructuredQuery};
+
+/// Longest session title, in characters.
+pub const MAX_TITLE_CHARS: usize = 80;
+
+/// Title for a session from its first query: the query's topics, or its
+/// first words when it has none.
+pub fn session_title(query: &StructuredQuery, message: &str) -> String {
+    let title = if query.topics.is_empty() {
+        message
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim_end_matches(['?', '.', '!'])
+            .to_string()
+    } else {
+        query.topics.join(", ")
+    };
+    let mut chars = title.chars();
+    let title: String = chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default();
+    truncate_title(&title)
+}
+
+/// Check and tidy a title given by the user: whitespace is collapsed and it
+/// must be 1 to [`MAX_TITLE_CHARS`] characters long.
+pub fn normalize_title(title: &str) -> Result<String, ChatError> {
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    if title.is_empty() {
+        return Err(ChatError::InvalidTitle("title cannot be empty".to_string()));
+    }
+    if title.chars().count() > MAX_TITLE_CHARS {
+        return Err(ChatError::InvalidTitle(format!(
+            "title exceeds {} characters",
+            MAX_TITLE_CHARS
+        )));
+    }
+    Ok(title)
+}
+
+/// Cut `title` at a word boundary to fit [`MAX_TITLE_CHARS`].
+fn truncate_title(title: &str) -> String {
+    if title.chars().count() <= MAX_TITLE_CHARS {
+        return title.to_string();
+    }
+    let mut cut = String::new();
+    for word in title.split_whitespace() {
+        if cut.chars().count() + word.chars().count() + 1 >= MAX_TITLE_CHARS {
+            break;
+        }
+        if !cut.is_empty() {
+            cut.push(' ');
+        }
+        cut.push_str(word);
+    }
+    if cut.is_empty() {
+        cut = title.chars().take(MAX_TITLE_CHARS - 1).collect();
+    }
+    cut.push('…');
+    cut
+}
+
+/// Render a session as Markdown.
+///
+/// `[chunk-id]` citations become footnotes, as do sources of an answer it
+/// does not cite inline. `resolve` gives the timestamp (epoch seconds) and
+/// app of a cited capture; captures it cannot find fall back to the
+/// answer's recorded source.
+pub fn session_markdown(
+    summary: &ChatSessionSummary,
+    messages: &[ChatMessage],
+    resolve: impl Fn(Uuid) -> Option<(i64, String)>,
+) -> String {
+    let mut out = String::new();
+    let title = summary.title.as_deref().unwrap_or("Chat session");
+    let _ = writeln!(out, "# {}\n", title);
+    let _ = writeln!(out, "- Session: `{}`", summary.id);
+    if let Some(first) = messages.first() {
+        let _ = writeln!(out, "- Started: {}", format_timestamp(first.created_at));
+    }
+    let _ = writeln!(out, "- Messages: {}", messages.len());
+
+    let mut footnotes: Vec<(Uuid, String)> = Vec::new();
+    let mut numbers: HashMap<Uuid, usize> = HashMap::new();
+    for message in messages {
+        let sources: Vec<SourceRef> = message
+            .sources
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default();
+        let mut cite = |chunk_id: Uuid| -> usize {
+            *numbers.entry(chunk_id).or_insert_with(|| {
+                let source = sources.iter().find(|s| s.chunk_id == chunk_id);
+                let (time, app) = match resolve(chunk_id) {
+                    Some((ts, app)) => (format_timestamp(ts), app),
+                    None => source
+                        .map(|s| (s.timestamp.clone(), s.source_app.clone()))
+                        .unwrap_or_else(|| ("unknown time".to_string(), String::new())),
+                };
+                let app = if app.is_empty() { "unknown app" } else { &app };
+                footnotes.push((chunk_id, format!("{} · {}", time, app)));
+                footnotes.len()
+            })
+        };
+
+        let speaker = if message.role == "user" {
+            "You"
+        } else {
+            "Engram"
+        };
+        let _ = writeln!(
+            out,
+            "\n## {} · {}\n",
+            speaker,
+            format_timestamp(message.created_at)
+        );
+        let mut cited = Vec::new();
+        let content =
+            CITATION_RE.replace_all(
+                &message.content,
+                |caps: &regex::Captures| match Uuid::parse_str(&caps[1]) {
+                    Ok(chunk_id) => {
+                        cited.push(chunk_id);
+                        format!("[^{}]", cite(chunk_id))
+                    }
+                    Err(_) => caps[0].to_string(),
+                },
+            );
+        let _ = writeln!(out, "{}", content.trim_end());
+
+        let uncited: Vec<String> = sources
+            .iter()
+            .filter(|s| !cited.contains(&s.chunk_id))
+            .map(|s| format!("[^{}]", cite(s.chunk_id)))
+            .collect();
+        if !uncited.is_empty() {
+            let _ = writeln!(out, "\nSources: {}", uncited.join(" "));
+        }
+    }
+
+    if !footnotes.is_empty() {
+        out.push('\n');
+        for (i, (chunk_id, label)) in footnotes.iter().enumerate() {
+            let _ = writeln!(out, "[^{}]: {} (`{}`)", i + 1, label, chunk_id);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::QueryParser;
+    use crate::types::RetrievalPath;
+
+    fn message(role: &str, content: &str, sources: &[SourceRef], created_at: i64) -> ChatMessage {
+        ChatMessage {
+            id: Uuid::new_v4(),
+            session_id: Uuid::nil(),
+            role: role.to_string(),
+            content: content.to_string(),
+            sources: (!sources.is_empty()).then(|| serde_json::to_string(sources).unwrap()),
+            suggestions: None,
+            created_at,
+        }
+    }
+
+    fn source(chunk_id: Uuid, app: &str) -> SourceRef {
+        SourceRef {
+            chunk_id,
+            timestamp: "2026-01-05 09:00".to_string(),
+            source_app: app.to_string(),
+            relevance_score: 0.9,
+            retrieval: RetrievalPath::Keyword,
+        }
+    }
+
+    #[test]
+    fn test_session_title_from_topics_or_words() {
+        let parser = QueryParser::new(7);
+        let message = "what did we decide about pricing?";
+        let query = parser.parse(message, &[]);
+        assert_eq!(session_title(&query, message), "Decide, pricing");
+
+        let mut query = parser.parse("hello there", &[]);
+        query.topics.clear();
+        assert_eq!(session_title(&query, "  hello   there! "), "Hello there");
+
+        let long = "word ".repeat(40);
+        let title = session_title(&query, &long);
+        assert!(title.chars().count() <= MAX_TITLE_CHARS);
+        assert!(title.ends_with('…'));
+    }
+
+    #[test]
+    fn test_normalize_title() {
+        assert_eq!(normalize_title("  Q3   pricing ").unwrap(), "Q3 pricing");
+        assert!(normalize_title("   ").is_err());
+        assert!(normalize_title(&"x".repeat(MAX_TITLE_CHARS + 1)).is_err());
+    }
+
+    #[test]
+    fn test_markdown_resolves_citations_to_footnotes() {
+        let cited = Uuid::new_v4();
+        let listed = Uuid::new_v4();
+        let deleted = Uuid::new_v4();
+        let summary = ChatSessionSummary {
+            id: Uuid::new_v4(),
+            started_at: String::new(),
+            last_message_at: String::new(),
+            message_count: 2,
+            title: Some("Pricing".to_string()),
+            pinned: false,
+        };
+        let messages = vec![
+            message("user", "pricing?", &[], 1_767_600_000),
+            message(
+                "assistant",
+                &format!("Pricing stays flat [{}]. Also [{}].", cited, deleted),
+                &[
+                    source(cited, "Slack"),
+                    source(listed, "Mail"),
+                    source(deleted, "Zoom"),
+                ],
+                1_767_600_060,
+            ),
+        ];
+        let md = session_markdown(&summary, &messages, |id| {
+            (id != deleted).then(|| (1_767_590_000, "Slack".to_string()))
+        });
+
+        assert!(md.starts_with("# Pricing\n"));
+        assert!(md.contains("## You · "));
+        assert!(md.contains("Pricing stays flat [^1]. Also [^2]."));
+        assert!(md.contains("\nSources: [^3]\n"));
+        let resolved = format_timestamp(1_767_590_000);
+        assert!(md.contains(&format!("[^1]: {} · Slack (`{}`)", resolved, cited)));
+        assert!(md.contains(&format!("[^2]: 2026-01-05 09:00 · Zoom (`{}`)", deleted)));
+        assert!(md.contains(&format!("[^3]: {} · Slack (`{}`)", resolved, listed)));
+    }
+}
@@ -3,22 +3,45 @@
 //! Provides natural-language query parsing, chat session management,
 //! and response generation for searching and interacting with captured data.
 
+pub mod analytics;
+pub mod backend;
 pub mod context;
 pub mod error;
+pub mod feedback;
+pub mod gguf;
+pub mod history;
 pub mod orchestrator;
 pub mod parser;
+pub mod planner;
 pub mod response;
+pub mod retrieval;
+pub mod stream;
+pub mod structured;
 pub mod types;
 pub mod voice;
 
+pub use backend::{
+    ExtractiveBackend, GeneratedResponse, GenerationRequest, LlmBackend, ResponseBackend,
+    ResponseMode, StubGenerator, TextGenerator,
+};
 pub use context::{ConversationManager, FollowUpResolver};
 pub use error::ChatError;
+pub use gguf::GgufModel;
 pub use orchestrator::{ChatBackends, ChatOrchestrator};
 pub use parser::QueryParser;
+pub use planner::{QueryPlan, QueryPlanner, SubQuery};
 pub use response::{ResponseGenerator, SearchResult};
+pub use retrieval::{HybridRetriever, RankingFeedback};
+pub use stream::{ChatStream, ChatStreamEvent, ChatStreams, SequencedEvent, StreamStage};
 pub use types::{
-    ChatConfig, ChatLlmConfig, ChatMessage, ChatMessageRecord, ChatMessageResponse, ChatRequest,
-    ChatResponse, ChatResponseBody, ChatSessionSummary, ChatSessionsResponse, ConversationSession,
-    QueryIntent, SessionContext, SourceRef, StructuredQuery, TimeRange, Turn,
+    AnalyticsMetric, AnalyticsTable, ChatConfig, ChatFeedback, ChatLlmConfig, ChatMessage,
+    ChatMessageHit, ChatMessageRecord, ChatMessageResponse, ChatRequest, ChatResponse,
+    ChatResponseBody, ChatSessionSummary, ChatSessionUpdate, ChatSessionsResponse, Composition,
+    ConversationSession, DataSource, FeedbackCounts, FeedbackRating, FeedbackRecorded,
+    FeedbackReport, QueryIntent, RankShift, RankingShift, ResponseSection, RetrievalPath,
+    SectionItem, SessionContext, SourceBoost, SourceRef, StructuredQuery, TimeRange, Turn,
+};
+pub use voice::{
+    DictationAudioSource, RecordedAudio, VoiceAnswer, VoiceAudioSource, VoiceInterface,
+    VoiceStatus, WavFileSource,
 };
-pub use voice::VoiceInterface;
@@ -5,7 +5,8 @@
 //! and analytics engines. Without backends (tests), uses mock fallbacks.
 
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::future::Future;
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
 
 use chrono::{DateTime, Local, TimeZone};
 use uuid::Uuid;
@@ -13,14 +14,27 @@ use uuid::Uuid;
 use engram_core::events::DomainEvent;
 use engram_core::types::Timestamp;
 
+use crate::analytics::AnalyticsEngine;
+use crate::backend::{
+    ExtractiveBackend, GeneratedResponse, GenerationRequest, ResponseBackend, ResponseMode,
+};
 use crate::context::{ConversationManager, FollowUpResolver};
 use crate::error::ChatError;
+use crate::feedback;
+use crate::history;
 use crate::parser::QueryParser;
-use crate::response::{ResponseGenerator, SearchResult};
+use crate::planner::QueryPlanner;
+use crate::response::{source_ref, ResponseGenerator, SearchResult};
+use crate::retrieval::{HybridRetriever, RankingFeedback, SEMANTIC_WEIGHT};
+use crate::stream::{answer_deltas, ChatStream, ChatStreamEvent, ChatStreams, StreamStage};
+use crate::structured::StructuredAnswerer;
 use crate::types::{
-    ChatConfig, ChatMessage, ChatResponse, ChatSessionSummary, ConversationSession, QueryIntent,
-    SessionContext,
+    ChatConfig, ChatFeedback, ChatMessage, ChatMessageHit, ChatMessageRecord, ChatResponse,
+    ChatSessionSummary, ChatSessionUpdate, ConversationSession, FeedbackCounts, FeedbackRating,
+    FeedbackRecorded, FeedbackReport, QueryIntent, RankingShift, RetrievalPath, SessionContext,
+    SourceBoost, SourceRef,
 };
+use crate::voice::{VoiceAnswer, VoiceInterface, VoiceStatus};
 
 /// Maximum message length in characters.
 const MAX_MESSAGE_LENGTH: usize = 2000;
@@ -28,6 +42,12 @@ const MAX_MESSAGE_LENGTH: usize = 2000;
 /// Maximum number of in-memory sessions before eviction (FIX-8).
 const MAX_SESSIONS: usize = 100;
 
+/// Learned boosts listed in a feedback report.
+const REPORT_BOOSTS: u32 = 20;
+
+/// Recently rated questions re-ranked in a feedback report.
+const REPORT_QUERIES: u32 = 5;
+
 /// Backend services for real integration (production mode).
 ///
 /// When provided to the orchestrator, queries are routed to real engines
@@ -37,6 +57,8 @@ pub struct ChatBackends {
     pub database: Arc<engram_storage::Database>,
     /// FTS5 full-text search engine.
     pub fts_search: Arc<engram_storage::FtsSearch>,
+    /// Vector search engine for semantic retrieval.
+    pub search_engine: Arc<engram_vector::SearchEngine>,
     /// Cross-type query service (for entity lookup and stats).
     pub query_service: Arc<engram_storage::QueryService>,
     /// Action engine task store.
@@ -47,16 +69,47 @@ pub struct ChatBackends {
     pub event_tx: tokio::sync::broadcast::Sender<serde_json::Value>,
 }
 
-/// Central chat orchestrator that coordinates parsing, context, and response.
-pub struct ChatOrchestrator {
+/// Configuration and the components built from it, swapped together on
+/// config reload.
+struct ChatSettings {
+    config: ChatConfig,
     parser: QueryParser,
     context_manager: ConversationManager,
-    follow_up_resolver: FollowUpResolver,
     response_generator: ResponseGenerator,
+}
+
+impl ChatSettings {
+    fn new(config: ChatConfig) -> Self {
+        Self {
+            parser: QueryParser::new(config.default_search_days),
+            context_manager: ConversationManager::new(
+                config.context_turns,
+                config.session_timeout_minutes,
+            ),
+            response_generator: ResponseGenerator::new(config.max_results_per_query),
+            config,
+        }
+    }
+}
+
+/// Central chat orchestrator that coordinates parsing, context, and response.
+pub struct ChatOrchestrator {
+    settings: RwLock<ChatSettings>,
+    follow_up_resolver: FollowUpResolver,
     sessions: Mutex<HashMap<Uuid, ConversationSession>>,
     messages: Mutex<HashMap<Uuid, Vec<ChatMessage>>>,
-    config: ChatConfig,
     backends: Option<ChatBackends>,
+    response_backend: Arc<dyn ResponseBackend>,
+    streams: ChatStreams,
+    voice: Option<tokio::sync::Mutex<VoiceQueries>>,
+}
+
+/// The voice interface and the query it is recording.
+struct VoiceQueries {
+    interface: VoiceInterface,
+    /// Session the recording will be asked in.
+    session_id: Option<Uuid>,
+    last: Option<VoiceAnswer>,
 }
 
 impl ChatOrchestrator {
@@ -65,21 +118,15 @@ impl ChatOrchestrator {
     /// Without backends, the orchestrator uses mock data for search/action/analytics.
     /// Call `with_backends()` to enable real integration.
     pub fn new(config: ChatConfig) -> Self {
-        let parser = QueryParser::new(config.default_search_days);
-        let context_manager =
-            ConversationManager::new(config.context_turns, config.session_timeout_minutes);
-        let follow_up_resolver = FollowUpResolver;
-        let response_generator = ResponseGenerator::new(config.max_results_per_query);
-
         Self {
-            parser,
-            context_manager,
-            follow_up_resolver,
-            response_generator,
+            settings: RwLock::new(ChatSettings::new(config)),
+            follow_up_resolver: FollowUpResolver,
             sessions: Mutex::new(HashMap::new()),
             messages: Mutex::new(HashMap::new()),
-            config,
             backends: None,
+            response_backend: Arc::new(ExtractiveBackend),
+            streams: ChatStreams::new(),
+            voice: None,
         }
     }
 
@@ -89,26 +136,214 @@ impl ChatOrchestrator {
         self
     }
 
-    /// Handle an incoming chat message.
+    /// Compose search answers with `backend` instead of templates.
+    pub fn with_response_backend(mut self, backend: Arc<dyn ResponseBackend>) -> Self {
+        self.response_backend = backend;
+        self
+    }
+
+    /// Answer spoken questions recorded by `voice`.
+    pub fn with_voice(mut self, voice: VoiceInterface) -> Self {
+        self.voice = Some(tokio::sync::Mutex::new(VoiceQueries {
+            interface: voice,
+            session_id: None,
+            last: None,
+        }));
+        self
+    }
+
+    /// Current chat configuration.
+    pub fn config(&self) -> ChatConfig {
+        self.settings().config.clone()
+    }
+
+    /// Replace the chat configuration.
     ///
-    /// Returns the generated response and the session ID (new or existing).
-    pub fn handle_message(
-        &self,
-        message: &str,
-        session_id: Option<Uuid>,
-    ) -> Result<(ChatResponse, Uuid), ChatError> {
-        // Check enabled
-        if !self.config.enabled {
-            return Err(ChatError::Disabled);
+    /// Applies to the next message; existing sessions are kept.
+    pub fn update_config(&self, config: ChatConfig) {
+        *self.settings.write().unwrap_or_else(|e| e.into_inner()) = ChatSettings::new(config);
+    }
+
+    fn settings(&self) -> RwLockReadGuard<'_, ChatSettings> {
+        self.settings.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Streams of messages answered via [`stream_message`](Self::stream_message).
+    pub fn streams(&self) -> &ChatStreams {
+        &self.streams
+    }
+
+    /// Start recording a voice query, to be asked in `session_id` (or a new
+    /// session). Returns the session ID.
+    pub async fn start_voice_query(&self, session_id: Option<Uuid>) -> Result<Uuid, ChatError> {
+        let max_duration_seconds = {
+            let settings = self.settings();
+            if !settings.config.enabled {
+                return Err(ChatError::Disabled);
+            }
+            settings.config.max_voice_duration_seconds
+        };
+        let mut voice = self.voice_queries()?.lock().await;
+        voice.interface.max_duration_seconds = max_duration_seconds;
+        voice.interface.start_listening()?;
+
+        let sid = self.resolve_session(session_id);
+        voice.session_id = Some(sid);
+        self.emit_event(DomainEvent::VoiceQueryStarted {
+            session_id: sid,
+            timestamp: Timestamp::now(),
+        });
+        Ok(sid)
+    }
+
+    /// Stop recording, transcribe the query and answer it.
+    ///
+    /// Fails with [`ChatError::VoiceError`] when no speech was recognised.
+    pub async fn finish_voice_query(&self) -> Result<VoiceAnswer, ChatError> {
+        let mut voice = self.voice_queries()?.lock().await;
+        let transcript = voice.interface.stop_listening().await;
+        let session_id = voice.session_id.take();
+        let transcript = transcript?.unwrap_or_default();
+        if let Some(sid) = session_id {
+            self.emit_event(DomainEvent::VoiceQueryCompleted {
+                session_id: sid,
+                transcription_length: transcript.chars().count(),
+                timestamp: Timestamp::now(),
+            });
+        }
+        if transcript.is_empty() {
+            return Err(ChatError::VoiceError(
+                "No speech was recognised".to_string(),
+            ));
+        }
+
+        let (response, session_id) = self.handle_message(&transcript, session_id).await?;
+        let answer = VoiceAnswer {
+            transcript,
+            session_id,
+            response,
+        };
+        voice.last = Some(answer.clone());
+        Ok(answer)
+    }
+
+    /// Whether voice queries can be recorded, whether one is being recorded
+    /// and the last answer.
+    pub async fn voice_status(&self) -> VoiceStatus {
+        let config = self.config();
+        let Some(voice) = &self.voice else {
+            return VoiceStatus {
+                available: false,
+                listening: false,
+                hotkey: config.voice_hotkey,
+                max_duration_seconds: config.max_voice_duration_seconds,
+                last: None,
+            };
+        };
+        let voice = voice.lock().await;
+        VoiceStatus {
+            available: config.enabled && voice.interface.is_available(),
+            listening: voice.interface.active,
+            hotkey: config.voice_hotkey,
+            max_duration_seconds: config.max_voice_duration_seconds,
+            last: voice.last.clone(),
         }
+    }
+
+    fn voice_queries(&self) -> Result<&tokio::sync::Mutex<VoiceQueries>, ChatError> {
+        self.voice
+            .as_ref()
+            .ok_or_else(|| ChatError::VoiceError("Voice queries are not configured".to_string()))
+    }
 
-        // Validate message
+    /// Check that chat is enabled and `message` can be answered.
+    pub fn validate_message(&self, message: &str) -> Result<(), ChatError> {
+        if !self.settings().config.enabled {
+            return Err(ChatError::Disabled);
+        }
         if message.trim().is_empty() {
             return Err(ChatError::EmptyMessage);
         }
         if message.chars().count() > MAX_MESSAGE_LENGTH {
             return Err(ChatError::MessageTooLong(MAX_MESSAGE_LENGTH));
         }
+        Ok(())
+    }
+
+    /// Handle an incoming chat message.
+    ///
+    /// Returns the generated response and the session ID (new or existing).
+    pub async fn handle_message(
+        &self,
+        message: &str,
+        session_id: Option<Uuid>,
+    ) -> Result<(ChatResponse, Uuid), ChatError> {
+        self.handle_message_as(feedback::DEFAULT_USER, message, session_id)
+            .await
+            .map(|(response, sid, _)| (response, sid))
+    }
+
+    /// Handle a chat message for `user`, ranking sources with what that
+    /// user's feedback has taught retrieval.
+    ///
+    /// Returns the response, the session ID and the ID of the stored
+    /// answer, which feedback refers to.
+    pub async fn handle_message_as(
+        &self,
+        user: &str,
+        message: &str,
+        session_id: Option<Uuid>,
+    ) -> Result<(ChatResponse, Uuid, Uuid), ChatError> {
+        self.process_message(message, session_id, user, None).await
+    }
+
+    /// Handle a chat message, recording progress, sources, answer text and
+    /// the stored message on `stream` (from [`ChatStreams::start`] on
+    /// [`streams`](Self::streams)).
+    ///
+    /// The stream ends with a `done`, `cancelled` or `error` event. When it
+    /// is cancelled before the answer is stored, nothing is persisted.
+    pub async fn stream_message(
+        &self,
+        message: &str,
+        session_id: Option<Uuid>,
+        stream: &ChatStream,
+    ) {
+        self.stream_message_as(feedback::DEFAULT_USER, message, session_id, stream)
+            .await
+    }
+
+    /// Like [`stream_message`](Self::stream_message), ranking sources with
+    /// `user`'s feedback.
+    pub async fn stream_message_as(
+        &self,
+        user: &str,
+        message: &str,
+        session_id: Option<Uuid>,
+        stream: &ChatStream,
+    ) {
+        match self
+            .process_message(message, session_id, user, Some(stream))
+            .await
+        {
+            Ok(_) => {}
+            Err(ChatError::Cancelled) => stream.emit(ChatStreamEvent::Cancelled {
+                message_id: stream.message_id(),
+            }),
+            Err(e) => stream.emit(ChatStreamEvent::Error {
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    async fn process_message(
+        &self,
+        message: &str,
+        session_id: Option<Uuid>,
+        user: &str,
+        stream: Option<&ChatStream>,
+    ) -> Result<(ChatResponse, Uuid, Uuid), ChatError> {
+        self.validate_message(message)?;
 
         // Get or create session
         let sid = self.resolve_session(session_id);
@@ -117,7 +352,16 @@ impl ChatOrchestrator {
         let known_entities = self.load_known_entities();
 
         // Parse the query with known entities
-        let mut query = self.parser.parse(message, &known_entities);
+        let mut query = self.settings().parser.parse(message, &known_entities);
+
+        // Title a new session after its first query
+        if let Ok(mut sessions) = self.sessions.lock() {
+            if let Some(session) = sessions.get_mut(&sid) {
+                if session.title.is_none() {
+                    session.title = Some(history::session_title(&query, message));
+                }
+            }
+        }
 
         // If session has context, resolve follow-ups
         {
@@ -167,53 +411,94 @@ impl ChatOrchestrator {
             timestamp: Timestamp::now(),
         });
 
-        // FIX-4: If "when was that?", compose temporal answer from prior turn's sources
-        if when_mode {
-            let response = self.route_when(sid)?;
-            self.emit_event(DomainEvent::ChatResponseGenerated {
+        if let Some(stream) = stream {
+            stream.emit(ChatStreamEvent::Started {
+                message_id: stream.message_id(),
                 session_id: sid,
-                source_count: response.sources.len(),
-                confidence: response.confidence,
-                mode: "template".to_string(),
-                timestamp: Timestamp::now(),
+                intent: query.intent,
             });
-            self.store_messages(sid, message, &response)?;
-            {
-                let mut sessions = self.sessions.lock().map_err(|e| {
-                    ChatError::StorageError(format!("session lock poisoned: {}", e))
-                })?;
-                if let Some(session) = sessions.get_mut(&sid) {
-                    self.context_manager
-                        .update_session(session, &query, &response);
-                }
-            }
-            return Ok((response, sid));
         }
 
-        // Route by intent
-        let result_limit = if more_mode {
-            self.config.max_results_per_query * 2
+        let (response, mode) = if when_mode {
+            // FIX-4: If "when was that?", compose temporal answer from prior turn's sources
+            (self.route_when(sid)?, ResponseMode::Template)
         } else {
-            self.config.max_results_per_query
-        };
-        let response = match query.intent {
-            QueryIntent::Search => self.route_search_with_limit(message, &query, result_limit),
-            QueryIntent::Action => self.route_action(message),
-            QueryIntent::Question => self.route_question(&query),
-            QueryIntent::Clarification => self.route_clarification(sid)?,
+            // Route by intent
+            let result_limit = if more_mode {
+                self.settings().config.max_results_per_query * 2
+            } else {
+                self.settings().config.max_results_per_query
+            };
+            // Queries about tasks, entities, summaries, digests or apps are
+            // answered from the matching store, analytics questions from
+            // capture timestamps, and comparisons and timelines in steps.
+            let mut routed = self
+                .route_structured(&query, result_limit, stream)
+                .or_else(|| self.route_analytics(&query, result_limit, stream));
+            if routed.is_none() {
+                routed = self
+                    .route_plan(message, &query, result_limit, user, stream)
+                    .await?;
+            }
+            match (routed, query.intent) {
+                (Some(response), _) => (response, ResponseMode::Template),
+                (None, QueryIntent::Search) => {
+                    let generated = self
+                        .route_search_with_limit(message, &query, result_limit, user, stream)
+                        .await?;
+                    (generated.response, generated.mode)
+                }
+                (None, QueryIntent::Action) => (self.route_action(message), ResponseMode::Template),
+                (None, QueryIntent::Question) => {
+                    (self.route_question(&query), ResponseMode::Template)
+                }
+                (None, QueryIntent::Clarification) => {
+                    (self.route_clarification(sid)?, ResponseMode::Template)
+                }
+            }
         };
 
+        // Last point at which a cancelled stream leaves nothing behind.
+        check_cancelled(stream)?;
+
         // FIX #5: Emit ChatResponseGenerated domain event
         self.emit_event(DomainEvent::ChatResponseGenerated {
             session_id: sid,
             source_count: response.sources.len(),
             confidence: response.confidence,
-            mode: "template".to_string(),
+            mode: mode.as_str().to_string(),
             timestamp: Timestamp::now(),
         });
 
+        if let Some(stream) = stream {
+            if let Some(table) = &response.table {
+                stream.emit(ChatStreamEvent::Table {
+                    table: table.clone(),
+                });
+            }
+            for section in &response.sections {
+                stream.emit(ChatStreamEvent::Section {
+                    section: section.clone(),
+                });
+            }
+            // Model answers were sent while they were written; other
+            // answers are sent in pieces now. A streamed answer that then
+            // changed, e.g. fell back to the extractive one, is resent whole.
+            let streamed = stream.answer_text();
+            if streamed.is_empty() {
+                for text in answer_deltas(&response.answer) {
+                    stream.emit(ChatStreamEvent::Delta { text });
+                }
+            } else if streamed != response.answer {
+                stream.emit(ChatStreamEvent::Replace {
+                    text: response.answer.clone(),
+                });
+            }
+        }
+
         // Store messages in history (in-memory + SQLite)
-        self.store_messages(sid, message, &response)?;
+        let assistant_msg_id = stream.map_or_else(Uuid::new_v4, ChatStream::message_id);
+        let stored_at = self.store_messages(sid, message, &response, assistant_msg_id)?;
 
         // Update session context
         {
@@ -222,12 +507,28 @@ impl ChatOrchestrator {
                 .lock()
                 .map_err(|e| ChatError::StorageError(format!("session lock poisoned: {}", e)))?;
             if let Some(session) = sessions.get_mut(&sid) {
-                self.context_manager
+                self.settings()
+                    .context_manager
                     .update_session(session, &query, &response);
             }
         }
 
-        Ok((response, sid))
+        if let Some(stream) = stream {
+            stream.emit(ChatStreamEvent::Done {
+                message_id: assistant_msg_id,
+                session_id: sid,
+                confidence: response.confidence,
+                message: ChatMessageRecord {
+                    role: "assistant".to_string(),
+                    content: response.answer.clone(),
+                    timestamp: format_epoch(stored_at),
+                    sources: Some(response.sources.clone()),
+                    suggestions: Some(response.suggestions.clone()),
+                },
+            });
+        }
+
+        Ok((response, sid, assistant_msg_id))
     }
 
     /// Get a session by ID.
@@ -250,15 +551,8 @@ impl ChatOrchestrator {
             Ok(s) => s,
             Err(_) => return vec![],
         };
-        let mut summaries: Vec<ChatSessionSummary> = sessions
-            .values()
-            .map(|s| ChatSessionSummary {
-                id: s.id,
-                started_at: format_epoch(s.started_at),
-                last_message_at: format_epoch(s.last_message_at),
-                message_count: s.message_count,
-            })
-            .collect();
+        let mut summaries: Vec<ChatSessionSummary> =
+            sessions.values().map(session_summary).collect();
 
         // FIX-1: Merge in sessions from SQLite that aren't in memory
         let in_memory_ids: std::collections::HashSet<Uuid> = sessions.keys().copied().collect();
@@ -268,7 +562,7 @@ impl ChatOrchestrator {
             if let Ok(sqlite_summaries) = backends.database.with_conn(|conn| {
                 let mut stmt = conn
                     .prepare(
-                        "SELECT id, started_at, last_message_at, message_count
+                        "SELECT id, started_at, last_message_at, message_count, title, pinned
                          FROM chat_sessions ORDER BY last_message_at DESC",
                     )
                     .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))?;
@@ -278,17 +572,21 @@ impl ChatOrchestrator {
                         let started_at: String = row.get(1)?;
                         let last_msg: String = row.get(2)?;
                         let msg_count: u32 = row.get(3)?;
-                        Ok((id_str, started_at, last_msg, msg_count))
+                        let title: Option<String> = row.get(4)?;
+                        let pinned: bool = row.get(5)?;
+                        Ok((id_str, started_at, last_msg, msg_count, title, pinned))
                     })
                     .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))?;
                 let mut result = Vec::new();
-                for (id_str, started_at, last_msg, msg_count) in rows.flatten() {
+                for (id_str, started_at, last_msg, msg_count, title, pinned) in rows.flatten() {
                     if let Ok(id) = Uuid::parse_str(&id_str) {
                         result.push(ChatSessionSummary {
                             id,
                             started_at,
                             last_message_at: last_msg,
                             message_count: msg_count,
+                            title,
+                            pinned,
                         });
                     }
                 }
@@ -302,8 +600,12 @@ impl ChatOrchestrator {
             }
         }
 
-        // Sort by last_message_at descending (most recent first)
-        summaries.sort_by(|a, b| b.last_message_at.cmp(&a.last_message_at));
+        // Pinned sessions first, then by last_message_at descending (most recent first)
+        summaries.sort_by(|a, b| {
+            b.pinned
+                .cmp(&a.pinned)
+                .then_with(|| b.last_message_at.cmp(&a.last_message_at))
+        });
         summaries
     }
 
@@ -383,60 +685,501 @@ impl ChatOrchestrator {
         Err(ChatError::SessionNotFound(session_id))
     }
 
+    /// Rename and/or pin a session.
+    pub fn update_session(
+        &self,
+        session_id: Uuid,
+        update: ChatSessionUpdate,
+    ) -> Result<ChatSessionSummary, ChatError> {
+        let title = update
+            .title
+            .as_deref()
+            .map(history::normalize_title)
+            .transpose()?;
+
+        let in_memory = {
+            let mut sessions = self
+                .sessions
+                .lock()
+                .map_err(|e| ChatError::StorageError(format!("session lock poisoned: {}", e)))?;
+            match sessions.get_mut(&session_id) {
+                Some(session) => {
+                    if let Some(ref title) = title {
+                        session.title = Some(title.clone());
+                    }
+                    if let Some(pinned) = update.pinned {
+                        session.pinned = pinned;
+                    }
+                    true
+                }
+                None => false,
+            }
+        };
+
+        let mut in_sqlite = false;
+        if let Some(ref backends) = self.backends {
+            let sid_str = session_id.to_string();
+            in_sqlite = backends
+                .database
+                .with_conn(|conn| {
+                    let updated = conn
+                        .execute(
+                            "UPDATE chat_sessions
+                             SET title = COALESCE(?2, title), pinned = COALESCE(?3, pinned)
+                             WHERE id = ?1",
+                            rusqlite::params![sid_str, title, update.pinned],
+                        )
+                        .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))?;
+                    Ok(updated > 0)
+                })
+                .map_err(|e| ChatError::StorageError(e.to_string()))?;
+        }
+
+        if !in_memory && !in_sqlite {
+            return Err(ChatError::SessionNotFound(session_id));
+        }
+        self.get_session(session_id)
+            .map(|s| session_summary(&s))
+            .ok_or(ChatError::SessionNotFound(session_id))
+    }
+
+    /// Full-text search over past chat messages, best matches first.
+    ///
+    /// Uses the `chat_messages_fts` index when a database is attached, and a
+    /// case-insensitive scan of in-memory sessions otherwise.
+    pub fn search_messages(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<ChatMessageHit>, ChatError> {
+        let fts_query = engram_storage::sanitize_fts5_query(query);
+        if fts_query.is_empty() || limit == 0 {
+            return Ok(vec![]);
+        }
+
+        if let Some(ref backends) = self.backends {
+            return backends
+                .database
+                .with_conn(|conn| {
+                    let mut stmt = conn
+                        .prepare(
+                            "SELECT m.session_id, s.title, m.id, m.role,
+                                    snippet(chat_messages_fts, 0, '**', '**', '…', 12),
+                                    m.created_at
+                             FROM chat_messages_fts
+                             JOIN chat_messages m ON m.rowid = chat_messages_fts.rowid
+                             JOIN chat_sessions s ON s.id = m.session_id
+                             WHERE chat_messages_fts MATCH ?1
+                             ORDER BY rank
+                             LIMIT ?2",
+                        )
+                        .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))?;
+                    let rows = stmt
+                        .query_map(rusqlite::params![fts_query, limit as i64], |row| {
+                            let session_id: String = row.get(0)?;
+                            let session_title: Option<String> = row.get(1)?;
+                            let message_id: String = row.get(2)?;
+                            let role: String = row.get(3)?;
+                            let snippet: String = row.get(4)?;
+                            let created_at: String = row.get(5)?;
+                            Ok((
+                                session_id,
+                                session_title,
+                                message_id,
+                                role,
+                                snippet,
+                                created_at,
+                            ))
+                        })
+                        .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))?;
+                    let mut hits = Vec::new();
+                    for (session_id, session_title, message_id, role, snippet, created_at) in
+                        rows.flatten()
+                    {
+                        if let (Ok(session_id), Ok(message_id)) =
+                            (Uuid::parse_str(&session_id), Uuid::parse_str(&message_id))
+                        {
+                            hits.push(ChatMessageHit {
+                                session_id,
+                                session_title,
+                                message_id,
+                                role,
+                                snippet,
+                                created_at: parse_sqlite_datetime(&created_at),
+                            });
+                        }
+                    }
+                    Ok(hits)
+                })
+                .map_err(|e| ChatError::SearchError(e.to_string()));
+        }
+
+        // In-memory fallback (no database attached)
+        let needle = query.trim().to_lowercase();
+        let titles: HashMap<Uuid, Option<String>> = self
+            .sessions
+            .lock()
+            .map_err(|e| ChatError::StorageError(format!("session lock poisoned: {}", e)))?
+            .iter()
+            .map(|(id, s)| (*id, s.title.clone()))
+            .collect();
+        let messages = self
+            .messages
+            .lock()
+            .map_err(|e| ChatError::StorageError(format!("messages lock poisoned: {}", e)))?;
+        let mut hits: Vec<ChatMessageHit> = messages
+            .values()
+            .flatten()
+            .filter(|m| m.content.to_lowercase().contains(&needle))
+            .map(|m| ChatMessageHit {
+                session_id: m.session_id,
+                session_title: titles.get(&m.session_id).cloned().flatten(),
+                message_id: m.id,
+                role: m.role.clone(),
+                snippet: m.content.clone(),
+                created_at: m.created_at,
+            })
+            .collect();
+        hits.sort_by_key(|h| std::cmp::Reverse(h.created_at));
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    /// Export a session as Markdown, with citations resolved to the cited
+    /// captures' timestamps and apps.
+    pub fn export_session_markdown(&self, session_id: Uuid) -> Result<String, ChatError> {
+        let messages = self.get_history(session_id)?;
+        let session = self
+            .get_session(session_id)
+            .ok_or(ChatError::SessionNotFound(session_id))?;
+        let summary = session_summary(&session);
+
+        Ok(history::session_markdown(&summary, &messages, |chunk_id| {
+            let backends = self.backends.as_ref()?;
+            backends
+                .database
+                .with_conn(|conn| {
+                    conn.query_row(
+                        "SELECT timestamp, app_name FROM captures WHERE id = ?1",
+                        rusqlite::params![chunk_id.to_string()],
+                        |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+                    )
+                    .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))
+                })
+                .ok()
+        }))
+    }
+
+    /// Record `user`'s feedback on an answer or one of its sources.
+    ///
+    /// Rated sources are boosted or penalized under the terms of the
+    /// question the answer was for, and the user's semantic weight is
+    /// tuned toward the retrieval path of the sources they liked. Rating
+    /// the same answer or source again replaces the earlier rating.
+    pub fn record_feedback(
+        &self,
+        user: &str,
+        rating: ChatFeedback,
+    ) -> Result<FeedbackRecorded, ChatError> {
+        let backends = self
+            .backends
+            .as_ref()
+            .ok_or_else(|| ChatError::StorageError("chat feedback needs a database".to_string()))?;
+        let mid_str = rating.message_id.to_string();
+
+        let target = backends
+            .database
+            .with_conn(|conn| {
+                let result = conn.query_row(
+                    "SELECT m.session_id, m.role, m.sources,
+                            (SELECT u.content FROM chat_messages u
+                             WHERE u.session_id = m.session_id AND u.role = 'user'
+                               AND u.rowid < m.rowid
+                             ORDER BY u.rowid DESC LIMIT 1)
+                     FROM chat_messages m WHERE m.id = ?1",
+                    rusqlite::params![mid_str],
+                    |row| {
+                        let session_id: String = row.get(0)?;
+                        let role: String = row.get(1)?;
+                        let sources: Option<String> = row.get(2)?;
+                        let question: Option<String> = row.get(3)?;
+                        Ok((session_id, role, sources, question))
+                    },
+                );
+                match result {
+                    Ok(target) => Ok(Some(target)),
+                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                    Err(e) => Err(engram_core::error::EngramError::Storage(e.to_string())),
+                }
+            })
+            .map_err(|e| ChatError::StorageError(e.to_string()))?;
+        let Some((session_id, role, sources, question)) = target else {
+            return Err(ChatError::MessageNotFound(rating.message_id));
+        };
+        if role != "assistant" {
+            return Err(ChatError::InvalidFeedback(
+                "only answers can be rated".to_string(),
+            ));
+        }
+
+        let sources: Vec<SourceRef> = sources
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default();
+        let rated: Vec<SourceRef> = match rating.chunk_id {
+            Some(chunk_id) => {
+                let rated: Vec<SourceRef> = sources
+                    .into_iter()
+                    .filter(|s| s.chunk_id == chunk_id)
+                    .collect();
+                if rated.is_empty() {
+                    return Err(ChatError::InvalidFeedback(format!(
+                        "source {} is not cited by message {}",
+                        chunk_id, rating.message_id
+                    )));
+                }
+                rated
+            }
+            None if rating.rating == FeedbackRating::Irrelevant => {
+                return Err(ChatError::InvalidFeedback(
+                    "irrelevant feedback needs a chunk_id".to_string(),
+                ));
+            }
+            None => sources,
+        };
+
+        let question = question.unwrap_or_default();
+        let terms = feedback::feedback_terms(&self.settings().parser.parse(&question, &[]));
+        let chunk_str = rating.chunk_id.map(|c| c.to_string());
+        let now = Local::now().timestamp();
+
+        let (id, semantic_weight) = backends
+            .database
+            .with_transaction(|conn| {
+                // A repeat rating replaces the earlier one: take its boosts
+                // and weight nudge back before applying the new rating.
+                let previous =
+                    engram_storage::get_chat_feedback(conn, user, &mid_str, chunk_str.as_deref())?;
+                let previous_rating = previous
+                    .as_ref()
+                    .and_then(|p| p.rating.parse::<FeedbackRating>().ok());
+                let id = previous
+                    .as_ref()
+                    .map_or_else(|| Uuid::new_v4().to_string(), |p| p.id.clone());
+                let step = feedback::boost_step(rating.rating)
+                    - previous_rating.map_or(0.0, feedback::boost_step);
+
+                engram_storage::store_chat_feedback(
+                    conn,
+                    &engram_storage::ChatFeedbackRow {
+                        id: id.clone(),
+                        message_id: mid_str.clone(),
+                        session_id: session_id.clone(),
+                        chunk_id: chunk_str.clone(),
+                        rating: rating.rating.as_str().to_string(),
+                        query: question.clone(),
+                        user_id: user.to_string(),
+                        created_at: now,
+                    },
+                )?;
+                for source in &rated {
+                    for term in &terms {
+                        engram_storage::adjust_source_boost(
+                            conn,
+                            user,
+                            term,
+                            &source.chunk_id.to_string(),
+                            step as f64,
+                            now,
+                        )?;
+                    }
+                }
+
+                let current = engram_storage::get_fusion_weight(conn, user)?;
+                let (weight, count) = current.map_or((SEMANTIC_WEIGHT, 0), |w| {
+                    (w.semantic_weight as f32, w.feedback_count)
+                });
+                let weight = match previous_rating {
+                    Some(earlier) => feedback::untune_semantic_weight(weight, earlier, &rated),
+                    None => weight,
+                };
+                let tuned = feedback::tune_semantic_weight(weight, rating.rating, &rated);
+                engram_storage::store_fusion_weight(
+                    conn,
+                    &engram_storage::FusionWeightRow {
+                        user_id: user.to_string(),
+                        semantic_weight: tuned as f64,
+                        feedback_count: count + i64::from(previous.is_none()),
+                        updated_at: now,
+                    },
+                )?;
+                Ok((id, tuned))
+            })
+            .map_err(|e| ChatError::StorageError(e.to_string()))?;
+
+        Ok(FeedbackRecorded {
+            id: Uuid::parse_str(&id).map_err(|e| ChatError::StorageError(e.to_string()))?,
+            adjusted_boosts: rated.len() * terms.len(),
+            semantic_weight,
+        })
+    }
+
+    /// How `user`'s feedback has shifted retrieval: feedback counts, the
+    /// tuned semantic weight, the strongest learned boosts, and the sources
+    /// of recently rated questions ranked without and with feedback.
+    pub async fn feedback_report(&self, user: &str) -> Result<FeedbackReport, ChatError> {
+        let backends = self
+            .backends
+            .as_ref()
+            .ok_or_else(|| ChatError::StorageError("chat feedback needs a database".to_string()))?;
+        let (counts, weight, boosts, queries) = backends
+            .database
+            .with_conn(|conn| {
+                Ok((
+                    engram_storage::get_chat_feedback_counts(conn, user)?,
+                    engram_storage::get_fusion_weight(conn, user)?,
+                    engram_storage::list_source_boosts(conn, user, REPORT_BOOSTS)?,
+                    engram_storage::list_feedback_queries(conn, user, REPORT_QUERIES)?,
+                ))
+            })
+            .map_err(|e| ChatError::StorageError(e.to_string()))?;
+
+        let mut feedback_counts = FeedbackCounts::default();
+        for row in counts {
+            let count = row.count.max(0) as u64;
+            match row.rating.as_str() {
+                "up" => feedback_counts.up = count,
+                "down" => feedback_counts.down = count,
+                "irrelevant" => feedback_counts.irrelevant = count,
+                _ => {}
+            }
+        }
+
+        let limit = self.settings().config.max_results_per_query;
+        let mut shifts = Vec::with_capacity(queries.len());
+        for question in queries {
+            let query = self.settings().parser.parse(&question, &[]);
+            let filters = search_filters(&query);
+            let keywords = search_terms(&question, &query, &filters);
+            let embedded = search_question(&question, &query);
+            let rank = |ranking: RankingFeedback| async {
+                HybridRetriever::new(&backends.fts_search, &backends.search_engine)
+                    .with_feedback(ranking)
+                    .retrieve(&embedded, &keywords, &query.topics, &filters, limit)
+                    .await
+                    .into_iter()
+                    .map(|r| r.chunk_id)
+                    .collect::<Vec<Uuid>>()
+            };
+            let baseline = rank(RankingFeedback::default()).await;
+            let adjusted =
+                rank(self.ranking_feedback(user, &feedback::feedback_terms(&query))).await;
+            shifts.push(RankingShift {
+                query: question,
+                results: feedback::rank_shifts(&baseline, &adjusted),
+            });
+        }
+
+        Ok(FeedbackReport {
+            user: user.to_string(),
+            counts: feedback_counts,
+            semantic_weight: weight.map_or(SEMANTIC_WEIGHT, |w| w.semantic_weight as f32),
+            default_semantic_weight: SEMANTIC_WEIGHT,
+            boosts: boosts
+                .into_iter()
+                .filter_map(|b| {
+                    Some(SourceBoost {
+                        chunk_id: Uuid::parse_str(&b.chunk_id).ok()?,
+                        term: b.term,
+                        score: b.score as f32,
+                    })
+                })
+                .collect(),
+            shifts,
+        })
+    }
+
     // =========================================================================
     // Private: intent routing
     // =========================================================================
 
     /// Route Search intent with a configurable result limit.
     /// FIX-3: supports doubled limit for "tell me more".
-    fn route_search_with_limit(
+    async fn route_search_with_limit(
         &self,
         message: &str,
         query: &crate::types::StructuredQuery,
         limit: usize,
-    ) -> ChatResponse {
-        if let Some(ref backends) = self.backends {
-            let search_query = if query.topics.is_empty() {
-                message.to_string()
-            } else {
-                query.topics.join(" ")
-            };
-
-            let gen = ResponseGenerator::new(limit);
-            match backends.fts_search.search(&search_query, limit as u64) {
-                Ok(results) if !results.is_empty() => {
-                    let search_results: Vec<SearchResult> = results
-                        .iter()
-                        .map(|r| SearchResult {
-                            chunk_id: r.id,
-                            content: r.text.clone(),
-                            timestamp: r.timestamp.timestamp(),
-                            source_app: r.app_name.clone(),
-                            relevance_score: (-r.rank as f32).clamp(0.0, 1.0),
-                            person: None,
-                        })
-                        .collect();
-                    gen.compose_extractive(&search_results, query)
-                }
-                Ok(_) => gen.compose_extractive(&[], query),
-                Err(e) => {
-                    tracing::warn!("FTS search failed: {}", e);
-                    gen.compose_extractive(&[], query)
-                }
-            }
+        user: &str,
+        stream: Option<&ChatStream>,
+    ) -> Result<GeneratedResponse, ChatError> {
+        let (max_tokens, temperature) = {
+            let llm = &self.settings().config.llm;
+            (llm.max_tokens, llm.temperature)
+        };
+        if let Some(stream) = stream {
+            stream.emit(ChatStreamEvent::Progress {
+                stage: StreamStage::Retrieving,
+                detail: None,
+            });
+        }
+        let results = if let Some(ref backends) = self.backends {
+            let filters = search_filters(query);
+            let search_query = search_terms(message, query, &filters);
+            let question = search_question(message, query);
+            let ranking = self.ranking_feedback(user, &feedback::feedback_terms(query));
+
+            until_cancelled(
+                stream,
+                HybridRetriever::new(&backends.fts_search, &backends.search_engine)
+                    .with_feedback(ranking)
+                    .retrieve(&question, &search_query, &query.topics, &filters, limit),
+            )
+            .await?
         } else {
             // Mock fallback (tests)
-            let mock_results = vec![SearchResult {
+            vec![SearchResult {
                 chunk_id: Uuid::new_v4(),
                 content: format!("Search results for: {}", message),
                 timestamp: Local::now().timestamp(),
                 source_app: "Engram".to_string(),
                 relevance_score: 0.75,
                 person: None,
-            }];
-            let gen = ResponseGenerator::new(limit);
-            gen.compose_extractive(&mock_results, query)
+                retrieval: RetrievalPath::Keyword,
+            }]
+        };
+
+        if let Some(stream) = stream {
+            for result in &results {
+                stream.emit(ChatStreamEvent::Source {
+                    source: source_ref(result),
+                });
+            }
+            stream.emit(ChatStreamEvent::Progress {
+                stage: StreamStage::Generating,
+                detail: Some(format!("{} captures found", results.len())),
+            });
         }
+
+        let emit_delta = |text: &str| {
+            if let Some(stream) = stream {
+                stream.emit(ChatStreamEvent::Delta {
+                    text: text.to_string(),
+                });
+            }
+        };
+        until_cancelled(
+            stream,
+            self.response_backend.generate(&GenerationRequest {
+                query,
+                results: &results,
+                max_results: limit,
+                max_tokens,
+                temperature,
+                on_delta: stream.map(|_| &emit_delta as &(dyn Fn(&str) + Send + Sync)),
+            }),
+        )
+        .await
     }
 
     /// FIX #3: Route Action intent to real IntentDetector + TaskStore or mock fallback.
@@ -465,6 +1208,8 @@ impl ChatOrchestrator {
                             "Check task status".to_string(),
                             "List my tasks".to_string(),
                         ],
+                        table: None,
+                        sections: vec![],
                     },
                     Err(e) => ChatResponse {
                         answer: format!(
@@ -474,6 +1219,8 @@ impl ChatOrchestrator {
                         sources: vec![],
                         confidence: 0.5,
                         suggestions: vec!["Try again".to_string(), "Check task status".to_string()],
+                        table: None,
+                        sections: vec![],
                     },
                 }
             } else {
@@ -489,49 +1236,210 @@ impl ChatOrchestrator {
                         "Try 'remind me to...'".to_string(),
                         "Try 'set a timer for...'".to_string(),
                     ],
+                    table: None,
+                    sections: vec![],
                 }
             }
-        } else {
-            // Mock fallback (tests)
-            ChatResponse {
-                answer: format!("I'll route this to the action engine: {}", message),
-                sources: vec![],
-                confidence: 0.8,
-                suggestions: vec!["Check task status".to_string(), "List my tasks".to_string()],
+        } else {
+            // Mock fallback (tests)
+            ChatResponse {
+                answer: format!("I'll route this to the action engine: {}", message),
+                sources: vec![],
+                confidence: 0.8,
+                suggestions: vec!["Check task status".to_string(), "List my tasks".to_string()],
+                table: None,
+                sections: vec![],
+            }
+        }
+    }
+
+    /// Answer a Search or Question about a structured store (tasks,
+    /// entities, summaries, digests, apps) from that store. `None` when the
+    /// query names no store or there are no backends.
+    fn route_structured(
+        &self,
+        query: &crate::types::StructuredQuery,
+        limit: usize,
+        stream: Option<&ChatStream>,
+    ) -> Option<ChatResponse> {
+        if !matches!(query.intent, QueryIntent::Search | QueryIntent::Question) {
+            return None;
+        }
+        let source = query.data_source.as_ref()?;
+        let backends = self.backends.as_ref()?;
+        if let Some(stream) = stream {
+            stream.emit(ChatStreamEvent::Progress {
+                stage: StreamStage::Retrieving,
+                detail: Some(format!("reading {}", source.name())),
+            });
+        }
+
+        // Only a range the query names narrows the store; the parser's
+        // default window would hide older tasks and entities.
+        let range = self.settings().parser.extract_time_range(&query.raw_query);
+        let response =
+            StructuredAnswerer::new(backends, limit).answer(source, query, range.as_ref());
+
+        if let Some(stream) = stream {
+            for source in &response.sources {
+                stream.emit(ChatStreamEvent::Source {
+                    source: source.clone(),
+                });
+            }
+            stream.emit(ChatStreamEvent::Progress {
+                stage: StreamStage::Generating,
+                detail: None,
+            });
+        }
+        Some(response)
+    }
+
+    /// Answer a Search or Question asking for time spent, counts, first or
+    /// last seen, or trends from capture timestamps. `None` when the query
+    /// asks for none of these or there are no backends.
+    fn route_analytics(
+        &self,
+        query: &crate::types::StructuredQuery,
+        limit: usize,
+        stream: Option<&ChatStream>,
+    ) -> Option<ChatResponse> {
+        if !matches!(query.intent, QueryIntent::Search | QueryIntent::Question) {
+            return None;
+        }
+        let metric = query.analytics?;
+        let backends = self.backends.as_ref()?;
+        if let Some(stream) = stream {
+            stream.emit(ChatStreamEvent::Progress {
+                stage: StreamStage::Retrieving,
+                detail: Some("computing analytics".to_string()),
+            });
+        }
+        let explicit = self.settings().parser.extract_time_range(&query.raw_query);
+        let response = AnalyticsEngine::new(&backends.database, limit).answer(
+            metric,
+            query,
+            explicit.as_ref(),
+        );
+        if let Some(stream) = stream {
+            stream.emit(ChatStreamEvent::Progress {
+                stage: StreamStage::Generating,
+                detail: None,
+            });
+        }
+        Some(response)
+    }
+
+    /// Answer a Search or Question asking for a comparison or a timeline
+    /// by running one search per planned step. `None` when the query asks
+    /// for neither, names nothing to compare, or there are no backends.
+    async fn route_plan(
+        &self,
+        message: &str,
+        query: &crate::types::StructuredQuery,
+        limit: usize,
+        user: &str,
+        stream: Option<&ChatStream>,
+    ) -> Result<Option<ChatResponse>, ChatError> {
+        if !matches!(query.intent, QueryIntent::Search | QueryIntent::Question) {
+            return Ok(None);
+        }
+        let Some(backends) = self.backends.as_ref() else {
+            return Ok(None);
+        };
+        let plan = {
+            let settings = self.settings();
+            let explicit = settings.parser.extract_time_range(&query.raw_query);
+            QueryPlanner::new(&settings.parser, &backends.fts_search).plan(query, explicit.as_ref())
+        };
+        let Some(plan) = plan else {
+            return Ok(None);
+        };
+
+        let terms: Vec<String> = plan
+            .steps
+            .iter()
+            .flat_map(|step| feedback::feedback_terms(&step.query))
+            .collect();
+        let retriever = HybridRetriever::new(&backends.fts_search, &backends.search_engine)
+            .with_feedback(self.ranking_feedback(user, &terms));
+        let step_limit = plan.step_limit(limit);
+        let mut results = Vec::with_capacity(plan.steps.len());
+        for (i, step) in plan.steps.iter().enumerate() {
+            if let Some(stream) = stream {
+                stream.emit(ChatStreamEvent::Progress {
+                    stage: StreamStage::Retrieving,
+                    detail: Some(format!(
+                        "step {} of {}: {}",
+                        i + 1,
+                        plan.steps.len(),
+                        step.label
+                    )),
+                });
+            }
+            let filters = search_filters(&step.query);
+            let keywords = search_terms(message, &step.query, &filters);
+            let question = search_question(message, &step.query);
+            let mut found = until_cancelled(
+                stream,
+                retriever.retrieve(
+                    &question,
+                    &keywords,
+                    &step.query.topics,
+                    &filters,
+                    step_limit,
+                ),
+            )
+            .await?;
+            // Each step searches a narrow slice, where the nearest vectors
+            // are often unrelated; with topics, keep keyword matches.
+            if !step.query.topics.is_empty() {
+                found.retain(|r| r.retrieval != RetrievalPath::Semantic);
+            }
+            if let Some(stream) = stream {
+                for result in &found {
+                    stream.emit(ChatStreamEvent::Source {
+                        source: source_ref(result),
+                    });
+                }
             }
+            results.push(found);
+        }
+
+        if let Some(stream) = stream {
+            stream.emit(ChatStreamEvent::Progress {
+                stage: StreamStage::Generating,
+                detail: None,
+            });
         }
+        Ok(Some(plan.compose(&results)))
     }
 
     /// Route Question intent to real database analytics or mock fallback.
     /// FIX-2: When query.time_range is Some, use FTS to count matching chunks within that range.
     fn route_question(&self, query: &crate::types::StructuredQuery) -> ChatResponse {
         if let Some(ref backends) = self.backends {
-            // FIX-2: If a time range is specified, count FTS results within that range
-            if let Some(ref tr) = query.time_range {
-                let search_query = if query.topics.is_empty() {
-                    query.raw_query.clone()
-                } else {
-                    query.topics.join(" ")
-                };
+            // FIX-2: If a time range is specified, count filtered FTS results
+            if query.time_range.is_some() {
+                let filters = search_filters(query);
+                let search_query = search_terms(&query.raw_query, query, &filters);
                 let limit = 1000u64; // upper bound for counting
-                match backends.fts_search.search(&search_query, limit) {
+                match backends
+                    .fts_search
+                    .search_filtered(&search_query, &filters, limit)
+                {
                     Ok(results) => {
-                        let filtered: Vec<_> = results
-                            .iter()
-                            .filter(|r| {
-                                let ts = r.timestamp.timestamp();
-                                ts >= tr.start && ts <= tr.end
-                            })
-                            .collect();
-                        let count = filtered.len();
+                        let count = results.len();
                         let details =
                             format!("{} matching captures in the specified time range", count);
-                        self.response_generator
+                        self.settings()
+                            .response_generator
                             .compose_analytics(query, count, &details)
                     }
                     Err(e) => {
                         tracing::warn!("FTS search for analytics failed: {}", e);
-                        self.response_generator.compose_analytics(query, 0, "")
+                        self.settings()
+                            .response_generator
+                            .compose_analytics(query, 0, "")
                     }
                 }
             } else {
@@ -545,7 +1453,7 @@ impl ChatOrchestrator {
                             stats.audio_count,
                             stats.dictation_count
                         );
-                        self.response_generator.compose_analytics(
+                        self.settings().response_generator.compose_analytics(
                             query,
                             stats.total_captures as usize,
                             &details,
@@ -553,13 +1461,17 @@ impl ChatOrchestrator {
                     }
                     Err(e) => {
                         tracing::warn!("Analytics query failed: {}", e);
-                        self.response_generator.compose_analytics(query, 0, "")
+                        self.settings()
+                            .response_generator
+                            .compose_analytics(query, 0, "")
                     }
                 }
             }
         } else {
             // Mock fallback (tests)
-            self.response_generator.compose_analytics(query, 0, "")
+            self.settings()
+                .response_generator
+                .compose_analytics(query, 0, "")
         }
     }
 
@@ -579,6 +1491,8 @@ impl ChatOrchestrator {
                         format!("Show me more about {}", topic),
                         "Try a different search".to_string(),
                     ],
+                    table: None,
+                    sections: vec![],
                 })
             } else {
                 Ok(no_context_response())
@@ -614,6 +1528,8 @@ impl ChatOrchestrator {
                             "Tell me more about this".to_string(),
                             "What happened before this?".to_string(),
                         ],
+                        table: None,
+                        sections: vec![],
                     });
                 }
             }
@@ -626,6 +1542,8 @@ impl ChatOrchestrator {
                 "Try searching for a specific topic".to_string(),
                 "What did I do today?".to_string(),
             ],
+            table: None,
+            sections: vec![],
         })
     }
 
@@ -634,15 +1552,17 @@ impl ChatOrchestrator {
     // =========================================================================
 
     /// Store user + assistant messages in memory and optionally in SQLite.
+    ///
+    /// Returns the time the messages were stored (epoch seconds).
     fn store_messages(
         &self,
         sid: Uuid,
         message: &str,
         response: &ChatResponse,
-    ) -> Result<(), ChatError> {
+        assistant_msg_id: Uuid,
+    ) -> Result<i64, ChatError> {
         let now = Local::now().timestamp();
         let user_msg_id = Uuid::new_v4();
-        let assistant_msg_id = Uuid::new_v4();
 
         // In-memory storage
         {
@@ -682,28 +1602,35 @@ impl ChatOrchestrator {
             let suggestions_json = serde_json::to_string(&response.suggestions).unwrap_or_default();
 
             // FIX-1: Serialize session context for the context column
-            let context_json = {
+            let (context_json, title, pinned) = {
                 let sessions = self.sessions.lock().ok();
                 sessions
                     .and_then(|s| {
                         s.get(&sid).map(|sess| {
-                            serde_json::to_string(&sess.context)
-                                .unwrap_or_else(|_| "{}".to_string())
+                            (
+                                serde_json::to_string(&sess.context)
+                                    .unwrap_or_else(|_| "{}".to_string()),
+                                sess.title.clone(),
+                                sess.pinned,
+                            )
                         })
                     })
-                    .unwrap_or_else(|| "{}".to_string())
+                    .unwrap_or_else(|| ("{}".to_string(), None, false))
             };
 
             if let Err(e) = backends.database.with_conn(|conn| {
-                // Upsert session (update last_message_at, increment count, and persist context)
+                // Upsert session (update last_message_at, increment count, and persist
+                // context, title and pin)
                 conn.execute(
-                    "INSERT INTO chat_sessions (id, started_at, last_message_at, context, message_count)
-                     VALUES (?1, datetime('now'), datetime('now'), ?2, 1)
+                    "INSERT INTO chat_sessions (id, started_at, last_message_at, context, message_count, title, pinned)
+                     VALUES (?1, datetime('now'), datetime('now'), ?2, 1, ?3, ?4)
                      ON CONFLICT(id) DO UPDATE SET
                        last_message_at = datetime('now'),
                        context = ?2,
-                       message_count = message_count + 1",
-                    rusqlite::params![sid_str, context_json],
+                       message_count = message_count + 1,
+                       title = ?3,
+                       pinned = ?4",
+                    rusqlite::params![sid_str, context_json, title, pinned],
                 )
                 .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))?;
 
@@ -735,13 +1662,40 @@ impl ChatOrchestrator {
             }
         }
 
-        Ok(())
+        Ok(now)
     }
 
     // =========================================================================
     // Private: helpers
     // =========================================================================
 
+    /// What `user`'s feedback has taught retrieval about `terms`; the
+    /// defaults without a database or when loading fails.
+    fn ranking_feedback(&self, user: &str, terms: &[String]) -> RankingFeedback {
+        let Some(ref backends) = self.backends else {
+            return RankingFeedback::default();
+        };
+        let loaded = backends.database.with_conn(|conn| {
+            Ok((
+                engram_storage::get_fusion_weight(conn, user)?,
+                engram_storage::get_source_boosts(conn, user, terms)?,
+            ))
+        });
+        match loaded {
+            Ok((weight, boosts)) => feedback::ranking_feedback(
+                weight.map_or(SEMANTIC_WEIGHT, |w| w.semantic_weight as f32),
+                boosts.into_iter().filter_map(|b| {
+                    let chunk_id = Uuid::parse_str(&b.chunk_id).ok()?;
+                    Some((b.term, chunk_id, b.score as f32))
+                }),
+            ),
+            Err(e) => {
+                tracing::warn!("Failed to load chat feedback: {}", e);
+                RankingFeedback::default()
+            }
+        }
+    }
+
     /// FIX-1: Load a session from SQLite by ID.
     fn load_session_from_sqlite(&self, session_id: Uuid) -> Option<ConversationSession> {
         let backends = self.backends.as_ref()?;
@@ -751,7 +1705,7 @@ impl ChatOrchestrator {
             .with_conn(|conn| {
                 let mut stmt = conn
                     .prepare(
-                        "SELECT started_at, last_message_at, context, message_count
+                        "SELECT started_at, last_message_at, context, message_count, title, pinned
                          FROM chat_sessions WHERE id = ?1",
                     )
                     .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))?;
@@ -761,7 +1715,16 @@ impl ChatOrchestrator {
                         let last_msg_str: String = row.get(1)?;
                         let context_json: String = row.get(2)?;
                         let message_count: u32 = row.get(3)?;
-                        Ok((started_at_str, last_msg_str, context_json, message_count))
+                        let title: Option<String> = row.get(4)?;
+                        let pinned: bool = row.get(5)?;
+                        Ok((
+                            started_at_str,
+                            last_msg_str,
+                            context_json,
+                            message_count,
+                            title,
+                            pinned,
+                        ))
                     })
                     .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))?;
 
@@ -775,6 +1738,8 @@ impl ChatOrchestrator {
                     last_message_at,
                     context,
                     message_count: result.3,
+                    title: result.4,
+                    pinned: result.5,
                 })
             })
             .ok()
@@ -842,10 +1807,19 @@ impl ChatOrchestrator {
         }
     }
 
-    /// FIX #5: Emit a domain event via the broadcast channel.
+    /// FIX #5: Emit a domain event via the broadcast channel, after
+    /// appending it to the durable event log.
     fn emit_event(&self, event: DomainEvent) {
         if let Some(ref backends) = self.backends {
-            let _ = backends.event_tx.send(event.to_json());
+            let mut payload = event.to_json();
+            match backends
+                .database
+                .with_conn(|conn| engram_storage::append_event_log_json(conn, &payload))
+            {
+                Ok(log_id) => payload[engram_storage::LOG_ID_FIELD] = log_id.into(),
+                Err(e) => tracing::warn!(error = %e, "Failed to log chat event"),
+            }
+            let _ = backends.event_tx.send(payload);
         }
     }
 
@@ -861,7 +1835,7 @@ impl ChatOrchestrator {
 
         if let Some(sid) = requested {
             if let Some(session) = sessions.get(&sid) {
-                if !self.context_manager.is_expired(session) {
+                if !self.settings().context_manager.is_expired(session) {
                     return sid;
                 }
                 // Session expired; remove and create new
@@ -891,7 +1865,7 @@ impl ChatOrchestrator {
         }
 
         // Create new session
-        let session = self.context_manager.create_session();
+        let session = self.settings().context_manager.create_session();
         let sid = session.id;
 
         // Emit ChatSessionStarted
@@ -930,6 +1904,73 @@ fn no_context_response() -> ChatResponse {
             "Try searching for a specific topic".to_string(),
             "What did I do today?".to_string(),
         ],
+        table: None,
+        sections: vec![],
+    }
+}
+
+/// Translate the parsed time range, app, people and content type into
+/// capture search filters.
+fn search_filters(query: &crate::types::StructuredQuery) -> engram_storage::FtsFilters {
+    let to_utc = |epoch: i64| chrono::Utc.timestamp_opt(epoch, 0).single();
+    engram_storage::FtsFilters {
+        content_type: query.content_type.clone(),
+        app_name: query.app_filter.clone(),
+        start: query.time_range.as_ref().and_then(|tr| to_utc(tr.start)),
+        end: query.time_range.as_ref().and_then(|tr| to_utc(tr.end)),
+        people: query.people.clone(),
+        ..Default::default()
+    }
+}
+
+/// Full-text terms for a search: the extracted topics, or nothing when the
+/// filters alone select captures ("what did Alice say yesterday"). Falls
+/// back to the whole message when there is neither.
+fn search_terms(
+    message: &str,
+    query: &crate::types::StructuredQuery,
+    filters: &engram_storage::FtsFilters,
+) -> String {
+    if !query.topics.is_empty() {
+        query.topics.join(" ")
+    } else if filters.is_empty() {
+        message.to_string()
+    } else {
+        String::new()
+    }
+}
+
+/// Text embedded for semantic search: the extracted topics, or the whole
+/// message when there are none.
+fn search_question(message: &str, query: &crate::types::StructuredQuery) -> String {
+    if query.topics.is_empty() {
+        message.to_string()
+    } else {
+        query.topics.join(" ")
+    }
+}
+
+/// Fail with `ChatError::Cancelled` if `stream` was cancelled.
+fn check_cancelled(stream: Option<&ChatStream>) -> Result<(), ChatError> {
+    match stream {
+        Some(stream) if stream.is_cancelled() => Err(ChatError::Cancelled),
+        _ => Ok(()),
+    }
+}
+
+/// Run `fut`, giving up with `ChatError::Cancelled` if `stream` is
+/// cancelled first.
+async fn until_cancelled<T>(
+    stream: Option<&ChatStream>,
+    fut: impl Future<Output = T>,
+) -> Result<T, ChatError> {
+    match stream {
+        Some(stream) => tokio::select! {
+            biased;
+            _ = stream.cancelled() => Err(ChatError::Cancelled),
+            out = fut => Ok(out),
+        },
+        None => Ok(fut.await),
     }
 }
 
@@ -942,6 +1983,18 @@ fn parse_sqlite_datetime(s: &str) -> i64 {
         .unwrap_or(0)
 }
 
+/// Summarize a session for listings.
+fn session_summary(session: &ConversationSession) -> ChatSessionSummary {
+    ChatSessionSummary {
+        id: session.id,
+        started_at: format_epoch(session.started_at),
+        last_message_at: format_epoch(session.last_message_at),
+        message_count: session.message_count,
+        title: session.title.clone(),
+        pinned: session.pinned,
+    }
+}
+
 /// Format epoch seconds as ISO 8601 string.
 fn format_epoch(epoch: i64) -> String {
     chrono::Local
@@ -972,6 +2025,21 @@ mod tests {
 
     /// Create an orchestrator with real SQLite backends for integration tests.
     fn orchestrator_with_backends() -> ChatOrchestrator {
+        orchestrator_with_db().0
+    }
+
+    /// Like [`orchestrator_with_backends`], also returning the database.
+    fn orchestrator_with_db() -> (ChatOrchestrator, Arc<engram_storage::Database>) {
+        let (orch, db, _index) = orchestrator_with_index();
+        (orch, db)
+    }
+
+    /// Like [`orchestrator_with_db`], also returning the vector index.
+    fn orchestrator_with_index() -> (
+        ChatOrchestrator,
+        Arc<engram_storage::Database>,
+        Arc<engram_vector::VectorIndex>,
+    ) {
         let db = Arc::new(engram_storage::Database::in_memory().unwrap());
         let fts_search = Arc::new(engram_storage::FtsSearch::new(Arc::clone(&db)));
         let query_service = Arc::new(engram_storage::QueryService::new(Arc::clone(&db)));
@@ -979,17 +2047,26 @@ mod tests {
         let action_config = engram_action::ActionConfig::default();
         let intent_detector = engram_action::intent::IntentDetector::new(action_config);
         let (event_tx, _rx) = tokio::sync::broadcast::channel(64);
+        let index = Arc::new(engram_vector::VectorIndex::new());
 
         let backends = ChatBackends {
-            database: db,
+            database: Arc::clone(&db),
             fts_search,
+            search_engine: Arc::new(engram_vector::SearchEngine::new(
+                Arc::clone(&index),
+                engram_vector::MockEmbedding::new(),
+            )),
             query_service,
             task_store,
             intent_detector,
             event_tx,
         };
 
-        ChatOrchestrator::new(default_config()).with_backends(backends)
+        (
+            ChatOrchestrator::new(default_config()).with_backends(backends),
+            db,
+            index,
+        )
     }
 
     // ---- Construction ----
@@ -1009,164 +2086,1032 @@ mod tests {
 
     // ---- Disabled ----
 
-    #[test]
-    fn test_disabled_returns_error() {
+    #[tokio::test]
+    async fn test_disabled_returns_error() {
         let orch = ChatOrchestrator::new(disabled_config());
-        let result = orch.handle_message("hello", None);
+        let result = orch.handle_message("hello", None).await;
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), ChatError::Disabled));
     }
 
+    #[tokio::test]
+    async fn test_update_config_applies_to_next_message() {
+        let orch = ChatOrchestrator::new(default_config());
+        assert!(orch.handle_message("hello", None).await.is_ok());
+
+        orch.update_config(disabled_config());
+        assert!(!orch.config().enabled);
+        assert!(matches!(
+            orch.handle_message("hello", None).await,
+            Err(ChatError::Disabled)
+        ));
+
+        orch.update_config(default_config());
+        assert!(orch.handle_message("hello", None).await.is_ok());
+    }
+
     // ---- Empty message ----
 
-    #[test]
-    fn test_empty_message_returns_error() {
+    #[tokio::test]
+    async fn test_empty_message_returns_error() {
         let orch = ChatOrchestrator::new(default_config());
-        let result = orch.handle_message("", None);
+        let result = orch.handle_message("", None).await;
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), ChatError::EmptyMessage));
     }
 
     // ---- Message too long ----
 
-    #[test]
-    fn test_message_too_long_returns_error() {
+    #[tokio::test]
+    async fn test_message_too_long_returns_error() {
         let orch = ChatOrchestrator::new(default_config());
         let long_msg = "a".repeat(MAX_MESSAGE_LENGTH + 1);
-        let result = orch.handle_message(&long_msg, None);
+        let result = orch.handle_message(&long_msg, None).await;
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), ChatError::MessageTooLong(_)));
     }
 
-    #[test]
-    fn test_message_at_max_length_ok() {
+    #[tokio::test]
+    async fn test_message_at_max_length_ok() {
         let orch = ChatOrchestrator::new(default_config());
         let msg = "a".repeat(MAX_MESSAGE_LENGTH);
-        let result = orch.handle_message(&msg, None);
+        let result = orch.handle_message(&msg, None).await;
         assert!(result.is_ok());
     }
 
     // ---- Basic message handling ----
 
-    #[test]
-    fn test_handle_message_creates_session() {
+    #[tokio::test]
+    async fn test_handle_message_creates_session() {
         let orch = ChatOrchestrator::new(default_config());
-        let (resp, sid) = orch.handle_message("find my notes", None).unwrap();
+        let (resp, sid) = orch.handle_message("find my notes", None).await.unwrap();
         assert!(!resp.answer.is_empty());
         assert_ne!(sid, Uuid::nil());
         assert_eq!(orch.list_sessions().len(), 1);
     }
 
-    #[test]
-    fn test_handle_message_returns_response() {
+    #[tokio::test]
+    async fn test_handle_message_returns_response() {
         let orch = ChatOrchestrator::new(default_config());
         let (resp, _) = orch
             .handle_message("what did I do yesterday", None)
+            .await
             .unwrap();
         assert!(!resp.answer.is_empty());
     }
 
     // ---- Session reuse ----
 
-    #[test]
-    fn test_same_session_id_reuses_session() {
+    #[tokio::test]
+    async fn test_same_session_id_reuses_session() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid1) = orch.handle_message("first query", None).unwrap();
-        let (_, sid2) = orch.handle_message("second query", Some(sid1)).unwrap();
+        let (_, sid1) = orch.handle_message("first query", None).await.unwrap();
+        let (_, sid2) = orch
+            .handle_message("second query", Some(sid1))
+            .await
+            .unwrap();
         assert_eq!(sid1, sid2);
         assert_eq!(orch.list_sessions().len(), 1);
     }
 
-    #[test]
-    fn test_invalid_session_id_creates_new() {
+    #[tokio::test]
+    async fn test_invalid_session_id_creates_new() {
         let orch = ChatOrchestrator::new(default_config());
         let fake_sid = Uuid::new_v4();
-        let (_, sid) = orch.handle_message("query", Some(fake_sid)).unwrap();
+        let (_, sid) = orch.handle_message("query", Some(fake_sid)).await.unwrap();
         assert_ne!(sid, fake_sid);
     }
 
     // ---- Intent routing (mock mode) ----
 
-    #[test]
-    fn test_search_intent_response() {
+    #[tokio::test]
+    async fn test_search_intent_response() {
         let orch = ChatOrchestrator::new(default_config());
-        let (resp, _) = orch.handle_message("find deployment notes", None).unwrap();
+        let (resp, _) = orch
+            .handle_message("find deployment notes", None)
+            .await
+            .unwrap();
         assert!(!resp.answer.is_empty());
         assert!(!resp.sources.is_empty());
     }
 
-    #[test]
-    fn test_action_intent_response() {
+    #[tokio::test]
+    async fn test_action_intent_response() {
         let orch = ChatOrchestrator::new(default_config());
         let (resp, _) = orch
             .handle_message("remind me to check logs", None)
+            .await
             .unwrap();
         assert!(resp.answer.contains("action engine"));
     }
 
-    #[test]
-    fn test_question_intent_response() {
+    #[tokio::test]
+    async fn test_question_intent_response() {
         let orch = ChatOrchestrator::new(default_config());
         let (resp, _) = orch
             .handle_message("how many meetings this week", None)
+            .await
             .unwrap();
         assert!(resp.answer.contains("Based on your data"));
     }
 
-    #[test]
-    fn test_clarification_no_context_response() {
-        let orch = ChatOrchestrator::new(default_config());
-        let (resp, _) = orch.handle_message("tell me more", None).unwrap();
-        assert!(!resp.answer.is_empty());
+    #[tokio::test]
+    async fn test_clarification_no_context_response() {
+        let orch = ChatOrchestrator::new(default_config());
+        let (resp, _) = orch.handle_message("tell me more", None).await.unwrap();
+        assert!(!resp.answer.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_clarification_with_context_response() {
+        let orch = ChatOrchestrator::new(default_config());
+        let (_, sid) = orch
+            .handle_message("find deployment notes", None)
+            .await
+            .unwrap();
+        let (resp, _) = orch
+            .handle_message("tell me more", Some(sid))
+            .await
+            .unwrap();
+        assert!(!resp.answer.is_empty());
+    }
+
+    // ---- Intent routing (with backends) ----
+
+    #[tokio::test]
+    async fn test_search_with_backends_empty_db() {
+        let orch = orchestrator_with_backends();
+        // No data in the in-memory DB, so FTS returns empty
+        let (resp, _) = orch
+            .handle_message("find deployment notes", None)
+            .await
+            .unwrap();
+        assert!(!resp.answer.is_empty());
+        // With empty DB, compose_extractive returns no-results response
+    }
+
+    /// Insert a capture and return its ID.
+    fn insert_capture(
+        db: &engram_storage::Database,
+        content_type: &str,
+        text: &str,
+        app: &str,
+        timestamp: i64,
+        speaker: Option<&str>,
+    ) -> Uuid {
+        let id = Uuid::new_v4();
+        db.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO captures (id, content_type, timestamp, text, app_name, speaker)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                rusqlite::params![id.to_string(), content_type, timestamp, text, app, speaker],
+            )
+            .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))?;
+            Ok(())
+        })
+        .unwrap();
+        id
+    }
+
+    #[tokio::test]
+    async fn test_search_reports_retrieval_path() {
+        use engram_vector::EmbeddingService;
+
+        let (orch, db, index) = orchestrator_with_index();
+        let now = Local::now().timestamp();
+        let both = insert_capture(&db, "screen", "cloud costs dashboard", "Chrome", now, None);
+        let related = insert_capture(
+            &db,
+            "audio",
+            "we should trim the AWS bill",
+            "Zoom",
+            now - 60,
+            None,
+        );
+        let keyword = insert_capture(
+            &db,
+            "screen",
+            "cloud costs report",
+            "Excel",
+            now - 120,
+            None,
+        );
+
+        // MockEmbedding is a text hash, so index the query's own vector to
+        // stand in for captures that are semantically close to it.
+        let query_vec = engram_vector::MockEmbedding::new()
+            .embed("cloud costs")
+            .await
+            .unwrap();
+        for id in [both, related] {
+            index
+                .insert(id, query_vec.clone(), serde_json::json!({}))
+                .unwrap();
+        }
+
+        let (resp, _) = orch.handle_message("find cloud costs", None).await.unwrap();
+        let found: Vec<(Uuid, RetrievalPath)> = resp
+            .sources
+            .iter()
+            .map(|s| (s.chunk_id, s.retrieval))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (both, RetrievalPath::Hybrid),
+                (related, RetrievalPath::Semantic),
+                (keyword, RetrievalPath::Keyword),
+            ]
+        );
+        assert!(resp.sources[0].relevance_score > resp.sources[2].relevance_score);
+    }
+
+    #[tokio::test]
+    async fn test_semantic_search_finds_filtered_match_beyond_global_neighbours() {
+        use engram_vector::EmbeddingService;
+
+        let (orch, db, index) = orchestrator_with_index();
+        let now = Local::now().timestamp();
+        let query_vec = engram_vector::MockEmbedding::new()
+            .embed("cloud costs")
+            .await
+            .unwrap();
+
+        // Exact matches in another app fill the global nearest neighbours.
+        for i in 0..150 {
+            let id = insert_capture(&db, "screen", "browser tab", "Chrome", now - i, None);
+            index
+                .insert(id, query_vec.clone(), serde_json::json!({}))
+                .unwrap();
+        }
+        // A close but weaker match is the only one in Zoom.
+        let zoom = insert_capture(&db, "audio", "trim the AWS bill", "Zoom", now - 300, None);
+        let mut near_vec = query_vec.clone();
+        near_vec[0] += 0.5;
+        index.insert(zoom, near_vec, serde_json::json!({})).unwrap();
+
+        let (resp, _) = orch
+            .handle_message("find cloud costs in Zoom", None)
+            .await
+            .unwrap();
+        let found: Vec<(Uuid, RetrievalPath)> = resp
+            .sources
+            .iter()
+            .map(|s| (s.chunk_id, s.retrieval))
+            .collect();
+        assert_eq!(found, vec![(zoom, RetrievalPath::Semantic)]);
+    }
+
+    #[tokio::test]
+    async fn test_search_applies_each_parsed_filter() {
+        let (orch, db) = orchestrator_with_db();
+        let now = Local::now();
+        let today_start = now
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .earliest()
+            .unwrap()
+            .timestamp();
+        let yesterday_noon = today_start - 12 * 3600;
+        let earlier_today = today_start + (now.timestamp() - today_start) / 2;
+        let days_ago = |days: i64| now.timestamp() - days * 86_400;
+
+        let alice = insert_capture(
+            &db,
+            "audio",
+            "budget review, we cut the cloud spend",
+            "Zoom",
+            yesterday_noon,
+            Some("Alice"),
+        );
+        let sheet = insert_capture(
+            &db,
+            "screen",
+            "budget spreadsheet Q3",
+            "Excel",
+            earlier_today,
+            None,
+        );
+        let bob = insert_capture(
+            &db,
+            "audio",
+            "budget notes for hiring",
+            "Zoom",
+            days_ago(3),
+            Some("Bob"),
+        );
+        let carol = insert_capture(
+            &db,
+            "screen",
+            "Carol approved the budget",
+            "Outlook",
+            days_ago(2),
+            None,
+        );
+        let old = insert_capture(
+            &db,
+            "screen",
+            "Bob posted the budget thread",
+            "Slack",
+            days_ago(20),
+            None,
+        );
+        db.with_conn(|conn| {
+            conn.execute_batch(
+                "INSERT INTO entities (id, entity_type, value) VALUES
+                     ('00000000-0000-0000-0000-00000000000a', 'person', 'Alice'),
+                     ('00000000-0000-0000-0000-00000000000c', 'person', 'Carol');",
+            )
+            .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))
+        })
+        .unwrap();
+
+        let cases: Vec<(&str, Vec<Uuid>)> = vec![
+            // Default search window (7 days) drops the 20-day-old capture.
+            ("find budget", vec![alice, sheet, bob, carol]),
+            // Time ranges.
+            ("find budget yesterday", vec![alice]),
+            ("find budget today", vec![sheet]),
+            (
+                "find budget last month",
+                vec![alice, sheet, bob, carol, old],
+            ),
+            // Apps.
+            ("find budget in Zoom", vec![alice, bob]),
+            ("find budget in Excel", vec![sheet]),
+            // People: "<name> said", "from <name>", known entities; matched
+            // by speaker or by mention in the text.
+            ("find budget Alice said", vec![alice]),
+            ("find budget from Bob last month", vec![bob, old]),
+            ("find budget carol", vec![carol]),
+            // Filters alone, with no topic left to match.
+            ("what did Alice say in Zoom yesterday", vec![alice]),
+            ("what did Alice say in Zoom today", vec![]),
+        ];
+        for (message, expected) in cases {
+            let (resp, _) = orch.handle_message(message, None).await.unwrap();
+            let mut found: Vec<Uuid> = resp.sources.iter().map(|s| s.chunk_id).collect();
+            found.sort();
+            let mut expected = expected;
+            expected.sort();
+            assert_eq!(found, expected, "{}", message);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_question_counts_filtered_captures() {
+        let (orch, db) = orchestrator_with_db();
+        let now = Local::now().timestamp();
+        insert_capture(&db, "screen", "deploy log", "Terminal", now - 60, None);
+        insert_capture(&db, "screen", "deploy checklist", "Chrome", now - 120, None);
+
+        let (resp, _) = orch
+            .handle_message("how many deploy in Terminal last month", None)
+            .await
+            .unwrap();
+        assert!(
+            resp.answer.contains("1 matching captures"),
+            "{}",
+            resp.answer
+        );
+    }
+
+    #[tokio::test]
+    async fn test_action_with_backends() {
+        let orch = orchestrator_with_backends();
+        let (resp, _) = orch
+            .handle_message("remind me to check logs tomorrow", None)
+            .await
+            .unwrap();
+        assert!(!resp.answer.is_empty());
+        // With real IntentDetector, it should detect the reminder intent
+        // and create a task, or report it couldn't detect a specific intent
+    }
+
+    #[tokio::test]
+    async fn test_question_with_backends_empty_db() {
+        let orch = orchestrator_with_backends();
+        let (resp, _) = orch
+            .handle_message("how many captures this week", None)
+            .await
+            .unwrap();
+        assert!(!resp.answer.is_empty());
+        // Empty DB returns 0 captures
+        assert!(resp.answer.contains("0") || resp.answer.contains("Based on your data"));
+    }
+
+    // ---- Structured stores (with backends) ----
+
+    fn test_backends(orch: &ChatOrchestrator) -> &ChatBackends {
+        orch.backends.as_ref().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_task_query_lists_tasks_from_task_store() {
+        let (orch, db) = orchestrator_with_db();
+        let now = Local::now().timestamp();
+        let source = insert_capture(&db, "screen", "email Bob the invoice", "Mail", now, None);
+        let tasks = &test_backends(&orch).task_store;
+        let pending = tasks
+            .create(
+                "Email Bob the invoice".to_string(),
+                engram_action::ActionType::Reminder,
+                "{}".to_string(),
+                None,
+                Some(source),
+                Some(Timestamp(now + 3600)),
+            )
+            .unwrap();
+        tasks
+            .update_status(pending.id, engram_action::TaskStatus::Pending)
+            .unwrap();
+        tasks
+            .create(
+                "Unrelated detected task".to_string(),
+                engram_action::ActionType::Reminder,
+                "{}".to_string(),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        let (resp, _) = orch
+            .handle_message("What tasks are pending?", None)
+            .await
+            .unwrap();
+        assert!(resp.answer.starts_with("You have 1 pending task:"));
+        assert!(resp
+            .answer
+            .contains("- Email Bob the invoice (pending, due "));
+        assert!(resp.answer.ends_with(&format!("[{}]", source)));
+        assert!(!resp.answer.contains("Unrelated"));
+        assert_eq!(resp.sources.len(), 1);
+        assert_eq!(resp.sources[0].chunk_id, source);
+        assert_eq!(resp.sources[0].source_app, "Mail");
+        assert!((resp.confidence - 0.9).abs() < f32::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_entity_query_groups_values_and_filters_by_topic() {
+        let (orch, db) = orchestrator_with_db();
+        let now = Local::now().timestamp();
+        let kafka = insert_capture(&db, "screen", "reading the kafka docs", "Chrome", now, None);
+        let shop = insert_capture(&db, "screen", "shopping for shoes", "Chrome", now, None);
+        let qs = &test_backends(&orch).query_service;
+        for (value, chunk) in [
+            ("https://kafka.apache.org", kafka),
+            ("https://kafka.apache.org", kafka),
+            ("https://docs.confluent.io", kafka),
+            ("https://shoes.example.com", shop),
+        ] {
+            qs.store_entity(
+                &Uuid::new_v4().to_string(),
+                "url",
+                value,
+                Some(&chunk.to_string()),
+                None,
+                0.9,
+            )
+            .unwrap();
+        }
+        qs.store_entity(
+            &Uuid::new_v4().to_string(),
+            "project",
+            "Kafka",
+            None,
+            None,
+            0.9,
+        )
+        .unwrap();
+
+        let (resp, _) = orch
+            .handle_message("Which URLs did I visit about kafka?", None)
+            .await
+            .unwrap();
+        assert!(resp.answer.starts_with("Found 2 URLs about kafka:"));
+        assert!(resp.answer.contains(&format!(
+            "- https://kafka.apache.org, seen 2 times [{}]",
+            kafka
+        )));
+        // Matched through its source capture's text.
+        assert!(resp
+            .answer
+            .contains(&format!("- https://docs.confluent.io [{}]", kafka)));
+        assert!(!resp.answer.contains("shoes"));
+        assert_eq!(
+            resp.sources.iter().map(|s| s.chunk_id).collect::<Vec<_>>(),
+            vec![kafka]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_summary_query_cites_source_captures() {
+        let (orch, db) = orchestrator_with_db();
+        let now = Local::now().timestamp();
+        let chunk = insert_capture(&db, "screen", "kafka rollout plan", "Notion", now, None);
+        let qs = &test_backends(&orch).query_service;
+        qs.store_summary(
+            &Uuid::new_v4().to_string(),
+            "Kafka rollout planning",
+            r#"["Staged rollout by region", "Rollback plan agreed"]"#,
+            &format!(r#"["{}"]"#, chunk),
+            Some("Notion"),
+            Some(&((now - 600) * 1000).to_string()),
+            Some(&(now * 1000).to_string()),
+        )
+        .unwrap();
+        qs.store_summary(
+            &Uuid::new_v4().to_string(),
+            "Last month's offsite",
+            "[]",
+            "[]",
+            Some("Slack"),
+            None,
+            Some(&((now - 40 * 86_400) * 1000).to_string()),
+        )
+        .unwrap();
+
+        let (resp, _) = orch.handle_message("summarize today", None).await.unwrap();
+        assert!(resp.answer.starts_with("1 summary on "));
+        assert!(resp.answer.contains("- Kafka rollout planning (Notion, "));
+        assert!(resp.answer.contains(&format!("[{}]", chunk)));
+        assert!(resp.answer.contains("  - Staged rollout by region"));
+        assert!(!resp.answer.contains("offsite"));
+        assert_eq!(resp.sources.len(), 1);
+        assert_eq!(resp.sources[0].chunk_id, chunk);
+    }
+
+    #[tokio::test]
+    async fn test_digest_query_reads_daily_digest() {
+        let (orch, db) = orchestrator_with_db();
+        let (resp, _) = orch.handle_message("how was my day", None).await.unwrap();
+        assert!(resp.answer.starts_with("There's no daily digest for "));
+        assert!(resp.sources.is_empty());
+
+        let now = Local::now().timestamp();
+        let chunk = insert_capture(&db, "screen", "quarterly numbers", "Excel", now, None);
+        let qs = &test_backends(&orch).query_service;
+        let summary_id = Uuid::new_v4();
+        qs.store_summary(
+            &summary_id.to_string(),
+            "Quarterly numbers review",
+            r#"["Revenue up"]"#,
+            &format!(r#"["{}"]"#, chunk),
+            Some("Excel"),
+            None,
+            None,
+        )
+        .unwrap();
+        let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        let content = serde_json::json!({
+            "date": date,
+            "summary_count": 1,
+            "entity_count": 2,
+            "chunk_count": 5,
+            "app_breakdown": {"Excel": 3, "Slack": 2},
+            "top_entities": [{"type": "project", "value": "Atlas", "count": 2}],
+            "summaries": [{
+                "id": summary_id.to_string(),
+                "title": "Quarterly numbers review",
+                "source_app": "Excel",
+                "bullet_count": 1
+            }]
+        });
+        qs.store_digest(
+            &Uuid::new_v4().to_string(),
+            &date,
+            &content.to_string(),
+            1,
+            2,
+            5,
+        )
+        .unwrap();
+
+        let (resp, _) = orch
+            .handle_message("show my daily digest", None)
+            .await
+            .unwrap();
+        assert!(resp.answer.starts_with(&format!(
+            "Daily digest for {}: 1 summary and 2 entities from 5 captures.",
+            date
+        )));
+        assert!(resp.answer.contains("Apps: Excel (3), Slack (2)"));
+        assert!(resp.answer.contains("Top entities: Atlas (project)"));
+        assert!(resp
+            .answer
+            .contains(&format!("- Quarterly numbers review [{}]", chunk)));
+        assert_eq!(resp.sources.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_app_queries_read_usage() {
+        let (orch, db) = orchestrator_with_db();
+        let now = Local::now().timestamp();
+        insert_capture(&db, "screen", "standup thread", "Slack", now - 60, None);
+        let latest_slack = insert_capture(&db, "screen", "release thread", "Slack", now, None);
+        let chrome = insert_capture(&db, "screen", "docs", "Chrome", now - 30, None);
+
+        let (resp, _) = orch
+            .handle_message("which apps did I use most", None)
+            .await
+            .unwrap();
+        assert!(resp
+            .answer
+            .starts_with("You've used 2 apps, most captured first:"));
+        assert!(resp.answer.contains("- Slack: 2 captures, last seen "));
+        assert!(resp.answer.contains(&format!("[{}]", latest_slack)));
+        assert!(resp.answer.contains(&format!("[{}]", chrome)));
+        assert_eq!(resp.sources[0].chunk_id, latest_slack);
+
+        let (resp, _) = orch
+            .handle_message("my slack activity today", None)
+            .await
+            .unwrap();
+        assert!(resp.answer.starts_with("You were in Slack during "));
+        assert!(resp.answer.contains("(2 captures)"));
+        assert!(resp.answer.contains(&format!("[{}]", latest_slack)));
+        assert!(!resp.answer.contains(&chrome.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_structured_query_without_backends_uses_search() {
+        let orch = ChatOrchestrator::new(default_config());
+        let (resp, _) = orch
+            .handle_message("What tasks are pending?", None)
+            .await
+            .unwrap();
+        assert!(!resp.answer.starts_with("You have"));
+    }
+
+    // ---- Analytics (with backends) ----
+
+    fn insert_window_capture(db: &engram_storage::Database, app: &str, window: &str, at: i64) {
+        db.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO captures (id, content_type, timestamp, text, app_name, window_title)
+                 VALUES (?1, 'screen', ?2, '', ?3, ?4)",
+                rusqlite::params![Uuid::new_v4().to_string(), at, app, window],
+            )
+            .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))?;
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_time_spent_per_window_and_app() {
+        let (orch, db) = orchestrator_with_db();
+        let t0 = Local::now().timestamp() - 3600;
+        insert_window_capture(&db, "VS Code", "main.rs", t0);
+        insert_window_capture(&db, "VS Code", "main.rs", t0 + 60);
+        insert_window_capture(&db, "VS Code", "lib.rs", t0 + 120);
+        insert_window_capture(&db, "Chrome", "docs", t0 + 180);
+        insert_window_capture(&db, "Chrome", "docs", t0 + 240);
+        // A lone capture after a long gap adds no focused time.
+        insert_window_capture(&db, "VS Code", "main.rs", t0 + 1800);
+
+        let (resp, _) = orch
+            .handle_message("how long was I in VS Code", None)
+            .await
+            .unwrap();
+        assert!(
+            resp.answer
+                .starts_with("You spent 3m focused in VS Code from "),
+            "{}",
+            resp.answer
+        );
+        assert!(resp.answer.contains("across 2 sessions"));
+        assert!(resp
+            .answer
+            .contains("Top windows: main.rs 2m (67%), lib.rs 1m (33%)."));
+        let table = resp.table.unwrap();
+        assert_eq!(
+            table.columns,
+            vec!["Window", "Minutes", "Captures", "Sessions"]
+        );
+        assert_eq!(table.value_column, 1);
+        assert_eq!(
+            table.rows,
+            vec![
+                vec![serde_json::json!("main.rs"), 2.0.into(), 3.into(), 2.into()],
+                vec![serde_json::json!("lib.rs"), 1.0.into(), 1.into(), 1.into()],
+            ]
+        );
+
+        let (resp, _) = orch
+            .handle_message("how much time did I spend in each app", None)
+            .await
+            .unwrap();
+        assert!(resp.answer.starts_with("You spent 4m focused overall"));
+        assert!(resp
+            .answer
+            .contains("Top apps: VS Code 3m (75%), Chrome 1m (25%)."));
+        assert_eq!(resp.table.unwrap().rows.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_trend_compares_days() {
+        let (orch, db) = orchestrator_with_db();
+        let today = Local::now().date_naive();
+        let noon = |day: chrono::NaiveDate| {
+            day.and_hms_opt(12, 0, 0)
+                .unwrap()
+                .and_local_timezone(Local)
+                .earliest()
+                .unwrap()
+                .timestamp()
+        };
+        let yesterday = noon(today - chrono::Duration::days(1));
+        for offset in [0, 60, 120] {
+            insert_window_capture(&db, "Slack", "general", yesterday + offset);
+        }
+        let now = Local::now().timestamp();
+        insert_window_capture(&db, "Slack", "general", now - 61);
+        insert_window_capture(&db, "Slack", "general", now - 1);
+
+        let (resp, _) = orch
+            .handle_message("how is my Slack time trending", None)
+            .await
+            .unwrap();
+        assert!(
+            resp.answer.starts_with(&format!(
+                "Focused time in Slack: 1m on {} vs 2m the day before (-50%).",
+                today
+            )),
+            "{}",
+            resp.answer
+        );
+        let table = resp.table.unwrap();
+        assert_eq!(table.rows.len(), 7);
+        assert_eq!(table.rows[6][0], serde_json::json!(today.to_string()));
+        assert_eq!(table.rows[6][1], serde_json::json!(1.0));
+        assert_eq!(table.rows[0][1], serde_json::json!(0.0));
+    }
+
+    #[tokio::test]
+    async fn test_first_last_seen_and_counts() {
+        let (orch, db) = orchestrator_with_db();
+        // Yesterday noon, so every capture falls on one day whatever the time.
+        let noon = (Local::now().date_naive() - chrono::Duration::days(1))
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .earliest()
+            .unwrap()
+            .timestamp();
+        let long_ago = noon - 90 * 86_400;
+        db.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO app_activity (app_name, first_seen, last_seen, capture_count)
+                 VALUES ('Zoom', ?1, ?1, 1)",
+                rusqlite::params![long_ago],
+            )
+            .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))?;
+            Ok(())
+        })
+        .unwrap();
+        insert_window_capture(&db, "Zoom", "standup", noon - 7200);
+        insert_window_capture(&db, "Zoom", "standup", noon - 7140);
+        insert_window_capture(&db, "Zoom", "retro", noon - 60);
+
+        let (resp, _) = orch
+            .handle_message("when did I last use Zoom?", None)
+            .await
+            .unwrap();
+        assert_eq!(
+            resp.answer,
+            format!(
+                "You first used Zoom at {} and last at {} (3 captures).",
+                crate::response::format_timestamp(long_ago),
+                crate::response::format_timestamp(noon - 60)
+            )
+        );
+        assert_eq!(resp.table.unwrap().columns[1], "First seen");
+
+        let (resp, _) = orch
+            .handle_message("how often did I open Zoom", None)
+            .await
+            .unwrap();
+        assert!(
+            resp.answer.starts_with("You have 3 captures in Zoom from "),
+            "{}",
+            resp.answer
+        );
+        assert!(resp.answer.ends_with("on 1 day and across 2 sessions."));
+        let table = resp.table.unwrap();
+        assert_eq!(table.columns, vec!["Day", "Captures"]);
+        assert_eq!(table.rows[0][1], serde_json::json!(3));
+    }
+
+    #[tokio::test]
+    async fn test_stream_sends_analytics_table() {
+        let (orch, db) = orchestrator_with_db();
+        let now = Local::now().timestamp();
+        insert_window_capture(&db, "Excel", "budget.xlsx", now - 120);
+        insert_window_capture(&db, "Excel", "budget.xlsx", now - 60);
+
+        let stream = orch.streams().start(feedback::DEFAULT_USER);
+        orch.stream_message("how long was I in Excel", None, &stream)
+            .await;
+        let (events, _) = stream.subscribe(None);
+        let names: Vec<&str> = events.iter().map(|e| e.event.name()).collect();
+        let table = names.iter().position(|n| *n == "table").unwrap();
+        let first_delta = names.iter().position(|n| *n == "delta").unwrap();
+        assert!(table < first_delta);
+        assert_eq!(names.last(), Some(&"done"));
+    }
+
+    // ---- Comparisons and timelines (with backends) ----
+
+    #[tokio::test]
+    async fn test_comparison_of_last_two_occasions() {
+        let (orch, db) = orchestrator_with_db();
+        let now = Local::now().timestamp();
+        let day = 86_400;
+        let capture = |text: &str, at: i64| insert_capture(&db, "screen", text, "Zoom", at, None);
+        capture("design review: onboarding flow", now - 10 * day);
+        let oldest = capture("pricing stays at $15 per seat", now - 10 * day + 600);
+        capture("design review: billing page", now - 3 * day);
+        let first = capture("pricing decided: $20 per seat", now - 3 * day + 900);
+        capture("Design reviews agenda", now - day);
+        let second = capture(
+            "pricing moved to $25 per seat, annual discount",
+            now - day + 1200,
+        );
+        let later = capture("pricing email to finance", now - day + 5 * 3600);
+
+        let (response, _) = orch
+            .handle_message(
+                "compare what we decided about pricing in the last two design reviews",
+                None,
+            )
+            .await
+            .unwrap();
+
+        let headings: Vec<&str> = response
+            .sections
+            .iter()
+            .map(|s| s.heading.as_str())
+            .collect();
+        assert_eq!(headings.len(), 2, "{}", response.answer);
+        assert!(headings.iter().all(|h| h.starts_with("Design review ")));
+        let ids = |i: usize| -> Vec<Uuid> {
+            response.sections[i]
+                .items
+                .iter()
+                .map(|item| item.chunk_id)
+                .collect()
+        };
+        assert_eq!(ids(0), vec![first]);
+        assert_eq!(ids(1), vec![second]);
+        assert!(response.sections[1]
+            .key_terms
+            .contains(&"annual".to_string()));
+        assert!(response
+            .answer
+            .starts_with("Comparing pricing: Design review"));
+        for id in [oldest, later] {
+            assert!(!response.answer.contains(&id.to_string()));
+        }
+        assert_eq!(response.sources.len(), 2);
     }
 
-    #[test]
-    fn test_clarification_with_context_response() {
-        let orch = ChatOrchestrator::new(default_config());
-        let (_, sid) = orch.handle_message("find deployment notes", None).unwrap();
-        let (resp, _) = orch.handle_message("tell me more", Some(sid)).unwrap();
-        assert!(!resp.answer.is_empty());
+    /// Orchestrator whose voice queries read a one-second WAV file and are
+    /// transcribed by the mock service.
+    fn orchestrator_with_voice(dir: &tempfile::TempDir) -> ChatOrchestrator {
+        let path = dir.path().join("question.wav");
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for i in 0..16000 {
+            writer.write_sample(((i % 64) as i16 - 32) * 100).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let voice = VoiceInterface::new("Ctrl+Shift+E".to_string(), 30)
+            .with_source(crate::voice::WavFileSource::new(path))
+            .with_transcription(Arc::new(engram_whisper::MockTranscriptionService::new()));
+        orchestrator_with_backends().with_voice(voice)
     }
 
-    // ---- Intent routing (with backends) ----
+    #[tokio::test]
+    async fn test_voice_query_is_transcribed_and_answered() {
+        let dir = tempfile::tempdir().unwrap();
+        let orch = orchestrator_with_voice(&dir);
+        let mut events = orch.backends.as_ref().unwrap().event_tx.subscribe();
+        assert!(orch.voice_status().await.available);
 
-    #[test]
-    fn test_search_with_backends_empty_db() {
-        let orch = orchestrator_with_backends();
-        // No data in the in-memory DB, so FTS returns empty
-        let (resp, _) = orch.handle_message("find deployment notes", None).unwrap();
-        assert!(!resp.answer.is_empty());
-        // With empty DB, compose_extractive returns no-results response
+        let sid = orch.start_voice_query(None).await.unwrap();
+        assert!(orch.voice_status().await.listening);
+        assert!(orch.start_voice_query(None).await.is_err());
+
+        let answer = orch.finish_voice_query().await.unwrap();
+        assert_eq!(answer.transcript, "[mock transcription]");
+        assert_eq!(answer.session_id, sid);
+        assert!(!answer.response.answer.is_empty());
+
+        let history = orch.get_history(sid).unwrap();
+        assert_eq!(history[0].content, "[mock transcription]");
+
+        let mut names = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            names.push(event["event"].as_str().unwrap().to_string());
+        }
+        let started = names.iter().position(|n| n == "voice_query_started");
+        let completed = names.iter().position(|n| n == "voice_query_completed");
+        assert!(started.is_some() && started < completed);
+
+        let status = orch.voice_status().await;
+        assert!(!status.listening);
+        assert_eq!(status.last.unwrap().transcript, "[mock transcription]");
     }
 
-    #[test]
-    fn test_action_with_backends() {
+    #[tokio::test]
+    async fn test_voice_query_without_voice_interface() {
         let orch = orchestrator_with_backends();
-        let (resp, _) = orch
-            .handle_message("remind me to check logs tomorrow", None)
-            .unwrap();
-        assert!(!resp.answer.is_empty());
-        // With real IntentDetector, it should detect the reminder intent
-        // and create a task, or report it couldn't detect a specific intent
+        assert!(!orch.voice_status().await.available);
+        assert!(matches!(
+            orch.start_voice_query(None).await,
+            Err(ChatError::VoiceError(_))
+        ));
+        assert!(orch.finish_voice_query().await.is_err());
     }
 
-    #[test]
-    fn test_question_with_backends_empty_db() {
-        let orch = orchestrator_with_backends();
-        let (resp, _) = orch
-            .handle_message("how many captures this week", None)
-            .unwrap();
-        assert!(!resp.answer.is_empty());
-        // Empty DB returns 0 captures
-        assert!(resp.answer.contains("0") || resp.answer.contains("Based on your data"));
+    #[tokio::test]
+    async fn test_stream_sends_timeline_sections() {
+        let (orch, db) = orchestrator_with_db();
+        let yesterday = Local::now().date_naive() - chrono::Duration::days(1);
+        let at = |h: u32, m: u32| {
+            Local
+                .from_local_datetime(&yesterday.and_hms_opt(h, m, 0).unwrap())
+                .single()
+                .unwrap()
+                .timestamp()
+        };
+        let capture =
+            |text: &str, app: &str, ts: i64| insert_capture(&db, "screen", text, app, ts, None);
+        let resolved = capture("outage resolved, postmortem scheduled", "Slack", at(14, 30));
+        let started = capture("outage: API returning 503s", "Slack", at(9, 10));
+        capture("lunch order", "Chrome", at(12, 0));
+
+        let stream = orch.streams().start(feedback::DEFAULT_USER);
+        let message = format!(
+            "give me a timeline of the outage on {}",
+            yesterday.format("%Y-%m-%d")
+        );
+        orch.stream_message(&message, None, &stream).await;
+        let (events, _) = stream.subscribe(None);
+
+        let sections: Vec<&crate::types::ResponseSection> = events
+            .iter()
+            .filter_map(|e| match &e.event {
+                ChatStreamEvent::Section { section } => Some(section),
+                _ => None,
+            })
+            .collect();
+        let headings: Vec<String> = sections.iter().map(|s| s.heading.clone()).collect();
+        let date = yesterday.format("%Y-%m-%d");
+        assert_eq!(
+            headings,
+            vec![format!("{} 09:00", date), format!("{} 12:00", date)]
+        );
+
+        let names: Vec<&str> = events.iter().map(|e| e.event.name()).collect();
+        let last_section = names.iter().rposition(|n| *n == "section").unwrap();
+        let first_delta = names.iter().position(|n| *n == "delta").unwrap();
+        assert!(last_section < first_delta);
+        let steps = events
+            .iter()
+            .filter(|e| matches!(&e.event, ChatStreamEvent::Progress { detail: Some(d), .. } if d.starts_with("step ")))
+            .count();
+        assert_eq!(steps, 8);
+
+        let answer: String = events
+            .iter()
+            .filter_map(|e| match &e.event {
+                ChatStreamEvent::Delta { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        let started_at = answer.find(&started.to_string()).unwrap();
+        let resolved_at = answer.find(&resolved.to_string()).unwrap();
+        assert!(started_at < resolved_at);
+        assert!(!answer.contains("lunch"));
     }
 
     // ---- SQLite persistence (with backends) ----
 
-    #[test]
-    fn test_messages_persisted_to_sqlite() {
+    #[tokio::test]
+    async fn test_messages_persisted_to_sqlite() {
         let orch = orchestrator_with_backends();
-        let (_, sid) = orch.handle_message("hello there", None).unwrap();
+        let (_, sid) = orch.handle_message("hello there", None).await.unwrap();
 
         // Verify session was written to SQLite
         let sid_str = sid.to_string();
@@ -1199,10 +3144,10 @@ mod tests {
         assert_eq!(msg_count, 2); // user + assistant
     }
 
-    #[test]
-    fn test_delete_session_removes_from_sqlite() {
+    #[tokio::test]
+    async fn test_delete_session_removes_from_sqlite() {
         let orch = orchestrator_with_backends();
-        let (_, sid) = orch.handle_message("test", None).unwrap();
+        let (_, sid) = orch.handle_message("test", None).await.unwrap();
         orch.delete_session(sid).unwrap();
 
         let sid_str = sid.to_string();
@@ -1223,8 +3168,8 @@ mod tests {
 
     // ---- Domain events (with backends) ----
 
-    #[test]
-    fn test_domain_events_emitted() {
+    #[tokio::test]
+    async fn test_domain_events_emitted() {
         let db = Arc::new(engram_storage::Database::in_memory().unwrap());
         let fts_search = Arc::new(engram_storage::FtsSearch::new(Arc::clone(&db)));
         let query_service = Arc::new(engram_storage::QueryService::new(Arc::clone(&db)));
@@ -1236,6 +3181,10 @@ mod tests {
         let backends = ChatBackends {
             database: db,
             fts_search,
+            search_engine: Arc::new(engram_vector::SearchEngine::new(
+                Arc::new(engram_vector::VectorIndex::new()),
+                engram_vector::MockEmbedding::new(),
+            )),
             query_service,
             task_store,
             intent_detector,
@@ -1243,7 +3192,7 @@ mod tests {
         };
 
         let orch = ChatOrchestrator::new(default_config()).with_backends(backends);
-        let _ = orch.handle_message("find notes", None).unwrap();
+        let _ = orch.handle_message("find notes", None).await.unwrap();
 
         // Should have received: ChatSessionStarted, ChatQueryReceived, ChatResponseGenerated
         let mut event_names = Vec::new();
@@ -1269,12 +3218,257 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_llm_backend_answers_with_citations() {
+        let (orch, db) = orchestrator_with_db();
+        let orch = orch.with_response_backend(Arc::new(crate::backend::LlmBackend::new(
+            crate::backend::StubGenerator,
+        )));
+        let mut event_rx = orch.backends.as_ref().unwrap().event_tx.subscribe();
+        let id = insert_capture(
+            &db,
+            "screen",
+            "deploy checklist for v2",
+            "Notes",
+            Local::now().timestamp(),
+            None,
+        );
+
+        let (resp, _) = orch
+            .handle_message("find deploy checklist", None)
+            .await
+            .unwrap();
+        assert!(
+            resp.answer.contains(&format!("[{}]", id)),
+            "{}",
+            resp.answer
+        );
+        assert_eq!(resp.sources.len(), 1);
+        assert_eq!(resp.sources[0].chunk_id, id);
+
+        let mut modes = Vec::new();
+        while let Ok(val) = event_rx.try_recv() {
+            if val["event"] == "chat_response_generated" {
+                modes.push(val["data"].to_string());
+            }
+        }
+        assert_eq!(modes.len(), 1);
+        assert!(modes[0].contains(r#""mode":"llm""#), "{}", modes[0]);
+    }
+
+    // ---- Streaming ----
+
+    /// Backend that never finishes, so tests can cancel mid-answer.
+    struct PendingBackend;
+
+    #[async_trait::async_trait]
+    impl ResponseBackend for PendingBackend {
+        async fn generate(&self, _request: &GenerationRequest<'_>) -> GeneratedResponse {
+            std::future::pending().await
+        }
+    }
+
+    fn chat_message_count(db: &engram_storage::Database) -> i64 {
+        db.with_conn(|conn| {
+            conn.query_row("SELECT COUNT(*) FROM chat_messages", [], |row| row.get(0))
+                .map_err(|e| engram_core::error::EngramError::Storage(e.to_string()))
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_stream_message_emits_sources_deltas_and_done() {
+        let (orch, db) = orchestrator_with_db();
+        let capture_id = insert_capture(
+            &db,
+            "screen",
+            "quarterly budget review with finance",
+            "Notes",
+            Local::now().timestamp(),
+            None,
+        );
+
+        let stream = orch.streams().start(feedback::DEFAULT_USER);
+        orch.stream_message("find budget review", None, &stream)
+            .await;
+
+        let (events, live) = stream.subscribe(None);
+        assert!(live.is_none());
+        let names: Vec<&str> = events.iter().map(|e| e.event.name()).collect();
+        assert_eq!(&names[..4], ["started", "progress", "source", "progress"]);
+        assert_eq!(names.last(), Some(&"done"));
+        assert!(events.iter().enumerate().all(|(i, e)| e.id == i as u64 + 1));
+
+        match &events[2].event {
+            ChatStreamEvent::Source { source } => assert_eq!(source.chunk_id, capture_id),
+            other => panic!("expected source, got {:?}", other),
+        }
+        let streamed: String = events
+            .iter()
+            .filter_map(|e| match &e.event {
+                ChatStreamEvent::Delta { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        let ChatStreamEvent::Done {
+            message_id,
+            session_id,
+            message,
+            ..
+        } = &events.last().unwrap().event
+        else {
+            unreachable!()
+        };
+        assert_eq!(*message_id, stream.message_id());
+        assert_eq!(streamed, message.content);
+
+        // The transcript is stored under the stream's message ID.
+        let history = orch.get_history(*session_id).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].id, stream.message_id());
+        assert_eq!(history[1].content, message.content);
+        assert_eq!(chat_message_count(&db), 2);
+    }
+
+    /// Model that writes an answer without citations.
+    struct UncitedGenerator;
+
+    impl crate::backend::TextGenerator for UncitedGenerator {
+        fn generate(&self, _: &str, _: u32, _: f32) -> Result<String, ChatError> {
+            Ok("It was on Friday.".to_string())
+        }
+    }
+
+    /// Stream `message` over one capture with `generator` answering, and
+    /// return the events and the stored answer.
+    async fn stream_llm_answer(
+        generator: impl crate::backend::TextGenerator + 'static,
+    ) -> (Vec<ChatStreamEvent>, String) {
+        let (orch, db) = orchestrator_with_db();
+        let orch = orch.with_response_backend(Arc::new(crate::backend::LlmBackend::new(generator)));
+        insert_capture(
+            &db,
+            "screen",
+            "deploy checklist for v2",
+            "Notes",
+            Local::now().timestamp(),
+            None,
+        );
+        let stream = orch.streams().start(feedback::DEFAULT_USER);
+        orch.stream_message("find deploy checklist", None, &stream)
+            .await;
+
+        let events: Vec<ChatStreamEvent> = stream
+            .subscribe(None)
+            .0
+            .into_iter()
+            .map(|e| e.event)
+            .collect();
+        let Some(ChatStreamEvent::Done { message, .. }) = events.last() else {
+            panic!("expected done, got {:?}", events.last());
+        };
+        let content = message.content.clone();
+        (events, content)
+    }
+
+    #[tokio::test]
+    async fn test_stream_sends_model_text_while_it_is_written() {
+        let (events, answer) = stream_llm_answer(crate::backend::StubGenerator).await;
+
+        let deltas: Vec<&str> = events
+            .iter()
+            .filter_map(|e| match e {
+                ChatStreamEvent::Delta { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        // One delta per word from the stub model, not per DELTA_WORDS.
+        assert_eq!(deltas.len(), answer.split_whitespace().count());
+        assert_eq!(deltas.concat(), answer);
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, ChatStreamEvent::Replace { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_stream_replaces_model_text_on_fallback() {
+        let (events, answer) = stream_llm_answer(UncitedGenerator).await;
+
+        let names: Vec<&str> = events.iter().map(ChatStreamEvent::name).collect();
+        let delta = names.iter().position(|n| *n == "delta").unwrap();
+        let replace = names.iter().position(|n| *n == "replace").unwrap();
+        assert!(delta < replace, "{:?}", names);
+        let ChatStreamEvent::Replace { text } = &events[replace] else {
+            unreachable!()
+        };
+        assert_eq!(*text, answer);
+        assert!(!answer.contains("Friday"), "{}", answer);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_stream_stores_nothing() {
+        let (orch, db) = orchestrator_with_db();
+        let orch = Arc::new(orch.with_response_backend(Arc::new(PendingBackend)));
+        let stream = orch.streams().start(feedback::DEFAULT_USER);
+        let (_, live) = stream.subscribe(None);
+        let mut live = live.unwrap();
+
+        let task = tokio::spawn({
+            let orch = Arc::clone(&orch);
+            let stream = Arc::clone(&stream);
+            async move {
+                orch.stream_message("find budget review", None, &stream)
+                    .await
+            }
+        });
+        // Wait until the answer is being generated, then cancel.
+        loop {
+            let event = live.recv().await.unwrap().event;
+            if matches!(
+                event,
+                ChatStreamEvent::Progress {
+                    stage: StreamStage::Generating,
+                    ..
+                }
+            ) {
+                break;
+            }
+        }
+        assert!(stream.cancel());
+        task.await.unwrap();
+
+        let (events, _) = stream.subscribe(None);
+        assert!(matches!(
+            events.last().unwrap().event,
+            ChatStreamEvent::Cancelled { message_id } if message_id == stream.message_id()
+        ));
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e.event, ChatStreamEvent::Delta { .. })));
+        assert!(!stream.cancel());
+        assert_eq!(chat_message_count(&db), 0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_message_reports_errors() {
+        let orch = ChatOrchestrator::new(disabled_config());
+        let stream = orch.streams().start(feedback::DEFAULT_USER);
+        orch.stream_message("hello", None, &stream).await;
+
+        let (events, _) = stream.subscribe(None);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0].event,
+            ChatStreamEvent::Error { message } if message == "chat is disabled"
+        ));
+    }
+
     // ---- Session management ----
 
-    #[test]
-    fn test_get_session() {
+    #[tokio::test]
+    async fn test_get_session() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid) = orch.handle_message("test", None).unwrap();
+        let (_, sid) = orch.handle_message("test", None).await.unwrap();
         let session = orch.get_session(sid);
         assert!(session.is_some());
         assert_eq!(session.unwrap().id, sid);
@@ -1286,18 +3480,18 @@ mod tests {
         assert!(orch.get_session(Uuid::new_v4()).is_none());
     }
 
-    #[test]
-    fn test_list_sessions_multiple() {
+    #[tokio::test]
+    async fn test_list_sessions_multiple() {
         let orch = ChatOrchestrator::new(default_config());
-        orch.handle_message("query 1", None).unwrap();
-        orch.handle_message("query 2", None).unwrap();
+        orch.handle_message("query 1", None).await.unwrap();
+        orch.handle_message("query 2", None).await.unwrap();
         assert_eq!(orch.list_sessions().len(), 2);
     }
 
-    #[test]
-    fn test_delete_session() {
+    #[tokio::test]
+    async fn test_delete_session() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid) = orch.handle_message("test", None).unwrap();
+        let (_, sid) = orch.handle_message("test", None).await.unwrap();
         assert!(orch.delete_session(sid).is_ok());
         assert!(orch.get_session(sid).is_none());
         assert!(orch.list_sessions().is_empty());
@@ -1313,10 +3507,10 @@ mod tests {
 
     // ---- Message history ----
 
-    #[test]
-    fn test_get_history() {
+    #[tokio::test]
+    async fn test_get_history() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid) = orch.handle_message("hello", None).unwrap();
+        let (_, sid) = orch.handle_message("hello", None).await.unwrap();
         let history = orch.get_history(sid).unwrap();
         assert_eq!(history.len(), 2); // user + assistant
         assert_eq!(history[0].role, "user");
@@ -1324,11 +3518,11 @@ mod tests {
         assert_eq!(history[1].role, "assistant");
     }
 
-    #[test]
-    fn test_get_history_multiple_messages() {
+    #[tokio::test]
+    async fn test_get_history_multiple_messages() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid) = orch.handle_message("first", None).unwrap();
-        orch.handle_message("second", Some(sid)).unwrap();
+        let (_, sid) = orch.handle_message("first", None).await.unwrap();
+        orch.handle_message("second", Some(sid)).await.unwrap();
         let history = orch.get_history(sid).unwrap();
         assert_eq!(history.len(), 4); // 2 pairs
     }
@@ -1341,30 +3535,33 @@ mod tests {
         assert!(matches!(result.unwrap_err(), ChatError::SessionNotFound(_)));
     }
 
-    #[test]
-    fn test_delete_session_clears_history() {
+    #[tokio::test]
+    async fn test_delete_session_clears_history() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid) = orch.handle_message("test", None).unwrap();
+        let (_, sid) = orch.handle_message("test", None).await.unwrap();
         orch.delete_session(sid).unwrap();
         assert!(orch.get_history(sid).is_err());
     }
 
     // ---- Session context carries forward ----
 
-    #[test]
-    fn test_session_context_carries_topic() {
+    #[tokio::test]
+    async fn test_session_context_carries_topic() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid) = orch.handle_message("find deployment notes", None).unwrap();
+        let (_, sid) = orch
+            .handle_message("find deployment notes", None)
+            .await
+            .unwrap();
         let session = orch.get_session(sid).unwrap();
         assert!(session.context.active_topic.is_some());
     }
 
     // ---- Session expiry ----
 
-    #[test]
-    fn test_expired_session_creates_new() {
+    #[tokio::test]
+    async fn test_expired_session_creates_new() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid1) = orch.handle_message("first", None).unwrap();
+        let (_, sid1) = orch.handle_message("first", None).await.unwrap();
 
         {
             let mut sessions = orch.sessions.lock().unwrap();
@@ -1373,7 +3570,7 @@ mod tests {
             }
         }
 
-        let (_, sid2) = orch.handle_message("second", Some(sid1)).unwrap();
+        let (_, sid2) = orch.handle_message("second", Some(sid1)).await.unwrap();
         assert_ne!(sid1, sid2);
     }
 
@@ -1393,68 +3590,78 @@ mod tests {
 
     // ---- Suggestions are present ----
 
-    #[test]
-    fn test_response_has_suggestions() {
+    #[tokio::test]
+    async fn test_response_has_suggestions() {
         let orch = ChatOrchestrator::new(default_config());
-        let (resp, _) = orch.handle_message("find notes about rust", None).unwrap();
+        let (resp, _) = orch
+            .handle_message("find notes about rust", None)
+            .await
+            .unwrap();
         assert!(!resp.suggestions.is_empty());
     }
 
     // ---- Whitespace-only message ----
 
-    #[test]
-    fn test_whitespace_only_message_rejected() {
+    #[tokio::test]
+    async fn test_whitespace_only_message_rejected() {
         let orch = ChatOrchestrator::new(default_config());
-        let result = orch.handle_message("   ", None);
+        let result = orch.handle_message("   ", None).await;
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), ChatError::EmptyMessage));
     }
 
     // ---- HTML/script tags ----
 
-    #[test]
-    fn test_html_script_tags_in_message() {
+    #[tokio::test]
+    async fn test_html_script_tags_in_message() {
         let orch = ChatOrchestrator::new(default_config());
         let (resp, _) = orch
             .handle_message("<script>alert('xss')</script>", None)
+            .await
             .unwrap();
         assert!(!resp.answer.is_empty());
     }
 
     // ---- Delete session then send message to it ----
 
-    #[test]
-    fn test_deleted_session_then_message_creates_new() {
+    #[tokio::test]
+    async fn test_deleted_session_then_message_creates_new() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid1) = orch.handle_message("first", None).unwrap();
+        let (_, sid1) = orch.handle_message("first", None).await.unwrap();
         orch.delete_session(sid1).unwrap();
-        let (_, sid2) = orch.handle_message("second", Some(sid1)).unwrap();
+        let (_, sid2) = orch.handle_message("second", Some(sid1)).await.unwrap();
         assert_ne!(sid1, sid2);
         assert_eq!(orch.list_sessions().len(), 1);
     }
 
     // ---- Session with 0 context_turns ----
 
-    #[test]
-    fn test_zero_context_turns_config() {
+    #[tokio::test]
+    async fn test_zero_context_turns_config() {
         let config = ChatConfig {
             context_turns: 0,
             ..ChatConfig::default()
         };
         let orch = ChatOrchestrator::new(config);
-        let (_, sid) = orch.handle_message("find notes", None).unwrap();
+        let (_, sid) = orch.handle_message("find notes", None).await.unwrap();
         let session = orch.get_session(sid).unwrap();
         assert!(session.context.recent_turns.is_empty());
     }
 
     // ---- Multiple sequential messages building context ----
 
-    #[test]
-    fn test_multiple_messages_build_context() {
+    #[tokio::test]
+    async fn test_multiple_messages_build_context() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid) = orch.handle_message("find deployment notes", None).unwrap();
-        orch.handle_message("tell me more", Some(sid)).unwrap();
+        let (_, sid) = orch
+            .handle_message("find deployment notes", None)
+            .await
+            .unwrap();
+        orch.handle_message("tell me more", Some(sid))
+            .await
+            .unwrap();
         orch.handle_message("what about the budget", Some(sid))
+            .await
             .unwrap();
 
         let session = orch.get_session(sid).unwrap();
@@ -1465,12 +3672,14 @@ mod tests {
 
     // ---- History messages are in order ----
 
-    #[test]
-    fn test_history_messages_in_order() {
+    #[tokio::test]
+    async fn test_history_messages_in_order() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid) = orch.handle_message("first query", None).unwrap();
-        orch.handle_message("second query", Some(sid)).unwrap();
-        orch.handle_message("third query", Some(sid)).unwrap();
+        let (_, sid) = orch.handle_message("first query", None).await.unwrap();
+        orch.handle_message("second query", Some(sid))
+            .await
+            .unwrap();
+        orch.handle_message("third query", Some(sid)).await.unwrap();
 
         let history = orch.get_history(sid).unwrap();
         assert_eq!(history.len(), 6);
@@ -1485,13 +3694,13 @@ mod tests {
 
     // ---- Rapid sequential messages ----
 
-    #[test]
-    fn test_rapid_sequential_messages() {
+    #[tokio::test]
+    async fn test_rapid_sequential_messages() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid) = orch.handle_message("start", None).unwrap();
+        let (_, sid) = orch.handle_message("start", None).await.unwrap();
         for i in 0..20 {
             let msg = format!("rapid message {}", i);
-            let (_, sid2) = orch.handle_message(&msg, Some(sid)).unwrap();
+            let (_, sid2) = orch.handle_message(&msg, Some(sid)).await.unwrap();
             assert_eq!(sid, sid2);
         }
         let history = orch.get_history(sid).unwrap();
@@ -1500,22 +3709,23 @@ mod tests {
 
     // ---- Concurrent access from multiple threads ----
 
-    #[test]
-    fn test_concurrent_handle_message() {
-        use std::thread;
-
+    #[tokio::test]
+    async fn test_concurrent_handle_message() {
         let orch = Arc::new(ChatOrchestrator::new(default_config()));
         let mut handles = Vec::new();
 
         for i in 0..10 {
             let orch_clone = Arc::clone(&orch);
-            handles.push(thread::spawn(move || {
+            handles.push(tokio::spawn(async move {
                 let msg = format!("concurrent message {}", i);
-                orch_clone.handle_message(&msg, None).unwrap()
+                orch_clone.handle_message(&msg, None).await.unwrap()
             }));
         }
 
-        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        let mut results = Vec::new();
+        for handle in handles {
+            results.push(handle.await.unwrap());
+        }
         assert_eq!(results.len(), 10);
 
         let sessions = orch.list_sessions();
@@ -1524,19 +3734,19 @@ mod tests {
 
     // ---- Message at exactly max length boundary ----
 
-    #[test]
-    fn test_message_one_under_max_length() {
+    #[tokio::test]
+    async fn test_message_one_under_max_length() {
         let orch = ChatOrchestrator::new(default_config());
         let msg = "a".repeat(MAX_MESSAGE_LENGTH - 1);
-        assert!(orch.handle_message(&msg, None).is_ok());
+        assert!(orch.handle_message(&msg, None).await.is_ok());
     }
 
     // ---- Session summary fields ----
 
-    #[test]
-    fn test_list_sessions_summary_fields() {
+    #[tokio::test]
+    async fn test_list_sessions_summary_fields() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid) = orch.handle_message("test message", None).unwrap();
+        let (_, sid) = orch.handle_message("test message", None).await.unwrap();
         let summaries = orch.list_sessions();
         assert_eq!(summaries.len(), 1);
         assert_eq!(summaries[0].id, sid);
@@ -1547,14 +3757,20 @@ mod tests {
 
     // ---- Clarification with active topic after search ----
 
-    #[test]
-    fn test_clarification_references_active_topic() {
+    #[tokio::test]
+    async fn test_clarification_references_active_topic() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid) = orch.handle_message("find deployment notes", None).unwrap();
+        let (_, sid) = orch
+            .handle_message("find deployment notes", None)
+            .await
+            .unwrap();
         let session = orch.get_session(sid).unwrap();
         assert!(session.context.active_topic.is_some());
 
-        let (resp, _) = orch.handle_message("tell me more", Some(sid)).unwrap();
+        let (resp, _) = orch
+            .handle_message("tell me more", Some(sid))
+            .await
+            .unwrap();
         let topic = session.context.active_topic.unwrap();
         assert!(
             resp.answer.contains(&topic) || !resp.answer.is_empty(),
@@ -1564,19 +3780,21 @@ mod tests {
 
     // ---- Unicode message ----
 
-    #[test]
-    fn test_unicode_message_handled() {
+    #[tokio::test]
+    async fn test_unicode_message_handled() {
         let orch = ChatOrchestrator::new(default_config());
-        let result = orch.handle_message("Qu'est-ce qui s'est pass\u{00e9} hier?", None);
+        let result = orch
+            .handle_message("Qu'est-ce qui s'est pass\u{00e9} hier?", None)
+            .await;
         assert!(result.is_ok());
     }
 
     // ---- Very short message ----
 
-    #[test]
-    fn test_single_char_message() {
+    #[tokio::test]
+    async fn test_single_char_message() {
         let orch = ChatOrchestrator::new(default_config());
-        let result = orch.handle_message("a", None);
+        let result = orch.handle_message("a", None).await;
         assert!(result.is_ok());
     }
 
@@ -1604,10 +3822,10 @@ mod tests {
 
     // ---- FIX-1: SQLite persistence on read paths ----
 
-    #[test]
-    fn test_get_history_from_sqlite_after_memory_clear() {
+    #[tokio::test]
+    async fn test_get_history_from_sqlite_after_memory_clear() {
         let orch = orchestrator_with_backends();
-        let (_, sid) = orch.handle_message("hello sqlite", None).unwrap();
+        let (_, sid) = orch.handle_message("hello sqlite", None).await.unwrap();
 
         // Verify in-memory history exists
         assert_eq!(orch.get_history(sid).unwrap().len(), 2);
@@ -1627,10 +3845,10 @@ mod tests {
         assert_eq!(history[1].role, "assistant");
     }
 
-    #[test]
-    fn test_get_session_from_sqlite_after_memory_clear() {
+    #[tokio::test]
+    async fn test_get_session_from_sqlite_after_memory_clear() {
         let orch = orchestrator_with_backends();
-        let (_, sid) = orch.handle_message("test session", None).unwrap();
+        let (_, sid) = orch.handle_message("test session", None).await.unwrap();
 
         // Clear in-memory
         {
@@ -1644,10 +3862,10 @@ mod tests {
         assert_eq!(session.unwrap().id, sid);
     }
 
-    #[test]
-    fn test_list_sessions_includes_sqlite_sessions() {
+    #[tokio::test]
+    async fn test_list_sessions_includes_sqlite_sessions() {
         let orch = orchestrator_with_backends();
-        let (_, sid) = orch.handle_message("test listing", None).unwrap();
+        let (_, sid) = orch.handle_message("test listing", None).await.unwrap();
 
         // Clear in-memory
         {
@@ -1660,13 +3878,382 @@ mod tests {
         assert!(summaries.iter().any(|s| s.id == sid));
     }
 
+    // ---- Session titles, pinning, search and export ----
+
+    #[tokio::test]
+    async fn test_first_query_titles_session() {
+        let orch = ChatOrchestrator::new(default_config());
+        let (_, sid) = orch
+            .handle_message("what did we decide about pricing?", None)
+            .await
+            .unwrap();
+        assert_eq!(
+            orch.get_session(sid).unwrap().title.as_deref(),
+            Some("Decide, pricing")
+        );
+
+        orch.handle_message("and the launch date?", Some(sid))
+            .await
+            .unwrap();
+        assert_eq!(
+            orch.get_session(sid).unwrap().title.as_deref(),
+            Some("Decide, pricing")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_session_renames_and_pins() {
+        let orch = orchestrator_with_backends();
+        let (_, first) = orch
+            .handle_message("find pricing notes", None)
+            .await
+            .unwrap();
+        let (_, second) = orch
+            .handle_message("find launch notes", None)
+            .await
+            .unwrap();
+
+        let summary = orch
+            .update_session(
+                first,
+                ChatSessionUpdate {
+                    title: Some("  Q3   pricing ".to_string()),
+                    pinned: Some(true),
+                },
+            )
+            .unwrap();
+        assert_eq!(summary.title.as_deref(), Some("Q3 pricing"));
+        assert!(summary.pinned);
+
+        // Pinned sessions list first even when older
+        let ids: Vec<Uuid> = orch.list_sessions().iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![first, second]);
+
+        // Persisted: survives clearing in-memory state
+        orch.sessions.lock().unwrap().clear();
+        let session = orch.get_session(first).unwrap();
+        assert_eq!(session.title.as_deref(), Some("Q3 pricing"));
+        assert!(session.pinned);
+        let summary = orch
+            .update_session(
+                first,
+                ChatSessionUpdate {
+                    title: None,
+                    pinned: Some(false),
+                },
+            )
+            .unwrap();
+        assert_eq!(summary.title.as_deref(), Some("Q3 pricing"));
+        assert!(!summary.pinned);
+
+        assert!(matches!(
+            orch.update_session(
+                first,
+                ChatSessionUpdate {
+                    title: Some("   ".to_string()),
+                    pinned: None,
+                },
+            ),
+            Err(ChatError::InvalidTitle(_))
+        ));
+        assert!(matches!(
+            orch.update_session(Uuid::new_v4(), ChatSessionUpdate::default()),
+            Err(ChatError::SessionNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_search_messages_uses_fts() {
+        let orch = orchestrator_with_backends();
+        let (_, sid) = orch
+            .handle_message("what happened with the marketing budget?", None)
+            .await
+            .unwrap();
+        orch.handle_message("find launch notes", None)
+            .await
+            .unwrap();
+
+        let hits = orch.search_messages("budget", 10).unwrap();
+        let hit = hits.iter().find(|h| h.role == "user").unwrap();
+        assert_eq!(hit.session_id, sid);
+        assert!(hit.snippet.contains("**budget**"));
+        assert!(hit.session_title.is_some());
+        assert!(hit.created_at > 0);
+        assert!(hits.iter().all(|h| h.session_id == sid));
+
+        assert!(orch.search_messages("   ", 10).unwrap().is_empty());
+        assert!(orch
+            .search_messages("nonexistentterm", 10)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_messages_in_memory() {
+        let orch = ChatOrchestrator::new(default_config());
+        let (_, sid) = orch
+            .handle_message("what happened with the Budget?", None)
+            .await
+            .unwrap();
+        let hits = orch.search_messages("budget", 10).unwrap();
+        assert!(!hits.is_empty());
+        assert!(hits.iter().all(|h| h.session_id == sid));
+        assert_eq!(orch.search_messages("budget", 1).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_export_session_markdown_resolves_sources() {
+        let (orch, db) = orchestrator_with_db();
+        let ts = Local::now().timestamp() - 3600;
+        let capture = insert_capture(&db, "screen", "pricing stays flat", "Slack", ts, None);
+        let (resp, sid) = orch.handle_message("find pricing", None).await.unwrap();
+        assert!(resp.sources.iter().any(|s| s.chunk_id == capture));
+
+        let md = orch.export_session_markdown(sid).unwrap();
+        assert!(md.starts_with("# Pricing\n"));
+        assert!(md.contains("## You · "));
+        assert!(md.contains("find pricing"));
+        assert!(md.contains("## Engram · "));
+        assert!(md.contains(&format!(
+            "{} · Slack (`{}`)",
+            crate::response::format_timestamp(ts),
+            capture
+        )));
+
+        assert!(matches!(
+            orch.export_session_markdown(Uuid::new_v4()),
+            Err(ChatError::SessionNotFound(_))
+        ));
+    }
+
+    // ---- Feedback on answers ----
+
+    #[tokio::test]
+    async fn test_irrelevant_feedback_demotes_source() {
+        let (orch, db) = orchestrator_with_db();
+        let now = Local::now().timestamp();
+        let first = insert_capture(&db, "screen", "pricing page draft", "Chrome", now, None);
+        let second = insert_capture(&db, "screen", "pricing call notes", "Notes", now - 60, None);
+
+        let (resp, _, message_id) = orch
+            .handle_message_as("primary", "find pricing", None)
+            .await
+            .unwrap();
+        let ranked: Vec<Uuid> = resp.sources.iter().map(|s| s.chunk_id).collect();
+        assert_eq!(ranked, vec![first, second]);
+
+        let recorded = orch
+            .record_feedback(
+                "primary",
+                ChatFeedback {
+                    message_id,
+                    rating: FeedbackRating::Irrelevant,
+                    chunk_id: Some(first),
+                },
+            )
+            .unwrap();
+        assert_eq!(recorded.adjusted_boosts, 1);
+        // A keyword-only source was irrelevant, so semantic similarity
+        // gains weight.
+        assert!(recorded.semantic_weight > SEMANTIC_WEIGHT);
+
+        let (resp, _, _) = orch
+            .handle_message_as("primary", "find pricing", None)
+            .await
+            .unwrap();
+        let ranked: Vec<Uuid> = resp.sources.iter().map(|s| s.chunk_id).collect();
+        assert_eq!(ranked, vec![second, first]);
+
+        let report = orch.feedback_report("primary").await.unwrap();
+        assert_eq!(report.counts.irrelevant, 1);
+        assert_eq!(report.semantic_weight, recorded.semantic_weight);
+        assert_eq!(report.default_semantic_weight, SEMANTIC_WEIGHT);
+        assert_eq!(report.boosts.len(), 1);
+        assert_eq!(report.boosts[0].term, "pricing");
+        assert_eq!(report.boosts[0].chunk_id, first);
+        assert!(report.boosts[0].score < 0.0);
+        assert_eq!(report.shifts.len(), 1);
+        assert_eq!(report.shifts[0].query, "find pricing");
+        let shift = |id| {
+            report.shifts[0]
+                .results
+                .iter()
+                .find(|r| r.chunk_id == id)
+                .map(|r| (r.baseline_rank, r.rank))
+                .unwrap()
+        };
+        assert_eq!(shift(first), (Some(1), Some(2)));
+        assert_eq!(shift(second), (Some(2), Some(1)));
+
+        // Fusion weights and boosts are tuned per user.
+        let other = orch.feedback_report("laptop").await.unwrap();
+        assert_eq!(other.semantic_weight, SEMANTIC_WEIGHT);
+        assert_eq!(other.counts, FeedbackCounts::default());
+        assert!(other.boosts.is_empty());
+        assert!(other.shifts.is_empty());
+        let (resp, _, _) = orch
+            .handle_message_as("laptop", "find pricing", None)
+            .await
+            .unwrap();
+        let ranked: Vec<Uuid> = resp.sources.iter().map(|s| s.chunk_id).collect();
+        assert_eq!(ranked, vec![first, second]);
+    }
+
+    #[tokio::test]
+    async fn test_repeat_feedback_replaces_earlier_rating() {
+        let (orch, db) = orchestrator_with_db();
+        let now = Local::now().timestamp();
+        insert_capture(&db, "screen", "pricing page draft", "Chrome", now, None);
+        let (resp, _, message_id) = orch
+            .handle_message_as("primary", "find pricing", None)
+            .await
+            .unwrap();
+        let rate = |rating| {
+            orch.record_feedback(
+                "primary",
+                ChatFeedback {
+                    message_id,
+                    rating,
+                    chunk_id: None,
+                },
+            )
+            .unwrap()
+        };
+        let feedback_count = || {
+            db.with_conn(|conn| engram_storage::get_fusion_weight(conn, "primary"))
+                .unwrap()
+                .unwrap()
+                .feedback_count
+        };
+
+        let first = rate(FeedbackRating::Up);
+        let again = rate(FeedbackRating::Up);
+        assert_eq!(again.id, first.id);
+        assert_eq!(again.semantic_weight, first.semantic_weight);
+        assert_eq!(feedback_count(), 1);
+        let report = orch.feedback_report("primary").await.unwrap();
+        assert_eq!(report.counts.up, 1);
+        assert_eq!(report.boosts.len(), 1);
+        assert_eq!(
+            report.boosts[0].score,
+            feedback::boost_step(FeedbackRating::Up)
+        );
+
+        // Changing the rating swaps its effect rather than stacking on it.
+        let changed = rate(FeedbackRating::Down);
+        assert_eq!(changed.id, first.id);
+        assert_eq!(
+            changed.semantic_weight,
+            feedback::tune_semantic_weight(SEMANTIC_WEIGHT, FeedbackRating::Down, &resp.sources)
+        );
+        assert_eq!(feedback_count(), 1);
+        let report = orch.feedback_report("primary").await.unwrap();
+        assert_eq!(
+            report.counts,
+            FeedbackCounts {
+                up: 0,
+                down: 1,
+                irrelevant: 0
+            }
+        );
+        assert_eq!(
+            report.boosts[0].score,
+            feedback::boost_step(FeedbackRating::Down)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_thumbs_up_boosts_every_source() {
+        let (orch, db) = orchestrator_with_db();
+        let now = Local::now().timestamp();
+        insert_capture(&db, "screen", "pricing page draft", "Chrome", now, None);
+        insert_capture(&db, "screen", "pricing call notes", "Notes", now - 60, None);
+        let (_, _, message_id) = orch
+            .handle_message_as("primary", "find pricing", None)
+            .await
+            .unwrap();
+
+        let recorded = orch
+            .record_feedback(
+                "primary",
+                ChatFeedback {
+                    message_id,
+                    rating: FeedbackRating::Up,
+                    chunk_id: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(recorded.adjusted_boosts, 2);
+        let report = orch.feedback_report("primary").await.unwrap();
+        assert_eq!(report.counts.up, 1);
+        assert!(report.boosts.iter().all(|b| b.score > 0.0));
+    }
+
+    #[tokio::test]
+    async fn test_feedback_rejects_bad_targets() {
+        let (orch, db) = orchestrator_with_db();
+        let now = Local::now().timestamp();
+        insert_capture(&db, "screen", "pricing page draft", "Chrome", now, None);
+        let (_, sid, message_id) = orch
+            .handle_message_as("primary", "find pricing", None)
+            .await
+            .unwrap();
+        let question_id = orch.get_history(sid).unwrap()[0].id;
+
+        let rate = |message_id, rating, chunk_id| {
+            orch.record_feedback(
+                "primary",
+                ChatFeedback {
+                    message_id,
+                    rating,
+                    chunk_id,
+                },
+            )
+        };
+        assert!(matches!(
+            rate(Uuid::new_v4(), FeedbackRating::Up, None),
+            Err(ChatError::MessageNotFound(_))
+        ));
+        assert!(matches!(
+            rate(question_id, FeedbackRating::Up, None),
+            Err(ChatError::InvalidFeedback(_))
+        ));
+        assert!(matches!(
+            rate(message_id, FeedbackRating::Irrelevant, None),
+            Err(ChatError::InvalidFeedback(_))
+        ));
+        assert!(matches!(
+            rate(message_id, FeedbackRating::Down, Some(Uuid::new_v4())),
+            Err(ChatError::InvalidFeedback(_))
+        ));
+
+        let orch = ChatOrchestrator::new(default_config());
+        assert!(matches!(
+            orch.record_feedback(
+                "primary",
+                ChatFeedback {
+                    message_id,
+                    rating: FeedbackRating::Up,
+                    chunk_id: None,
+                },
+            ),
+            Err(ChatError::StorageError(_))
+        ));
+    }
+
     // ---- FIX-3: "tell me more" doubles result limit ----
 
-    #[test]
-    fn test_tell_me_more_routes_as_search() {
+    #[tokio::test]
+    async fn test_tell_me_more_routes_as_search() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid) = orch.handle_message("find deployment notes", None).unwrap();
-        let (resp, _) = orch.handle_message("tell me more", Some(sid)).unwrap();
+        let (_, sid) = orch
+            .handle_message("find deployment notes", None)
+            .await
+            .unwrap();
+        let (resp, _) = orch
+            .handle_message("tell me more", Some(sid))
+            .await
+            .unwrap();
         // Should route through search, not clarification
         assert!(!resp.answer.is_empty());
         // Should have sources (mock search returns results)
@@ -1675,28 +4262,34 @@ mod tests {
 
     // ---- FIX-4: "when was that?" ----
 
-    #[test]
-    fn test_when_was_that_no_prior_context() {
+    #[tokio::test]
+    async fn test_when_was_that_no_prior_context() {
         let orch = ChatOrchestrator::new(default_config());
-        let (resp, _) = orch.handle_message("when was that?", None).unwrap();
+        let (resp, _) = orch.handle_message("when was that?", None).await.unwrap();
         // No prior turns, so follow-up resolver doesn't run;
         // query routes normally (not as __when__)
         assert!(!resp.answer.is_empty());
     }
 
-    #[test]
-    fn test_when_was_that_with_prior_search() {
+    #[tokio::test]
+    async fn test_when_was_that_with_prior_search() {
         let orch = ChatOrchestrator::new(default_config());
-        let (_, sid) = orch.handle_message("find deployment notes", None).unwrap();
-        let (resp, _) = orch.handle_message("when was that?", Some(sid)).unwrap();
+        let (_, sid) = orch
+            .handle_message("find deployment notes", None)
+            .await
+            .unwrap();
+        let (resp, _) = orch
+            .handle_message("when was that?", Some(sid))
+            .await
+            .unwrap();
         // Should either show timestamps or indicate no timestamp info
         assert!(!resp.answer.is_empty());
     }
 
     // ---- FIX-6: ChatSessionEnded with real data ----
 
-    #[test]
-    fn test_delete_session_emits_event_with_data() {
+    #[tokio::test]
+    async fn test_delete_session_emits_event_with_data() {
         let db = Arc::new(engram_storage::Database::in_memory().unwrap());
         let fts_search = Arc::new(engram_storage::FtsSearch::new(Arc::clone(&db)));
         let query_service = Arc::new(engram_storage::QueryService::new(Arc::clone(&db)));
@@ -1708,6 +4301,10 @@ mod tests {
         let backends = ChatBackends {
             database: db,
             fts_search,
+            search_engine: Arc::new(engram_vector::SearchEngine::new(
+                Arc::new(engram_vector::VectorIndex::new()),
+                engram_vector::MockEmbedding::new(),
+            )),
             query_service,
             task_store,
             intent_detector,
@@ -1715,8 +4312,8 @@ mod tests {
         };
 
         let orch = ChatOrchestrator::new(default_config()).with_backends(backends);
-        let (_, sid) = orch.handle_message("test", None).unwrap();
-        orch.handle_message("second msg", Some(sid)).unwrap();
+        let (_, sid) = orch.handle_message("test", None).await.unwrap();
+        orch.handle_message("second msg", Some(sid)).await.unwrap();
 
         // Drain existing events
         while event_rx.try_recv().is_ok() {}
@@ -1749,8 +4346,8 @@ mod tests {
 
     // ---- FIX-8: Session eviction at MAX_SESSIONS ----
 
-    #[test]
-    fn test_session_eviction_at_max_sessions() {
+    #[tokio::test]
+    async fn test_session_eviction_at_max_sessions() {
         let config = ChatConfig {
             session_timeout_minutes: 60, // long timeout so nothing expires
             ..ChatConfig::default()
@@ -1760,12 +4357,12 @@ mod tests {
         // Create MAX_SESSIONS sessions
         for i in 0..MAX_SESSIONS {
             let msg = format!("session {}", i);
-            orch.handle_message(&msg, None).unwrap();
+            orch.handle_message(&msg, None).await.unwrap();
         }
         assert_eq!(orch.list_sessions().len(), MAX_SESSIONS);
 
         // Creating one more should evict the oldest
-        orch.handle_message("over the limit", None).unwrap();
+        orch.handle_message("over the limit", None).await.unwrap();
         assert_eq!(orch.list_sessions().len(), MAX_SESSIONS);
     }
 
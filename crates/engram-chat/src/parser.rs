@@ -3,11 +3,13 @@
 //! Classifies intent, extracts time ranges, people, apps, and topics
 //! from raw user input to produce a [`StructuredQuery`].
 
-use chrono::{Datelike, Duration, Local, NaiveTime, Weekday};
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveTime, Weekday};
 use regex::Regex;
 use std::sync::LazyLock;
 
-use crate::types::{QueryIntent, StructuredQuery, TimeRange};
+use crate::types::{
+    AnalyticsMetric, Composition, DataSource, QueryIntent, StructuredQuery, TimeRange,
+};
 
 // =============================================================================
 // Compiled regex sets (compiled once, reused across calls)
@@ -91,6 +93,10 @@ struct TimePatterns {
     last_month: Regex,
     on_weekday: Regex,
     between_months: Regex,
+    /// "Oct 3", "October 3rd, 2025"; the month word is checked separately.
+    month_day: Regex,
+    /// "2025-10-03"
+    iso_date: Regex,
 }
 
 static TIME_PATTERNS: LazyLock<TimePatterns> = LazyLock::new(|| {
@@ -103,16 +109,37 @@ static TIME_PATTERNS: LazyLock<TimePatterns> = LazyLock::new(|| {
     this_week: Regex::new(r"(?i)\bthis\s+week\b").unwrap(),
     last_month: Regex::new(r"(?i)\blast\s+month\b").unwrap(),
     on_weekday: Regex::new(
-        r"(?i)\bon\s+(monday|tuesday|wednesday|thursday|friday|saturday|sunday)\b",
+        r"(?i)\b(on|last)\s+(monday|tuesday|wednesday|thursday|friday|saturday|sunday)\b",
     )
     .unwrap(),
     between_months: Regex::new(
         r"(?i)\bbetween\s+(january|february|march|april|may|june|july|august|september|october|november|december)\s+and\s+(january|february|march|april|may|june|july|august|september|october|november|december)\b",
     )
     .unwrap(),
+    month_day: Regex::new(
+        r"(?i)\b([a-z]{3,9})\.?\s+(\d{1,2})(?:st|nd|rd|th)?\b(?:,?\s+(\d{4})\b)?",
+    )
+    .unwrap(),
+    iso_date: Regex::new(r"\b(\d{4})-(\d{2})-(\d{2})\b").unwrap(),
 }
 });
 
+// Month names, matched by prefix for abbreviations
+static MONTHS: &[&str] = &[
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
 // Person extraction patterns
 static PERSON_CONTEXT_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(?i)\b(\w+)\s+(?:said|mentioned|asked|told|wrote)\b").unwrap());
@@ -237,6 +264,7 @@ static STOP_WORDS: &[&str] = &[
     "where",
     "why",
     "said",
+    "say",
     "did",
     "find",
     "show",
@@ -277,6 +305,234 @@ static TIME_WORDS: &[&str] = &[
     "ago",
 ];
 
+// Structured data source patterns
+struct DataSourcePatterns {
+    digest: Regex,
+    summaries: Regex,
+    tasks: Regex,
+    apps: Regex,
+    app_usage: Regex,
+    /// Entity lists and the entity type each selects.
+    entities: Vec<(Regex, &'static str)>,
+    any_entities: Regex,
+}
+
+static DATA_SOURCE_PATTERNS: LazyLock<DataSourcePatterns> = LazyLock::new(|| {
+    let re = |p: &str| Regex::new(p).expect("Invalid data source regex");
+    DataSourcePatterns {
+        digest: re(r"(?i)\b(?:digest|recap)\b|\bhow\s+was\s+my\s+day\b"),
+        summaries: re(r"(?i)\bsummar(?:y|ies|ize|ise)\b"),
+        tasks: re(r"(?i)\b(?:tasks|to-?dos|reminders)\b"),
+        apps: re(r"(?i)\b(?:apps|applications|programs)\b"),
+        app_usage: re(r"(?i)\b(?:activity|usage)\b|\b(?:time|hours)\s+(?:in|on)\b"),
+        entities: vec![
+            (re(r"(?i)\b(?:urls|links|websites|sites)\b"), "url"),
+            (re(r"(?i)\bprojects\b"), "project"),
+            (re(r"(?i)\b(?:dates|deadlines)\b"), "date"),
+            (re(r"(?i)\b(?:amounts|prices)\b"), "money"),
+            (
+                re(r"(?i)\b(?:which|what|list)\s+(?:\w+\s+)?(?:people|contacts)\b"),
+                "person",
+            ),
+        ],
+        any_entities: re(r"(?i)\bentities\b"),
+    }
+});
+
+// Task status words and the status each selects
+static TASK_STATUS_WORDS: &[(&str, &str)] = &[
+    ("pending", "pending"),
+    ("open", "pending"),
+    ("outstanding", "pending"),
+    ("active", "active"),
+    ("running", "active"),
+    ("done", "done"),
+    ("completed", "done"),
+    ("finished", "done"),
+    ("dismissed", "dismissed"),
+    ("expired", "expired"),
+    ("failed", "failed"),
+    ("detected", "detected"),
+];
+
+// Words naming a structured store, stripped from the topics of queries
+// answered from one
+static DATA_SOURCE_WORDS: &[&str] = &[
+    "digest",
+    "recap",
+    "day",
+    "summary",
+    "summaries",
+    "summarize",
+    "summarise",
+    "task",
+    "tasks",
+    "todo",
+    "todos",
+    "to-do",
+    "to-dos",
+    "reminders",
+    "app",
+    "apps",
+    "applications",
+    "programs",
+    "activity",
+    "usage",
+    "time",
+    "hours",
+    "url",
+    "urls",
+    "links",
+    "websites",
+    "sites",
+    "projects",
+    "dates",
+    "deadlines",
+    "amounts",
+    "prices",
+    "people",
+    "contacts",
+    "entities",
+    "visit",
+    "visited",
+    "used",
+    "use",
+    "using",
+    "list",
+    "give",
+    "pending",
+    "open",
+    "outstanding",
+    "active",
+    "running",
+    "done",
+    "completed",
+    "finished",
+    "dismissed",
+    "expired",
+    "failed",
+    "detected",
+];
+
+// Analytics question patterns, checked in order
+static ANALYTICS_PATTERNS: LazyLock<Vec<(Regex, AnalyticsMetric)>> = LazyLock::new(|| {
+    let re = |p: &str| Regex::new(p).expect("Invalid analytics regex");
+    vec![
+        (
+            re(
+                r"(?i)\btrend(?:s|ing)?\b|\bday[\s-]+over[\s-]+day\b|\b(?:per|each|by)\s+day\b|\bcompared?\s+(?:to|with)\s+yesterday\b|\b(?:more|less)\s+than\s+yesterday\b",
+            ),
+            AnalyticsMetric::Trend,
+        ),
+        (
+            re(
+                r"(?i)\bwhen\s+did\s+i\s+(?:first|last)\b|\b(?:first|last)\s+(?:seen|used|opened)\b|\bsince\s+when\b",
+            ),
+            AnalyticsMetric::FirstLastSeen,
+        ),
+        (
+            re(
+                r"(?i)\bhow\s+(?:long|much\s+time)\b|\b(?:time|hours|minutes)\s+spent\b|\bspen[dt]\b.*\b(?:time|hours|minutes)\b|\b(?:hours|minutes)\s+(?:did|have)\s+i\b|\bscreen\s+time\b",
+            ),
+            AnalyticsMetric::TimeSpent,
+        ),
+        (
+            re(
+                r"(?i)\bhow\s+often\b|\bhow\s+many\s+(?:captures|screenshots|times)\b|\bnumber\s+of\s+captures\b",
+            ),
+            AnalyticsMetric::Count,
+        ),
+    ]
+});
+
+// Words of analytics questions, stripped from their topics
+static ANALYTICS_WORDS: &[&str] = &[
+    "long",
+    "much",
+    "time",
+    "spent",
+    "spend",
+    "spending",
+    "hours",
+    "minutes",
+    "screen",
+    "trend",
+    "trends",
+    "trending",
+    "day",
+    "over",
+    "per",
+    "each",
+    "compared",
+    "compare",
+    "yesterday",
+    "more",
+    "less",
+    "first",
+    "last",
+    "seen",
+    "used",
+    "use",
+    "using",
+    "opened",
+    "open",
+    "since",
+    "often",
+    "many",
+    "times",
+    "captures",
+    "screenshots",
+    "number",
+];
+
+// Multi-step question patterns, checked in order
+static COMPOSITION_PATTERNS: LazyLock<Vec<(Regex, Composition)>> = LazyLock::new(|| {
+    let re = |p: &str| Regex::new(p).expect("Invalid composition regex");
+    vec![
+        (
+            re(
+                r"(?i)\btimeline\b|\bchronolog(?:y|ical(?:ly)?)\b|\bsequence\s+of\s+events\b|\bblow[\s-]+by[\s-]+blow\b",
+            ),
+            Composition::Timeline,
+        ),
+        (
+            re(
+                r"(?i)\bcompar(?:e|ed|ing|ison)\b|\bvs\b|\bversus\b|\bdifferences?\s+between\b|\bcontrast\b",
+            ),
+            Composition::Comparison,
+        ),
+    ]
+});
+
+// Words of multi-step questions, stripped from their topics
+static COMPOSITION_WORDS: &[&str] = &[
+    "timeline",
+    "chronology",
+    "chronological",
+    "chronologically",
+    "sequence",
+    "events",
+    "blow",
+    "compare",
+    "compared",
+    "comparing",
+    "comparison",
+    "versus",
+    "difference",
+    "differences",
+    "contrast",
+    "give",
+    "decide",
+    "decided",
+    "discussed",
+    "talked",
+    "happened",
+    "two",
+    "three",
+    "four",
+    "five",
+];
+
 // =============================================================================
 // QueryParser
 // =============================================================================
@@ -454,9 +710,11 @@ impl QueryParser {
             });
         }
 
-        // "on Monday/Tuesday/..." (most recent occurrence)
+        // "on Monday/Tuesday/..." (most recent occurrence) or "last Monday"
+        // (most recent before today)
         if let Some(caps) = tp.on_weekday.captures(raw_query) {
-            let day_str = caps.get(1)?.as_str().to_lowercase();
+            let before_today = caps.get(1)?.as_str().eq_ignore_ascii_case("last");
+            let day_str = caps.get(2)?.as_str().to_lowercase();
             let target_wd = match day_str.as_str() {
                 "monday" => Weekday::Mon,
                 "tuesday" => Weekday::Tue,
@@ -473,7 +731,12 @@ impl QueryParser {
                 - target_wd.num_days_from_monday() as i64
                 + 7)
                 % 7;
-            // If same day, treat as today (0 days back)
+            // If same day, treat as today (0 days back), or a week ago for "last"
+            let days_back = if before_today && days_back == 0 {
+                7
+            } else {
+                days_back
+            };
             let target_date = now.date_naive() - Duration::days(days_back);
             let start = target_date
                 .and_time(NaiveTime::from_hms_opt(0, 0, 0)?)
@@ -489,6 +752,22 @@ impl QueryParser {
             });
         }
 
+        // "Oct 3", "October 3rd, 2025" or "2025-10-03": that whole day
+        if let Some(date) = calendar_date(raw_query, now.date_naive()) {
+            let start = date
+                .and_time(NaiveTime::from_hms_opt(0, 0, 0)?)
+                .and_local_timezone(Local)
+                .single()?;
+            let end = date
+                .and_time(NaiveTime::from_hms_opt(23, 59, 59)?)
+                .and_local_timezone(Local)
+                .single()?;
+            return Some(TimeRange {
+                start: start.timestamp(),
+                end: end.timestamp(),
+            });
+        }
+
         // FIX-5(a): "between [month] and [month]"
         if let Some(caps) = tp.between_months.captures(raw_query) {
             let start_month_str = caps.get(1)?.as_str().to_lowercase();
@@ -596,6 +875,19 @@ impl QueryParser {
             .map(|m| normalize_app_name(m.as_str()))
     }
 
+    /// Every distinct application named in the query, in order of mention
+    /// ("Slack vs Teams").
+    pub fn extract_apps(&self, raw_query: &str) -> Vec<String> {
+        let mut apps: Vec<String> = Vec::new();
+        for m in APP_RE.find_iter(raw_query) {
+            let app = normalize_app_name(m.as_str());
+            if !apps.contains(&app) {
+                apps.push(app);
+            }
+        }
+        apps
+    }
+
     // -----------------------------------------------------------------
     // Topic extraction
     // -----------------------------------------------------------------
@@ -647,6 +939,82 @@ impl QueryParser {
             .collect()
     }
 
+    // -----------------------------------------------------------------
+    // Data source extraction
+    // -----------------------------------------------------------------
+
+    /// Detect a query about a structured store (tasks, entities, summaries,
+    /// the daily digest or app usage) rather than capture text.
+    ///
+    /// Checks in order: digest, summaries, tasks, apps, entities.
+    pub fn extract_data_source(
+        &self,
+        raw_query: &str,
+        app_filter: &Option<String>,
+    ) -> Option<DataSource> {
+        let dp = &*DATA_SOURCE_PATTERNS;
+
+        if dp.digest.is_match(raw_query) {
+            return Some(DataSource::Digest);
+        }
+        if dp.summaries.is_match(raw_query) {
+            return Some(DataSource::Summaries);
+        }
+        if dp.tasks.is_match(raw_query) {
+            let lower = raw_query.to_lowercase();
+            let status = lower
+                .split(|c: char| !c.is_alphanumeric())
+                .find_map(|w| TASK_STATUS_WORDS.iter().find(|(word, _)| *word == w))
+                .map(|(_, status)| status.to_string());
+            return Some(DataSource::Tasks { status });
+        }
+        if dp.apps.is_match(raw_query) || (app_filter.is_some() && dp.app_usage.is_match(raw_query))
+        {
+            return Some(DataSource::Apps);
+        }
+        for (re, entity_type) in &dp.entities {
+            if re.is_match(raw_query) {
+                return Some(DataSource::Entities {
+                    entity_type: Some(entity_type.to_string()),
+                });
+            }
+        }
+        if dp.any_entities.is_match(raw_query) {
+            return Some(DataSource::Entities { entity_type: None });
+        }
+        None
+    }
+
+    // -----------------------------------------------------------------
+    // Analytics extraction
+    // -----------------------------------------------------------------
+
+    /// Detect an analytics question (time spent, counts, first/last seen,
+    /// trends) answered from capture timestamps.
+    ///
+    /// Checks in order: trend, first/last seen, time spent, count.
+    pub fn extract_analytics(&self, raw_query: &str) -> Option<AnalyticsMetric> {
+        ANALYTICS_PATTERNS
+            .iter()
+            .find(|(re, _)| re.is_match(raw_query))
+            .map(|(_, metric)| *metric)
+    }
+
+    // -----------------------------------------------------------------
+    // Composition extraction
+    // -----------------------------------------------------------------
+
+    /// Detect a question answered in several steps: a comparison or a
+    /// timeline.
+    ///
+    /// Checks in order: timeline, comparison.
+    pub fn extract_composition(&self, raw_query: &str) -> Option<Composition> {
+        COMPOSITION_PATTERNS
+            .iter()
+            .find(|(re, _)| re.is_match(raw_query))
+            .map(|(_, composition)| *composition)
+    }
+
     // -----------------------------------------------------------------
     // Full parse
     // -----------------------------------------------------------------
@@ -670,7 +1038,30 @@ impl QueryParser {
         });
         let people = self.extract_people(raw_query, known_entities);
         let app_filter = self.extract_app(raw_query);
-        let topics = self.extract_topics(raw_query, &people, &app_filter);
+        let mut topics =
+            self.extract_topics(&strip_calendar_dates(raw_query), &people, &app_filter);
+        let analytics = self.extract_analytics(raw_query);
+        let mut data_source = self.extract_data_source(raw_query, &app_filter);
+        if analytics.is_some() {
+            // "How long was I in each app?" is analytics, not an app list.
+            if data_source == Some(DataSource::Apps) {
+                data_source = None;
+            }
+            topics.retain(|t| !ANALYTICS_WORDS.contains(&t.as_str()));
+        }
+        if data_source.is_some() || analytics.is_some() {
+            topics.retain(|t| !DATA_SOURCE_WORDS.contains(&t.as_str()));
+        }
+        // Stores and analytics answer in one step; "compared to yesterday"
+        // is a trend.
+        let composition = if data_source.is_none() && analytics.is_none() {
+            self.extract_composition(raw_query)
+        } else {
+            None
+        };
+        if composition.is_some() {
+            topics.retain(|t| !COMPOSITION_WORDS.contains(&t.as_str()));
+        }
 
         StructuredQuery {
             intent,
@@ -679,6 +1070,9 @@ impl QueryParser {
             time_range,
             content_type: None,
             app_filter,
+            data_source,
+            analytics,
+            composition,
             raw_query: raw_query.to_string(),
         }
     }
@@ -693,7 +1087,7 @@ fn is_sentence_start(text: &str, word: &str) -> bool {
     text.trim_start().starts_with(word)
 }
 
-fn is_stop_or_time_word(word: &str) -> bool {
+pub(crate) fn is_stop_or_time_word(word: &str) -> bool {
     let lower = word.to_lowercase();
     STOP_WORDS.contains(&lower.as_str()) || TIME_WORDS.contains(&lower.as_str())
 }
@@ -707,23 +1101,55 @@ fn is_known_app_lower(lower: &str) -> bool {
     KNOWN_APPS.iter().any(|a| a.to_lowercase() == lower)
 }
 
-/// Map month name (lowercase) to 1-based month number.
+/// Map a month name or an abbreviation of at least three letters
+/// (lowercase, e.g. "oct" or "sept") to its 1-based month number.
 fn month_name_to_number(name: &str) -> Option<u32> {
-    match name {
-        "january" => Some(1),
-        "february" => Some(2),
-        "march" => Some(3),
-        "april" => Some(4),
-        "may" => Some(5),
-        "june" => Some(6),
-        "july" => Some(7),
-        "august" => Some(8),
-        "september" => Some(9),
-        "october" => Some(10),
-        "november" => Some(11),
-        "december" => Some(12),
-        _ => None,
+    if name.len() < 3 {
+        return None;
     }
+    MONTHS
+        .iter()
+        .position(|month| month.starts_with(name))
+        .map(|i| i as u32 + 1)
+}
+
+/// The first calendar date named in `text`. A date without a year is its
+/// most recent occurrence on or before `today`.
+fn calendar_date(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let tp = &*TIME_PATTERNS;
+    if let Some(caps) = tp.iso_date.captures(text) {
+        return NaiveDate::from_ymd_opt(
+            caps[1].parse().ok()?,
+            caps[2].parse().ok()?,
+            caps[3].parse().ok()?,
+        );
+    }
+    tp.month_day.captures_iter(text).find_map(|caps| {
+        let month = month_name_to_number(&caps[1].to_lowercase())?;
+        let day = caps[2].parse().ok()?;
+        if let Some(year) = caps.get(3) {
+            return NaiveDate::from_ymd_opt(year.as_str().parse().ok()?, month, day);
+        }
+        match NaiveDate::from_ymd_opt(today.year(), month, day) {
+            Some(date) if date <= today => Some(date),
+            _ => NaiveDate::from_ymd_opt(today.year() - 1, month, day),
+        }
+    })
+}
+
+/// `text` without its calendar dates, so "Oct 3rd" leaves no topics.
+fn strip_calendar_dates(text: &str) -> String {
+    let tp = &*TIME_PATTERNS;
+    let text = tp.iso_date.replace_all(text, " ");
+    tp.month_day
+        .replace_all(&text, |caps: &regex::Captures| {
+            if month_name_to_number(&caps[1].to_lowercase()).is_some() {
+                " ".to_string()
+            } else {
+                caps[0].to_string()
+            }
+        })
+        .into_owned()
 }
 
 /// Normalize matched app text to canonical casing.
@@ -1471,6 +1897,22 @@ mod tests {
         assert_eq!(tr.end - tr.start, 86399);
     }
 
+    #[test]
+    fn test_time_last_weekday_is_a_past_day() {
+        let now = Local::now();
+        let today = now.format("%A").to_string().to_lowercase();
+        let tr = parser()
+            .extract_time_range(&format!("last {}", today))
+            .unwrap();
+        let week_ago = (now.date_naive() - Duration::days(7))
+            .and_time(NaiveTime::from_hms_opt(0, 0, 0).unwrap())
+            .and_local_timezone(Local)
+            .unwrap()
+            .timestamp();
+        assert_eq!(tr.start, week_ago);
+        assert_eq!(tr.end - tr.start, 86399);
+    }
+
     // ---- Time extraction: edge cases ----
 
     #[test]
@@ -1791,6 +2233,214 @@ mod tests {
         assert_eq!(tr.start, yesterday_start);
     }
 
+    // ---- Data source extraction ----
+
+    fn data_source(query: &str) -> Option<DataSource> {
+        let p = parser();
+        p.extract_data_source(query, &p.extract_app(query))
+    }
+
+    #[test]
+    fn test_data_source_tasks_with_status() {
+        assert_eq!(
+            data_source("What tasks are pending?"),
+            Some(DataSource::Tasks {
+                status: Some("pending".to_string())
+            })
+        );
+        assert_eq!(
+            data_source("show my finished to-dos"),
+            Some(DataSource::Tasks {
+                status: Some("done".to_string())
+            })
+        );
+        assert_eq!(
+            data_source("list my reminders"),
+            Some(DataSource::Tasks { status: None })
+        );
+    }
+
+    #[test]
+    fn test_data_source_entities_by_type() {
+        assert_eq!(
+            data_source("Which URLs did I visit about kafka?"),
+            Some(DataSource::Entities {
+                entity_type: Some("url".to_string())
+            })
+        );
+        assert_eq!(
+            data_source("what projects came up this week"),
+            Some(DataSource::Entities {
+                entity_type: Some("project".to_string())
+            })
+        );
+        assert_eq!(
+            data_source("show extracted entities"),
+            Some(DataSource::Entities { entity_type: None })
+        );
+    }
+
+    #[test]
+    fn test_data_source_summaries_digest_and_apps() {
+        assert_eq!(
+            data_source("summarize yesterday"),
+            Some(DataSource::Summaries)
+        );
+        assert_eq!(data_source("how was my day"), Some(DataSource::Digest));
+        assert_eq!(
+            data_source("which apps did I use most"),
+            Some(DataSource::Apps)
+        );
+        assert_eq!(
+            data_source("my slack activity last week"),
+            Some(DataSource::Apps)
+        );
+        assert_eq!(data_source("find the kafka migration notes"), None);
+        assert_eq!(data_source("what did Alice say about the budget"), None);
+    }
+
+    #[test]
+    fn test_parse_drops_data_source_words_from_topics() {
+        let q = parser().parse("Which URLs did I visit about kafka?", &[]);
+        assert_eq!(q.topics, vec!["kafka"]);
+        assert!(q.data_source.is_some());
+
+        let q = parser().parse("find the kafka migration notes", &[]);
+        assert!(q.data_source.is_none());
+    }
+
+    // ---- Analytics extraction ----
+
+    #[test]
+    fn test_analytics_metrics() {
+        let p = parser();
+        for (query, metric) in [
+            (
+                "how long was I in VS Code this week?",
+                AnalyticsMetric::TimeSpent,
+            ),
+            (
+                "how much time did I spend in Slack",
+                AnalyticsMetric::TimeSpent,
+            ),
+            (
+                "hours spent on the kafka migration",
+                AnalyticsMetric::TimeSpent,
+            ),
+            ("how often did I open Zoom", AnalyticsMetric::Count),
+            ("how many captures today", AnalyticsMetric::Count),
+            ("when did I last use Excel?", AnalyticsMetric::FirstLastSeen),
+            ("how is my Slack time trending", AnalyticsMetric::Trend),
+            ("Chrome usage per day", AnalyticsMetric::Trend),
+        ] {
+            assert_eq!(p.extract_analytics(query), Some(metric), "{}", query);
+        }
+        assert_eq!(p.extract_analytics("how many meetings this week"), None);
+        assert_eq!(p.extract_analytics("find kafka notes"), None);
+    }
+
+    #[test]
+    fn test_parse_analytics_wins_over_app_list() {
+        let q = parser().parse("how much time did I spend in each app this week", &[]);
+        assert_eq!(q.analytics, Some(AnalyticsMetric::TimeSpent));
+        assert_eq!(q.data_source, None);
+        assert!(q.topics.is_empty(), "{:?}", q.topics);
+
+        let q = parser().parse("how long was the standup", &[]);
+        assert_eq!(q.analytics, Some(AnalyticsMetric::TimeSpent));
+        assert_eq!(q.topics, vec!["standup"]);
+    }
+
+    #[test]
+    fn test_time_calendar_dates() {
+        let p = parser();
+        let day = |y, m, d| {
+            let start = Local
+                .from_local_datetime(
+                    &NaiveDate::from_ymd_opt(y, m, d)
+                        .unwrap()
+                        .and_hms_opt(0, 0, 0)
+                        .unwrap(),
+                )
+                .single()
+                .unwrap()
+                .timestamp();
+            (start, start + 86_399)
+        };
+        let range = |q: &str| p.extract_time_range(q).map(|tr| (tr.start, tr.end));
+
+        assert_eq!(
+            range("timeline of the outage on Oct 3, 2025"),
+            Some(day(2025, 10, 3))
+        );
+        assert_eq!(range("what happened on 2025-10-03"), Some(day(2025, 10, 3)));
+        assert_eq!(
+            range("notes from September 30th 2024"),
+            Some(day(2024, 9, 30))
+        );
+        assert_eq!(range("review 2 items"), None);
+
+        // Without a year, the most recent such day.
+        let today = Local::now().date_naive();
+        let (start, _) = range("standup on Jan 1").unwrap();
+        let date = Local.timestamp_opt(start, 0).unwrap().date_naive();
+        assert_eq!((date.month(), date.day()), (1, 1));
+        assert!(date <= today && date > today - Duration::days(366));
+    }
+
+    #[test]
+    fn test_composition() {
+        let p = parser();
+        for (query, composition) in [
+            (
+                "give me a timeline of the outage on Oct 3",
+                Composition::Timeline,
+            ),
+            (
+                "what happened in chronological order",
+                Composition::Timeline,
+            ),
+            (
+                "compare what we decided about pricing",
+                Composition::Comparison,
+            ),
+            ("Slack vs Teams this week", Composition::Comparison),
+            (
+                "differences between the two proposals",
+                Composition::Comparison,
+            ),
+        ] {
+            assert_eq!(p.extract_composition(query), Some(composition), "{}", query);
+        }
+        assert_eq!(p.extract_composition("find pricing notes"), None);
+
+        let q = p.parse("give me a timeline of the outage on Oct 3rd", &[]);
+        assert_eq!(q.composition, Some(Composition::Timeline));
+        assert_eq!(q.topics, vec!["outage"]);
+
+        let q = p.parse(
+            "compare what we decided about pricing in the last two design reviews",
+            &[],
+        );
+        assert_eq!(q.composition, Some(Composition::Comparison));
+        assert_eq!(q.topics, vec!["pricing", "design", "reviews"]);
+
+        // Trends compare days themselves.
+        let q = p.parse("Slack time compared to yesterday", &[]);
+        assert_eq!(q.analytics, Some(AnalyticsMetric::Trend));
+        assert_eq!(q.composition, None);
+    }
+
+    #[test]
+    fn test_extract_apps_in_order() {
+        let p = parser();
+        assert_eq!(
+            p.extract_apps("slack vs Teams vs Slack"),
+            vec!["Slack", "Teams"]
+        );
+        assert!(p.extract_apps("pricing").is_empty());
+    }
+
     // ---- month_name_to_number helper ----
 
     #[test]
@@ -1808,5 +2458,8 @@ mod tests {
         assert_eq!(super::month_name_to_number("november"), Some(11));
         assert_eq!(super::month_name_to_number("december"), Some(12));
         assert_eq!(super::month_name_to_number("invalid"), None);
+        assert_eq!(super::month_name_to_number("oct"), Some(10));
+        assert_eq!(super::month_name_to_number("sept"), Some(9));
+        assert_eq!(super::month_name_to_number("ma"), None);
     }
 }
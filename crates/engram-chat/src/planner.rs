@@ -0,0 +1,842 @@
+//! Multi-step answers: comparisons and timelines.
+//!
+//! Queries the parser tags with a [`Composition`] are split into
+//! sub-queries: one per occasion, period, person, app or topic for a
+//! comparison, and one per time window for a timeline. The orchestrator
+//! runs each sub-query through capture search, and [`QueryPlan::compose`]
+//! turns the results into one answer with a [`ResponseSection`] per
+//! sub-query. Listed captures end with an inline `[chunk-id]` citation.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use chrono::{Local, TimeZone, Utc};
+use regex::Regex;
+
+use engram_storage::{FtsFilters, FtsSearch};
+
+use crate::parser::{is_stop_or_time_word, QueryParser};
+use crate::response::{describe_range, format_timestamp, source_ref, SearchResult};
+use crate::types::{
+    ChatResponse, Composition, ResponseSection, SectionItem, StructuredQuery, TimeRange,
+};
+
+/// Most sub-queries in one plan.
+const MAX_STEPS: usize = 8;
+
+/// Fewest results searched for per sub-query.
+const MIN_STEP_RESULTS: usize = 3;
+
+/// Timeline window lengths in seconds, shortest first; the shortest that
+/// covers the range in at most [`MAX_STEPS`] windows is used.
+const TIMELINE_WINDOWS: &[i64] = &[3600, 3 * 3600, 6 * 3600, 86_400, 7 * 86_400];
+
+/// Mentions of an occasion ("design review") further apart than this
+/// belong to different occasions.
+const OCCASION_GAP_SECS: i64 = 2 * 3600;
+
+/// How long after its last mention an occasion is searched.
+const OCCASION_TAIL_SECS: i64 = 3600;
+
+/// Captures scanned for mentions of an occasion.
+const OCCASION_SCAN_LIMIT: u64 = 500;
+
+/// Characters of capture text kept per section item.
+const SNIPPET_CHARS: usize = 160;
+
+/// Key terms listed per comparison side, and in common.
+const KEY_TERMS: usize = 5;
+
+/// Confidence of a composed answer that found captures.
+const COMPOSED_CONFIDENCE: f32 = 0.8;
+
+/// "last two design reviews": a count and what is counted.
+static LAST_N_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(?:last|past|previous)\s+(two|three|four|five|[2-5])\s+([a-z][\w-]*(?:\s+[a-z][\w-]*){0,2})",
+    )
+    .expect("Invalid occasion regex")
+});
+
+/// Words between the sides of a comparison ("Slack vs Teams").
+static SIDES_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:vs|versus|compared\s+(?:to|with)|and|or)\b\.?")
+        .expect("Invalid comparison regex")
+});
+
+/// One step of a [`QueryPlan`]: a labelled query with its own filters.
+#[derive(Debug, Clone)]
+pub struct SubQuery {
+    /// Heading of the step's section, e.g. "Design review 2026-10-02 14:00".
+    pub label: String,
+    /// What the step searches for.
+    pub query: StructuredQuery,
+}
+
+/// How a comparison or timeline question is answered.
+#[derive(Debug, Clone)]
+pub struct QueryPlan {
+    pub composition: Composition,
+    /// What is compared or traced, e.g. "pricing"; may be empty.
+    pub subject: String,
+    /// The range the whole plan covers, if any.
+    pub time_range: Option<TimeRange>,
+    /// Sub-queries, in section order.
+    pub steps: Vec<SubQuery>,
+}
+
+/// Splits comparison and timeline questions into sub-queries.
+pub struct QueryPlanner<'a> {
+    parser: &'a QueryParser,
+    fts: &'a FtsSearch,
+}
+
+impl<'a> QueryPlanner<'a> {
+    pub fn new(parser: &'a QueryParser, fts: &'a FtsSearch) -> Self {
+        Self { parser, fts }
+    }
+
+    /// Plan `query`. `explicit` is the time range the query named, if any.
+    ///
+    /// `None` when the query is neither a comparison nor a timeline, or a
+    /// comparison without two sides to compare.
+    pub fn plan(&self, query: &StructuredQuery, explicit: Option<&TimeRange>) -> Option<QueryPlan> {
+        match query.composition? {
+            Composition::Comparison => self.comparison(query, explicit),
+            Composition::Timeline => timeline(query, explicit),
+        }
+    }
+
+    /// Sides, tried in order: the last N occasions or periods ("last two
+    /// design reviews", "last three weeks"), two time ranges ("this week
+    /// vs last week"), people, apps, then topics ("Postgres vs MySQL").
+    fn comparison(
+        &self,
+        query: &StructuredQuery,
+        explicit: Option<&TimeRange>,
+    ) -> Option<QueryPlan> {
+        let plan = |subject: String, time_range: Option<TimeRange>, steps: Vec<SubQuery>| {
+            (steps.len() >= 2).then_some(QueryPlan {
+                composition: Composition::Comparison,
+                subject,
+                time_range,
+                steps,
+            })
+        };
+        let raw = query.raw_query.as_str();
+        let topic_subject = query.topics.join(" ");
+
+        if let Some(caps) = LAST_N_RE.captures(raw) {
+            let count = count_word(&caps[1])?;
+            let what: Vec<String> = caps[2]
+                .split_whitespace()
+                .map(str::to_lowercase)
+                .take_while(|w| !is_stop_or_time_word(w) || period_secs(w).is_some())
+                .collect();
+            if !what.is_empty() {
+                let rest: Vec<String> = query
+                    .topics
+                    .iter()
+                    .filter(|t| !what.contains(t))
+                    .cloned()
+                    .collect();
+                let windows = match what.as_slice() {
+                    [unit] if period_secs(unit).is_some() => periods(unit, count),
+                    _ => self.occasions(&what, query, explicit, count),
+                };
+                let steps = windows
+                    .into_iter()
+                    .map(|(label, range)| SubQuery {
+                        label,
+                        query: StructuredQuery {
+                            topics: rest.clone(),
+                            time_range: Some(range),
+                            ..query.clone()
+                        },
+                    })
+                    .collect();
+                let subject = if rest.is_empty() {
+                    what.join(" ")
+                } else {
+                    rest.join(" ")
+                };
+                if let Some(plan) = plan(subject, None, steps) {
+                    return Some(plan);
+                }
+            }
+        }
+
+        for m in SIDES_RE.find_iter(raw) {
+            let sides = (
+                self.parser.extract_time_range(&raw[..m.start()]),
+                self.parser.extract_time_range(&raw[m.end()..]),
+            );
+            if let (Some(a), Some(b)) = sides {
+                if (a.start, a.end) == (b.start, b.end) {
+                    continue;
+                }
+                let steps = [a, b]
+                    .into_iter()
+                    .map(|range| SubQuery {
+                        label: describe_range(&range).trim_start().to_string(),
+                        query: StructuredQuery {
+                            time_range: Some(range),
+                            ..query.clone()
+                        },
+                    })
+                    .collect();
+                return plan(topic_subject, None, steps);
+            }
+        }
+
+        if query.people.len() >= 2 {
+            // In order of mention, not of extraction.
+            let lower = raw.to_lowercase();
+            let mut people = query.people.clone();
+            people.sort_by_key(|p| lower.find(&p.to_lowercase()).unwrap_or(usize::MAX));
+            let steps = people
+                .iter()
+                .take(MAX_STEPS)
+                .map(|person| SubQuery {
+                    label: person.clone(),
+                    query: StructuredQuery {
+                        people: vec![person.clone()],
+                        ..query.clone()
+                    },
+                })
+                .collect();
+            return plan(topic_subject, query.time_range.clone(), steps);
+        }
+
+        let apps = self.parser.extract_apps(raw);
+        if apps.len() >= 2 {
+            let steps = apps
+                .into_iter()
+                .take(MAX_STEPS)
+                .map(|app| SubQuery {
+                    label: app.clone(),
+                    query: StructuredQuery {
+                        app_filter: Some(app),
+                        ..query.clone()
+                    },
+                })
+                .collect();
+            return plan(topic_subject, query.time_range.clone(), steps);
+        }
+
+        for m in SIDES_RE.find_iter(raw) {
+            let side = |text: &str| -> Vec<String> {
+                self.parser
+                    .extract_topics(text, &query.people, &query.app_filter)
+                    .into_iter()
+                    .filter(|t| query.topics.contains(t))
+                    .collect()
+            };
+            let (left, right) = (side(&raw[..m.start()]), side(&raw[m.end()..]));
+            if left.is_empty() || right.is_empty() || left.iter().any(|t| right.contains(t)) {
+                continue;
+            }
+            let steps = [left, right]
+                .into_iter()
+                .map(|topics| SubQuery {
+                    label: topics.join(" "),
+                    query: StructuredQuery {
+                        topics,
+                        ..query.clone()
+                    },
+                })
+                .collect();
+            return plan(String::new(), query.time_range.clone(), steps);
+        }
+        None
+    }
+
+    /// Windows of the last `count` occasions mentioning `what`, oldest
+    /// first. Mentions within [`OCCASION_GAP_SECS`] of each other are one
+    /// occasion, searched until [`OCCASION_TAIL_SECS`] after its last
+    /// mention.
+    fn occasions(
+        &self,
+        what: &[String],
+        query: &StructuredQuery,
+        explicit: Option<&TimeRange>,
+        count: usize,
+    ) -> Vec<(String, TimeRange)> {
+        let to_utc = |epoch: i64| Utc.timestamp_opt(epoch, 0).single();
+        let filters = FtsFilters {
+            app_name: query.app_filter.clone(),
+            start: explicit.and_then(|tr| to_utc(tr.start)),
+            end: explicit.and_then(|tr| to_utc(tr.end)),
+            ..FtsFilters::default()
+        };
+        // The index does no stemming: "design reviews" is searched as
+        // written and as "design review".
+        let singular: Vec<String> = what.iter().map(|w| singular(w)).collect();
+        let mut phrases = vec![singular.join(" ")];
+        if singular != what {
+            phrases.push(what.join(" "));
+        }
+
+        let mut mentions: Vec<i64> = Vec::new();
+        for phrase in &phrases {
+            match self
+                .fts
+                .search_filtered(phrase, &filters, OCCASION_SCAN_LIMIT)
+            {
+                Ok(results) => mentions.extend(results.iter().map(|r| r.timestamp.timestamp())),
+                Err(e) => tracing::warn!("Searching for occasions failed: {}", e),
+            }
+        }
+        mentions.sort_unstable();
+        mentions.dedup();
+
+        let mut occasions: Vec<(i64, i64)> = Vec::new();
+        for at in mentions {
+            match occasions.last_mut() {
+                Some((_, last)) if at - *last <= OCCASION_GAP_SECS => *last = at,
+                _ => occasions.push((at, at)),
+            }
+        }
+        let skip = occasions.len().saturating_sub(count);
+        let name = capitalize(&singular.join(" "));
+        occasions
+            .into_iter()
+            .skip(skip)
+            .map(|(first, last)| {
+                (
+                    format!("{} {}", name, format_timestamp(first)),
+                    TimeRange {
+                        start: first,
+                        end: last + OCCASION_TAIL_SECS,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+impl QueryPlan {
+    /// Results to search for per step so the plan lists about `limit`.
+    pub fn step_limit(&self, limit: usize) -> usize {
+        limit
+            .div_ceil(self.steps.len().max(1))
+            .max(MIN_STEP_RESULTS)
+    }
+
+    /// Compose the answer from each step's results, in step order.
+    pub fn compose(&self, results: &[Vec<SearchResult>]) -> ChatResponse {
+        let mut sources = Vec::new();
+        let mut cited = HashSet::new();
+        let mut sections = Vec::new();
+        let mut found: Vec<Vec<&SearchResult>> = Vec::new();
+        for (step, step_results) in self.steps.iter().zip(results) {
+            let mut step_results: Vec<&SearchResult> = step_results.iter().collect();
+            step_results.sort_by_key(|r| r.timestamp);
+            for r in &step_results {
+                if cited.insert(r.chunk_id) {
+                    sources.push(source_ref(r));
+                }
+            }
+            sections.push(ResponseSection {
+                kind: self.composition,
+                heading: step.label.clone(),
+                time_range: step.query.time_range.clone(),
+                key_terms: vec![],
+                items: step_results.iter().map(|r| section_item(r)).collect(),
+            });
+            found.push(step_results);
+        }
+
+        let (lines, suggestions) = match self.composition {
+            Composition::Comparison => self.comparison_answer(&mut sections, &found),
+            Composition::Timeline => {
+                sections.retain(|s| !s.items.is_empty());
+                self.timeline_answer(&found)
+            }
+        };
+        let confidence = if sources.is_empty() {
+            0.3
+        } else {
+            COMPOSED_CONFIDENCE
+        };
+        ChatResponse {
+            answer: lines.join("\n"),
+            sources,
+            confidence,
+            suggestions,
+            table: None,
+            sections,
+        }
+    }
+
+    fn comparison_answer(
+        &self,
+        sections: &mut [ResponseSection],
+        found: &[Vec<&SearchResult>],
+    ) -> (Vec<String>, Vec<String>) {
+        let labels: Vec<&str> = self.steps.iter().map(|s| s.label.as_str()).collect();
+        let mut lines = vec![if self.subject.is_empty() {
+            format!("Comparing {}:", labels.join(" vs "))
+        } else {
+            format!("Comparing {}: {}", self.subject, labels.join(" vs "))
+        }];
+
+        // Terms every side shares with the question say nothing about
+        // how the sides differ.
+        let mut skip: HashSet<String> = self
+            .steps
+            .iter()
+            .flat_map(|s| s.query.topics.iter().chain(&s.query.people))
+            .map(|t| t.to_lowercase())
+            .collect();
+        skip.extend(self.subject.split_whitespace().map(str::to_lowercase));
+        let counts: Vec<HashMap<String, usize>> = found
+            .iter()
+            .map(|results| term_counts(results, &skip))
+            .collect();
+
+        for (i, (section, results)) in sections.iter_mut().zip(found).enumerate() {
+            lines.push(String::new());
+            if results.is_empty() {
+                lines.push(format!("{}: nothing found.", section.heading));
+                continue;
+            }
+            lines.push(format!(
+                "{} ({} {}):",
+                section.heading,
+                results.len(),
+                plural(results.len(), "capture", "captures")
+            ));
+            lines.extend(results.iter().map(|r| bullet(r, false)));
+            let only_here = counts[i].iter().filter(|(term, _)| {
+                counts
+                    .iter()
+                    .enumerate()
+                    .all(|(j, other)| j == i || !other.contains_key(*term))
+            });
+            section.key_terms = top_terms(only_here.map(|(t, n)| (t, *n)));
+            if !section.key_terms.is_empty() {
+                lines.push(format!("Only here: {}", section.key_terms.join(", ")));
+            }
+        }
+
+        let sides_found = found.iter().filter(|r| !r.is_empty()).count();
+        if sides_found == 0 {
+            lines.push(format!(
+                "I couldn't find anything{} to compare.",
+                about(&self.subject)
+            ));
+        } else if sides_found == found.len() {
+            let shared = counts[0]
+                .keys()
+                .filter(|term| counts[1..].iter().all(|c| c.contains_key(*term)))
+                .map(|term| (term, counts.iter().map(|c| c[term]).sum()));
+            let common = top_terms(shared);
+            if !common.is_empty() {
+                lines.push(String::new());
+                lines.push(format!("In common: {}", common.join(", ")));
+            }
+        }
+
+        let mut suggestions = Vec::new();
+        if !self.subject.is_empty() {
+            suggestions.push(format!("Give me a timeline of {}", self.subject));
+        }
+        suggestions.push(format!("Tell me more about {}", labels[0]));
+        (lines, suggestions)
+    }
+
+    fn timeline_answer(&self, found: &[Vec<&SearchResult>]) -> (Vec<String>, Vec<String>) {
+        let range = self
+            .time_range
+            .as_ref()
+            .map(describe_range)
+            .unwrap_or_default();
+        let results: Vec<&SearchResult> = found.iter().flatten().copied().collect();
+        let mut lines = Vec::new();
+        if results.is_empty() {
+            lines.push(format!(
+                "I couldn't find anything{}{} to put on a timeline.",
+                about(&self.subject),
+                range
+            ));
+        } else {
+            let subject = if self.subject.is_empty() {
+                "your activity"
+            } else {
+                &self.subject
+            };
+            lines.push(format!(
+                "Timeline of {}{} ({} {}):",
+                subject,
+                range,
+                results.len(),
+                plural(results.len(), "capture", "captures")
+            ));
+            lines.extend(results.iter().map(|r| bullet(r, true)));
+        }
+
+        let mut suggestions = Vec::new();
+        if !self.subject.is_empty() {
+            suggestions.push(format!("Tell me more about {}", self.subject));
+            suggestions.push(format!("Compare {} this week vs last week", self.subject));
+        }
+        suggestions.push("What happened yesterday?".to_string());
+        (lines, suggestions)
+    }
+}
+
+/// One window per [`TIMELINE_WINDOWS`] length across the query's range,
+/// aligned to local hours or days.
+fn timeline(query: &StructuredQuery, explicit: Option<&TimeRange>) -> Option<QueryPlan> {
+    let range = explicit.or(query.time_range.as_ref())?.clone();
+    let offset = Local
+        .timestamp_opt(range.start, 0)
+        .single()
+        .map_or(0, |dt| i64::from(dt.offset().local_minus_utc()));
+    let aligned = |len: i64| range.start - (range.start + offset).rem_euclid(len);
+    let windows = |len: i64| (range.end - aligned(len)) / len + 1;
+    let len = TIMELINE_WINDOWS
+        .iter()
+        .copied()
+        .find(|&len| windows(len) <= MAX_STEPS as i64)
+        .unwrap_or_else(|| (range.end - range.start).max(1) / MAX_STEPS as i64 + 1);
+
+    let mut steps = Vec::new();
+    let mut start = aligned(len);
+    while start <= range.end {
+        let window = TimeRange {
+            start: start.max(range.start),
+            end: (start + len - 1).min(range.end),
+        };
+        steps.push(SubQuery {
+            label: window_label(window.start, len),
+            query: StructuredQuery {
+                time_range: Some(window),
+                ..query.clone()
+            },
+        });
+        start += len;
+    }
+    Some(QueryPlan {
+        composition: Composition::Timeline,
+        subject: query.topics.join(" "),
+        time_range: Some(range),
+        steps,
+    })
+}
+
+/// The last `count` days, weeks or months, oldest first.
+fn periods(unit: &str, count: usize) -> Vec<(String, TimeRange)> {
+    let Some(len) = period_secs(unit) else {
+        return Vec::new();
+    };
+    let now = Local::now().timestamp();
+    (0..count as i64)
+        .rev()
+        .map(|i| {
+            let range = TimeRange {
+                start: now - (i + 1) * len,
+                end: now - i * len,
+            };
+            (describe_range(&range).trim_start().to_string(), range)
+        })
+        .collect()
+}
+
+fn period_secs(unit: &str) -> Option<i64> {
+    match unit {
+        "day" | "days" => Some(86_400),
+        "week" | "weeks" => Some(7 * 86_400),
+        "month" | "months" => Some(30 * 86_400),
+        _ => None,
+    }
+}
+
+fn count_word(word: &str) -> Option<usize> {
+    match word.to_lowercase().as_str() {
+        "two" => Some(2),
+        "three" => Some(3),
+        "four" => Some(4),
+        "five" => Some(5),
+        digits => digits.parse().ok(),
+    }
+}
+
+/// "reviews" → "review"; words ending in "ss" are left alone.
+fn singular(word: &str) -> String {
+    match word.strip_suffix('s') {
+        Some(stem) if stem.len() >= 3 && !stem.ends_with('s') => stem.to_string(),
+        _ => word.to_string(),
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn window_label(start: i64, len: i64) -> String {
+    let format = if len < 86_400 {
+        "%Y-%m-%d %H:%M"
+    } else if len == 86_400 {
+        "%Y-%m-%d"
+    } else {
+        "week of %Y-%m-%d"
+    };
+    Local
+        .timestamp_opt(start, 0)
+        .single()
+        .map(|dt| dt.format(format).to_string())
+        .unwrap_or_else(|| start.to_string())
+}
+
+fn section_item(result: &SearchResult) -> SectionItem {
+    SectionItem {
+        chunk_id: result.chunk_id,
+        timestamp: format_timestamp(result.timestamp),
+        source_app: result.source_app.clone(),
+        text: snippet(&result.content),
+    }
+}
+
+/// "- 14:05 · Slack: text [chunk-id]", with the date when `dated`.
+fn bullet(result: &SearchResult, dated: bool) -> String {
+    let at = format_timestamp(result.timestamp);
+    let at = if dated {
+        at.as_str()
+    } else {
+        at.split_once(' ').map_or(at.as_str(), |(_, time)| time)
+    };
+    format!(
+        "- {} · {}: {} [{}]",
+        at,
+        result.source_app,
+        snippet(&result.content),
+        result.chunk_id
+    )
+}
+
+/// Capture text on one line, cut to [`SNIPPET_CHARS`].
+fn snippet(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= SNIPPET_CHARS {
+        return text;
+    }
+    let cut: String = text.chars().take(SNIPPET_CHARS).collect();
+    format!("{}…", cut.trim_end())
+}
+
+/// Occurrences of each word of four or more letters in `results`, other
+/// than stop words, numbers and `skip`.
+fn term_counts(results: &[&SearchResult], skip: &HashSet<String>) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for r in results {
+        for word in r.content.split(|c: char| !c.is_alphanumeric()) {
+            let word = word.to_lowercase();
+            if word.chars().count() < 4
+                || word.chars().all(|c| c.is_ascii_digit())
+                || is_stop_or_time_word(&word)
+                || skip.contains(&word)
+            {
+                continue;
+            }
+            *counts.entry(word).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// The [`KEY_TERMS`] most frequent terms, ties alphabetical.
+fn top_terms<'t>(terms: impl Iterator<Item = (&'t String, usize)>) -> Vec<String> {
+    let mut terms: Vec<(&String, usize)> = terms.collect();
+    terms.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    terms
+        .into_iter()
+        .take(KEY_TERMS)
+        .map(|(t, _)| t.clone())
+        .collect()
+}
+
+fn about(subject: &str) -> String {
+    if subject.is_empty() {
+        String::new()
+    } else {
+        format!(" about {}", subject)
+    }
+}
+
+fn plural<'s>(n: usize, one: &'s str, many: &'s str) -> &'s str {
+    if n == 1 {
+        one
+    } else {
+        many
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use uuid::Uuid;
+
+    use crate::types::RetrievalPath;
+
+    fn plan(raw: &str, known_entities: &[String]) -> Option<QueryPlan> {
+        let parser = QueryParser::new(7);
+        let db = Arc::new(engram_storage::Database::in_memory().unwrap());
+        let fts = FtsSearch::new(db);
+        let query = parser.parse(raw, known_entities);
+        let explicit = parser.extract_time_range(raw);
+        QueryPlanner::new(&parser, &fts).plan(&query, explicit.as_ref())
+    }
+
+    fn result(timestamp: i64, app: &str, content: &str) -> SearchResult {
+        SearchResult {
+            chunk_id: Uuid::new_v4(),
+            content: content.to_string(),
+            timestamp,
+            source_app: app.to_string(),
+            relevance_score: 0.5,
+            person: None,
+            retrieval: RetrievalPath::Keyword,
+        }
+    }
+
+    #[test]
+    fn test_comparison_sides() {
+        let people = vec!["Alice".to_string(), "Bob".to_string()];
+        let p = plan("compare what Alice and Bob said about pricing", &people).unwrap();
+        let labels: Vec<&str> = p.steps.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, vec!["Alice", "Bob"]);
+        assert_eq!(p.steps[1].query.people, vec!["Bob"]);
+        assert_eq!(p.subject, "pricing");
+
+        let p = plan("Slack vs Teams for the launch", &[]).unwrap();
+        assert_eq!(p.steps[0].query.app_filter.as_deref(), Some("Slack"));
+        assert_eq!(p.steps[1].query.app_filter.as_deref(), Some("Teams"));
+
+        let p = plan("compare postgres vs mysql", &[]).unwrap();
+        assert_eq!(p.steps[0].query.topics, vec!["postgres"]);
+        assert_eq!(p.steps[1].query.topics, vec!["mysql"]);
+
+        let p = plan("budget yesterday compared to today", &[]).unwrap();
+        let ranges: Vec<_> = p
+            .steps
+            .iter()
+            .map(|s| s.query.time_range.clone().unwrap())
+            .collect();
+        assert!(ranges[0].end <= ranges[1].start);
+        assert_eq!(p.steps[0].query.topics, vec!["budget"]);
+
+        let p = plan("compare my last three weeks", &[]).unwrap();
+        assert_eq!(p.steps.len(), 3);
+        let ranges: Vec<_> = p
+            .steps
+            .iter()
+            .map(|s| s.query.time_range.clone().unwrap())
+            .collect();
+        assert_eq!(ranges[0].end, ranges[1].start);
+        assert_eq!(ranges[1].end - ranges[1].start, 7 * 86_400);
+
+        // Nothing to compare, and not a multi-step question.
+        assert!(plan("compare pricing", &[]).is_none());
+        assert!(plan("find pricing notes", &[]).is_none());
+    }
+
+    #[test]
+    fn test_timeline_splits_day_into_windows() {
+        let p = plan("give me a timeline of the outage on 2025-10-03", &[]).unwrap();
+        assert_eq!(p.composition, Composition::Timeline);
+        assert_eq!(p.subject, "outage");
+        assert_eq!(p.steps.len(), 8);
+        assert_eq!(p.steps[0].label, "2025-10-03 00:00");
+        assert_eq!(p.steps[1].label, "2025-10-03 03:00");
+        let range = p.time_range.clone().unwrap();
+        let first = p.steps[0].query.time_range.clone().unwrap();
+        let last = p.steps[7].query.time_range.clone().unwrap();
+        assert_eq!((first.start, last.end), (range.start, range.end));
+        assert_eq!(p.step_limit(10), 3);
+
+        // The default week is split by day.
+        let p = plan("timeline of the migration", &[]).unwrap();
+        assert!(p.steps.len() <= MAX_STEPS, "{}", p.steps.len());
+        assert!(p.steps.iter().all(|s| s.label.len() == "2025-10-03".len()));
+    }
+
+    #[test]
+    fn test_compose_comparison_lists_sides_and_key_terms() {
+        let p = plan("compare postgres vs mysql", &[]).unwrap();
+        let postgres = vec![
+            result(
+                200,
+                "Slack",
+                "postgres replication works, replication lag is low",
+            ),
+            result(100, "Slack", "postgres replication costs more"),
+        ];
+        let mysql = vec![result(300, "Chrome", "mysql replication is cheaper")];
+        let response = p.compose(&[postgres.clone(), mysql]);
+
+        assert_eq!(response.sections.len(), 2);
+        let items = &response.sections[0].items;
+        assert_eq!(items[0].chunk_id, postgres[1].chunk_id, "oldest first");
+        assert_eq!(response.sections[0].key_terms[0], "costs");
+        assert!(response.sections[1]
+            .key_terms
+            .contains(&"cheaper".to_string()));
+        assert!(response.answer.starts_with("Comparing postgres vs mysql:"));
+        assert!(response.answer.contains("In common: replication"));
+        assert!(response
+            .answer
+            .contains(&format!("[{}]", postgres[0].chunk_id)));
+        assert_eq!(response.sources.len(), 3);
+        assert_eq!(response.confidence, COMPOSED_CONFIDENCE);
+
+        let empty = p.compose(&[vec![], vec![]]);
+        assert!(empty.answer.contains("mysql: nothing found."));
+        assert!(empty
+            .answer
+            .contains("I couldn't find anything to compare."));
+        assert_eq!(empty.sections.len(), 2);
+    }
+
+    #[test]
+    fn test_compose_timeline_is_chronological() {
+        let p = plan("give me a timeline of the outage on 2025-10-03", &[]).unwrap();
+        let start = p.time_range.clone().unwrap().start;
+        let mut results = vec![Vec::new(); p.steps.len()];
+        results[4] = vec![
+            result(start + 13 * 3600, "Slack", "outage resolved"),
+            result(start + 12 * 3600, "Slack", "outage   started\nin eu-west"),
+        ];
+        results[0] = vec![result(start + 60, "Terminal", "deploy before the outage")];
+        let response = p.compose(&results);
+
+        assert_eq!(response.sections.len(), 2, "empty windows are dropped");
+        let lines: Vec<&str> = response.answer.lines().collect();
+        assert!(lines[0].starts_with("Timeline of outage on 2025-10-03 (3 captures)"));
+        assert!(lines[1].contains("Terminal: deploy before the outage"));
+        assert!(lines[2].contains("12:00 · Slack: outage started in eu-west"));
+        assert!(lines[3].contains("outage resolved"));
+
+        let empty = p.compose(&vec![Vec::new(); p.steps.len()]);
+        assert!(empty
+            .answer
+            .starts_with("I couldn't find anything about outage on 2025-10-03"));
+        assert!(empty.sections.is_empty());
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(singular("reviews"), "review");
+        assert_eq!(singular("class"), "class");
+        assert_eq!(singular("bus"), "bus");
+        assert_eq!(count_word("Three"), Some(3));
+        assert_eq!(snippet(&"word ".repeat(100)).chars().count(), SNIPPET_CHARS);
+    }
+}
@@ -6,7 +6,7 @@
 use chrono::{DateTime, Local, TimeZone};
 use uuid::Uuid;
 
-use crate::types::{ChatResponse, SourceRef, StructuredQuery};
+use crate::types::{ChatResponse, RetrievalPath, SourceRef, StructuredQuery, TimeRange};
 
 // =============================================================================
 // SearchResult
@@ -27,6 +27,8 @@ pub struct SearchResult {
     pub relevance_score: f32,
     /// Optional person associated with the content.
     pub person: Option<String>,
+    /// Which retrieval path found the chunk.
+    pub retrieval: RetrievalPath,
 }
 
 // =============================================================================
@@ -71,15 +73,7 @@ impl ResponseGenerator {
             answer
         };
 
-        let sources = limited
-            .iter()
-            .map(|r| SourceRef {
-                chunk_id: r.chunk_id,
-                timestamp: format_timestamp(r.timestamp),
-                source_app: r.source_app.clone(),
-                relevance_score: r.relevance_score,
-            })
-            .collect();
+        let sources = limited.iter().map(source_ref).collect();
 
         let suggestions = self.generate_suggestions(query, limited);
 
@@ -88,6 +82,8 @@ impl ResponseGenerator {
             sources,
             confidence: avg_confidence,
             suggestions,
+            table: None,
+            sections: vec![],
         }
     }
 
@@ -111,6 +107,8 @@ impl ResponseGenerator {
             sources: vec![],
             confidence: 0.9,
             suggestions,
+            table: None,
+            sections: vec![],
         }
     }
 
@@ -169,6 +167,8 @@ impl ResponseGenerator {
                 "Try different keywords".to_string(),
                 "Search a wider time range".to_string(),
             ],
+            table: None,
+            sections: vec![],
         }
     }
 
@@ -210,7 +210,18 @@ impl ResponseGenerator {
 // Helpers
 // =============================================================================
 
-fn format_timestamp(epoch_secs: i64) -> String {
+/// Reference a search result as a response source.
+pub(crate) fn source_ref(result: &SearchResult) -> SourceRef {
+    SourceRef {
+        chunk_id: result.chunk_id,
+        timestamp: format_timestamp(result.timestamp),
+        source_app: result.source_app.clone(),
+        relevance_score: result.relevance_score,
+        retrieval: result.retrieval,
+    }
+}
+
+pub(crate) fn format_timestamp(epoch_secs: i64) -> String {
     Local
         .timestamp_opt(epoch_secs, 0)
         .single()
@@ -218,6 +229,23 @@ fn format_timestamp(epoch_secs: i64) -> String {
         .unwrap_or_else(|| epoch_secs.to_string())
 }
 
+/// " on <day>" or " from <start day> to <end day>", in local time.
+pub(crate) fn describe_range(range: &TimeRange) -> String {
+    let day = |epoch: i64| {
+        Local
+            .timestamp_opt(epoch, 0)
+            .single()
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| epoch.to_string())
+    };
+    let (start, end) = (day(range.start), day(range.end));
+    if start == end {
+        format!(" on {}", start)
+    } else {
+        format!(" from {} to {}", start, end)
+    }
+}
+
 fn details_suffix(count: usize) -> String {
     if count == 1 {
         "That's 1 occurrence.".to_string()
@@ -247,6 +275,9 @@ mod tests {
             time_range: None,
             content_type: None,
             app_filter: None,
+            data_source: None,
+            analytics: None,
+            composition: None,
             raw_query: "find deployment info".to_string(),
         }
     }
@@ -259,6 +290,7 @@ mod tests {
             source_app: "Teams".to_string(),
             relevance_score: score,
             person: None,
+            retrieval: RetrievalPath::Keyword,
         }
     }
 
@@ -270,6 +302,7 @@ mod tests {
             source_app: "Slack".to_string(),
             relevance_score: 0.85,
             person: Some(person.to_string()),
+            retrieval: RetrievalPath::Keyword,
         }
     }
 
@@ -373,6 +406,9 @@ mod tests {
             }),
             content_type: None,
             app_filter: None,
+            data_source: None,
+            analytics: None,
+            composition: None,
             raw_query: "how many meetings this week".to_string(),
         };
         let resp = gen().compose_analytics(&q, 5, "you had 5 meetings");
@@ -0,0 +1,400 @@
+//! Hybrid keyword + semantic retrieval for chat search.
+//!
+//! Keyword (FTS5) and semantic (vector) candidates are fused with reciprocal
+//! rank fusion, then reranked by similarity to the question and by how many
+//! of the question's topic terms each candidate mentions. Both paths apply
+//! the same parsed filters; when the global nearest neighbours mostly fail
+//! them, the filtered captures are scored from their stored embeddings.
+//! Either path failing leaves the other's results. Feedback on past answers adds a learned per-term boost and tunes
+//! the semantic weight (see [`RankingFeedback`]).
+
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use engram_core::types::Embedding;
+use engram_storage::{FtsFilters, FtsResult, FtsSearch};
+use engram_vector::SearchEngine;
+
+use crate::response::SearchResult;
+use crate::types::RetrievalPath;
+
+/// Reciprocal rank fusion constant; larger values flatten the rank curve.
+const RRF_K: f32 = 60.0;
+
+/// Candidates kept from each path per requested result.
+const CANDIDATES_PER_RESULT: usize = 3;
+
+/// Vector hits fetched per semantic candidate, to leave room for filters.
+const VECTOR_OVERFETCH: usize = 3;
+
+/// Vector hits below this cosine similarity are not considered matches.
+const MIN_SEMANTIC_SIMILARITY: f32 = 0.35;
+
+/// Most filtered captures scored from their stored embeddings when the
+/// nearest neighbours leave too few past the filters.
+const FILTERED_SCAN_LIMIT: u64 = 10_000;
+
+/// Default weight of semantic similarity in the rerank score; term
+/// coverage gets the rest.
+pub(crate) const SEMANTIC_WEIGHT: f32 = 0.6;
+
+/// Weight of a source's learned feedback boost in the rerank score.
+const FEEDBACK_WEIGHT: f32 = 0.3;
+
+/// A fused candidate awaiting reranking.
+struct Candidate {
+    result: FtsResult,
+    path: RetrievalPath,
+    fused: f32,
+    similarity: Option<f32>,
+}
+
+/// What feedback on chat answers has taught retrieval for one user.
+#[derive(Debug, Clone)]
+pub struct RankingFeedback {
+    /// Weight of semantic similarity in the rerank score; term coverage
+    /// gets the rest.
+    pub semantic_weight: f32,
+    /// Boost (positive) or penalty (negative), in -1..1, of each source
+    /// under each lowercased query term.
+    pub boosts: HashMap<String, HashMap<Uuid, f32>>,
+}
+
+impl Default for RankingFeedback {
+    fn default() -> Self {
+        Self {
+            semantic_weight: SEMANTIC_WEIGHT,
+            boosts: HashMap::new(),
+        }
+    }
+}
+
+impl RankingFeedback {
+    /// Mean boost of source `id` over `terms` (lowercased); 0.0 when there
+    /// are no terms.
+    pub fn boost(&self, id: Uuid, terms: &[String]) -> f32 {
+        if terms.is_empty() {
+            return 0.0;
+        }
+        let total: f32 = terms
+            .iter()
+            .filter_map(|t| self.boosts.get(t).and_then(|b| b.get(&id)))
+            .sum();
+        total / terms.len() as f32
+    }
+}
+
+/// Retrieves captures for a chat question from full-text and vector search.
+pub struct HybridRetriever<'a> {
+    fts: &'a FtsSearch,
+    engine: &'a SearchEngine,
+    feedback: RankingFeedback,
+}
+
+impl<'a> HybridRetriever<'a> {
+    pub fn new(fts: &'a FtsSearch, engine: &'a SearchEngine) -> Self {
+        Self {
+            fts,
+            engine,
+            feedback: RankingFeedback::default(),
+        }
+    }
+
+    /// Rerank with what `feedback` learned instead of the defaults.
+    pub fn with_feedback(mut self, feedback: RankingFeedback) -> Self {
+        self.feedback = feedback;
+        self
+    }
+
+    /// Retrieve up to `limit` captures passing `filters`, best first.
+    ///
+    /// `question` is embedded for the semantic path, `keywords` is the
+    /// full-text query (empty to list captures by filters alone) and
+    /// `topics` are the terms used for coverage and feedback boosts when
+    /// reranking.
+    pub async fn retrieve(
+        &self,
+        question: &str,
+        keywords: &str,
+        topics: &[String],
+        filters: &FtsFilters,
+        limit: usize,
+    ) -> Vec<SearchResult> {
+        let pool = limit.max(1) * CANDIDATES_PER_RESULT;
+
+        let keyword = match self.fts.search_filtered(keywords, filters, pool as u64) {
+            Ok(results) => results,
+            Err(e) => {
+                tracing::warn!("FTS search failed: {}", e);
+                Vec::new()
+            }
+        };
+
+        let query_vec = match self.engine.embed_query(question).await {
+            Ok(vec) => Some(Embedding(vec)),
+            Err(e) => {
+                tracing::warn!("Query embedding failed, using keyword search only: {}", e);
+                None
+            }
+        };
+        let semantic = match query_vec {
+            Some(ref vec) => self.semantic_candidates(vec, filters, pool),
+            None => Vec::new(),
+        };
+
+        let mut candidates = fuse(keyword, semantic);
+        candidates.truncate(pool);
+
+        let terms: Vec<String> = topics.iter().map(|t| t.to_lowercase()).collect();
+        let weight = self.feedback.semantic_weight;
+        let mut scored: Vec<(f32, Candidate)> = candidates
+            .into_iter()
+            .map(|mut c| {
+                let coverage = term_coverage(&c.result.text, &terms);
+                let score = match query_vec {
+                    Some(ref vec) => {
+                        if c.similarity.is_none() {
+                            c.similarity = self.stored_similarity(vec, c.result.id);
+                        }
+                        let similarity = c.similarity.unwrap_or(0.0);
+                        weight * similarity + (1.0 - weight) * coverage
+                    }
+                    None => coverage,
+                };
+                let boost = self.feedback.boost(c.result.id, &terms);
+                (score + FEEDBACK_WEIGHT * boost, c)
+            })
+            .collect();
+        // Stable, so equally scored candidates keep their fused order.
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.truncate(limit);
+
+        scored
+            .into_iter()
+            .map(|(score, c)| SearchResult {
+                chunk_id: c.result.id,
+                content: c.result.text,
+                timestamp: c.result.timestamp.timestamp(),
+                source_app: c.result.app_name,
+                relevance_score: score.clamp(0.0, 1.0),
+                person: None,
+                retrieval: c.path,
+            })
+            .collect()
+    }
+
+    /// Nearest captures to `query` that pass `filters`, most similar first,
+    /// with their similarity.
+    ///
+    /// The global nearest neighbours are tried first. When the filters leave
+    /// fewer than `pool` of them, the filtered captures are also scored from
+    /// their stored embeddings, so a narrow filter still finds its closest
+    /// matches even when they are far down the global ranking.
+    fn semantic_candidates(
+        &self,
+        query: &Embedding,
+        filters: &FtsFilters,
+        pool: usize,
+    ) -> Vec<(FtsResult, f32)> {
+        let mut candidates = self.nearest_filtered(query, filters, pool);
+        if !filters.is_empty() && candidates.len() < pool {
+            let seen: HashSet<Uuid> = candidates.iter().map(|(r, _)| r.id).collect();
+            let scanned = self.score_filtered(query, filters);
+            candidates.extend(scanned.into_iter().filter(|(r, _)| !seen.contains(&r.id)));
+        }
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+        candidates.truncate(pool);
+        candidates
+    }
+
+    /// Global nearest neighbours of `query` that pass `filters`.
+    fn nearest_filtered(
+        &self,
+        query: &Embedding,
+        filters: &FtsFilters,
+        pool: usize,
+    ) -> Vec<(FtsResult, f32)> {
+        let hits = match self
+            .engine
+            .index()
+            .search(&query.0, pool * VECTOR_OVERFETCH)
+        {
+            Ok(hits) => hits,
+            Err(e) => {
+                tracing::warn!("Vector search failed: {}", e);
+                return Vec::new();
+            }
+        };
+        let similarity: HashMap<Uuid, f32> = hits
+            .iter()
+            .map(|h| (h.id, h.score as f32))
+            .filter(|(_, score)| *score >= MIN_SEMANTIC_SIMILARITY)
+            .collect();
+        let ids: Vec<Uuid> = similarity.keys().copied().collect();
+
+        match self.fts.find_filtered(&ids, filters) {
+            Ok(rows) => rows
+                .into_iter()
+                .map(|r| {
+                    let score = similarity[&r.id];
+                    (r, score)
+                })
+                .collect(),
+            Err(e) => {
+                tracing::warn!("Loading vector search hits failed: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// The newest captures passing `filters`, scored against `query` from
+    /// their stored embeddings.
+    fn score_filtered(&self, query: &Embedding, filters: &FtsFilters) -> Vec<(FtsResult, f32)> {
+        let rows = match self.fts.search_filtered("", filters, FILTERED_SCAN_LIMIT) {
+            Ok(rows) => rows,
+            Err(e) => {
+                tracing::warn!("Listing filtered captures failed: {}", e);
+                return Vec::new();
+            }
+        };
+        rows.into_iter()
+            .filter_map(|r| {
+                let score = self.stored_similarity(query, r.id)?;
+                (score >= MIN_SEMANTIC_SIMILARITY).then_some((r, score))
+            })
+            .collect()
+    }
+
+    /// Similarity of a keyword-only candidate, from its stored embedding.
+    fn stored_similarity(&self, query: &Embedding, id: Uuid) -> Option<f32> {
+        match self.engine.index().get_embedding(id) {
+            Ok(Some(embedding)) => {
+                let similarity = query.cosine_similarity(&Embedding(embedding));
+                Some(similarity.clamp(0.0, 1.0) as f32)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                tracing::debug!("No embedding for capture {}: {}", id, e);
+                None
+            }
+        }
+    }
+}
+
+/// Merge both ranked lists with reciprocal rank fusion, best first.
+fn fuse(keyword: Vec<FtsResult>, semantic: Vec<(FtsResult, f32)>) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = Vec::new();
+    let mut positions: HashMap<Uuid, usize> = HashMap::new();
+
+    for (rank, result) in keyword.into_iter().enumerate() {
+        if positions.contains_key(&result.id) {
+            continue;
+        }
+        positions.insert(result.id, candidates.len());
+        candidates.push(Candidate {
+            result,
+            path: RetrievalPath::Keyword,
+            fused: rrf(rank),
+            similarity: None,
+        });
+    }
+    for (rank, (result, similarity)) in semantic.into_iter().enumerate() {
+        match positions.get(&result.id) {
+            Some(&i) => {
+                let c = &mut candidates[i];
+                c.path = RetrievalPath::Hybrid;
+                c.fused += rrf(rank);
+                c.similarity = Some(similarity);
+            }
+            None => {
+                positions.insert(result.id, candidates.len());
+                candidates.push(Candidate {
+                    result,
+                    path: RetrievalPath::Semantic,
+                    fused: rrf(rank),
+                    similarity: Some(similarity),
+                });
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| b.fused.total_cmp(&a.fused));
+    candidates
+}
+
+fn rrf(rank: usize) -> f32 {
+    1.0 / (RRF_K + rank as f32 + 1.0)
+}
+
+/// Fraction of the (lowercased) topic terms that appear in `text`; 1.0
+/// when there are no terms.
+fn term_coverage(text: &str, terms: &[String]) -> f32 {
+    if terms.is_empty() {
+        return 1.0;
+    }
+    let text = text.to_lowercase();
+    let found = terms.iter().filter(|t| text.contains(t.as_str())).count();
+    found as f32 / terms.len() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn result(id: Uuid, text: &str) -> FtsResult {
+        FtsResult {
+            id,
+            content_type: "screen".to_string(),
+            timestamp: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+            text: text.to_string(),
+            app_name: "Notes".to_string(),
+            rank: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_fuse_marks_retrieval_path() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let fused = fuse(
+            vec![result(a, "a"), result(b, "b")],
+            vec![(result(b, "b"), 0.9), (result(c, "c"), 0.8)],
+        );
+
+        assert_eq!(fused.len(), 3);
+        assert_eq!(fused[0].result.id, b);
+        assert_eq!(fused[0].path, RetrievalPath::Hybrid);
+        assert_eq!(fused[0].similarity, Some(0.9));
+        let path = |id| fused.iter().find(|c| c.result.id == id).unwrap().path;
+        assert_eq!(path(a), RetrievalPath::Keyword);
+        assert_eq!(path(c), RetrievalPath::Semantic);
+    }
+
+    #[test]
+    fn test_term_coverage() {
+        let terms = vec!["cloud".to_string(), "costs".to_string()];
+        assert_eq!(term_coverage("Cutting CLOUD costs", &terms), 1.0);
+        assert_eq!(term_coverage("cloud migration", &terms), 0.5);
+        assert_eq!(term_coverage("lunch", &terms), 0.0);
+        assert_eq!(term_coverage("lunch", &[]), 1.0);
+    }
+
+    #[test]
+    fn test_feedback_boost_is_mean_over_terms() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut feedback = RankingFeedback::default();
+        feedback
+            .boosts
+            .insert("pricing".to_string(), HashMap::from([(a, 1.0), (b, -0.5)]));
+        feedback
+            .boosts
+            .insert("launch".to_string(), HashMap::from([(a, 0.5)]));
+
+        let terms = vec!["pricing".to_string(), "launch".to_string()];
+        assert_eq!(feedback.boost(a, &terms), 0.75);
+        assert_eq!(feedback.boost(b, &terms), -0.25);
+        assert_eq!(feedback.boost(Uuid::new_v4(), &terms), 0.0);
+        assert_eq!(feedback.boost(a, &[]), 0.0);
+    }
+}
@@ -0,0 +1,400 @@
+//! Streaming chat responses.
+//!
+//! A [`ChatStream`] records the events produced while one message is
+//! answered: retrieval progress, sources as they are found, answer text as
+//! it is written and a final message record. Every event is kept with a
+//! per-message sequence number, so a client that disconnects can resume
+//! from the last event it saw, and a stream can be cancelled by message ID
+//! while it runs.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tokio::sync::{broadcast, watch};
+use uuid::Uuid;
+
+use crate::types::{AnalyticsTable, ChatMessageRecord, QueryIntent, ResponseSection, SourceRef};
+
+/// Finished streams kept for resuming; older ones are dropped first.
+const MAX_FINISHED_STREAMS: usize = 50;
+
+/// Capacity of each stream's live channel. Subscribers that fall further
+/// behind resubscribe from the recorded events.
+const LIVE_CHANNEL_CAPACITY: usize = 256;
+
+/// Orders finished streams for retention.
+static FINISH_ORDER: AtomicU64 = AtomicU64::new(0);
+
+/// Words of template answer text per [`ChatStreamEvent::Delta`].
+pub const DELTA_WORDS: usize = 8;
+
+/// Stage of answering a message, reported by [`ChatStreamEvent::Progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamStage {
+    /// Searching captures.
+    Retrieving,
+    /// Composing the answer.
+    Generating,
+}
+
+/// One event of a streamed chat response.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChatStreamEvent {
+    /// The message was accepted and parsed.
+    Started {
+        message_id: Uuid,
+        session_id: Uuid,
+        intent: QueryIntent,
+    },
+    /// Answering reached a new stage.
+    Progress {
+        stage: StreamStage,
+        /// Extra detail, e.g. how many captures were found.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        detail: Option<String>,
+    },
+    /// A capture found while retrieving.
+    Source { source: SourceRef },
+    /// Analytics results as a chartable table, sent before the answer text.
+    Table { table: AnalyticsTable },
+    /// A comparison side or timeline window, sent before the answer text.
+    Section { section: ResponseSection },
+    /// The next piece of answer text.
+    Delta { text: String },
+    /// The answer changed after it was streamed, e.g. a model answer that
+    /// fell back to the extractive one; `text` replaces all answer text
+    /// sent so far.
+    Replace { text: String },
+    /// The answer was stored; `message` is its history record.
+    Done {
+        message_id: Uuid,
+        session_id: Uuid,
+        confidence: f32,
+        message: ChatMessageRecord,
+    },
+    /// The stream was cancelled before the answer was stored.
+    Cancelled { message_id: Uuid },
+    /// Answering failed.
+    Error { message: String },
+}
+
+impl ChatStreamEvent {
+    /// Event name, used as the SSE event type.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChatStreamEvent::Started { .. } => "started",
+            ChatStreamEvent::Progress { .. } => "progress",
+            ChatStreamEvent::Source { .. } => "source",
+            ChatStreamEvent::Table { .. } => "table",
+            ChatStreamEvent::Section { .. } => "section",
+            ChatStreamEvent::Delta { .. } => "delta",
+            ChatStreamEvent::Replace { .. } => "replace",
+            ChatStreamEvent::Done { .. } => "done",
+            ChatStreamEvent::Cancelled { .. } => "cancelled",
+            ChatStreamEvent::Error { .. } => "error",
+        }
+    }
+
+    /// Whether this event ends the stream.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            ChatStreamEvent::Done { .. }
+                | ChatStreamEvent::Cancelled { .. }
+                | ChatStreamEvent::Error { .. }
+        )
+    }
+}
+
+/// A stream event with its sequence number (starting at 1).
+#[derive(Debug, Clone)]
+pub struct SequencedEvent {
+    pub id: u64,
+    pub event: ChatStreamEvent,
+}
+
+#[derive(Debug, Default)]
+struct StreamState {
+    events: Vec<SequencedEvent>,
+    /// Position in [`FINISH_ORDER`] once a terminal event was recorded.
+    finished: Option<u64>,
+}
+
+/// The recorded and live events of one streamed message.
+#[derive(Debug)]
+pub struct ChatStream {
+    message_id: Uuid,
+    /// User (API token name) that started the stream.
+    owner: String,
+    state: Mutex<StreamState>,
+    live: broadcast::Sender<SequencedEvent>,
+    cancel: watch::Sender<bool>,
+}
+
+impl ChatStream {
+    fn new(owner: &str) -> Self {
+        Self {
+            message_id: Uuid::new_v4(),
+            owner: owner.to_string(),
+            state: Mutex::new(StreamState::default()),
+            live: broadcast::channel(LIVE_CHANNEL_CAPACITY).0,
+            cancel: watch::channel(false).0,
+        }
+    }
+
+    /// ID of the assistant message this stream produces.
+    pub fn message_id(&self) -> Uuid {
+        self.message_id
+    }
+
+    /// User that started the stream; only they may resume or cancel it.
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    /// Record an event and send it to live subscribers. Events after a
+    /// terminal event are ignored.
+    pub(crate) fn emit(&self, event: ChatStreamEvent) {
+        let mut state = self.lock();
+        if state.finished.is_some() {
+            return;
+        }
+        if event.is_terminal() {
+            state.finished = Some(FINISH_ORDER.fetch_add(1, Ordering::Relaxed));
+        }
+        let sequenced = SequencedEvent {
+            id: state.events.len() as u64 + 1,
+            event,
+        };
+        state.events.push(sequenced.clone());
+        // Sent under the lock so `subscribe` sees each event exactly once.
+        let _ = self.live.send(sequenced);
+    }
+
+    /// Request cancellation. Returns `false` if the stream already finished.
+    pub fn cancel(&self) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        self.cancel.send_replace(true);
+        true
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancel.borrow()
+    }
+
+    /// Resolves once cancellation is requested.
+    pub async fn cancelled(&self) {
+        let mut rx = self.cancel.subscribe();
+        let _ = rx.wait_for(|cancelled| *cancelled).await;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.lock().finished.is_some()
+    }
+
+    /// Answer text sent so far: the last [`ChatStreamEvent::Replace`] text
+    /// followed by the deltas after it.
+    pub(crate) fn answer_text(&self) -> String {
+        let mut answer = String::new();
+        for e in &self.lock().events {
+            match &e.event {
+                ChatStreamEvent::Delta { text } => answer.push_str(text),
+                ChatStreamEvent::Replace { text } => answer.clone_from(text),
+                _ => {}
+            }
+        }
+        answer
+    }
+
+    /// Recorded events after sequence number `after` (all when `None`),
+    /// plus a receiver for later events while the stream is running.
+    pub fn subscribe(
+        &self,
+        after: Option<u64>,
+    ) -> (
+        Vec<SequencedEvent>,
+        Option<broadcast::Receiver<SequencedEvent>>,
+    ) {
+        let state = self.lock();
+        let after = after.unwrap_or(0);
+        let backlog = state
+            .events
+            .iter()
+            .filter(|e| e.id > after)
+            .cloned()
+            .collect();
+        let live = state.finished.is_none().then(|| self.live.subscribe());
+        (backlog, live)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StreamState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Tracks running and recently finished chat streams by message ID.
+#[derive(Debug, Default)]
+pub struct ChatStreams {
+    streams: Mutex<HashMap<Uuid, Arc<ChatStream>>>,
+}
+
+impl ChatStreams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new stream for `owner`, dropping the oldest finished
+    /// streams beyond the retention limit.
+    pub fn start(&self, owner: &str) -> Arc<ChatStream> {
+        let stream = Arc::new(ChatStream::new(owner));
+        let mut streams = self.lock();
+        let mut finished: Vec<(u64, Uuid)> = streams
+            .values()
+            .filter_map(|s| s.lock().finished.map(|order| (order, s.message_id)))
+            .collect();
+        if finished.len() > MAX_FINISHED_STREAMS {
+            finished.sort();
+            for (_, id) in &finished[..finished.len() - MAX_FINISHED_STREAMS] {
+                streams.remove(id);
+            }
+        }
+        streams.insert(stream.message_id, Arc::clone(&stream));
+        stream
+    }
+
+    pub fn get(&self, message_id: Uuid) -> Option<Arc<ChatStream>> {
+        self.lock().get(&message_id).cloned()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Uuid, Arc<ChatStream>>> {
+        self.streams.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Split answer text into [`ChatStreamEvent::Delta`] pieces of
+/// [`DELTA_WORDS`] words. Joining the pieces gives back the text.
+pub(crate) fn answer_deltas(answer: &str) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    let mut words = 0;
+    for (i, c) in answer.char_indices() {
+        current.push(c);
+        let at_word_end = !c.is_whitespace()
+            && answer[i + c.len_utf8()..]
+                .chars()
+                .next()
+                .is_none_or(char::is_whitespace);
+        if at_word_end {
+            words += 1;
+            if words == DELTA_WORDS {
+                pieces.push(std::mem::take(&mut current));
+                words = 0;
+            }
+        }
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delta(text: &str) -> ChatStreamEvent {
+        ChatStreamEvent::Delta {
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_answer_deltas_rejoin_to_answer() {
+        let answer = "On 2026-01-05 in Slack:\n  we shipped the release notes and then went home for the day";
+        let pieces = answer_deltas(answer);
+        assert_eq!(pieces.concat(), answer);
+        assert_eq!(pieces.len(), 2);
+        assert!(answer_deltas("").is_empty());
+        assert_eq!(answer_deltas("one"), vec!["one"]);
+    }
+
+    #[test]
+    fn test_answer_text_follows_replace() {
+        let stream = ChatStreams::new().start("primary");
+        stream.emit(delta("model "));
+        stream.emit(delta("text"));
+        assert_eq!(stream.answer_text(), "model text");
+        stream.emit(ChatStreamEvent::Replace {
+            text: "extractive".to_string(),
+        });
+        stream.emit(delta(" answer"));
+        assert_eq!(stream.answer_text(), "extractive answer");
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_replays_then_follows_live() {
+        let streams = ChatStreams::new();
+        let stream = streams.start("primary");
+        stream.emit(delta("a"));
+        stream.emit(delta("b"));
+
+        let (backlog, live) = stream.subscribe(Some(1));
+        assert_eq!(backlog.iter().map(|e| e.id).collect::<Vec<_>>(), vec![2]);
+        let mut live = live.unwrap();
+
+        stream.emit(ChatStreamEvent::Cancelled {
+            message_id: stream.message_id(),
+        });
+        stream.emit(delta("ignored"));
+        let next = live.recv().await.unwrap();
+        assert_eq!(next.id, 3);
+        assert!(next.event.is_terminal());
+        assert!(stream.is_finished());
+
+        let (backlog, live) = stream.subscribe(None);
+        assert_eq!(backlog.len(), 3);
+        assert!(live.is_none());
+        assert!(streams.get(stream.message_id()).is_some());
+        assert!(streams.get(Uuid::new_v4()).is_none());
+        assert_eq!(stream.owner(), "primary");
+    }
+
+    #[tokio::test]
+    async fn test_cancel_wakes_waiters_until_finished() {
+        let stream = ChatStreams::new().start("primary");
+        assert!(!stream.is_cancelled());
+        assert!(stream.cancel());
+        stream.cancelled().await;
+        assert!(stream.is_cancelled());
+
+        stream.emit(ChatStreamEvent::Error {
+            message: "failed".to_string(),
+        });
+        assert!(!stream.cancel());
+    }
+
+    #[test]
+    fn test_old_finished_streams_are_dropped() {
+        let streams = ChatStreams::new();
+        let first = streams.start("primary");
+        first.emit(ChatStreamEvent::Cancelled {
+            message_id: first.message_id(),
+        });
+        for _ in 0..MAX_FINISHED_STREAMS {
+            let stream = streams.start("primary");
+            stream.emit(ChatStreamEvent::Cancelled {
+                message_id: stream.message_id(),
+            });
+        }
+        let running = streams.start("primary");
+
+        assert!(streams.get(first.message_id()).is_none());
+        assert!(streams.get(running.message_id()).is_some());
+    }
+}
@@ -0,0 +1,698 @@
+//! Answers from structured stores: tasks, extracted entities, activity
+//! summaries, daily digests and app usage.
+//!
+//! Queries the parser tags with a [`DataSource`] are answered from the
+//! matching store instead of capture search. Each listed item ends with an
+//! inline `[chunk-id]` citation of the capture it came from, and the cited
+//! captures are returned as sources.
+
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDateTime, TimeZone, Utc};
+use uuid::Uuid;
+
+use engram_action::TaskStatus;
+use engram_core::error::EngramError;
+use engram_storage::{EntityRow, FtsFilters, SummaryRow};
+
+use crate::orchestrator::ChatBackends;
+use crate::response::{describe_range, format_timestamp};
+use crate::types::{
+    ChatResponse, DataSource, RetrievalPath, SourceRef, StructuredQuery, TimeRange,
+};
+
+/// Entities read per query before topic filtering and de-duplication.
+const ENTITY_SCAN_LIMIT: u32 = 500;
+
+/// Summaries read per query before time and topic filtering.
+const SUMMARY_SCAN_LIMIT: u32 = 200;
+
+/// Bullet points shown per summary.
+const BULLETS_PER_SUMMARY: usize = 3;
+
+/// Confidence of answers read from a structured store.
+const STRUCTURED_CONFIDENCE: f32 = 0.9;
+
+/// Answers queries from the structured stores behind [`ChatBackends`].
+pub(crate) struct StructuredAnswerer<'a> {
+    backends: &'a ChatBackends,
+    /// Items listed per answer.
+    limit: usize,
+}
+
+impl<'a> StructuredAnswerer<'a> {
+    pub(crate) fn new(backends: &'a ChatBackends, limit: usize) -> Self {
+        Self {
+            backends,
+            limit: limit.max(1),
+        }
+    }
+
+    /// Answer `query` from `source`. `range` is the time range the query
+    /// named explicitly, if any.
+    pub(crate) fn answer(
+        &self,
+        source: &DataSource,
+        query: &StructuredQuery,
+        range: Option<&TimeRange>,
+    ) -> ChatResponse {
+        let (store, result) = match source {
+            DataSource::Tasks { status } => ("tasks", self.tasks(status.as_deref())),
+            DataSource::Entities { entity_type } => (
+                "extracted entities",
+                self.entities(entity_type.as_deref(), &query.topics, range),
+            ),
+            DataSource::Summaries => ("summaries", self.summaries(query, range)),
+            DataSource::Digest => ("daily digests", self.digest(range)),
+            DataSource::Apps => (
+                "app activity",
+                self.apps(query.app_filter.as_deref(), range),
+            ),
+        };
+        result.unwrap_or_else(|e| {
+            tracing::warn!("Reading {} for chat failed: {}", store, e);
+            ChatResponse {
+                answer: format!("I couldn't read your {} right now.", store),
+                sources: vec![],
+                confidence: 0.0,
+                suggestions: vec!["Try again".to_string()],
+                table: None,
+                sections: vec![],
+            }
+        })
+    }
+
+    /// `TaskStore::list`, newest first.
+    fn tasks(&self, status: Option<&str>) -> Result<ChatResponse, EngramError> {
+        let filter = status
+            .map(|s| s.parse::<TaskStatus>().map_err(EngramError::Storage))
+            .transpose()?;
+        let tasks = self.backends.task_store.list(filter, None, None);
+        let noun = |n: usize| match status {
+            Some(s) => format!("{} {}", s, plural(n, "task", "tasks")),
+            None => plural(n, "task", "tasks").to_string(),
+        };
+        let suggestions = vec![
+            if status == Some("pending") {
+                "What tasks are done?".to_string()
+            } else {
+                "What tasks are pending?".to_string()
+            },
+            "Show my daily digest".to_string(),
+        ];
+        if tasks.is_empty() {
+            return Ok(empty_answer(
+                format!("You have no {}.", noun(0)),
+                suggestions,
+            ));
+        }
+
+        let mut citations = Citations::new(self.backends);
+        let mut lines = vec![format!("You have {} {}:", tasks.len(), noun(tasks.len()))];
+        for task in tasks.iter().take(self.limit) {
+            let mut line = format!("- {} ({}", task.title, task.status);
+            if let Some(at) = task.scheduled_at {
+                line.push_str(&format!(", due {}", format_timestamp(at.0)));
+            }
+            line.push(')');
+            lines.push(citations.cite(line, task.source_chunk_id));
+        }
+        push_remainder(&mut lines, tasks.len(), self.limit);
+        Ok(citations.answer(lines, suggestions))
+    }
+
+    /// `QueryService::get_entities`, de-duplicated by value and narrowed to
+    /// entities whose value or source capture mentions a topic.
+    fn entities(
+        &self,
+        entity_type: Option<&str>,
+        topics: &[String],
+        range: Option<&TimeRange>,
+    ) -> Result<ChatResponse, EngramError> {
+        let since = range.map(|r| sqlite_datetime(r.start));
+        let until = range.map(|r| sqlite_datetime(r.end));
+        let rows = self.backends.query_service.get_entities(
+            entity_type,
+            since.as_deref(),
+            Some(ENTITY_SCAN_LIMIT),
+        )?;
+
+        // (type, value) -> (rows seen, source chunks), newest first.
+        let mut order: Vec<(String, String)> = Vec::new();
+        let mut groups: HashMap<(String, String), (usize, Vec<Uuid>)> = HashMap::new();
+        let mut capture_text: HashMap<Uuid, String> = HashMap::new();
+        for row in rows {
+            if until
+                .as_deref()
+                .is_some_and(|u| row.created_at.as_str() > u)
+            {
+                continue;
+            }
+            let chunk = row
+                .source_chunk_id
+                .as_deref()
+                .and_then(|id| id.parse().ok());
+            if !topics.is_empty() && !self.entity_mentions(&row, chunk, topics, &mut capture_text) {
+                continue;
+            }
+            let key = (row.entity_type, row.value);
+            let group = groups.entry(key.clone()).or_insert_with(|| {
+                order.push(key);
+                (0, Vec::new())
+            });
+            group.0 += 1;
+            group.1.extend(chunk);
+        }
+
+        let label = entity_label(entity_type);
+        let about = if topics.is_empty() {
+            String::new()
+        } else {
+            format!(" about {}", topics.join(" "))
+        };
+        let suggestions = vec![
+            "Which projects came up this week?".to_string(),
+            "Which URLs did I visit today?".to_string(),
+        ];
+        if order.is_empty() {
+            return Ok(empty_answer(
+                format!("I couldn't find any {}{}.", label, about),
+                suggestions,
+            ));
+        }
+
+        let mut citations = Citations::new(self.backends);
+        let mut lines = vec![format!("Found {} {}{}:", order.len(), label, about)];
+        for key in order.iter().take(self.limit) {
+            let (count, chunks) = &groups[key];
+            let mut line = format!("- {}", key.1);
+            if entity_type.is_none() {
+                line.push_str(&format!(" ({})", key.0));
+            }
+            if *count > 1 {
+                line.push_str(&format!(", seen {} times", count));
+            }
+            lines.push(citations.cite(line, chunks.iter().copied()));
+        }
+        push_remainder(&mut lines, order.len(), self.limit);
+        Ok(citations.answer(lines, suggestions))
+    }
+
+    /// Whether an entity's value or source capture mentions any topic.
+    fn entity_mentions(
+        &self,
+        row: &EntityRow,
+        chunk: Option<Uuid>,
+        topics: &[String],
+        capture_text: &mut HashMap<Uuid, String>,
+    ) -> bool {
+        if mentions_any(&row.value, topics) {
+            return true;
+        }
+        let Some(chunk) = chunk else {
+            return false;
+        };
+        let text = capture_text.entry(chunk).or_insert_with(|| {
+            match self.backends.query_service.get_capture(chunk) {
+                Ok(Some(capture)) => capture.text,
+                _ => String::new(),
+            }
+        });
+        mentions_any(text, topics)
+    }
+
+    /// `QueryService::get_summaries`, narrowed to the time range and topics.
+    fn summaries(
+        &self,
+        query: &StructuredQuery,
+        range: Option<&TimeRange>,
+    ) -> Result<ChatResponse, EngramError> {
+        let rows = self.backends.query_service.get_summaries(
+            None,
+            query.app_filter.as_deref(),
+            Some(SUMMARY_SCAN_LIMIT),
+        )?;
+        let summaries: Vec<(SummaryRow, Vec<String>)> = rows
+            .into_iter()
+            .filter(|row| {
+                range.is_none_or(|r| {
+                    let at = summary_epoch(row);
+                    at >= r.start && at <= r.end
+                })
+            })
+            .map(|row| {
+                let bullets: Vec<String> =
+                    serde_json::from_str(&row.bullet_points).unwrap_or_default();
+                (row, bullets)
+            })
+            .filter(|(row, bullets)| {
+                query.topics.is_empty()
+                    || mentions_any(&row.title, &query.topics)
+                    || bullets.iter().any(|b| mentions_any(b, &query.topics))
+            })
+            .collect();
+
+        let period = range.map(describe_range).unwrap_or_default();
+        let suggestions = vec![
+            "Show my daily digest".to_string(),
+            "What tasks are pending?".to_string(),
+        ];
+        if summaries.is_empty() {
+            return Ok(empty_answer(
+                format!("I couldn't find any summaries{}.", period),
+                suggestions,
+            ));
+        }
+
+        let mut citations = Citations::new(self.backends);
+        let mut lines = vec![format!(
+            "{} {}{}:",
+            summaries.len(),
+            plural(summaries.len(), "summary", "summaries"),
+            period
+        )];
+        for (row, bullets) in summaries.iter().take(self.limit) {
+            let app = row.source_app.as_deref().unwrap_or("unknown app");
+            let line = format!(
+                "- {} ({}, {})",
+                row.title,
+                app,
+                format_timestamp(summary_epoch(row))
+            );
+            lines.push(citations.cite(line, summary_chunks(row)));
+            for bullet in bullets.iter().take(BULLETS_PER_SUMMARY) {
+                lines.push(format!("  - {}", bullet));
+            }
+        }
+        push_remainder(&mut lines, summaries.len(), self.limit);
+        Ok(citations.answer(lines, suggestions))
+    }
+
+    /// `QueryService::get_digest` for the day the query named, or today
+    /// (yesterday if today's digest hasn't been generated yet).
+    fn digest(&self, range: Option<&TimeRange>) -> Result<ChatResponse, EngramError> {
+        let qs = &self.backends.query_service;
+        let today = Utc::now();
+        let dates = match range {
+            Some(r) => vec![utc_date(r.start)],
+            None => vec![
+                today.format("%Y-%m-%d").to_string(),
+                (today - Duration::days(1)).format("%Y-%m-%d").to_string(),
+            ],
+        };
+        let mut digest = None;
+        for date in &dates {
+            digest = qs.get_digest(date)?;
+            if digest.is_some() {
+                break;
+            }
+        }
+        let suggestions = vec![
+            "Summarize today".to_string(),
+            "What tasks are pending?".to_string(),
+        ];
+        let Some(digest) = digest else {
+            return Ok(empty_answer(
+                format!("There's no daily digest for {} yet.", dates[0]),
+                suggestions,
+            ));
+        };
+
+        let content: serde_json::Value = serde_json::from_str(&digest.content).unwrap_or_default();
+        let mut lines = vec![format!(
+            "Daily digest for {}: {} {} and {} {} from {} {}.",
+            digest.digest_date,
+            digest.summary_count,
+            plural(digest.summary_count as usize, "summary", "summaries"),
+            digest.entity_count,
+            plural(digest.entity_count as usize, "entity", "entities"),
+            digest.chunk_count,
+            plural(digest.chunk_count as usize, "capture", "captures"),
+        )];
+
+        if let Some(apps) = content["app_breakdown"].as_object() {
+            let mut apps: Vec<(&String, u64)> = apps
+                .iter()
+                .map(|(app, n)| (app, n.as_u64().unwrap_or(0)))
+                .collect();
+            apps.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+            if !apps.is_empty() {
+                let apps: Vec<String> = apps
+                    .iter()
+                    .map(|(app, n)| format!("{} ({})", app, n))
+                    .collect();
+                lines.push(format!("Apps: {}", apps.join(", ")));
+            }
+        }
+        if let Some(entities) = content["top_entities"].as_array() {
+            let entities: Vec<String> = entities
+                .iter()
+                .take(self.limit)
+                .filter_map(|e| {
+                    Some(format!(
+                        "{} ({})",
+                        e["value"].as_str()?,
+                        e["type"].as_str().unwrap_or("entity")
+                    ))
+                })
+                .collect();
+            if !entities.is_empty() {
+                lines.push(format!("Top entities: {}", entities.join(", ")));
+            }
+        }
+
+        // Cite each listed summary through its stored source captures.
+        let mut citations = Citations::new(self.backends);
+        if let Some(summaries) = content["summaries"].as_array() {
+            let stored: HashMap<Uuid, SummaryRow> = qs
+                .get_summaries(Some(&digest.digest_date), None, Some(SUMMARY_SCAN_LIMIT))?
+                .into_iter()
+                .map(|row| (row.id, row))
+                .collect();
+            for summary in summaries.iter().take(self.limit) {
+                let Some(title) = summary["title"].as_str() else {
+                    continue;
+                };
+                let chunks = summary["id"]
+                    .as_str()
+                    .and_then(|id| id.parse::<Uuid>().ok())
+                    .and_then(|id| stored.get(&id))
+                    .map(summary_chunks)
+                    .unwrap_or_default();
+                lines.push(citations.cite(format!("- {}", title), chunks));
+            }
+            push_remainder(&mut lines, summaries.len(), self.limit);
+        }
+        Ok(citations.answer(lines, suggestions))
+    }
+
+    /// `QueryService::app_activity` for the named app, otherwise
+    /// `QueryService::list_apps`.
+    fn apps(
+        &self,
+        app: Option<&str>,
+        range: Option<&TimeRange>,
+    ) -> Result<ChatResponse, EngramError> {
+        let qs = &self.backends.query_service;
+        let mut citations = Citations::new(self.backends);
+
+        let Some(app) = app else {
+            let apps = qs.list_apps()?;
+            let suggestions = vec![
+                apps.first().map_or_else(
+                    || "Show my daily digest".to_string(),
+                    |a| format!("Show my {} activity", a.name),
+                ),
+                "Summarize today".to_string(),
+            ];
+            if apps.is_empty() {
+                return Ok(empty_answer(
+                    "I haven't captured any apps yet.".to_string(),
+                    suggestions,
+                ));
+            }
+            let mut lines = vec![format!(
+                "You've used {} {}, most captured first:",
+                apps.len(),
+                plural(apps.len(), "app", "apps")
+            )];
+            for a in apps.iter().take(self.limit) {
+                let line = format!(
+                    "- {}: {} {}, last seen {}",
+                    a.name,
+                    a.capture_count,
+                    plural(a.capture_count as usize, "capture", "captures"),
+                    format_timestamp(a.last_seen.timestamp())
+                );
+                lines.push(citations.cite(line, self.latest_capture(&a.name, None)));
+            }
+            push_remainder(&mut lines, apps.len(), self.limit);
+            return Ok(citations.answer(lines, suggestions));
+        };
+
+        // Chat app filters match case-insensitively by substring (`zoom`
+        // covers `Zoom.exe`); activity is stored per exact app name.
+        let needle = app.to_lowercase();
+        let mut names: Vec<String> = qs
+            .list_apps()?
+            .into_iter()
+            .map(|a| a.name)
+            .filter(|name| name.to_lowercase().contains(&needle))
+            .collect();
+        if names.is_empty() {
+            names.push(app.to_string());
+        }
+        let mut segments = Vec::new();
+        for name in &names {
+            segments.extend(qs.app_activity(name)?.into_iter().filter(|s| {
+                range.is_none_or(|r| s.end.timestamp() >= r.start && s.start.timestamp() <= r.end)
+            }));
+        }
+        segments.sort_by_key(|s| std::cmp::Reverse(s.start));
+        let period = range.map(describe_range).unwrap_or_default();
+        let suggestions = vec![
+            format!("Find {} notes from today", app),
+            "Which apps did I use most?".to_string(),
+        ];
+        if segments.is_empty() {
+            return Ok(empty_answer(
+                format!("I have no {} activity{}.", app, period),
+                suggestions,
+            ));
+        }
+
+        let captures: u64 = segments.iter().map(|s| s.capture_count).sum();
+        let mut lines = vec![format!(
+            "You were in {} during {} {}{} ({} {}):",
+            app,
+            segments.len(),
+            plural(segments.len(), "hour", "hours"),
+            period,
+            captures,
+            plural(captures as usize, "capture", "captures"),
+        )];
+        for (i, s) in segments.iter().take(self.limit).enumerate() {
+            let line = format!(
+                "- {} to {}: {} {}",
+                format_timestamp(s.start.timestamp()),
+                format_timestamp(s.end.timestamp()),
+                s.capture_count,
+                plural(s.capture_count as usize, "capture", "captures"),
+            );
+            // The most recent hour cites the app's latest capture in it.
+            let cited = if i == 0 {
+                self.latest_capture(app, Some(s))
+            } else {
+                None
+            };
+            lines.push(citations.cite(line, cited));
+        }
+        push_remainder(&mut lines, segments.len(), self.limit);
+        Ok(citations.answer(lines, suggestions))
+    }
+
+    /// Latest capture from `app`, within `segment` if given.
+    fn latest_capture(
+        &self,
+        app: &str,
+        segment: Option<&engram_storage::queries::ActivitySegment>,
+    ) -> Option<Uuid> {
+        let filters = FtsFilters {
+            app_name: Some(app.to_string()),
+            start: segment.map(|s| s.start),
+            end: segment.map(|s| s.end),
+            ..Default::default()
+        };
+        match self.backends.fts_search.search_filtered("", &filters, 1) {
+            Ok(results) => results.first().map(|r| r.id),
+            Err(e) => {
+                tracing::debug!("Latest {} capture lookup failed: {}", app, e);
+                None
+            }
+        }
+    }
+}
+
+/// Sources cited by an answer, in citation order.
+struct Citations<'a> {
+    backends: &'a ChatBackends,
+    sources: Vec<SourceRef>,
+}
+
+impl<'a> Citations<'a> {
+    fn new(backends: &'a ChatBackends) -> Self {
+        Self {
+            backends,
+            sources: Vec::new(),
+        }
+    }
+
+    /// `line` followed by the `[chunk-id]` of the first of `chunks` that
+    /// is still stored; `line` unchanged if none is.
+    fn cite(&mut self, line: String, chunks: impl IntoIterator<Item = Uuid>) -> String {
+        for chunk in chunks {
+            if self.sources.iter().any(|s| s.chunk_id == chunk) {
+                return format!("{} [{}]", line, chunk);
+            }
+            match self.backends.query_service.get_capture(chunk) {
+                Ok(Some(capture)) => {
+                    self.sources.push(SourceRef {
+                        chunk_id: chunk,
+                        timestamp: format_timestamp(capture.timestamp.timestamp()),
+                        source_app: capture.app_name,
+                        relevance_score: 1.0,
+                        retrieval: RetrievalPath::Keyword,
+                    });
+                    return format!("{} [{}]", line, chunk);
+                }
+                Ok(None) => {}
+                Err(e) => tracing::debug!("Citing capture {} failed: {}", chunk, e),
+            }
+        }
+        line
+    }
+
+    fn answer(self, lines: Vec<String>, suggestions: Vec<String>) -> ChatResponse {
+        ChatResponse {
+            answer: lines.join("\n"),
+            sources: self.sources,
+            confidence: STRUCTURED_CONFIDENCE,
+            suggestions,
+            table: None,
+            sections: vec![],
+        }
+    }
+}
+
+fn empty_answer(answer: String, suggestions: Vec<String>) -> ChatResponse {
+    ChatResponse {
+        answer,
+        sources: vec![],
+        confidence: STRUCTURED_CONFIDENCE,
+        suggestions,
+        table: None,
+        sections: vec![],
+    }
+}
+
+fn push_remainder(lines: &mut Vec<String>, total: usize, shown: usize) {
+    if total > shown {
+        lines.push(format!("...and {} more.", total - shown));
+    }
+}
+
+fn plural<'s>(n: usize, one: &'s str, many: &'s str) -> &'s str {
+    if n == 1 {
+        one
+    } else {
+        many
+    }
+}
+
+/// Plural label for an entity type.
+fn entity_label(entity_type: Option<&str>) -> &str {
+    match entity_type {
+        Some("url") => "URLs",
+        Some("person") => "people",
+        Some("project") => "projects",
+        Some("date") => "dates",
+        Some("money") => "amounts",
+        Some(other) => other,
+        None => "entities",
+    }
+}
+
+/// Case-insensitive check for any (lowercase) topic in `text`.
+fn mentions_any(text: &str, topics: &[String]) -> bool {
+    let text = text.to_lowercase();
+    topics.iter().any(|t| text.contains(t.as_str()))
+}
+
+/// Source captures of a summary, from its JSON id list.
+fn summary_chunks(row: &SummaryRow) -> Vec<Uuid> {
+    serde_json::from_str::<Vec<String>>(&row.source_chunk_ids)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|id| id.parse().ok())
+        .collect()
+}
+
+/// When a summary's activity ended (epoch seconds): the stored end of its
+/// time range (epoch millis), else when it was created.
+fn summary_epoch(row: &SummaryRow) -> i64 {
+    row.time_range_end
+        .as_deref()
+        .and_then(|t| t.parse::<i64>().ok())
+        .map(|millis| millis / 1000)
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(&row.created_at, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|ndt| ndt.and_utc().timestamp())
+        })
+        .unwrap_or(0)
+}
+
+/// Epoch seconds as a SQLite `datetime('now')` string (UTC).
+fn sqlite_datetime(epoch: i64) -> String {
+    Utc.timestamp_opt(epoch, 0)
+        .single()
+        .unwrap_or_default()
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+fn utc_date(epoch: i64) -> String {
+    Utc.timestamp_opt(epoch, 0)
+        .single()
+        .unwrap_or_default()
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(time_range_end: Option<&str>, created_at: &str) -> SummaryRow {
+        SummaryRow {
+            id: Uuid::new_v4(),
+            title: "Kafka rollout".to_string(),
+            bullet_points: "[]".to_string(),
+            source_chunk_ids: format!("[\"{}\", \"not-a-uuid\"]", Uuid::nil()),
+            source_app: None,
+            time_range_start: None,
+            time_range_end: time_range_end.map(str::to_string),
+            created_at: created_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_summary_epoch_prefers_time_range_millis() {
+        assert_eq!(
+            summary_epoch(&summary(Some("1700000000123"), "2026-01-05 10:00:00")),
+            1_700_000_000
+        );
+        assert_eq!(summary_epoch(&summary(None, "1970-01-02 00:00:00")), 86_400);
+        assert_eq!(summary_epoch(&summary(None, "garbage")), 0);
+        assert_eq!(summary_chunks(&summary(None, "")), vec![Uuid::nil()]);
+    }
+
+    #[test]
+    fn test_sqlite_datetime_round_trips_utc() {
+        assert_eq!(sqlite_datetime(86_400), "1970-01-02 00:00:00");
+        assert_eq!(utc_date(86_399), "1970-01-01");
+    }
+
+    #[test]
+    fn test_labels_and_matching() {
+        assert_eq!(entity_label(Some("url")), "URLs");
+        assert_eq!(entity_label(None), "entities");
+        assert_eq!(plural(1, "task", "tasks"), "task");
+        assert_eq!(plural(0, "task", "tasks"), "tasks");
+        assert!(mentions_any(
+            "https://Kafka.apache.org",
+            &["kafka".to_string()]
+        ));
+        assert!(!mentions_any("https://example.com", &["kafka".to_string()]));
+    }
+}
@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use uuid::Uuid;
 
+use engram_core::config::{LLM_MAX_TEMPERATURE, LLM_MAX_TOKENS};
+
 // =============================================================================
 // Enums
 // =============================================================================
@@ -55,6 +57,7 @@ impl std::str::FromStr for QueryIntent {
 
 /// A time range for filtering queries.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct TimeRange {
     /// Start of the range as epoch seconds.
     pub start: i64,
@@ -77,12 +80,78 @@ pub struct StructuredQuery {
     pub content_type: Option<String>,
     /// Optional application filter.
     pub app_filter: Option<String>,
+    /// Structured store that answers the query instead of capture search.
+    #[serde(default)]
+    pub data_source: Option<DataSource>,
+    /// Analytics computed from capture timestamps instead of search.
+    #[serde(default)]
+    pub analytics: Option<AnalyticsMetric>,
+    /// How a multi-step answer is composed from sub-query results.
+    #[serde(default)]
+    pub composition: Option<Composition>,
     /// The original raw query text.
     pub raw_query: String,
 }
 
+/// A structured store a chat query can be answered from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DataSource {
+    /// Action engine tasks, optionally with one status (e.g. "pending").
+    Tasks { status: Option<String> },
+    /// Extracted entities, optionally of one type (e.g. "url").
+    Entities { entity_type: Option<String> },
+    /// Activity summaries.
+    Summaries,
+    /// The daily digest.
+    Digest,
+    /// Application usage.
+    Apps,
+}
+
+impl DataSource {
+    /// Store name, as shown in stream progress.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DataSource::Tasks { .. } => "tasks",
+            DataSource::Entities { .. } => "entities",
+            DataSource::Summaries => "summaries",
+            DataSource::Digest => "digest",
+            DataSource::Apps => "apps",
+        }
+    }
+}
+
+/// An analytics question answered from capture timestamps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalyticsMetric {
+    /// Focused time per app or window ("how long was I in VS Code?").
+    TimeSpent,
+    /// Capture counts ("how often did I open Slack?").
+    Count,
+    /// First and last seen ("when did I last use Figma?").
+    FirstLastSeen,
+    /// Day-over-day focused time ("how is my Slack time trending?").
+    Trend,
+}
+
+/// How the planner composes the results of several sub-queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Composition {
+    /// Side by side, one section per time window, person, app or topic
+    /// ("compare what we decided about pricing in the last two reviews").
+    Comparison,
+    /// In time order, one section per window ("give me a timeline of the
+    /// outage on Oct 3").
+    Timeline,
+}
+
 /// A response generated by the chat engine.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ChatResponse {
     /// The generated answer text.
     pub answer: String,
@@ -92,10 +161,63 @@ pub struct ChatResponse {
     pub confidence: f32,
     /// Suggested follow-up queries.
     pub suggestions: Vec<String>,
+    /// Analytics results as a chartable table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<AnalyticsTable>,
+    /// Comparison sides or timeline windows of a multi-step answer.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<ResponseSection>,
+}
+
+/// Analytics results as rows of cells, for the dashboard to chart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AnalyticsTable {
+    /// What the table shows, e.g. "Focused time by app".
+    pub title: String,
+    /// Column headers. The first column labels each row.
+    pub columns: Vec<String>,
+    /// One cell per column: strings for labels, numbers for values.
+    pub rows: Vec<Vec<serde_json::Value>>,
+    /// Index of the numeric column to chart.
+    pub value_column: usize,
+}
+
+/// One part of a multi-step answer: a comparison side or a timeline window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ResponseSection {
+    /// Whether this is a comparison side or a timeline window.
+    pub kind: Composition,
+    /// What the section covers, e.g. "Design review, 2026-10-02" or "14:00".
+    pub heading: String,
+    /// The window the section's sub-query searched, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_range: Option<TimeRange>,
+    /// Terms found in this side only (comparisons).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_terms: Vec<String>,
+    /// Captures found by the sub-query, oldest first.
+    pub items: Vec<SectionItem>,
+}
+
+/// A capture cited in a [`ResponseSection`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SectionItem {
+    /// ID of the source chunk.
+    pub chunk_id: Uuid,
+    /// Timestamp of the capture.
+    pub timestamp: String,
+    /// Application that captured it.
+    pub source_app: String,
+    /// Capture text, shortened.
+    pub text: String,
 }
 
 /// A reference to a source chunk that contributed to a response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct SourceRef {
     /// ID of the source chunk.
     pub chunk_id: Uuid,
@@ -105,6 +227,23 @@ pub struct SourceRef {
     pub source_app: String,
     /// Relevance score of this source to the query (0.0 to 1.0).
     pub relevance_score: f32,
+    /// Which retrieval path found this source.
+    #[serde(default)]
+    pub retrieval: RetrievalPath,
+}
+
+/// How a source was retrieved for a chat answer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RetrievalPath {
+    /// Full-text match on the query terms, or a filter-only listing.
+    #[default]
+    Keyword,
+    /// Vector similarity to the question.
+    Semantic,
+    /// Found by both keyword and vector search.
+    Hybrid,
 }
 
 /// An active conversation session with context tracking.
@@ -120,6 +259,12 @@ pub struct ConversationSession {
     pub context: SessionContext,
     /// Number of messages exchanged in this session.
     pub message_count: u32,
+    /// Title, generated from the first query's topics or set by the user.
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Whether the session is pinned to the top of the session list.
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 /// Contextual state accumulated during a conversation session.
@@ -196,6 +341,12 @@ pub struct ChatResponseBody {
     pub confidence: f32,
     /// Suggested follow-up queries.
     pub suggestions: Vec<String>,
+    /// Analytics results as a chartable table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<AnalyticsTable>,
+    /// Comparison sides or timeline windows of a multi-step answer.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<ResponseSection>,
 }
 
 /// Response body for the chat history endpoint.
@@ -229,6 +380,7 @@ pub struct ChatSessionsResponse {
 
 /// Summary of a chat session.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ChatSessionSummary {
     /// Session identifier.
     pub id: Uuid,
@@ -238,6 +390,149 @@ pub struct ChatSessionSummary {
     pub last_message_at: String,
     /// Number of messages exchanged.
     pub message_count: u32,
+    /// Session title, once the first query was asked or it was renamed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Pinned sessions are listed first.
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// Changes to a chat session's title or pinning; unset fields are kept.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ChatSessionUpdate {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub pinned: Option<bool>,
+}
+
+/// A past chat message matching a message search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ChatMessageHit {
+    pub session_id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_title: Option<String>,
+    pub message_id: Uuid,
+    /// `user` or `assistant`.
+    pub role: String,
+    /// Matching part of the message, with matched terms in `**bold**`.
+    pub snippet: String,
+    /// When the message was sent (epoch seconds).
+    pub created_at: i64,
+}
+
+/// A rating of a chat answer or of one of its sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackRating {
+    /// Thumbs up: the answer (or source) was right.
+    Up,
+    /// Thumbs down: the answer (or source) was wrong.
+    Down,
+    /// The source had nothing to do with the question.
+    Irrelevant,
+}
+
+impl FeedbackRating {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeedbackRating::Up => "up",
+            FeedbackRating::Down => "down",
+            FeedbackRating::Irrelevant => "irrelevant",
+        }
+    }
+}
+
+impl std::str::FromStr for FeedbackRating {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "up" => Ok(FeedbackRating::Up),
+            "down" => Ok(FeedbackRating::Down),
+            "irrelevant" => Ok(FeedbackRating::Irrelevant),
+            _ => Err(format!("Unknown feedback rating: {}", s)),
+        }
+    }
+}
+
+/// Feedback on an assistant message, or on one of its sources when
+/// `chunk_id` is set. `irrelevant` always names a source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ChatFeedback {
+    pub message_id: Uuid,
+    pub rating: FeedbackRating,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_id: Option<Uuid>,
+}
+
+/// What recording a piece of feedback changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FeedbackRecorded {
+    pub id: Uuid,
+    /// Source boosts adjusted, one per source and query term.
+    pub adjusted_boosts: usize,
+    /// The user's semantic weight after this feedback.
+    pub semantic_weight: f32,
+}
+
+/// How feedback has shifted a user's chat retrieval.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FeedbackReport {
+    pub user: String,
+    pub counts: FeedbackCounts,
+    /// The user's tuned weight of semantic similarity.
+    pub semantic_weight: f32,
+    /// The weight before any feedback.
+    pub default_semantic_weight: f32,
+    /// Strongest learned boosts and penalties first.
+    pub boosts: Vec<SourceBoost>,
+    /// Rankings of recently rated questions without and with feedback.
+    pub shifts: Vec<RankingShift>,
+}
+
+/// Feedback entries per rating.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FeedbackCounts {
+    pub up: u64,
+    pub down: u64,
+    pub irrelevant: u64,
+}
+
+/// A learned boost (positive) or penalty (negative) of a source under a
+/// query term.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SourceBoost {
+    pub term: String,
+    pub chunk_id: Uuid,
+    pub score: f32,
+}
+
+/// How feedback reordered the sources retrieved for one question.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RankingShift {
+    pub query: String,
+    /// Sources ranked either way, in their ranking with feedback.
+    pub results: Vec<RankShift>,
+}
+
+/// A source's 1-based rank without and with feedback; `None` when it was
+/// not retrieved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RankShift {
+    pub chunk_id: Uuid,
+    pub baseline_rank: Option<usize>,
+    pub rank: Option<usize>,
 }
 
 // =============================================================================
@@ -289,15 +584,16 @@ pub struct ChatLlmConfig {
     pub model_path: String,
     /// Maximum tokens for generated responses.
     pub max_tokens: u32,
-    /// Temperature for response generation (0.0 to 1.0).
+    /// Temperature for response generation (0.0 to 2.0).
     pub temperature: f32,
 }
 
 impl ChatLlmConfig {
-    /// Clamp temperature to [0.0, 2.0] and max_tokens to [1, 4096].
+    /// Clamp temperature to [0.0, 2.0] and max_tokens to [1, 4096], the
+    /// bounds the config schema enforces.
     pub fn clamp(&mut self) {
-        self.temperature = self.temperature.clamp(0.0, 2.0);
-        self.max_tokens = self.max_tokens.clamp(1, 4096);
+        self.temperature = self.temperature.clamp(0.0, LLM_MAX_TEMPERATURE);
+        self.max_tokens = self.max_tokens.clamp(1, LLM_MAX_TOKENS);
     }
 }
 
@@ -451,6 +747,9 @@ mod tests {
             }),
             content_type: Some("screen".to_string()),
             app_filter: Some("Chrome".to_string()),
+            data_source: None,
+            analytics: None,
+            composition: None,
             raw_query: "what did Alice say about rust?".to_string(),
         };
         let json = serde_json::to_string(&sq).unwrap();
@@ -472,6 +771,9 @@ mod tests {
             time_range: None,
             content_type: None,
             app_filter: None,
+            data_source: None,
+            analytics: None,
+            composition: None,
             raw_query: "what happened today?".to_string(),
         };
         let json = serde_json::to_string(&sq).unwrap();
@@ -492,9 +794,12 @@ mod tests {
                 timestamp: "2026-02-19T10:00:00".to_string(),
                 source_app: "Chrome".to_string(),
                 relevance_score: 0.92,
+                retrieval: RetrievalPath::Keyword,
             }],
             confidence: 0.85,
             suggestions: vec!["Tell me more".to_string()],
+            table: None,
+            sections: vec![],
         };
         let json = serde_json::to_string(&resp).unwrap();
         let rt: ChatResponse = serde_json::from_str(&json).unwrap();
@@ -513,6 +818,7 @@ mod tests {
             timestamp: "2026-02-19T10:00:00".to_string(),
             source_app: "Teams".to_string(),
             relevance_score: 0.78,
+            retrieval: RetrievalPath::Keyword,
         };
         let json = serde_json::to_string(&sr).unwrap();
         let rt: SourceRef = serde_json::from_str(&json).unwrap();
@@ -532,6 +838,8 @@ mod tests {
             last_message_at: 1700001000,
             context: SessionContext::default(),
             message_count: 5,
+            title: None,
+            pinned: false,
         };
         let json = serde_json::to_string(&session).unwrap();
         let rt: ConversationSession = serde_json::from_str(&json).unwrap();
@@ -626,6 +934,8 @@ mod tests {
                 sources: vec![],
                 confidence: 0.9,
                 suggestions: vec!["Try refining your query".to_string()],
+                table: None,
+                sections: vec![],
             },
             session_id: Uuid::new_v4(),
         };
@@ -670,12 +980,16 @@ mod tests {
                 started_at: "2026-02-19T10:00:00".to_string(),
                 last_message_at: "2026-02-19T10:05:00".to_string(),
                 message_count: 10,
+                title: Some("Pricing".to_string()),
+                pinned: true,
             }],
         };
         let json = serde_json::to_string(&resp).unwrap();
         let rt: ChatSessionsResponse = serde_json::from_str(&json).unwrap();
         assert_eq!(rt.sessions.len(), 1);
         assert_eq!(rt.sessions[0].message_count, 10);
+        assert_eq!(rt.sessions[0].title.as_deref(), Some("Pricing"));
+        assert!(rt.sessions[0].pinned);
     }
 
     // ---- Config defaults ----
@@ -818,6 +1132,9 @@ mod tests {
             time_range: None,
             content_type: None,
             app_filter: None,
+            data_source: None,
+            analytics: None,
+            composition: None,
             raw_query: String::new(),
         };
         let json = serde_json::to_string(&sq).unwrap();
@@ -837,6 +1154,9 @@ mod tests {
             time_range: None,
             content_type: None,
             app_filter: None,
+            data_source: None,
+            analytics: None,
+            composition: None,
             raw_query: "many topics".to_string(),
         };
         let json = serde_json::to_string(&sq).unwrap();
@@ -885,6 +1205,8 @@ mod tests {
             sources: vec![],
             confidence: 0.0,
             suggestions: vec![],
+            table: None,
+            sections: vec![],
         };
         let json = serde_json::to_string(&resp).unwrap();
         let rt: ChatResponse = serde_json::from_str(&json).unwrap();
@@ -900,6 +1222,8 @@ mod tests {
             sources: vec![],
             confidence: 0.0,
             suggestions: vec![],
+            table: None,
+            sections: vec![],
         };
         let json = serde_json::to_string(&resp).unwrap();
         let rt: ChatResponse = serde_json::from_str(&json).unwrap();
@@ -913,6 +1237,8 @@ mod tests {
             sources: vec![],
             confidence: 1.0,
             suggestions: vec![],
+            table: None,
+            sections: vec![],
         };
         let json = serde_json::to_string(&resp).unwrap();
         let rt: ChatResponse = serde_json::from_str(&json).unwrap();
@@ -926,6 +1252,7 @@ mod tests {
             timestamp: "2026-02-19T00:00:00".to_string(),
             source_app: String::new(),
             relevance_score: 0.0,
+            retrieval: RetrievalPath::Keyword,
         };
         let json = serde_json::to_string(&sr).unwrap();
         let rt: SourceRef = serde_json::from_str(&json).unwrap();
@@ -941,6 +1268,8 @@ mod tests {
             last_message_at: 0,
             context: SessionContext::default(),
             message_count: 0,
+            title: None,
+            pinned: false,
         };
         let json = serde_json::to_string(&session).unwrap();
         let rt: ConversationSession = serde_json::from_str(&json).unwrap();
@@ -959,6 +1288,9 @@ mod tests {
                     time_range: None,
                     content_type: None,
                     app_filter: None,
+                    data_source: None,
+                    analytics: None,
+                    composition: None,
                     raw_query: "find rust".to_string(),
                 },
                 response: ChatResponse {
@@ -966,6 +1298,8 @@ mod tests {
                     sources: vec![],
                     confidence: 0.8,
                     suggestions: vec![],
+                    table: None,
+                    sections: vec![],
                 },
             }],
             active_topic: Some("rust".to_string()),
@@ -1,38 +1,231 @@
-//! Voice interface stub for the conversational engine.
+//! Voice queries for the conversational engine.
 //!
-//! Provides a placeholder for voice capture flow. Actual Windows hotkey
-//! registration and audio capture happen in `engram-app`.
+//! A [`VoiceInterface`] records a spoken question from a [`VoiceAudioSource`]
+//! and transcribes it with a Whisper [`DynTranscriptionService`]. In the app
+//! the source is the dictation engine, which the audio capture loop feeds
+//! while a session is listening and which is topped up from the capture
+//! buffer when recording stops; tests and replays read a WAV file instead.
+//! The orchestrator answers the transcript like a typed message.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use engram_audio::AudioBuffer;
+use engram_core::types::DictationMode;
+use engram_dictation::DictationEngine;
+use engram_whisper::DynTranscriptionService;
 
 use crate::error::ChatError;
+use crate::types::ChatResponse;
+
+/// Audio recorded for one voice query.
+#[derive(Debug, Clone, Default)]
+pub struct RecordedAudio {
+    /// Mono PCM samples in [-1.0, 1.0].
+    pub samples: Vec<f32>,
+    /// Sample rate in Hz.
+    pub sample_rate: u32,
+}
+
+/// Where the audio of a voice query comes from.
+pub trait VoiceAudioSource: Send + Sync {
+    /// Whether recording can start on this machine.
+    fn is_available(&self) -> bool;
+
+    /// Begin recording.
+    fn start(&self) -> Result<(), ChatError>;
+
+    /// End recording and return what was captured.
+    fn stop(&self) -> Result<RecordedAudio, ChatError>;
+}
+
+/// Records through the dictation engine's session buffer.
+///
+/// Starting opens a store-only dictation session; stopping takes its audio
+/// and cancels the session, so nothing is typed or stored as dictation.
+/// The app's audio capture loop pushes microphone samples into the session
+/// while it listens. With a capture buffer attached, audio still waiting
+/// there is dropped on start (it was spoken before the question) and moved
+/// into the session on stop, so the recording does not depend on when the
+/// loop last ran.
+pub struct DictationAudioSource {
+    engine: Arc<DictationEngine>,
+    sample_rate: u32,
+    capture: Option<AudioBuffer>,
+}
+
+impl DictationAudioSource {
+    /// Record via `engine`, whose audio arrives at `sample_rate` Hz.
+    pub fn new(engine: Arc<DictationEngine>, sample_rate: u32) -> Self {
+        Self {
+            engine,
+            sample_rate,
+            capture: None,
+        }
+    }
+
+    /// Flush `buffer`, the microphone capture buffer, on start and stop.
+    pub fn with_capture_buffer(mut self, buffer: AudioBuffer) -> Self {
+        self.capture = Some(buffer);
+        self
+    }
+}
+
+impl VoiceAudioSource for DictationAudioSource {
+    fn is_available(&self) -> bool {
+        // Microphone capture only runs on Windows.
+        cfg!(target_os = "windows")
+    }
+
+    fn start(&self) -> Result<(), ChatError> {
+        if let Some(capture) = &self.capture {
+            capture.take();
+        }
+        self.engine
+            .start_dictation(
+                "Engram".to_string(),
+                "Voice query".to_string(),
+                DictationMode::StoreOnly,
+            )
+            .map_err(|e| ChatError::VoiceError(e.to_string()))
+    }
 
-/// Stub voice interface for managing voice capture state.
+    fn stop(&self) -> Result<RecordedAudio, ChatError> {
+        if let Some(capture) = &self.capture {
+            let pending = capture.take();
+            if !pending.is_empty() {
+                self.engine
+                    .push_audio(&pending)
+                    .map_err(|e| ChatError::VoiceError(e.to_string()))?;
+            }
+        }
+        let samples = self
+            .engine
+            .current_session()
+            .map_err(|e| ChatError::VoiceError(e.to_string()))?
+            .map(|session| session.audio_buffer)
+            .unwrap_or_default();
+        self.engine
+            .cancel_dictation()
+            .map_err(|e| ChatError::VoiceError(e.to_string()))?;
+        Ok(RecordedAudio {
+            samples,
+            sample_rate: self.sample_rate,
+        })
+    }
+}
+
+/// Plays back a WAV file as the recording, for tests and replays.
+pub struct WavFileSource {
+    path: PathBuf,
+}
+
+impl WavFileSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl VoiceAudioSource for WavFileSource {
+    fn is_available(&self) -> bool {
+        self.path.is_file()
+    }
+
+    fn start(&self) -> Result<(), ChatError> {
+        if !self.is_available() {
+            return Err(ChatError::VoiceError(format!(
+                "WAV file not found: {}",
+                self.path.display()
+            )));
+        }
+        Ok(())
+    }
+
+    fn stop(&self) -> Result<RecordedAudio, ChatError> {
+        let (samples, sample_rate) =
+            engram_audio::read_wav(&self.path).map_err(|e| ChatError::VoiceError(e.to_string()))?;
+        Ok(RecordedAudio {
+            samples,
+            sample_rate,
+        })
+    }
+}
+
+/// A voice query and its answer.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct VoiceAnswer {
+    /// What was heard.
+    pub transcript: String,
+    pub session_id: Uuid,
+    pub response: ChatResponse,
+}
+
+/// Voice query state, for the tray panel.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct VoiceStatus {
+    /// Whether a recording can be started.
+    pub available: bool,
+    /// Whether a recording is in progress.
+    pub listening: bool,
+    pub hotkey: String,
+    pub max_duration_seconds: u32,
+    /// The most recent answered voice query.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last: Option<VoiceAnswer>,
+}
+
+/// Records and transcribes voice queries.
 pub struct VoiceInterface {
     /// Hotkey combination to activate voice capture.
     pub hotkey: String,
-    /// Maximum recording duration in seconds.
+    /// Maximum recording duration in seconds; longer recordings are cut.
+    /// `0` means no limit.
     pub max_duration_seconds: u32,
     /// Whether voice capture is currently active.
     pub active: bool,
+    source: Option<Box<dyn VoiceAudioSource>>,
+    transcription: Option<Arc<dyn DynTranscriptionService>>,
 }
 
 impl VoiceInterface {
     /// Create a new voice interface with the given hotkey and max duration.
+    ///
+    /// It is unavailable until an audio source and a transcription service
+    /// are attached.
     pub fn new(hotkey: String, max_duration_seconds: u32) -> Self {
         Self {
             hotkey,
             max_duration_seconds,
             active: false,
+            source: None,
+            transcription: None,
         }
     }
 
-    /// Check if voice capture is available on the current platform.
+    /// Record from `source`.
+    pub fn with_source(mut self, source: impl VoiceAudioSource + 'static) -> Self {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    /// Transcribe recordings with `transcription`.
+    pub fn with_transcription(mut self, transcription: Arc<dyn DynTranscriptionService>) -> Self {
+        self.transcription = Some(transcription);
+        self
+    }
+
+    /// Check if voice capture is available: the audio source can record
+    /// and a transcription service is attached.
     pub fn is_available(&self) -> bool {
-        cfg!(target_os = "windows")
+        self.transcription.is_some() && self.source.as_ref().is_some_and(|s| s.is_available())
     }
 
-    /// Start listening for voice input.
-    ///
-    /// This is a stub; the real implementation will use Windows audio APIs.
+    /// Start recording a voice query.
     pub fn start_listening(&mut self) -> Result<(), ChatError> {
         if self.active {
             return Err(ChatError::VoiceError(
@@ -41,25 +234,45 @@ impl VoiceInterface {
         }
         if !self.is_available() {
             return Err(ChatError::VoiceError(
-                "Voice capture is only available on Windows".to_string(),
+                "Voice capture is not available".to_string(),
             ));
         }
+        if let Some(source) = &self.source {
+            source.start()?;
+        }
         self.active = true;
         Ok(())
     }
 
-    /// Stop listening and return any captured text.
+    /// Stop recording and return the transcribed text.
     ///
-    /// This is a stub; returns `None` since no actual capture occurs.
-    pub fn stop_listening(&mut self) -> Result<Option<String>, ChatError> {
+    /// Returns `None` when nothing was recorded or no speech was recognised.
+    pub async fn stop_listening(&mut self) -> Result<Option<String>, ChatError> {
         if !self.active {
             return Err(ChatError::VoiceError(
                 "Voice capture is not active".to_string(),
             ));
         }
         self.active = false;
-        // Stub: no actual transcription
-        Ok(None)
+
+        let (Some(source), Some(transcription)) = (&self.source, &self.transcription) else {
+            return Ok(None);
+        };
+        let mut audio = source.stop()?;
+        if self.max_duration_seconds > 0 {
+            let max_samples = self.max_duration_seconds as usize * audio.sample_rate as usize;
+            audio.samples.truncate(max_samples);
+        }
+        if audio.samples.is_empty() {
+            return Ok(None);
+        }
+
+        let result = transcription
+            .transcribe_boxed(&audio.samples, audio.sample_rate)
+            .await
+            .map_err(|e| ChatError::VoiceError(e.to_string()))?;
+        let text = result.text.trim();
+        Ok((!text.is_empty()).then(|| text.to_string()))
     }
 }
 
@@ -70,6 +283,34 @@ impl VoiceInterface {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use engram_whisper::MockTranscriptionService;
+
+    /// Write `seconds` of a quiet tone as a 16 kHz mono WAV.
+    fn write_wav(dir: &tempfile::TempDir, seconds: u32) -> PathBuf {
+        let path = dir.path().join("query.wav");
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for i in 0..16000 * seconds {
+            let t = i as f32 / 16000.0;
+            let sample = (t * 440.0 * std::f32::consts::TAU).sin() * 0.1;
+            writer
+                .write_sample((sample * i16::MAX as f32) as i16)
+                .unwrap();
+        }
+        writer.finalize().unwrap();
+        path
+    }
+
+    fn wav_interface(path: PathBuf, max_duration_seconds: u32) -> VoiceInterface {
+        VoiceInterface::new("Ctrl+Shift+E".to_string(), max_duration_seconds)
+            .with_source(WavFileSource::new(path))
+            .with_transcription(Arc::new(MockTranscriptionService::new()))
+    }
 
     #[test]
     fn test_new_voice_interface() {
@@ -82,24 +323,22 @@ mod tests {
     #[test]
     fn test_is_available_returns_bool() {
         let vi = VoiceInterface::new("Ctrl+Shift+E".to_string(), 30);
-        // Just check it returns a bool without panicking
-        let _avail = vi.is_available();
+        // No source or transcription attached.
+        assert!(!vi.is_available());
     }
 
     #[test]
     fn test_start_listening_not_available() {
-        // On non-Windows, start_listening should fail
-        if !cfg!(target_os = "windows") {
-            let mut vi = VoiceInterface::new("Ctrl+Shift+E".to_string(), 30);
-            let result = vi.start_listening();
-            assert!(result.is_err());
-        }
+        let mut vi = VoiceInterface::new("Ctrl+Shift+E".to_string(), 30);
+        let result = vi.start_listening();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("not available"));
     }
 
-    #[test]
-    fn test_stop_listening_when_not_active() {
+    #[tokio::test]
+    async fn test_stop_listening_when_not_active() {
         let mut vi = VoiceInterface::new("Ctrl+Shift+E".to_string(), 30);
-        let result = vi.stop_listening();
+        let result = vi.stop_listening().await;
         assert!(result.is_err());
     }
 
@@ -127,11 +366,11 @@ mod tests {
         assert!(err_msg.contains("already active"));
     }
 
-    #[test]
-    fn test_stop_listening_when_not_active_returns_error() {
+    #[tokio::test]
+    async fn test_stop_listening_when_not_active_returns_error() {
         let mut vi = VoiceInterface::new("Ctrl+Shift+E".to_string(), 30);
         assert!(!vi.active);
-        let result = vi.stop_listening();
+        let result = vi.stop_listening().await;
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(err_msg.contains("not active"));
@@ -142,20 +381,18 @@ mod tests {
         let mut vi = VoiceInterface::new("Ctrl+Shift+E".to_string(), 30);
         assert!(!vi.active);
 
-        // On non-Windows, start should fail and active stays false
-        if !cfg!(target_os = "windows") {
-            let _ = vi.start_listening();
-            assert!(!vi.active);
-        }
+        // Unavailable, so start fails and active stays false
+        let _ = vi.start_listening();
+        assert!(!vi.active);
     }
 
-    #[test]
-    fn test_stop_listening_returns_none_stub() {
+    #[tokio::test]
+    async fn test_stop_listening_without_source_returns_none() {
         let mut vi = VoiceInterface::new("Ctrl+Shift+E".to_string(), 30);
         // Manually set active to test stop path
         vi.active = true;
-        let result = vi.stop_listening().unwrap();
-        assert!(result.is_none()); // stub always returns None
+        let result = vi.stop_listening().await.unwrap();
+        assert!(result.is_none()); // nothing was recorded
         assert!(!vi.active); // should be deactivated
     }
 
@@ -170,4 +407,68 @@ mod tests {
         let vi = VoiceInterface::new("Ctrl+Shift+E".to_string(), u32::MAX);
         assert_eq!(vi.max_duration_seconds, u32::MAX);
     }
+
+    #[tokio::test]
+    async fn test_wav_source_is_transcribed() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut vi = wav_interface(write_wav(&dir, 1), 30);
+        assert!(vi.is_available());
+
+        vi.start_listening().unwrap();
+        assert!(vi.active);
+        let text = vi.stop_listening().await.unwrap();
+        assert_eq!(text.as_deref(), Some("[mock transcription]"));
+        assert!(!vi.active);
+    }
+
+    #[tokio::test]
+    async fn test_missing_wav_is_unavailable() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut vi = wav_interface(dir.path().join("missing.wav"), 30);
+        assert!(!vi.is_available());
+        assert!(vi.start_listening().is_err());
+    }
+
+    #[test]
+    fn test_wav_source_reads_samples() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = WavFileSource::new(write_wav(&dir, 2));
+        source.start().unwrap();
+        let audio = source.stop().unwrap();
+        assert_eq!(audio.sample_rate, 16000);
+        assert_eq!(audio.samples.len(), 32000);
+    }
+
+    #[test]
+    fn test_dictation_source_discards_session() {
+        let engine = Arc::new(DictationEngine::new());
+        let source = DictationAudioSource::new(Arc::clone(&engine), 16000);
+        source.start().unwrap();
+        engine.push_audio(&[0.1; 800]).unwrap();
+
+        let audio = source.stop().unwrap();
+        assert_eq!(audio.samples.len(), 800);
+        assert_eq!(audio.sample_rate, 16000);
+        assert!(engine.current_session().unwrap().is_none());
+        // The engine is idle again, so dictation can start.
+        assert!(source.start().is_ok());
+    }
+
+    #[test]
+    fn test_dictation_source_flushes_capture_buffer() {
+        // The capture loop never runs: the question starts and ends between
+        // two of its ticks.
+        let engine = Arc::new(DictationEngine::new());
+        let capture = AudioBuffer::new(16000 * 30);
+        let source = DictationAudioSource::new(Arc::clone(&engine), 16000)
+            .with_capture_buffer(capture.clone());
+
+        capture.push(&[0.5; 4000]);
+        source.start().unwrap();
+        capture.push(&[0.1; 1600]);
+
+        let audio = source.stop().unwrap();
+        assert_eq!(audio.samples, vec![0.1; 1600]);
+        assert!(capture.is_empty());
+    }
 }
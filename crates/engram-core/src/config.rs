@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
@@ -9,7 +10,7 @@ use crate::error::{EngramError, Result};
 ///
 /// Loaded from `~/.engram/config.toml` by default. Each section corresponds
 /// to a bounded context or cross-cutting concern.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct EngramConfig {
     #[serde(default)]
     pub general: GeneralConfig,
@@ -33,25 +34,37 @@ pub struct EngramConfig {
     pub actions: ActionsConfig,
     #[serde(default)]
     pub chat: ChatConfig,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    #[serde(default)]
+    pub documents: DocumentsConfig,
+    #[serde(default)]
+    pub replay: ReplayConfig,
 }
 
 /// Conversational interface configuration (loaded from `[chat]` in config.toml).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct ChatConfig {
     /// Whether the chat interface is enabled.
     pub enabled: bool,
     /// Hotkey to activate voice query.
+    #[schemars(length(min = 1))]
     pub voice_hotkey: String,
     /// Number of recent turns to keep in session context.
+    #[schemars(range(min = 1, max = 50))]
     pub context_turns: usize,
     /// Session timeout in minutes.
+    #[schemars(range(min = 1, max = 1440))]
     pub session_timeout_minutes: u32,
     /// Maximum voice recording duration in seconds.
+    #[schemars(range(min = 1, max = 300))]
     pub max_voice_duration_seconds: u32,
     /// Default number of days to search back.
+    #[schemars(range(min = 1, max = 3650))]
     pub default_search_days: u32,
     /// Maximum results per query.
+    #[schemars(range(min = 1, max = 100))]
     pub max_results_per_query: usize,
     /// LLM configuration.
     #[serde(default)]
@@ -73,8 +86,14 @@ impl Default for ChatConfig {
     }
 }
 
+/// Largest accepted `chat.llm.max_tokens`.
+pub const LLM_MAX_TOKENS: u32 = 4096;
+
+/// Highest accepted `chat.llm.temperature`.
+pub const LLM_MAX_TEMPERATURE: f32 = 2.0;
+
 /// LLM-specific configuration for response generation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct ChatLlmConfig {
     /// Whether LLM-based response generation is enabled.
@@ -82,8 +101,10 @@ pub struct ChatLlmConfig {
     /// Path to the local LLM model.
     pub model_path: String,
     /// Maximum tokens for generated responses.
+    #[schemars(range(min = 1, max = LLM_MAX_TOKENS))]
     pub max_tokens: u32,
-    /// Temperature for response generation (0.0 to 1.0).
+    /// Temperature for response generation (0.0 to 2.0).
+    #[schemars(range(min = 0.0, max = LLM_MAX_TEMPERATURE))]
     pub temperature: f32,
 }
 
@@ -99,20 +120,25 @@ impl Default for ChatLlmConfig {
 }
 
 /// Action engine configuration (loaded from `[actions]` in config.toml).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct ActionsConfig {
     /// Whether the action engine is enabled.
     pub enabled: bool,
     /// Minimum confidence to create a task from an intent.
+    #[schemars(range(min = 0.0, max = 1.0))]
     pub min_confidence: f32,
     /// Confidence threshold for auto-execution without confirmation.
+    #[schemars(range(min = 0.0, max = 1.0))]
     pub auto_execute_threshold: f32,
     /// Days before a task expires.
+    #[schemars(range(min = 1, max = 365))]
     pub task_ttl_days: u32,
     /// Seconds before a confirmation request times out.
+    #[schemars(range(min = 1, max = 86400))]
     pub confirmation_timeout_seconds: u64,
     /// Maximum notifications per minute.
+    #[schemars(range(min = 1, max = 1000))]
     pub max_notifications_per_minute: u32,
 }
 
@@ -129,8 +155,185 @@ impl Default for ActionsConfig {
     }
 }
 
+/// Watched document folders (loaded from `[documents]` in config.toml).
+///
+/// Markdown, text and PDF files under `watch_dirs` are split into passages
+/// and indexed as `document` captures. Edited files are re-indexed and
+/// deleted files are removed on the next scan.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DocumentsConfig {
+    /// Whether watched folders are scanned.
+    pub enabled: bool,
+    /// Directories to index, searched recursively.
+    pub watch_dirs: Vec<String>,
+    /// File extensions to index (without the dot, case-insensitive).
+    pub extensions: Vec<String>,
+    /// Seconds between folder scans.
+    #[schemars(range(min = 5, max = 86400))]
+    pub scan_interval_secs: u64,
+    /// Target passage length in characters.
+    #[schemars(range(min = 200, max = 20000))]
+    pub passage_chars: usize,
+    /// Files larger than this are skipped.
+    #[schemars(range(min = 1, max = 1024))]
+    pub max_file_size_mb: u64,
+}
+
+impl Default for DocumentsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            watch_dirs: Vec::new(),
+            extensions: vec![
+                "md".to_string(),
+                "markdown".to_string(),
+                "txt".to_string(),
+                "pdf".to_string(),
+            ],
+            scan_interval_secs: 60,
+            passage_chars: 1500,
+            max_file_size_mb: 20,
+        }
+    }
+}
+
+/// Headless capture replay (loaded from `[replay]` in config.toml).
+///
+/// When enabled, screen and audio capture read a recorded session from
+/// `session_dir` instead of the live devices, so the full pipeline can run
+/// where Win32 capture and WASAPI are unavailable (CI, Linux dev boxes).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ReplayConfig {
+    /// Replace live screen and audio capture with the recorded session.
+    pub enabled: bool,
+    /// Directory containing `session.json` and the files it references.
+    pub session_dir: String,
+    /// Playback speed relative to the recording; 0 replays without delays.
+    #[schemars(range(min = 0.0, max = 1000.0))]
+    pub speed: f64,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            session_dir: String::new(),
+            speed: 1.0,
+        }
+    }
+}
+
+/// API rate limiting (loaded from `[rate_limit]` in config.toml).
+///
+/// Each API token gets its own token bucket per route class, so a client
+/// exhausting one budget does not affect other clients or other classes.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// Search, recall and other read endpoints.
+    pub search: RouteBudget,
+    /// `/ingest` and dictation control.
+    pub ingest: RouteBudget,
+    /// Config, storage and task/action mutations.
+    pub admin: RouteBudget,
+    /// `/chat` and chat session endpoints.
+    pub chat: RouteBudget,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            search: RouteBudget {
+                per_sec: 50,
+                burst: 100,
+            },
+            ingest: RouteBudget {
+                per_sec: 100,
+                burst: 200,
+            },
+            admin: RouteBudget {
+                per_sec: 10,
+                burst: 20,
+            },
+            chat: RouteBudget {
+                per_sec: 5,
+                burst: 10,
+            },
+        }
+    }
+}
+
+/// Token bucket budget for one route class.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RouteBudget {
+    /// Sustained requests per second (bucket refill rate).
+    #[schemars(range(min = 1, max = 100000))]
+    pub per_sec: u32,
+    /// Bucket capacity: requests allowed in a burst after idling.
+    #[schemars(range(min = 1, max = 100000))]
+    pub burst: u32,
+}
+
 // Default is derived via #[derive(Default)] on each sub-config's #[serde(default)].
 
+/// Config fields that running subsystems pick up without a restart.
+///
+/// Entries match a field exactly or as a section prefix (`safety` covers
+/// `safety.ssn_redaction`). Everything else is read once at startup.
+const LIVE_FIELDS: &[&str] = &[
+    "screen.enabled",
+    "screen.fps",
+    "screen.save_screenshots",
+    "search.query_log",
+    "storage.hot_days",
+    "storage.warm_days",
+    "storage.max_db_size_mb",
+    "storage.event_log_retention_days",
+    "safety",
+    "insight.summary_interval_minutes",
+    "insight.min_chunks_for_summary",
+    "insight.max_bullet_points",
+    "insight.digest_time",
+    "insight.export",
+    // `chat.enabled` is not live: the chat orchestrator is only built at
+    // startup when chat is enabled.
+    "chat.context_turns",
+    "chat.session_timeout_minutes",
+    "chat.default_search_days",
+    "chat.max_results_per_query",
+    "chat.llm.max_tokens",
+    "chat.llm.temperature",
+    "rate_limit",
+    "documents",
+];
+
+/// How a configuration change takes effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplyMode {
+    /// Running subsystems picked the change up immediately.
+    Live,
+    /// The change is saved but only takes effect after a restart.
+    Restart,
+}
+
+/// A single changed configuration field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChange {
+    /// Dotted field path, e.g. `screen.fps`.
+    pub field: String,
+    pub apply: ApplyMode,
+}
+
+impl ConfigChange {
+    /// Top-level section of the changed field.
+    pub fn section(&self) -> &str {
+        self.field.split('.').next().unwrap_or(&self.field)
+    }
+}
+
 impl EngramConfig {
     /// Load configuration from a TOML file.
     ///
@@ -170,6 +373,158 @@ impl EngramConfig {
         Ok(())
     }
 
+    /// JSON Schema (draft 2020-12) describing every config field, its type,
+    /// allowed values and numeric ranges.
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(EngramConfig)).unwrap_or_default()
+    }
+
+    /// Check every field against [`EngramConfig::json_schema`] plus the
+    /// cross-field rules the schema cannot express.
+    ///
+    /// Errors name the offending field by its dotted path, e.g.
+    /// `screen.fps must be greater than 0, got -3`.
+    pub fn validate(&self) -> Result<()> {
+        let schema = Self::json_schema();
+        let value = serde_json::to_value(self).map_err(|e| EngramError::Config(e.to_string()))?;
+        check_schema(&schema, &schema, "", &value)?;
+
+        if self.search.default_limit > self.search.max_limit {
+            return Err(EngramError::Config(
+                "search.default_limit must not exceed search.max_limit".into(),
+            ));
+        }
+        let valid_time = self
+            .insight
+            .digest_time
+            .split_once(':')
+            .and_then(|(h, m)| Some((h.parse::<u32>().ok()?, m.parse::<u32>().ok()?)))
+            .is_some_and(|(h, m)| h < 24 && m < 60 && self.insight.digest_time.len() == 5);
+        if !valid_time {
+            return Err(EngramError::Config(format!(
+                "insight.digest_time must be HH:MM, got '{}'",
+                self.insight.digest_time
+            )));
+        }
+        Ok(())
+    }
+
+    /// Deep-merge a JSON patch into a copy of this config.
+    ///
+    /// Objects merge recursively, so `{"screen":{"screenshot_storage":{"quality":50}}}`
+    /// leaves every other screen field as it was; any other value replaces
+    /// the field. Keys that are not config fields are rejected with their
+    /// path. The result is not validated; call [`EngramConfig::validate`].
+    pub fn merge_patch(&self, patch: &serde_json::Value) -> Result<EngramConfig> {
+        fn merge(
+            path: &str,
+            target: &mut serde_json::Value,
+            patch: &serde_json::Value,
+        ) -> Result<()> {
+            let (Some(target_obj), Some(patch_obj)) = (target.as_object_mut(), patch.as_object())
+            else {
+                *target = patch.clone();
+                return Ok(());
+            };
+            for (key, child) in patch_obj {
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                match target_obj.get_mut(key) {
+                    Some(existing) => merge(&child_path, existing, child)?,
+                    None if path.is_empty() => {
+                        return Err(EngramError::Config(format!(
+                            "Unknown configuration section: '{}'",
+                            key
+                        )))
+                    }
+                    None => {
+                        return Err(EngramError::Config(format!(
+                            "Unknown configuration field: '{}'",
+                            child_path
+                        )))
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        if !patch.is_object() {
+            return Err(EngramError::Config(
+                "Request body must be a JSON object".into(),
+            ));
+        }
+        let mut merged =
+            serde_json::to_value(self).map_err(|e| EngramError::Config(e.to_string()))?;
+        merge("", &mut merged, patch)?;
+        serde_json::from_value(merged)
+            .map_err(|e| EngramError::Config(format!("Invalid configuration value: {}", e)))
+    }
+
+    /// List the fields that differ between `self` and `other`, sorted by
+    /// path, with whether each change applies live or needs a restart.
+    pub fn diff(&self, other: &EngramConfig) -> Vec<ConfigChange> {
+        fn flatten(
+            prefix: &str,
+            value: &serde_json::Value,
+            out: &mut std::collections::BTreeMap<String, serde_json::Value>,
+        ) {
+            match value.as_object() {
+                Some(obj) => {
+                    for (key, child) in obj {
+                        let path = if prefix.is_empty() {
+                            key.clone()
+                        } else {
+                            format!("{}.{}", prefix, key)
+                        };
+                        flatten(&path, child, out);
+                    }
+                }
+                None => {
+                    out.insert(prefix.to_string(), value.clone());
+                }
+            }
+        }
+
+        let mut before = std::collections::BTreeMap::new();
+        let mut after = std::collections::BTreeMap::new();
+        flatten(
+            "",
+            &serde_json::to_value(self).unwrap_or_default(),
+            &mut before,
+        );
+        flatten(
+            "",
+            &serde_json::to_value(other).unwrap_or_default(),
+            &mut after,
+        );
+
+        let mut fields: Vec<&String> = before.keys().chain(after.keys()).collect();
+        fields.sort();
+        fields.dedup();
+        fields
+            .into_iter()
+            .filter(|field| before.get(*field) != after.get(*field))
+            .map(|field| {
+                let live = LIVE_FIELDS.iter().any(|live| {
+                    field == live
+                        || (field.starts_with(live)
+                            && field.as_bytes().get(live.len()) == Some(&b'.'))
+                });
+                ConfigChange {
+                    field: field.clone(),
+                    apply: if live {
+                        ApplyMode::Live
+                    } else {
+                        ApplyMode::Restart
+                    },
+                }
+            })
+            .collect()
+    }
+
     /// Save the current configuration to a TOML file.
     pub fn save(&self, path: &Path) -> Result<()> {
         if let Some(parent) = path.parent() {
@@ -183,13 +538,124 @@ impl EngramConfig {
     }
 }
 
+/// Recursively check `value` against `schema`, resolving `$ref`s in `root`.
+///
+/// Supports the subset of JSON Schema that `schemars` emits for the config
+/// types: `$ref`, `enum`, `const`, `oneOf`/`anyOf`, numeric bounds,
+/// `minLength`, `properties` and `items`.
+fn check_schema(
+    root: &serde_json::Value,
+    schema: &serde_json::Value,
+    path: &str,
+    value: &serde_json::Value,
+) -> Result<()> {
+    let invalid = |msg: String| Err(EngramError::Config(format!("{} {}", path, msg)));
+
+    if let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+        let target = reference
+            .strip_prefix("#/")
+            .map(|pointer| format!("/{}", pointer))
+            .and_then(|pointer| root.pointer(&pointer));
+        if let Some(target) = target {
+            check_schema(root, target, path, value)?;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array()) {
+        if !allowed.contains(value) {
+            return invalid(format!("must be one of {}, got {}", list(allowed), value));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            return invalid(format!("must be {}, got {}", expected, value));
+        }
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(variants) = schema.get(key).and_then(|v| v.as_array()) {
+            let matched = variants
+                .iter()
+                .any(|variant| check_schema(root, variant, path, value).is_ok());
+            if !matched {
+                let consts: Vec<serde_json::Value> = variants
+                    .iter()
+                    .filter_map(|variant| variant.get("const").cloned())
+                    .collect();
+                return if consts.is_empty() {
+                    invalid(format!("has an invalid value: {}", value))
+                } else {
+                    invalid(format!("must be one of {}, got {}", list(&consts), value))
+                };
+            }
+        }
+    }
+
+    if let Some(number) = value.as_f64() {
+        let bound = |key: &str| schema.get(key).and_then(|b| b.as_f64());
+        if let Some(min) = bound("minimum").filter(|min| number < *min) {
+            return invalid(format!("must be at least {}, got {}", min, value));
+        }
+        if let Some(max) = bound("maximum").filter(|max| number > *max) {
+            return invalid(format!("must be at most {}, got {}", max, value));
+        }
+        if let Some(min) = bound("exclusiveMinimum").filter(|min| number <= *min) {
+            return invalid(format!("must be greater than {}, got {}", min, value));
+        }
+        if let Some(max) = bound("exclusiveMaximum").filter(|max| number >= *max) {
+            return invalid(format!("must be less than {}, got {}", max, value));
+        }
+    }
+
+    if let Some(text) = value.as_str() {
+        if let Some(min) = schema.get("minLength").and_then(|m| m.as_u64()) {
+            if (text.chars().count() as u64) < min {
+                return invalid("must not be empty".to_string());
+            }
+        }
+    }
+
+    if let (Some(properties), Some(obj)) = (
+        schema.get("properties").and_then(|p| p.as_object()),
+        value.as_object(),
+    ) {
+        for (key, child_schema) in properties {
+            if let Some(child) = obj.get(key) {
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                check_schema(root, child_schema, &child_path, child)?;
+            }
+        }
+    }
+
+    if let (Some(item_schema), Some(items)) = (schema.get("items"), value.as_array()) {
+        for (i, item) in items.iter().enumerate() {
+            check_schema(root, item_schema, &format!("{}[{}]", path, i), item)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn list(values: &[serde_json::Value]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// General application settings.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct GeneralConfig {
     /// Data directory for SQLite, vectors, screenshots, etc.
+    #[schemars(length(min = 1))]
     pub data_dir: String,
     /// Log level: trace, debug, info, warn, error.
+    #[schemars(with = "crate::types::LogLevel")]
     pub log_level: String,
     /// Whether to start Engram on system boot.
     pub autostart: bool,
@@ -199,6 +665,7 @@ pub struct GeneralConfig {
     pub embedding_model_dir: String,
     /// API server port.
     #[serde(default = "default_port")]
+    #[schemars(range(min = 1))]
     pub port: u16,
     /// Whether to start Engram on system boot (OS-level autostart).
     #[serde(default)]
@@ -206,12 +673,21 @@ pub struct GeneralConfig {
     /// Whether to minimize to system tray instead of closing.
     #[serde(default = "default_true")]
     pub minimize_to_tray: bool,
+    /// Number of recent event log entries available for SSE
+    /// `Last-Event-ID` replay.
+    #[serde(default = "default_stream_replay_events")]
+    #[schemars(range(min = 1, max = 100000))]
+    pub stream_replay_events: usize,
 }
 
 fn default_port() -> u16 {
     3030
 }
 
+fn default_stream_replay_events() -> usize {
+    1000
+}
+
 fn default_true() -> bool {
     true
 }
@@ -226,12 +702,13 @@ impl Default for GeneralConfig {
             port: 3030,
             start_on_boot: false,
             minimize_to_tray: true,
+            stream_replay_events: 1000,
         }
     }
 }
 
 /// System tray configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct TrayConfig {
     /// Show the tray icon.
@@ -240,12 +717,15 @@ pub struct TrayConfig {
     pub show_notifications: bool,
     /// Width of the tray panel in pixels.
     #[serde(default = "default_panel_width")]
+    #[schemars(range(min = 200, max = 2000))]
     pub panel_width: u32,
     /// Height of the tray panel in pixels.
     #[serde(default = "default_panel_height")]
+    #[schemars(range(min = 200, max = 2000))]
     pub panel_height: u32,
     /// Number of recent items to show in the tray panel.
     #[serde(default = "default_recent_items")]
+    #[schemars(range(min = 1, max = 100))]
     pub recent_items: u32,
     /// Show statistics in the tray panel.
     #[serde(default = "default_true")]
@@ -282,12 +762,14 @@ impl Default for TrayConfig {
 }
 
 /// Screen capture configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct ScreenConfig {
     /// Capture frames per second.
+    #[schemars(range(max = 30.0), extend("exclusiveMinimum" = 0.0))]
     pub fps: f64,
     /// OCR engine: "windows-native" or "tesseract".
+    #[schemars(with = "crate::types::OcrEngineType")]
     pub ocr_engine: String,
     /// Window titles to ignore (substring match).
     pub ignored_windows: Vec<String>,
@@ -303,6 +785,7 @@ pub struct ScreenConfig {
     pub enabled: bool,
     /// Screenshot quality for capture (1-100). Separate from screenshot_storage.quality.
     #[serde(default = "default_screenshot_quality")]
+    #[schemars(range(min = 1, max = 100))]
     pub screenshot_quality: u8,
     /// Whether to capture screenshots when the window is unfocused.
     #[serde(default = "default_true")]
@@ -330,14 +813,17 @@ impl Default for ScreenConfig {
 }
 
 /// Screenshot storage sub-configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct ScreenshotStorageConfig {
     /// Format: "png" or "jpeg".
+    #[schemars(extend("enum" = ["png", "jpeg"]))]
     pub format: String,
     /// JPEG quality (1-100).
+    #[schemars(range(min = 1, max = 100))]
     pub quality: u8,
     /// Maximum screenshots to retain.
+    #[schemars(range(min = 1))]
     pub max_count: u64,
 }
 
@@ -352,18 +838,22 @@ impl Default for ScreenshotStorageConfig {
 }
 
 /// Audio capture configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct AudioConfig {
     /// Whether audio capture is enabled.
     pub enabled: bool,
     /// Audio chunk duration in seconds.
+    #[schemars(range(min = 1, max = 300))]
     pub chunk_duration_secs: u32,
     /// VAD engine: "silero" or "webrtc".
+    #[schemars(with = "crate::types::VadEngine")]
     pub vad_engine: String,
     /// VAD sensitivity: "low", "medium", "high".
+    #[schemars(with = "crate::types::VadSensitivity")]
     pub vad_sensitivity: String,
     /// Whisper model size: "tiny", "base", "small".
+    #[schemars(extend("enum" = ["tiny", "base", "small"]))]
     pub whisper_model: String,
     /// Audio file storage configuration.
     #[serde(default)]
@@ -373,18 +863,22 @@ pub struct AudioConfig {
     pub source_device: Option<String>,
     /// Virtual device name for audio routing.
     #[serde(default = "default_virtual_device_name")]
+    #[schemars(length(min = 1))]
     pub virtual_device_name: String,
     /// Transcription engine: "whisper" or other.
     #[serde(default = "default_transcription_engine")]
+    #[schemars(length(min = 1))]
     pub transcription_engine: String,
     /// Language for transcription ("auto" for automatic detection).
     #[serde(default = "default_language")]
+    #[schemars(length(min = 1))]
     pub language: String,
     /// Whether to store raw audio files.
     #[serde(default)]
     pub store_audio_files: bool,
     /// Number of days to retain audio files.
     #[serde(default = "default_audio_retention_days")]
+    #[schemars(range(min = 1, max = 3650))]
     pub audio_retention_days: u32,
 }
 
@@ -424,14 +918,16 @@ impl Default for AudioConfig {
 }
 
 /// Audio file storage sub-configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct AudioFileStorageConfig {
     /// Whether to save audio files.
     pub save_audio: bool,
     /// Format: "wav" or "opus".
+    #[schemars(extend("enum" = ["wav", "opus"]))]
     pub format: String,
     /// Maximum audio files to retain.
+    #[schemars(range(min = 1))]
     pub max_count: u64,
 }
 
@@ -446,18 +942,23 @@ impl Default for AudioFileStorageConfig {
 }
 
 /// Dictation configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct DictationConfig {
     /// Global hotkey to activate dictation.
+    #[schemars(length(min = 1))]
     pub hotkey: String,
     /// Default dictation mode.
+    #[schemars(with = "crate::types::DictationMode")]
     pub default_mode: String,
     /// Maximum dictation duration in seconds.
+    #[schemars(range(min = 1, max = 600))]
     pub max_duration_secs: u32,
     /// Silence timeout in milliseconds before auto-stop.
+    #[schemars(range(min = 100, max = 60000))]
     pub silence_timeout_ms: u32,
     /// Overlay indicator position.
+    #[schemars(with = "crate::types::OverlayPosition")]
     pub overlay_position: String,
     /// Whether dictation is enabled.
     #[serde(default = "default_true")]
@@ -470,9 +971,11 @@ pub struct DictationConfig {
     pub store_dictation: bool,
     /// Transcription engine for dictation.
     #[serde(default = "default_transcription_engine")]
+    #[schemars(length(min = 1))]
     pub transcription_engine: String,
     /// Language for dictation transcription.
     #[serde(default = "default_language")]
+    #[schemars(length(min = 1))]
     pub language: String,
 }
 
@@ -494,30 +997,41 @@ impl Default for DictationConfig {
 }
 
 /// Search configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct SearchConfig {
     /// Embedding model name.
+    #[schemars(length(min = 1))]
     pub embedding_model: String,
     /// Embedding dimension.
+    #[schemars(range(min = 1, max = 4096))]
     pub embedding_dim: usize,
     /// Default number of results.
+    #[schemars(range(min = 1, max = 1000))]
     pub default_limit: usize,
     /// Maximum number of results.
+    #[schemars(range(min = 1, max = 1000))]
     pub max_limit: usize,
     /// Cosine similarity threshold for deduplication.
+    #[schemars(range(min = 0.0, max = 1.0))]
     pub dedup_threshold: f64,
     /// Default semantic weight for hybrid search (0.0 to 1.0).
+    #[schemars(range(min = 0.0, max = 1.0))]
     pub semantic_weight: f64,
     /// Search engine type: "hybrid", "semantic", "keyword".
     #[serde(default = "default_search_engine")]
+    #[schemars(extend("enum" = ["hybrid", "semantic", "keyword"]))]
     pub engine: String,
     /// Whether to redact PII in search results.
     #[serde(default = "default_true")]
     pub pii_redaction: bool,
     /// Vector quantization format for search index.
     #[serde(default = "default_quantization")]
+    #[schemars(extend("enum" = ["float32", "int8", "binary"]))]
     pub quantization: String,
+    /// Record queries, latency and result counts to the local search log
+    /// (opt-in; powers `/search/analytics`).
+    pub query_log: bool,
 }
 
 fn default_search_engine() -> String {
@@ -540,22 +1054,30 @@ impl Default for SearchConfig {
             engine: "hybrid".to_string(),
             pii_redaction: true,
             quantization: "float32".to_string(),
+            query_log: false,
         }
     }
 }
 
 /// Storage and retention configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct StorageConfig {
     /// Days in the Hot tier (full precision).
+    #[schemars(range(min = 1, max = 3650))]
     pub hot_days: u32,
     /// Days in the Warm tier (int8 vectors).
+    #[schemars(range(min = 1, max = 3650))]
     pub warm_days: u32,
     /// Hours between purge cycles.
+    #[schemars(range(min = 1, max = 168))]
     pub purge_interval_hours: u32,
     /// Maximum database size in MB.
+    #[schemars(range(min = 1))]
     pub max_db_size_mb: u64,
+    /// Days to keep entries in the durable domain event log (0 = forever).
+    #[schemars(range(max = 3650))]
+    pub event_log_retention_days: u32,
     /// Quantization settings by tier.
     #[serde(default)]
     pub quantization: QuantizationConfig,
@@ -568,20 +1090,24 @@ impl Default for StorageConfig {
             warm_days: 30,
             purge_interval_hours: 6,
             max_db_size_mb: 2048,
+            event_log_retention_days: 30,
             quantization: QuantizationConfig::default(),
         }
     }
 }
 
 /// Vector quantization settings by storage tier.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct QuantizationConfig {
     /// Vector format for the Hot tier.
+    #[schemars(extend("enum" = ["f32", "int8", "binary"]))]
     pub hot_format: String,
     /// Vector format for the Warm tier.
+    #[schemars(extend("enum" = ["f32", "int8", "binary"]))]
     pub warm_format: String,
     /// Vector format for the Cold tier.
+    #[schemars(extend("enum" = ["f32", "int8", "binary"]))]
     pub cold_format: String,
 }
 
@@ -596,20 +1122,25 @@ impl Default for QuantizationConfig {
 }
 
 /// Configuration for the intelligence/insight pipeline.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct InsightConfig {
     /// Whether the insight pipeline is enabled.
     pub enabled: bool,
     /// Minutes between automatic summarization runs.
+    #[schemars(range(min = 1, max = 1440))]
     pub summary_interval_minutes: u32,
     /// Minimum number of chunks required to generate a summary.
+    #[schemars(range(min = 1, max = 1000))]
     pub min_chunks_for_summary: u32,
     /// Time of day to generate the daily digest (HH:MM format).
+    #[schemars(regex(pattern = r"^([01][0-9]|2[0-3]):[0-5][0-9]$"))]
     pub digest_time: String,
     /// Cosine similarity threshold for topic clustering.
+    #[schemars(range(min = 0.0, max = 1.0))]
     pub cluster_threshold: f64,
     /// Maximum number of bullet points per summary.
+    #[schemars(range(min = 1, max = 50))]
     pub max_bullet_points: u32,
     /// Export configuration for vault/Obsidian integration.
     #[serde(default)]
@@ -651,7 +1182,7 @@ impl Default for InsightConfig {
 }
 
 /// Export configuration for the insight pipeline.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct InsightExportConfig {
     /// Whether export is enabled.
@@ -659,6 +1190,7 @@ pub struct InsightExportConfig {
     /// Path to the Obsidian vault directory.
     pub vault_path: String,
     /// Export format: "obsidian" or other.
+    #[schemars(length(min = 1))]
     pub format: String,
     /// Whether to export the daily digest.
     pub export_daily_digest: bool,
@@ -686,7 +1218,7 @@ impl Default for InsightExportConfig {
 }
 
 /// Safety gate configuration for PII detection and content redaction.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct SafetyConfig {
     /// Enable PII detection (email addresses).
@@ -1124,6 +1656,7 @@ cold_format = "binary"
         let storage = StorageConfig::default();
         assert_eq!(storage.hot_days, 7);
         assert_eq!(storage.warm_days, 30);
+        assert_eq!(storage.event_log_retention_days, 30);
 
         let quant = QuantizationConfig::default();
         assert_eq!(quant.hot_format, "f32");
@@ -1185,6 +1718,58 @@ cold_format = "binary"
         assert_eq!(g.port, 3030);
         assert!(!g.start_on_boot);
         assert!(g.minimize_to_tray);
+        assert_eq!(g.stream_replay_events, 1000);
+    }
+
+    #[test]
+    fn test_rate_limit_config_from_toml() {
+        let toml_str = r#"
+[rate_limit.chat]
+per_sec = 2
+burst = 4
+"#;
+        let config: EngramConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.rate_limit.chat,
+            RouteBudget {
+                per_sec: 2,
+                burst: 4
+            }
+        );
+        assert_eq!(config.rate_limit.search, RateLimitConfig::default().search);
+    }
+
+    #[test]
+    fn test_documents_config_from_toml() {
+        let toml_str = r#"
+[documents]
+enabled = true
+watch_dirs = ["~/notes", "D:/runbooks"]
+passage_chars = 800
+"#;
+        let config: EngramConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.documents.enabled);
+        assert_eq!(config.documents.watch_dirs, vec!["~/notes", "D:/runbooks"]);
+        assert_eq!(config.documents.passage_chars, 800);
+        assert_eq!(config.documents.scan_interval_secs, 60);
+        assert!(config.documents.extensions.contains(&"pdf".to_string()));
+        assert!(!DocumentsConfig::default().enabled);
+    }
+
+    #[test]
+    fn test_replay_config_from_toml() {
+        let toml_str = r#"
+[replay]
+enabled = true
+session_dir = "fixtures/standup"
+speed = 0.0
+"#;
+        let config: EngramConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.replay.enabled);
+        assert_eq!(config.replay.session_dir, "fixtures/standup");
+        assert_eq!(config.replay.speed, 0.0);
+        assert!(!ReplayConfig::default().enabled);
+        assert_eq!(ReplayConfig::default().speed, 1.0);
     }
 
     #[test]
@@ -1222,6 +1807,7 @@ cold_format = "binary"
         assert_eq!(s.engine, "hybrid");
         assert!(s.pii_redaction);
         assert_eq!(s.quantization, "float32");
+        assert!(!s.query_log);
     }
 
     #[test]
@@ -1572,4 +2158,175 @@ data_dir = "/old/data"
         assert_eq!(config.insight.summary_interval_minutes, 30);
         assert!(!config.insight.export.enabled);
     }
+
+    #[test]
+    fn test_validate_accepts_defaults() {
+        assert!(EngramConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_values() {
+        let mut config = EngramConfig::default();
+        config.screen.fps = 0.0;
+        assert!(config.validate().is_err());
+
+        let mut config = EngramConfig::default();
+        config.search.semantic_weight = 1.5;
+        assert!(config.validate().is_err());
+
+        let mut config = EngramConfig::default();
+        config.insight.digest_time = "25:00".to_string();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("insight.digest_time"), "{}", err);
+
+        let mut config = EngramConfig::default();
+        config.rate_limit.chat.burst = 0;
+        assert!(config.validate().is_err());
+
+        let mut config = EngramConfig::default();
+        config.documents.passage_chars = 10;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("documents.passage_chars"), "{}", err);
+
+        let mut config = EngramConfig::default();
+        config.screen.fps = -3.0;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("screen.fps must be greater than 0"), "{}", err);
+
+        let mut config = EngramConfig::default();
+        config.audio.vad_sensitivity = "banana".to_string();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(
+            err.contains("audio.vad_sensitivity must be one of"),
+            "{}",
+            err
+        );
+        assert!(err.contains("\"medium\""), "{}", err);
+
+        let mut config = EngramConfig::default();
+        config.screen.screenshot_storage.quality = 0;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(
+            err.contains("screen.screenshot_storage.quality must be at least 1"),
+            "{}",
+            err
+        );
+
+        let mut config = EngramConfig::default();
+        config.storage.quantization.warm_format = "f16".to_string();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("storage.quantization.warm_format"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_chat_llm_bounds() {
+        let mut config = EngramConfig::default();
+        config.chat.llm.temperature = LLM_MAX_TEMPERATURE;
+        config.chat.llm.max_tokens = LLM_MAX_TOKENS;
+        assert!(config.validate().is_ok());
+
+        config.chat.llm.temperature = LLM_MAX_TEMPERATURE + 0.5;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("chat.llm.temperature"), "{}", err);
+
+        let mut config = EngramConfig::default();
+        config.chat.llm.max_tokens = LLM_MAX_TOKENS + 1;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("chat.llm.max_tokens"), "{}", err);
+    }
+
+    #[test]
+    fn test_merge_patch_is_recursive() {
+        let config = EngramConfig::default();
+        let patch = serde_json::json!({"screen": {"screenshot_storage": {"quality": 50}}});
+        let merged = config.merge_patch(&patch).unwrap();
+
+        assert_eq!(merged.screen.screenshot_storage.quality, 50);
+        assert_eq!(
+            merged.screen.screenshot_storage.format,
+            config.screen.screenshot_storage.format
+        );
+        assert_eq!(
+            merged.screen.screenshot_storage.max_count,
+            config.screen.screenshot_storage.max_count
+        );
+        assert_eq!(merged.screen.fps, config.screen.fps);
+    }
+
+    #[test]
+    fn test_merge_patch_rejects_unknown_and_mistyped_fields() {
+        let config = EngramConfig::default();
+
+        let err = config
+            .merge_patch(&serde_json::json!({"bogus": {}}))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Unknown configuration section: 'bogus'"),
+            "{}",
+            err
+        );
+
+        let err = config
+            .merge_patch(&serde_json::json!({"screen": {"screenshot_storage": {"qualty": 5}}}))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("screen.screenshot_storage.qualty"), "{}", err);
+
+        let err = config
+            .merge_patch(&serde_json::json!({"screen": {"fps": "fast"}}))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Invalid configuration value"), "{}", err);
+
+        assert!(config.merge_patch(&serde_json::json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn test_json_schema_describes_ranges_and_enums() {
+        let schema = EngramConfig::json_schema();
+        let defs = &schema["$defs"];
+
+        assert_eq!(
+            defs["ScreenshotStorageConfig"]["properties"]["quality"]["minimum"],
+            1
+        );
+        assert_eq!(
+            defs["ScreenConfig"]["properties"]["fps"]["exclusiveMinimum"],
+            0.0
+        );
+        assert_eq!(
+            defs["SearchConfig"]["properties"]["engine"]["enum"],
+            serde_json::json!(["hybrid", "semantic", "keyword"])
+        );
+        assert_eq!(
+            defs["VadSensitivity"]["enum"],
+            serde_json::json!(["low", "medium", "high"])
+        );
+    }
+
+    #[test]
+    fn test_diff_reports_live_and_restart_fields() {
+        let before = EngramConfig::default();
+        let mut after = before.clone();
+        after.screen.fps = 2.0;
+        after.safety.ssn_redaction = false;
+        after.general.port = 4040;
+        after.screen.ignored_apps = vec!["Vault".to_string()];
+        after.chat.enabled = false;
+        after.chat.context_turns = 3;
+
+        let changes = before.diff(&after);
+        let find = |field: &str| changes.iter().find(|c| c.field == field).unwrap().apply;
+        assert_eq!(changes.len(), 6);
+        assert_eq!(find("screen.fps"), ApplyMode::Live);
+        assert_eq!(find("safety.ssn_redaction"), ApplyMode::Live);
+        assert_eq!(find("general.port"), ApplyMode::Restart);
+        assert_eq!(find("screen.ignored_apps"), ApplyMode::Restart);
+        assert_eq!(find("chat.enabled"), ApplyMode::Restart);
+        assert_eq!(find("chat.context_turns"), ApplyMode::Live);
+        assert_eq!(changes[0].section(), "chat");
+
+        assert!(before.diff(&before.clone()).is_empty());
+    }
 }
@@ -175,6 +175,34 @@ pub enum DomainEvent {
         timestamp: Timestamp,
     },
 
+    // =========================================================================
+    // Document Events
+    // =========================================================================
+    /// A document passage was stored.
+    DocumentIngested {
+        document_id: Uuid,
+        app_name: AppName,
+        title: String,
+        source_path: String,
+        passage_index: u32,
+        text_length: usize,
+        timestamp: Timestamp,
+    },
+
+    // =========================================================================
+    // Import Events
+    // =========================================================================
+    /// An import of externally recorded captures finished.
+    ImportCompleted {
+        job_id: Uuid,
+        path: String,
+        format: String,
+        imported: u64,
+        duplicates: u64,
+        failed: u64,
+        timestamp: Timestamp,
+    },
+
     // =========================================================================
     // Storage Events
     // =========================================================================
@@ -217,13 +245,25 @@ pub enum DomainEvent {
     // =========================================================================
     /// A search query was executed.
     SearchPerformed {
-        query: String,
+        /// The query text; `None` unless `search.query_log` is enabled.
+        #[serde(default)]
+        query: Option<String>,
         result_count: usize,
         route: String,
         latency_ms: u64,
         timestamp: Timestamp,
     },
 
+    /// A saved search found captures that were not present on its last run.
+    SavedSearchMatched {
+        search_id: Uuid,
+        name: String,
+        query: String,
+        new_match_count: usize,
+        capture_ids: Vec<Uuid>,
+        timestamp: Timestamp,
+    },
+
     // =========================================================================
     // Configuration Events
     // =========================================================================
@@ -451,11 +491,14 @@ impl DomainEvent {
             | DomainEvent::DictationFailed { timestamp, .. }
             | DomainEvent::DictationSilenceTimeout { timestamp, .. }
             | DomainEvent::DictationMaxDuration { timestamp, .. }
+            | DomainEvent::DocumentIngested { timestamp, .. }
+            | DomainEvent::ImportCompleted { timestamp, .. }
             | DomainEvent::StorageTierChanged { timestamp, .. }
             | DomainEvent::VectorQuantized { timestamp, .. }
             | DomainEvent::StoragePurgeCompleted { timestamp, .. }
             | DomainEvent::PiiRedacted { timestamp, .. }
             | DomainEvent::SearchPerformed { timestamp, .. }
+            | DomainEvent::SavedSearchMatched { timestamp, .. }
             | DomainEvent::ConfigUpdated { timestamp, .. }
             | DomainEvent::ApplicationStarted { timestamp, .. }
             | DomainEvent::ApplicationShutdown { timestamp, .. }
@@ -520,11 +563,14 @@ impl DomainEvent {
             DomainEvent::DictationFailed { .. } => "dictation_failed",
             DomainEvent::DictationSilenceTimeout { .. } => "dictation_silence_timeout",
             DomainEvent::DictationMaxDuration { .. } => "dictation_max_duration",
+            DomainEvent::DocumentIngested { .. } => "document_ingested",
+            DomainEvent::ImportCompleted { .. } => "import_completed",
             DomainEvent::StorageTierChanged { .. } => "storage_tier_changed",
             DomainEvent::VectorQuantized { .. } => "vector_quantized",
             DomainEvent::StoragePurgeCompleted { .. } => "storage_purge_completed",
             DomainEvent::PiiRedacted { .. } => "pii_redacted",
             DomainEvent::SearchPerformed { .. } => "search_performed",
+            DomainEvent::SavedSearchMatched { .. } => "saved_search_matched",
             DomainEvent::ConfigUpdated { .. } => "config_updated",
             DomainEvent::ApplicationStarted { .. } => "application_started",
             DomainEvent::ApplicationShutdown { .. } => "application_shutdown",
@@ -774,7 +820,7 @@ mod tests {
         assert_eq!(pii.event_name(), "pii_redacted");
 
         let search = DomainEvent::SearchPerformed {
-            query: "meeting notes".to_string(),
+            query: Some("meeting notes".to_string()),
             result_count: 15,
             route: "hybrid".to_string(),
             latency_ms: 45,
@@ -924,7 +970,7 @@ mod tests {
                 timestamp: ts,
             },
             DomainEvent::SearchPerformed {
-                query: "test".to_string(),
+                query: Some("test".to_string()),
                 result_count: 5,
                 route: "semantic".to_string(),
                 latency_ms: 20,
@@ -1035,6 +1081,24 @@ mod tests {
                 approved: false,
                 timestamp: ts,
             },
+            DomainEvent::DocumentIngested {
+                document_id: id,
+                app_name: AppName("Obsidian".to_string()),
+                title: "Runbook".to_string(),
+                source_path: "/notes/runbook.md".to_string(),
+                passage_index: 0,
+                text_length: 42,
+                timestamp: ts,
+            },
+            DomainEvent::ImportCompleted {
+                job_id: id,
+                path: "/exports/screenpipe.jsonl".to_string(),
+                format: "screenpipe".to_string(),
+                imported: 120,
+                duplicates: 4,
+                failed: 1,
+                timestamp: ts,
+            },
             // Chat events
             DomainEvent::ChatQueryReceived {
                 session_id,
@@ -1253,7 +1317,7 @@ mod tests {
                 timestamp: ts,
             },
             DomainEvent::SearchPerformed {
-                query: "q".to_string(),
+                query: Some("q".to_string()),
                 result_count: 0,
                 route: "keyword".to_string(),
                 latency_ms: 1,
@@ -1364,6 +1428,15 @@ mod tests {
                 approved: true,
                 timestamp: ts,
             },
+            DomainEvent::DocumentIngested {
+                document_id: id,
+                app_name: AppName("Obsidian".to_string()),
+                title: "Runbook".to_string(),
+                source_path: "/notes/runbook.md".to_string(),
+                passage_index: 0,
+                text_length: 42,
+                timestamp: ts,
+            },
             // Chat events
             DomainEvent::ChatQueryReceived {
                 session_id,
@@ -1498,7 +1571,7 @@ mod tests {
     #[test]
     fn test_event_clone() {
         let event = DomainEvent::SearchPerformed {
-            query: "important query".to_string(),
+            query: Some("important query".to_string()),
             result_count: 42,
             route: "hybrid".to_string(),
             latency_ms: 100,
@@ -1830,7 +1903,7 @@ mod tests {
                 timestamp: ts,
             },
             DomainEvent::SearchPerformed {
-                query: "q".to_string(),
+                query: Some("q".to_string()),
                 result_count: 1,
                 route: "s".to_string(),
                 latency_ms: 1,
@@ -1940,6 +2013,23 @@ mod tests {
                 approved: true,
                 timestamp: ts,
             },
+            DomainEvent::SavedSearchMatched {
+                search_id: id,
+                name: "n".to_string(),
+                query: "q".to_string(),
+                new_match_count: 1,
+                capture_ids: vec![id],
+                timestamp: ts,
+            },
+            DomainEvent::DocumentIngested {
+                document_id: id,
+                app_name: AppName("Obsidian".to_string()),
+                title: "Runbook".to_string(),
+                source_path: "/notes/runbook.md".to_string(),
+                passage_index: 0,
+                text_length: 42,
+                timestamp: ts,
+            },
             // Chat events
             DomainEvent::ChatQueryReceived {
                 session_id: sid,
@@ -2442,7 +2532,7 @@ mod tests {
                 timestamp: ts,
             },
             DomainEvent::SearchPerformed {
-                query: "q".to_string(),
+                query: Some("q".to_string()),
                 result_count: 1,
                 route: "s".to_string(),
                 latency_ms: 1,
@@ -2554,6 +2644,23 @@ mod tests {
                 approved: true,
                 timestamp: ts,
             },
+            DomainEvent::SavedSearchMatched {
+                search_id: id,
+                name: "n".to_string(),
+                query: "q".to_string(),
+                new_match_count: 1,
+                capture_ids: vec![id],
+                timestamp: ts,
+            },
+            DomainEvent::DocumentIngested {
+                document_id: id,
+                app_name: AppName("Obsidian".to_string()),
+                title: "Runbook".to_string(),
+                source_path: "/notes/runbook.md".to_string(),
+                passage_index: 0,
+                text_length: 42,
+                timestamp: ts,
+            },
             // Chat events
             DomainEvent::ChatQueryReceived {
                 session_id: sid,
@@ -2588,6 +2695,29 @@ mod tests {
                 timestamp: ts,
             },
         ];
-        assert_eq!(events.len(), 51);
+        assert_eq!(events.len(), 53);
+    }
+
+    #[test]
+    fn test_saved_search_matched_event() {
+        let search_id = Uuid::new_v4();
+        let capture_id = Uuid::new_v4();
+        let event = DomainEvent::SavedSearchMatched {
+            search_id,
+            name: "Incidents".to_string(),
+            query: "INC-4521".to_string(),
+            new_match_count: 1,
+            capture_ids: vec![capture_id],
+            timestamp: Timestamp(1000),
+        };
+        assert_eq!(event.event_name(), "saved_search_matched");
+        assert_eq!(event.timestamp(), Timestamp(1000));
+
+        let json = event.to_json();
+        assert_eq!(json["event"], "saved_search_matched");
+        assert_eq!(
+            json["data"]["SavedSearchMatched"]["capture_ids"][0],
+            capture_id.to_string()
+        );
     }
 }
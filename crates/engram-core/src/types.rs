@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -16,10 +17,12 @@ pub enum ContentType {
     Audio,
     /// Voice dictation.
     Dictation,
+    /// A passage of a text document (notes, transcripts, logs).
+    Document,
 }
 
 /// Dictation output mode.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum DictationMode {
     /// Inject text into the focused window only.
@@ -135,7 +138,7 @@ pub enum QueryRoute {
 }
 
 /// Voice Activity Detection sensitivity level.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum VadSensitivity {
     Low,
@@ -145,7 +148,7 @@ pub enum VadSensitivity {
 }
 
 /// OCR engine selection.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum OcrEngineType {
     #[default]
@@ -154,7 +157,7 @@ pub enum OcrEngineType {
 }
 
 /// Position of the dictation overlay indicator.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum OverlayPosition {
     #[default]
@@ -164,7 +167,7 @@ pub enum OverlayPosition {
 }
 
 /// Application log level.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum LogLevel {
     Trace,
@@ -176,7 +179,7 @@ pub enum LogLevel {
 }
 
 /// VAD engine selection.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum VadEngine {
     #[default]
@@ -481,6 +484,26 @@ pub struct DictationEntry {
     pub mode: DictationMode,
 }
 
+/// A passage of a text document supplied by an ingestion source.
+///
+/// Long documents are split into passages; each passage is stored and
+/// embedded on its own and shares `source_path` with its siblings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentEntry {
+    pub id: Uuid,
+    pub content_type: ContentType,
+    pub timestamp: DateTime<Utc>,
+    /// Document title, shown where screen captures show the window title.
+    pub title: String,
+    /// File path or URI the document came from.
+    pub source_path: String,
+    /// Zero-based position of this passage within the document.
+    pub passage_index: u32,
+    /// Application or tool that produced the document.
+    pub app_name: String,
+    pub text: String,
+}
+
 /// A search result with relevance scoring.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
@@ -659,6 +682,12 @@ mod tests {
         assert_eq!(json, "\"dictation\"");
         let rt: ContentType = serde_json::from_str(&json).unwrap();
         assert_eq!(rt, ContentType::Dictation);
+
+        // Document
+        let json = serde_json::to_string(&ContentType::Document).unwrap();
+        assert_eq!(json, "\"document\"");
+        let rt: ContentType = serde_json::from_str(&json).unwrap();
+        assert_eq!(rt, ContentType::Document);
     }
 
     #[test]
@@ -82,13 +82,26 @@ impl HotkeyService {
     #[cfg(target_os = "windows")]
     pub fn was_pressed(&self) -> bool {
         use global_hotkey::GlobalHotKeyEvent;
+        use std::sync::Mutex;
 
-        if let Some(hotkey) = &self.hotkey {
-            if let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
-                return event.id() == hotkey.id();
+        // Events of every registered hotkey arrive on one global channel.
+        // Park those of other services so each service sees its own.
+        static PENDING: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+        let Some(hotkey) = &self.hotkey else {
+            return false;
+        };
+        let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+        while let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
+            pending.push(event.id());
+        }
+        match pending.iter().position(|id| *id == hotkey.id()) {
+            Some(i) => {
+                pending.remove(i);
+                true
             }
+            None => false,
         }
-        false
     }
 
     /// Stub: always returns false on non-Windows.
@@ -0,0 +1,110 @@
+//! Text extraction for Markdown, plain text and PDF files.
+
+use std::path::Path;
+
+use engram_core::error::EngramError;
+
+/// Extract the text of a document.
+///
+/// PDFs go through `pdf-extract`; everything else is read as UTF-8 (invalid
+/// bytes are replaced). Markdown front matter is dropped. Read failures are
+/// returned as [`EngramError::Io`] so callers can retry them; unparseable
+/// files are [`EngramError::Capture`].
+pub fn extract_text(path: &Path) -> Result<String, EngramError> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "pdf" => extract_pdf(path),
+        "md" | "markdown" => {
+            let text = read_text(path)?;
+            Ok(strip_front_matter(&text).to_string())
+        }
+        _ => read_text(path),
+    }
+}
+
+fn read_text(path: &Path) -> Result<String, EngramError> {
+    let bytes = std::fs::read(path)?;
+    let text = String::from_utf8_lossy(&bytes);
+    Ok(text.trim_start_matches('\u{feff}').to_string())
+}
+
+fn extract_pdf(path: &Path) -> Result<String, EngramError> {
+    let bytes = std::fs::read(path)?;
+    // pdf-extract panics on some malformed files; treat that as a parse error.
+    std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem(&bytes))
+        .map_err(|_| EngramError::Capture(format!("PDF parser panicked on {}", path.display())))?
+        .map_err(|e| {
+            EngramError::Capture(format!(
+                "Failed to extract text from {}: {}",
+                path.display(),
+                e
+            ))
+        })
+}
+
+/// Drop a leading `---` YAML front matter block.
+fn strip_front_matter(text: &str) -> &str {
+    let Some(rest) = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+    else {
+        return text;
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        offset += line.len();
+        if line.trim_end() == "---" {
+            return &rest[offset..];
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_front_matter_is_stripped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("runbook.md");
+        std::fs::write(&path, "---\ntags: [ops]\n---\n# Runbook\nRotate keys.\n").unwrap();
+
+        assert_eq!(extract_text(&path).unwrap(), "# Runbook\nRotate keys.\n");
+    }
+
+    #[test]
+    fn test_unterminated_front_matter_is_kept() {
+        assert_eq!(strip_front_matter("---\nno end"), "---\nno end");
+        assert_eq!(strip_front_matter("plain"), "plain");
+    }
+
+    #[test]
+    fn test_text_with_bom_and_invalid_utf8() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        std::fs::write(&path, b"\xEF\xBB\xBFdeploy ok \xFF done").unwrap();
+
+        assert_eq!(extract_text(&path).unwrap(), "deploy ok \u{fffd} done");
+    }
+
+    #[test]
+    fn test_invalid_pdf_is_a_parse_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("broken.pdf");
+        std::fs::write(&path, b"not a pdf").unwrap();
+
+        assert!(matches!(extract_text(&path), Err(EngramError::Capture(_))));
+    }
+
+    #[test]
+    fn test_missing_file_is_io_error() {
+        let err = extract_text(Path::new("/nonexistent/notes.md")).unwrap_err();
+        assert!(matches!(err, EngramError::Io(_)));
+    }
+}
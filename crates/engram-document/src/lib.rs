@@ -0,0 +1,14 @@
+//! Engram Document crate - watched folders of Markdown, text and PDF files.
+//!
+//! Scans configured directories, extracts text, splits it into passages and
+//! feeds them through `EngramPipeline` as `document` captures. Each indexed
+//! file is tracked by path, modification time and size, so edited files are
+//! re-indexed and deleted files have their passages removed.
+
+pub mod extract;
+pub mod passage;
+pub mod watcher;
+
+pub use extract::extract_text;
+pub use passage::split_passages;
+pub use watcher::{DocumentWatcher, ScanReport};
@@ -0,0 +1,122 @@
+//! Splitting document text into passages for embedding.
+
+/// Split text into passages of at most `max_chars` characters.
+///
+/// Paragraphs (separated by blank lines) are packed together until the next
+/// one would overflow; a paragraph longer than `max_chars` is cut at the
+/// last whitespace before the limit. Blank passages are dropped.
+pub fn split_passages(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut passages = Vec::new();
+    let mut current = String::new();
+
+    for paragraph in paragraphs(text) {
+        let len = paragraph.chars().count();
+        if len > max_chars {
+            flush(&mut current, &mut passages);
+            passages.extend(split_long(&paragraph, max_chars));
+            continue;
+        }
+        if !current.is_empty() && current.chars().count() + 2 + len > max_chars {
+            flush(&mut current, &mut passages);
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(&paragraph);
+    }
+    flush(&mut current, &mut passages);
+
+    passages
+}
+
+/// Paragraphs of `text`, with trailing whitespace trimmed from each line.
+fn paragraphs(text: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            if !lines.is_empty() {
+                paragraphs.push(lines.join("\n"));
+                lines.clear();
+            }
+        } else {
+            lines.push(line);
+        }
+    }
+    if !lines.is_empty() {
+        paragraphs.push(lines.join("\n"));
+    }
+    paragraphs
+}
+
+fn flush(current: &mut String, passages: &mut Vec<String>) {
+    if !current.trim().is_empty() {
+        passages.push(std::mem::take(current));
+    }
+    current.clear();
+}
+
+fn split_long(paragraph: &str, max_chars: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut rest = paragraph.trim();
+    while rest.chars().count() > max_chars {
+        let (limit, next) = rest
+            .char_indices()
+            .nth(max_chars)
+            .unwrap_or((rest.len(), ' '));
+        // Include the first char past the limit: a space there is a clean cut.
+        let cut = rest[..limit + next.len_utf8()]
+            .rfind(char::is_whitespace)
+            .filter(|&i| i > 0)
+            .unwrap_or(limit);
+        pieces.push(rest[..cut].trim_end().to_string());
+        rest = rest[cut..].trim_start();
+    }
+    if !rest.is_empty() {
+        pieces.push(rest.to_string());
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_paragraphs_are_packed() {
+        let text = "# Title\n\nFirst para.\n\n\n\nSecond para.\n";
+        assert_eq!(
+            split_passages(text, 100),
+            vec!["# Title\n\nFirst para.\n\nSecond para."]
+        );
+    }
+
+    #[test]
+    fn test_paragraphs_start_new_passage_when_full() {
+        let text = "aaaa aaaa\n\nbbbb bbbb\n\ncccc";
+        assert_eq!(
+            split_passages(text, 20),
+            vec!["aaaa aaaa\n\nbbbb bbbb", "cccc"]
+        );
+    }
+
+    #[test]
+    fn test_long_paragraph_is_cut_at_whitespace() {
+        let passages = split_passages("one two three four five", 10);
+        assert_eq!(passages, vec!["one two", "three four", "five"]);
+        assert!(passages.iter().all(|p| p.chars().count() <= 10));
+    }
+
+    #[test]
+    fn test_unbroken_text_is_cut_at_char_boundary() {
+        let passages = split_passages("ééééé", 2);
+        assert_eq!(passages, vec!["éé", "éé", "é"]);
+    }
+
+    #[test]
+    fn test_blank_text_has_no_passages() {
+        assert!(split_passages("  \n\n \r\n", 100).is_empty());
+    }
+}
@@ -0,0 +1,411 @@
+//! Folder scanning and re-indexing of watched documents.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use chrono::{TimeZone, Utc};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+use engram_core::config::DocumentsConfig;
+use engram_core::error::EngramError;
+use engram_core::types::{ContentType, DocumentEntry};
+use engram_storage::Database;
+use engram_vector::{EngramPipeline, IngestResult};
+
+use crate::extract::extract_text;
+use crate::passage::split_passages;
+
+/// App name recorded on document passages.
+const DOCUMENTS_APP_NAME: &str = "Documents";
+
+/// Outcome of one folder scan.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ScanReport {
+    /// Files indexed for the first time or re-indexed after a change.
+    pub indexed: usize,
+    /// Passages stored for the indexed files.
+    pub passages: usize,
+    /// Files whose modification time and size were unchanged.
+    pub unchanged: usize,
+    /// Files removed because they were deleted or are no longer watched.
+    pub removed: usize,
+    /// Files that could not be read or parsed.
+    pub failed: usize,
+}
+
+/// A file found under a watched directory.
+#[derive(Debug)]
+struct FoundFile {
+    path: PathBuf,
+    mtime: i64,
+    size: i64,
+}
+
+/// Indexes watched folders into the pipeline.
+///
+/// Each [`scan`](Self::scan) reconciles the files on disk with the
+/// `document_files` tracking table, so changes made while Engram was not
+/// running are picked up too.
+pub struct DocumentWatcher {
+    pipeline: Arc<EngramPipeline>,
+    db: Arc<Database>,
+}
+
+impl DocumentWatcher {
+    /// Create a watcher. `pipeline` should write through to `db`.
+    pub fn new(pipeline: Arc<EngramPipeline>, db: Arc<Database>) -> Self {
+        Self { pipeline, db }
+    }
+
+    /// Index new and changed files and remove deleted ones.
+    pub async fn scan(&self, config: &DocumentsConfig) -> Result<ScanReport, EngramError> {
+        let mut report = ScanReport::default();
+        let (found, unavailable) = discover(config);
+        let tracked = self.db.with_conn(engram_storage::list_document_files)?;
+
+        let mut known = HashMap::new();
+        for row in tracked {
+            if found.contains_key(&row.path) {
+                known.insert(row.path.clone(), row);
+                continue;
+            }
+            // Keep files under a directory that is missing right now (e.g.
+            // an unplugged drive) rather than dropping them from memory.
+            if unavailable
+                .iter()
+                .any(|dir| Path::new(&row.path).starts_with(dir))
+            {
+                continue;
+            }
+            let passages = self.pipeline.remove_document(&row.path)?;
+            self.db
+                .with_conn(|conn| engram_storage::delete_document_file(conn, &row.path))?;
+            debug!(path = %row.path, passages, "Removed deleted document");
+            report.removed += 1;
+        }
+
+        for (key, file) in &found {
+            if let Some(row) = known.get(key) {
+                if row.mtime == file.mtime && row.size == file.size {
+                    report.unchanged += 1;
+                    continue;
+                }
+            }
+            match self.index_file(key, file, config.passage_chars).await {
+                Ok(passages) => {
+                    report.indexed += 1;
+                    report.passages += passages;
+                }
+                Err(e) => {
+                    warn!(path = %key, error = %e, "Failed to index document");
+                    report.failed += 1;
+                }
+            }
+        }
+
+        if report.indexed > 0 || report.removed > 0 {
+            info!(
+                indexed = report.indexed,
+                passages = report.passages,
+                removed = report.removed,
+                failed = report.failed,
+                "Document scan complete"
+            );
+        }
+        Ok(report)
+    }
+
+    /// Replace a file's passages with freshly extracted ones.
+    ///
+    /// Read errors leave the file untracked so the next scan retries it.
+    /// Parse errors are recorded with no passages, so a broken file is only
+    /// retried once it changes.
+    async fn index_file(
+        &self,
+        key: &str,
+        file: &FoundFile,
+        passage_chars: usize,
+    ) -> Result<usize, EngramError> {
+        let path = file.path.clone();
+        let extracted = tokio::task::spawn_blocking(move || extract_text(&path))
+            .await
+            .map_err(|e| EngramError::Capture(format!("Extraction task failed: {}", e)))?;
+        let text = match extracted {
+            Ok(text) => text,
+            Err(EngramError::Io(e)) => return Err(EngramError::Io(e)),
+            Err(e) => {
+                self.pipeline.remove_document(key)?;
+                self.db.with_conn(|conn| {
+                    engram_storage::upsert_document_file(conn, key, file.mtime, file.size, 0)
+                })?;
+                return Err(e);
+            }
+        };
+
+        self.pipeline.remove_document(key)?;
+
+        let title = file
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let timestamp = Utc
+            .timestamp_millis_opt(file.mtime)
+            .single()
+            .unwrap_or_else(Utc::now);
+
+        let mut stored = 0;
+        for (index, passage) in split_passages(&text, passage_chars).into_iter().enumerate() {
+            let entry = DocumentEntry {
+                id: Uuid::new_v4(),
+                content_type: ContentType::Document,
+                timestamp,
+                title: title.clone(),
+                source_path: key.to_string(),
+                passage_index: index as u32,
+                app_name: DOCUMENTS_APP_NAME.to_string(),
+                text: passage,
+            };
+            if matches!(
+                self.pipeline.ingest_document(entry).await?,
+                IngestResult::Stored { .. } | IngestResult::Redacted { .. }
+            ) {
+                stored += 1;
+            }
+        }
+
+        self.db.with_conn(|conn| {
+            engram_storage::upsert_document_file(conn, key, file.mtime, file.size, stored as i64)
+        })?;
+        debug!(path = %key, passages = stored, "Indexed document");
+        Ok(stored)
+    }
+}
+
+/// Find indexable files under the watched directories.
+///
+/// Returns the files keyed by path, and the watched directories that do not
+/// currently exist. Hidden files and directories are skipped.
+fn discover(config: &DocumentsConfig) -> (BTreeMap<String, FoundFile>, Vec<PathBuf>) {
+    let extensions: Vec<String> = config
+        .extensions
+        .iter()
+        .map(|e| e.trim_start_matches('.').to_ascii_lowercase())
+        .collect();
+    let max_bytes = config.max_file_size_mb.saturating_mul(1024 * 1024);
+
+    let mut found = BTreeMap::new();
+    let mut unavailable = Vec::new();
+    for dir in &config.watch_dirs {
+        let root = expand_home(dir);
+        if !root.is_dir() {
+            warn!(dir = %root.display(), "Watched document folder not found");
+            unavailable.push(root);
+            continue;
+        }
+
+        let entries = WalkDir::new(&root)
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file());
+        for entry in entries {
+            let matches_extension = entry
+                .path()
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| extensions.contains(&e.to_ascii_lowercase()));
+            if !matches_extension {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.len() > max_bytes {
+                debug!(path = %entry.path().display(), "Skipping oversized document");
+                continue;
+            }
+            let mtime = metadata
+                .modified()
+                .ok()
+                .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as i64)
+                .unwrap_or(0);
+            found.insert(
+                entry.path().to_string_lossy().to_string(),
+                FoundFile {
+                    path: entry.path().to_path_buf(),
+                    mtime,
+                    size: metadata.len() as i64,
+                },
+            );
+        }
+    }
+    (found, unavailable)
+}
+
+/// Expand a leading `~/` to the user's home directory.
+fn expand_home(dir: &str) -> PathBuf {
+    if let Some(rest) = dir.strip_prefix("~/").or_else(|| dir.strip_prefix("~\\")) {
+        #[cfg(target_os = "windows")]
+        let home = std::env::var("USERPROFILE").unwrap_or_else(|_| ".".to_string());
+        #[cfg(not(target_os = "windows"))]
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        return PathBuf::from(home).join(rest);
+    }
+    PathBuf::from(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engram_core::config::SafetyConfig;
+    use engram_storage::DocumentRepository;
+    use engram_vector::{MockEmbedding, VectorIndex};
+
+    fn make_watcher() -> (DocumentWatcher, Arc<Database>, Arc<EngramPipeline>) {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let pipeline = Arc::new(
+            EngramPipeline::new(
+                Arc::new(VectorIndex::new()),
+                MockEmbedding::new(),
+                SafetyConfig::default(),
+                0.95,
+            )
+            .with_database(Arc::clone(&db)),
+        );
+        let watcher = DocumentWatcher::new(Arc::clone(&pipeline), Arc::clone(&db));
+        (watcher, db, pipeline)
+    }
+
+    fn config_for(dir: &Path) -> DocumentsConfig {
+        DocumentsConfig {
+            enabled: true,
+            watch_dirs: vec![dir.to_string_lossy().to_string()],
+            passage_chars: 200,
+            ..DocumentsConfig::default()
+        }
+    }
+
+    fn key(path: &Path) -> String {
+        path.to_string_lossy().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_scan_indexes_matching_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("runbook.md"), "# Runbook\n\nRotate keys.").unwrap();
+        std::fs::create_dir(dir.path().join("meetings")).unwrap();
+        let notes = dir.path().join("meetings").join("standup.txt");
+        std::fs::write(&notes, "Dana owns the release checklist.").unwrap();
+        std::fs::write(dir.path().join("photo.jpg"), "not text").unwrap();
+        std::fs::create_dir(dir.path().join(".obsidian")).unwrap();
+        std::fs::write(dir.path().join(".obsidian").join("cache.md"), "internal").unwrap();
+
+        let (watcher, db, pipeline) = make_watcher();
+        let report = watcher.scan(&config_for(dir.path())).await.unwrap();
+
+        assert_eq!(report.indexed, 2);
+        assert_eq!(report.passages, 2);
+        assert_eq!(pipeline.index().len(), 2);
+        let passages = DocumentRepository::new(Arc::clone(&db))
+            .find_by_source(&key(&notes))
+            .unwrap();
+        assert_eq!(passages.len(), 1);
+        assert_eq!(passages[0].title, "standup.txt");
+        assert_eq!(passages[0].app_name, "Documents");
+        let tracked = db
+            .with_conn(|conn| engram_storage::get_document_file(conn, &key(&notes)))
+            .unwrap()
+            .unwrap();
+        assert_eq!(tracked.passage_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_scan_reindexes_changed_and_removes_deleted_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let runbook = dir.path().join("runbook.md");
+        let old = dir.path().join("old.txt");
+        std::fs::write(&runbook, "Rotate keys quarterly.").unwrap();
+        std::fs::write(&old, "Legacy deploy steps.").unwrap();
+
+        let (watcher, db, pipeline) = make_watcher();
+        let config = config_for(dir.path());
+        watcher.scan(&config).await.unwrap();
+
+        let report = watcher.scan(&config).await.unwrap();
+        assert_eq!(report.unchanged, 2);
+        assert_eq!(report.indexed, 0);
+
+        std::fs::write(
+            &runbook,
+            "Rotate keys monthly.\n\nPage the on-call engineer first.",
+        )
+        .unwrap();
+        std::fs::remove_file(&old).unwrap();
+        let report = watcher.scan(&config).await.unwrap();
+        assert_eq!(report.indexed, 1);
+        assert_eq!(report.removed, 1);
+        assert_eq!(report.unchanged, 0);
+
+        let repo = DocumentRepository::new(Arc::clone(&db));
+        let passages = repo.find_by_source(&key(&runbook)).unwrap();
+        assert_eq!(passages.len(), 1);
+        assert!(passages[0].text.contains("monthly"));
+        assert!(repo.find_by_source(&key(&old)).unwrap().is_empty());
+        assert_eq!(pipeline.index().len(), 1);
+        let tracked = db.with_conn(engram_storage::list_document_files).unwrap();
+        assert_eq!(tracked.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_missing_folder_keeps_its_documents() {
+        let dir = tempfile::tempdir().unwrap();
+        let notes = dir.path().join("notes");
+        std::fs::create_dir(&notes).unwrap();
+        std::fs::write(notes.join("a.md"), "Quarterly planning notes.").unwrap();
+
+        let (watcher, _db, pipeline) = make_watcher();
+        let config = config_for(&notes);
+        watcher.scan(&config).await.unwrap();
+
+        std::fs::rename(&notes, dir.path().join("moved")).unwrap();
+        let report = watcher.scan(&config).await.unwrap();
+        assert_eq!(report.removed, 0);
+        assert_eq!(pipeline.index().len(), 1);
+
+        // Dropping the folder from the config removes its documents.
+        let report = watcher.scan(&DocumentsConfig::default()).await.unwrap();
+        assert_eq!(report.removed, 1);
+        assert_eq!(pipeline.index().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unparseable_file_is_tracked_without_passages() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("broken.pdf"), "not a pdf").unwrap();
+
+        let (watcher, db, _pipeline) = make_watcher();
+        let config = config_for(dir.path());
+        let report = watcher.scan(&config).await.unwrap();
+        assert_eq!(report.failed, 1);
+
+        let tracked = db.with_conn(engram_storage::list_document_files).unwrap();
+        assert_eq!(tracked.len(), 1);
+        assert_eq!(tracked[0].passage_count, 0);
+
+        // Not retried until the file changes.
+        let report = watcher.scan(&config).await.unwrap();
+        assert_eq!((report.failed, report.unchanged), (0, 1));
+    }
+
+    #[test]
+    fn test_expand_home() {
+        assert_eq!(expand_home("/srv/notes"), PathBuf::from("/srv/notes"));
+        assert!(!expand_home("~/notes").starts_with("~"));
+    }
+}
@@ -0,0 +1,716 @@
+//! Table-by-table export of the database to a directory.
+
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use rusqlite::types::Value;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use engram_core::error::EngramError;
+use engram_storage::Database;
+
+use crate::writer::{JsonlWriter, TableWriter};
+
+/// Name of the manifest written next to the table files.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Version of the export layout, bumped when files or manifest fields change.
+pub const MANIFEST_FORMAT_VERSION: u32 = 1;
+
+/// Capture content types accepted by [`ExportOptions::content_types`].
+pub const CONTENT_TYPES: &[&str] = &["screen", "audio", "dictation", "document"];
+
+/// A table included in exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportTable {
+    pub name: &'static str,
+    /// Column compared against the date range.
+    pub time_column: &'static str,
+}
+
+/// Every exported table, in export order.
+///
+/// Screen text, audio transcriptions, dictations and document passages all
+/// live in `captures`, distinguished by `content_type`.
+pub const EXPORT_TABLES: &[ExportTable] = &[
+    ExportTable {
+        name: "captures",
+        time_column: "timestamp",
+    },
+    ExportTable {
+        name: "tasks",
+        time_column: "created_at",
+    },
+    ExportTable {
+        name: "intents",
+        time_column: "detected_at",
+    },
+    ExportTable {
+        name: "action_history",
+        time_column: "executed_at",
+    },
+    ExportTable {
+        name: "chat_sessions",
+        time_column: "started_at",
+    },
+    ExportTable {
+        name: "chat_messages",
+        time_column: "created_at",
+    },
+    ExportTable {
+        name: "summaries",
+        time_column: "created_at",
+    },
+    ExportTable {
+        name: "entities",
+        time_column: "created_at",
+    },
+    ExportTable {
+        name: "daily_digests",
+        time_column: "created_at",
+    },
+];
+
+/// File format of exported tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Jsonl,
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Parquet => "parquet",
+        }
+    }
+
+    /// Whether this build can write the format.
+    pub fn is_supported(&self) -> bool {
+        match self {
+            ExportFormat::Jsonl => true,
+            ExportFormat::Parquet => cfg!(feature = "parquet"),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "jsonl" => Ok(ExportFormat::Jsonl),
+            "parquet" => Ok(ExportFormat::Parquet),
+            _ => Err(format!(
+                "Unknown export format: {} (expected jsonl or parquet)",
+                s
+            )),
+        }
+    }
+}
+
+/// What to export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+    /// Formats to write; each table gets one file per format.
+    pub formats: Vec<ExportFormat>,
+    /// Only rows at or after this time.
+    pub start: Option<DateTime<Utc>>,
+    /// Only rows at or before this time.
+    pub end: Option<DateTime<Utc>>,
+    /// Only captures of these content types. Empty exports all of them.
+    pub content_types: Vec<String>,
+    /// Only these tables. Empty exports every table in [`EXPORT_TABLES`].
+    pub tables: Vec<String>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            formats: vec![ExportFormat::Jsonl],
+            start: None,
+            end: None,
+            content_types: Vec::new(),
+            tables: Vec::new(),
+        }
+    }
+}
+
+impl ExportOptions {
+    /// Parse a date range bound given as RFC 3339 or `YYYY-MM-DD`.
+    ///
+    /// A bare date means the start of that day (UTC), or its last second
+    /// when `end_of_day` is set, so `--start 2026-01-01 --end 2026-01-31`
+    /// covers the whole of January.
+    pub fn parse_time(value: &str, end_of_day: bool) -> Result<DateTime<Utc>, EngramError> {
+        if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+            return Ok(dt.with_timezone(&Utc));
+        }
+        let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+            EngramError::Config(format!(
+                "Invalid date: {} (expected YYYY-MM-DD or RFC 3339)",
+                value
+            ))
+        })?;
+        let time = if end_of_day {
+            date.and_hms_opt(23, 59, 59)
+        } else {
+            date.and_hms_opt(0, 0, 0)
+        };
+        Ok(time.expect("valid time of day").and_utc())
+    }
+
+    /// Check formats, tables, content types and the date range.
+    pub fn validate(&self) -> Result<(), EngramError> {
+        if self.formats.is_empty() {
+            return Err(EngramError::Config(
+                "At least one export format is required".to_string(),
+            ));
+        }
+        if let Some(format) = self.formats.iter().find(|f| !f.is_supported()) {
+            return Err(EngramError::Config(format!(
+                "This build cannot write {} exports (enable the `parquet` feature)",
+                format
+            )));
+        }
+        if let Some(table) = self
+            .tables
+            .iter()
+            .find(|t| !EXPORT_TABLES.iter().any(|e| e.name == t.as_str()))
+        {
+            return Err(EngramError::Config(format!(
+                "Unknown export table: {}",
+                table
+            )));
+        }
+        if let Some(content_type) = self
+            .content_types
+            .iter()
+            .find(|c| !CONTENT_TYPES.contains(&c.as_str()))
+        {
+            return Err(EngramError::Config(format!(
+                "Unknown content type: {} (expected one of {})",
+                content_type,
+                CONTENT_TYPES.join(", ")
+            )));
+        }
+        if let (Some(start), Some(end)) = (self.start, self.end) {
+            if start > end {
+                return Err(EngramError::Config(
+                    "Export start must not be after end".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn selected_tables(&self) -> impl Iterator<Item = &'static ExportTable> + '_ {
+        EXPORT_TABLES
+            .iter()
+            .filter(|t| self.tables.is_empty() || self.tables.iter().any(|n| n == t.name))
+    }
+
+    fn has_time_filter(&self) -> bool {
+        self.start.is_some() || self.end.is_some()
+    }
+
+    fn in_range(&self, seconds: i64) -> bool {
+        self.start.is_none_or(|s| seconds >= s.timestamp())
+            && self.end.is_none_or(|e| seconds <= e.timestamp())
+    }
+}
+
+/// Describes a finished export; written to [`MANIFEST_FILE`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportManifest {
+    pub format_version: u32,
+    /// Highest applied database migration.
+    pub schema_version: i64,
+    pub engram_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub content_types: Vec<String>,
+    pub formats: Vec<ExportFormat>,
+    pub tables: Vec<TableManifest>,
+}
+
+impl ExportManifest {
+    /// Total rows exported across all tables.
+    pub fn total_rows(&self) -> u64 {
+        self.tables.iter().map(|t| t.rows).sum()
+    }
+}
+
+/// One exported table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableManifest {
+    pub name: String,
+    pub rows: u64,
+    /// File names, relative to the export directory.
+    pub files: Vec<String>,
+    pub columns: Vec<ColumnManifest>,
+}
+
+/// A column as declared in the database schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnManifest {
+    pub name: String,
+    /// Declared SQLite type, e.g. `TEXT` or `INTEGER`.
+    #[serde(rename = "type")]
+    pub data_type: String,
+    pub nullable: bool,
+}
+
+/// Exports database tables to portable files.
+pub struct DataExporter {
+    db: Arc<Database>,
+}
+
+impl DataExporter {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Export the selected tables into `dir`.
+    ///
+    /// `dir` must be an absolute path without `..` and must be empty or not
+    /// exist yet. Tables are read from one snapshot on a separate read-only
+    /// connection, so every table comes from the same point in time without
+    /// holding up writers.
+    pub fn export(
+        &self,
+        dir: &Path,
+        options: &ExportOptions,
+    ) -> Result<ExportManifest, EngramError> {
+        options.validate()?;
+        prepare_dir(dir)?;
+
+        let exported_at = Utc::now();
+        let (schema_version, tables) = self.db.with_snapshot(|conn| {
+            let schema_version: i64 = conn
+                .query_row(
+                    "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+                    [],
+                    |row| row.get(0),
+                )
+                .map_err(|e| EngramError::Storage(format!("Read schema version: {}", e)))?;
+            let tables = options
+                .selected_tables()
+                .map(|table| export_table(conn, table, dir, options))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok((schema_version, tables))
+        })?;
+
+        let manifest = ExportManifest {
+            format_version: MANIFEST_FORMAT_VERSION,
+            schema_version,
+            engram_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at,
+            start: options.start,
+            end: options.end,
+            content_types: options.content_types.clone(),
+            formats: options.formats.clone(),
+            tables,
+        };
+        let json = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| EngramError::Serialization(format!("Serialize manifest: {}", e)))?;
+        std::fs::write(dir.join(MANIFEST_FILE), json)?;
+
+        info!(
+            dir = %dir.display(),
+            tables = manifest.tables.len(),
+            rows = manifest.total_rows(),
+            "Export finished"
+        );
+        Ok(manifest)
+    }
+}
+
+/// Check the output directory and create it if needed.
+fn prepare_dir(dir: &Path) -> Result<(), EngramError> {
+    if !dir.is_absolute() {
+        return Err(EngramError::Config(format!(
+            "Export directory must be an absolute path: {}",
+            dir.display()
+        )));
+    }
+    if dir
+        .components()
+        .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return Err(EngramError::Config(format!(
+            "Export directory must not contain '..': {}",
+            dir.display()
+        )));
+    }
+    if dir.exists() {
+        if !dir.is_dir() {
+            return Err(EngramError::Config(format!(
+                "Export path is not a directory: {}",
+                dir.display()
+            )));
+        }
+        if std::fs::read_dir(dir)?.next().is_some() {
+            return Err(EngramError::Config(format!(
+                "Export directory is not empty: {}",
+                dir.display()
+            )));
+        }
+    }
+    std::fs::create_dir_all(dir)?;
+    Ok(())
+}
+
+fn table_columns(conn: &Connection, table: &str) -> Result<Vec<ColumnManifest>, EngramError> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info({})", table))
+        .map_err(|e| EngramError::Storage(format!("Read columns of {}: {}", table, e)))?;
+    let columns = stmt
+        .query_map([], |row| {
+            let not_null: bool = row.get(3)?;
+            let primary_key: i64 = row.get(5)?;
+            Ok(ColumnManifest {
+                name: row.get(1)?,
+                data_type: row.get(2)?,
+                nullable: !not_null && primary_key == 0,
+            })
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| EngramError::Storage(format!("Read columns of {}: {}", table, e)))?;
+    Ok(columns)
+}
+
+fn create_writer(
+    format: ExportFormat,
+    path: &Path,
+    columns: &[ColumnManifest],
+) -> Result<Box<dyn TableWriter>, EngramError> {
+    match format {
+        ExportFormat::Jsonl => Ok(Box::new(JsonlWriter::create(path, columns)?)),
+        #[cfg(feature = "parquet")]
+        ExportFormat::Parquet => Ok(Box::new(crate::writer::ParquetWriter::create(
+            path, columns,
+        )?)),
+        #[cfg(not(feature = "parquet"))]
+        ExportFormat::Parquet => Err(EngramError::Config(
+            "This build cannot write parquet exports".to_string(),
+        )),
+    }
+}
+
+fn export_table(
+    conn: &Connection,
+    table: &ExportTable,
+    dir: &Path,
+    options: &ExportOptions,
+) -> Result<TableManifest, EngramError> {
+    let storage_err =
+        |e: rusqlite::Error| EngramError::Storage(format!("Export {}: {}", table.name, e));
+    let columns = table_columns(conn, table.name)?;
+
+    // Captures store unix seconds, so their filters run in SQL. Other tables
+    // keep timestamps as text in a few formats and are filtered row by row.
+    let mut sql = format!("SELECT * FROM {}", table.name);
+    let mut params: Vec<Value> = Vec::new();
+    let mut row_filter = None;
+    if table.name == "captures" {
+        let mut conditions = Vec::new();
+        if let Some(start) = options.start {
+            conditions.push("timestamp >= ?".to_string());
+            params.push(Value::Integer(start.timestamp()));
+        }
+        if let Some(end) = options.end {
+            conditions.push("timestamp <= ?".to_string());
+            params.push(Value::Integer(end.timestamp()));
+        }
+        if !options.content_types.is_empty() {
+            let placeholders = vec!["?"; options.content_types.len()].join(", ");
+            conditions.push(format!("content_type IN ({})", placeholders));
+            params.extend(options.content_types.iter().cloned().map(Value::Text));
+        }
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+    } else if options.has_time_filter() {
+        row_filter = columns.iter().position(|c| c.name == table.time_column);
+    }
+    sql.push_str(" ORDER BY rowid");
+
+    let mut files = Vec::new();
+    let mut writers = Vec::new();
+    for format in &options.formats {
+        let file = format!("{}.{}", table.name, format.as_str());
+        writers.push(create_writer(*format, &dir.join(&file), &columns)?);
+        files.push(file);
+    }
+
+    let mut stmt = conn.prepare(&sql).map_err(storage_err)?;
+    let mut rows = stmt
+        .query(rusqlite::params_from_iter(params))
+        .map_err(storage_err)?;
+    let mut count = 0u64;
+    while let Some(row) = rows.next().map_err(storage_err)? {
+        let values = (0..columns.len())
+            .map(|i| row.get::<_, Value>(i))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(storage_err)?;
+        if let Some(index) = row_filter {
+            // Rows whose time cannot be read are left out of dated exports.
+            if !parse_row_time(&values[index]).is_some_and(|t| options.in_range(t)) {
+                continue;
+            }
+        }
+        for writer in writers.iter_mut() {
+            writer.write_row(&values)?;
+        }
+        count += 1;
+    }
+    for writer in writers {
+        writer.finish()?;
+    }
+
+    Ok(TableManifest {
+        name: table.name.to_string(),
+        rows: count,
+        files,
+        columns,
+    })
+}
+
+/// Read a stored timestamp as unix seconds.
+///
+/// Accepts integer seconds, RFC 3339 and SQLite's `datetime('now')` format.
+fn parse_row_time(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(i) => Some(*i),
+        Value::Real(f) => Some(*f as i64),
+        Value::Text(s) => {
+            let s = s.trim();
+            if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+                return Some(dt.timestamp());
+            }
+            if let Ok(dt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f") {
+                return Some(dt.and_utc().timestamp());
+            }
+            s.parse().ok()
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufRead;
+
+    fn make_db() -> Arc<Database> {
+        let db = Arc::new(Database::in_memory().unwrap());
+        db.with_conn(|conn| {
+            conn.execute_batch(
+                "INSERT INTO captures (id, content_type, timestamp, text, app_name)
+                     VALUES ('c1', 'screen', 1767607200, 'cargo build', 'Terminal'),
+                            ('c2', 'audio', 1767610800, 'standup notes', ''),
+                            ('c3', 'dictation', 1770000000, 'reply later', 'Mail');
+                 INSERT INTO tasks (id, title, action_type, action_payload, created_at)
+                     VALUES ('t1', 'Send deck', 'reminder', '{}', '2026-01-05 09:00:00'),
+                            ('t2', 'Book room', 'reminder', '{}', '2026-02-10T12:00:00+00:00');
+                 INSERT INTO chat_sessions (id, started_at) VALUES ('s1', '2026-01-05 10:00:00');
+                 INSERT INTO chat_messages (id, session_id, role, content, created_at)
+                     VALUES ('m1', 's1', 'user', 'what did I build?', '2026-01-05 10:00:01');",
+            )
+            .map_err(|e| EngramError::Storage(e.to_string()))
+        })
+        .unwrap();
+        db
+    }
+
+    fn read_jsonl(path: &Path) -> Vec<serde_json::Value> {
+        let file = std::fs::File::open(path).unwrap();
+        std::io::BufReader::new(file)
+            .lines()
+            .map(|l| serde_json::from_str(&l.unwrap()).unwrap())
+            .collect()
+    }
+
+    fn table<'a>(manifest: &'a ExportManifest, name: &str) -> &'a TableManifest {
+        manifest.tables.iter().find(|t| t.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_export_writes_every_table_and_manifest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("export");
+        let manifest = DataExporter::new(make_db())
+            .export(&dir, &ExportOptions::default())
+            .unwrap();
+
+        assert_eq!(manifest.tables.len(), EXPORT_TABLES.len());
+        assert!(manifest.schema_version >= 13);
+        assert_eq!(table(&manifest, "captures").rows, 3);
+        assert_eq!(table(&manifest, "tasks").rows, 2);
+        assert_eq!(table(&manifest, "summaries").rows, 0);
+        assert_eq!(manifest.total_rows(), 7);
+
+        let captures = read_jsonl(&dir.join("captures.jsonl"));
+        assert_eq!(captures.len(), 3);
+        assert_eq!(captures[0]["id"], "c1");
+        assert_eq!(captures[0]["content_type"], "screen");
+        assert_eq!(captures[0]["timestamp"], 1767607200);
+        assert!(captures[0]["session_id"].is_null());
+
+        let id_column = &table(&manifest, "captures").columns[0];
+        assert_eq!(id_column.name, "id");
+        assert_eq!(id_column.data_type, "TEXT");
+        assert!(!id_column.nullable);
+
+        let written: ExportManifest =
+            serde_json::from_str(&std::fs::read_to_string(dir.join(MANIFEST_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(written, manifest);
+    }
+
+    #[test]
+    fn test_export_filters_by_date_and_content_type() {
+        let tmp = tempfile::tempdir().unwrap();
+        let options = ExportOptions {
+            start: Some(ExportOptions::parse_time("2026-01-05", false).unwrap()),
+            end: Some(ExportOptions::parse_time("2026-01-31", true).unwrap()),
+            content_types: vec!["screen".to_string(), "dictation".to_string()],
+            tables: vec!["captures".to_string(), "tasks".to_string()],
+            ..ExportOptions::default()
+        };
+        let manifest = DataExporter::new(make_db())
+            .export(tmp.path(), &options)
+            .unwrap();
+
+        assert_eq!(manifest.tables.len(), 2);
+        let captures = read_jsonl(&tmp.path().join("captures.jsonl"));
+        assert_eq!(captures.len(), 1);
+        assert_eq!(captures[0]["id"], "c1");
+        let tasks = read_jsonl(&tmp.path().join("tasks.jsonl"));
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0]["id"], "t1");
+        assert_eq!(manifest.content_types, vec!["screen", "dictation"]);
+    }
+
+    #[test]
+    fn test_export_rejects_bad_options_and_directories() {
+        let tmp = tempfile::tempdir().unwrap();
+        let exporter = DataExporter::new(make_db());
+
+        for options in [
+            ExportOptions {
+                formats: Vec::new(),
+                ..ExportOptions::default()
+            },
+            ExportOptions {
+                tables: vec!["api_tokens".to_string()],
+                ..ExportOptions::default()
+            },
+            ExportOptions {
+                content_types: vec!["video".to_string()],
+                ..ExportOptions::default()
+            },
+            ExportOptions {
+                start: Some(ExportOptions::parse_time("2026-02-01", false).unwrap()),
+                end: Some(ExportOptions::parse_time("2026-01-01", true).unwrap()),
+                ..ExportOptions::default()
+            },
+        ] {
+            let result = exporter.export(tmp.path(), &options);
+            assert!(
+                matches!(result, Err(EngramError::Config(_))),
+                "{:?}",
+                options
+            );
+        }
+
+        let options = ExportOptions::default();
+        for dir in [Path::new("relative/export"), Path::new("/tmp/a/../b")] {
+            assert!(matches!(
+                exporter.export(dir, &options),
+                Err(EngramError::Config(_))
+            ));
+        }
+        std::fs::write(tmp.path().join("existing.txt"), "keep").unwrap();
+        assert!(matches!(
+            exporter.export(tmp.path(), &options),
+            Err(EngramError::Config(_))
+        ));
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_export_writes_parquet() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let options = ExportOptions {
+            formats: vec![ExportFormat::Jsonl, ExportFormat::Parquet],
+            tables: vec!["captures".to_string()],
+            ..ExportOptions::default()
+        };
+        let manifest = DataExporter::new(make_db())
+            .export(tmp.path(), &options)
+            .unwrap();
+        assert_eq!(
+            manifest.tables[0].files,
+            vec!["captures.jsonl", "captures.parquet"]
+        );
+
+        let file = std::fs::File::open(tmp.path().join("captures.parquet")).unwrap();
+        let reader = SerializedFileReader::new(file).unwrap();
+        let metadata = reader.metadata().file_metadata();
+        assert_eq!(metadata.num_rows(), 3);
+        assert_eq!(
+            metadata.schema_descr().num_columns(),
+            manifest.tables[0].columns.len()
+        );
+    }
+
+    #[test]
+    fn test_parse_time_and_formats() {
+        assert_eq!(
+            ExportOptions::parse_time("2026-01-05", false)
+                .unwrap()
+                .to_rfc3339(),
+            "2026-01-05T00:00:00+00:00"
+        );
+        assert_eq!(
+            ExportOptions::parse_time("2026-01-05", true)
+                .unwrap()
+                .to_rfc3339(),
+            "2026-01-05T23:59:59+00:00"
+        );
+        assert_eq!(
+            ExportOptions::parse_time("2026-01-05T10:00:00+02:00", true)
+                .unwrap()
+                .to_rfc3339(),
+            "2026-01-05T08:00:00+00:00"
+        );
+        assert!(ExportOptions::parse_time("last week", false).is_err());
+
+        assert_eq!("parquet".parse(), Ok(ExportFormat::Parquet));
+        assert!("csv".parse::<ExportFormat>().is_err());
+
+        assert_eq!(
+            parse_row_time(&Value::Text("2026-01-05 09:00:00".into())),
+            Some(1767603600)
+        );
+        assert_eq!(parse_row_time(&Value::Integer(42)), Some(42));
+        assert_eq!(parse_row_time(&Value::Text("soon".into())), None);
+    }
+}
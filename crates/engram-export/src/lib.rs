@@ -0,0 +1,16 @@
+//! Engram Export crate - portable dumps of the local database.
+//!
+//! Writes captures, tasks, intents, action history, chat history and
+//! insights to one file per table, as JSONL and (with the `parquet`
+//! feature) Parquet, next to a `manifest.json` describing the schema
+//! version, filters, columns and row counts. Capture files use the same
+//! field names as the `engram` import format, so screen, audio and
+//! dictation captures can be imported on another machine.
+
+pub mod exporter;
+mod writer;
+
+pub use exporter::{
+    ColumnManifest, DataExporter, ExportFormat, ExportManifest, ExportOptions, ExportTable,
+    TableManifest, CONTENT_TYPES, EXPORT_TABLES, MANIFEST_FILE, MANIFEST_FORMAT_VERSION,
+};
@@ -0,0 +1,262 @@
+//! Per-format writers for exported table rows.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use rusqlite::types::Value;
+
+use engram_core::error::EngramError;
+
+use crate::exporter::ColumnManifest;
+
+/// Receives the rows of one table and writes them to a file.
+pub(crate) trait TableWriter {
+    fn write_row(&mut self, row: &[Value]) -> Result<(), EngramError>;
+
+    /// Flush buffered rows and close the file.
+    fn finish(self: Box<Self>) -> Result<(), EngramError>;
+}
+
+/// One JSON object per line, keyed by column name.
+pub(crate) struct JsonlWriter {
+    out: BufWriter<File>,
+    columns: Vec<String>,
+}
+
+impl JsonlWriter {
+    pub(crate) fn create(path: &Path, columns: &[ColumnManifest]) -> Result<Self, EngramError> {
+        Ok(Self {
+            out: BufWriter::new(File::create(path)?),
+            columns: columns.iter().map(|c| c.name.clone()).collect(),
+        })
+    }
+}
+
+impl TableWriter for JsonlWriter {
+    fn write_row(&mut self, row: &[Value]) -> Result<(), EngramError> {
+        let object: serde_json::Map<String, serde_json::Value> = self
+            .columns
+            .iter()
+            .zip(row)
+            .map(|(name, value)| (name.clone(), to_json(value)))
+            .collect();
+        serde_json::to_writer(&mut self.out, &object)
+            .map_err(|e| EngramError::Serialization(format!("Write JSONL row: {}", e)))?;
+        self.out.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), EngramError> {
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+fn to_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Integer(i) => (*i).into(),
+        Value::Real(f) => serde_json::Number::from_f64(*f)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        Value::Text(s) => s.clone().into(),
+        Value::Blob(b) => b
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>()
+            .into(),
+    }
+}
+
+#[cfg(feature = "parquet")]
+pub(crate) use parquet_writer::ParquetWriter;
+
+#[cfg(feature = "parquet")]
+mod parquet_writer {
+    use std::fs::File;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use arrow_array::builder::{BinaryBuilder, Float64Builder, Int64Builder, StringBuilder};
+    use arrow_array::{ArrayRef, RecordBatch};
+    use arrow_schema::{DataType, Field, Schema, SchemaRef};
+    use parquet::arrow::ArrowWriter;
+    use rusqlite::types::Value;
+
+    use engram_core::error::EngramError;
+
+    use super::TableWriter;
+    use crate::exporter::ColumnManifest;
+
+    /// Rows buffered per Parquet row group.
+    const BATCH_ROWS: usize = 8192;
+
+    /// A column builder typed by the column's SQLite affinity.
+    enum ColumnBuilder {
+        Int(Int64Builder),
+        Float(Float64Builder),
+        Text(StringBuilder),
+        Blob(BinaryBuilder),
+    }
+
+    impl ColumnBuilder {
+        fn for_type(data_type: &DataType) -> Self {
+            match data_type {
+                DataType::Int64 => ColumnBuilder::Int(Int64Builder::new()),
+                DataType::Float64 => ColumnBuilder::Float(Float64Builder::new()),
+                DataType::Binary => ColumnBuilder::Blob(BinaryBuilder::new()),
+                _ => ColumnBuilder::Text(StringBuilder::new()),
+            }
+        }
+
+        /// Append a value, converting SQLite's dynamically typed storage to
+        /// the column type. Values that do not convert become null.
+        fn append(&mut self, value: &Value) {
+            match self {
+                ColumnBuilder::Int(b) => b.append_option(match value {
+                    Value::Integer(i) => Some(*i),
+                    Value::Real(f) => Some(*f as i64),
+                    Value::Text(s) => s.trim().parse().ok(),
+                    _ => None,
+                }),
+                ColumnBuilder::Float(b) => b.append_option(match value {
+                    Value::Integer(i) => Some(*i as f64),
+                    Value::Real(f) => Some(*f),
+                    Value::Text(s) => s.trim().parse().ok(),
+                    _ => None,
+                }),
+                ColumnBuilder::Text(b) => match value {
+                    Value::Null | Value::Blob(_) => b.append_null(),
+                    Value::Integer(i) => b.append_value(i.to_string()),
+                    Value::Real(f) => b.append_value(f.to_string()),
+                    Value::Text(s) => b.append_value(s),
+                },
+                ColumnBuilder::Blob(b) => match value {
+                    Value::Blob(bytes) => b.append_value(bytes),
+                    Value::Text(s) => b.append_value(s.as_bytes()),
+                    _ => b.append_null(),
+                },
+            }
+        }
+
+        fn finish(&mut self) -> ArrayRef {
+            match self {
+                ColumnBuilder::Int(b) => Arc::new(b.finish()),
+                ColumnBuilder::Float(b) => Arc::new(b.finish()),
+                ColumnBuilder::Text(b) => Arc::new(b.finish()),
+                ColumnBuilder::Blob(b) => Arc::new(b.finish()),
+            }
+        }
+    }
+
+    /// Map a declared SQLite column type to Arrow using SQLite's affinity rules.
+    fn arrow_type(declared: &str) -> DataType {
+        let declared = declared.to_ascii_uppercase();
+        if declared.contains("INT") {
+            DataType::Int64
+        } else if ["CHAR", "CLOB", "TEXT"]
+            .iter()
+            .any(|t| declared.contains(t))
+        {
+            DataType::Utf8
+        } else if declared.contains("BLOB") || declared.is_empty() {
+            DataType::Binary
+        } else {
+            DataType::Float64
+        }
+    }
+
+    /// Buffers rows into record batches written as Parquet row groups.
+    pub(crate) struct ParquetWriter {
+        writer: ArrowWriter<File>,
+        schema: SchemaRef,
+        builders: Vec<ColumnBuilder>,
+        buffered: usize,
+    }
+
+    impl ParquetWriter {
+        pub(crate) fn create(path: &Path, columns: &[ColumnManifest]) -> Result<Self, EngramError> {
+            let schema: SchemaRef = Arc::new(Schema::new(
+                columns
+                    .iter()
+                    .map(|c| Field::new(&c.name, arrow_type(&c.data_type), true))
+                    .collect::<Vec<_>>(),
+            ));
+            let builders = schema
+                .fields()
+                .iter()
+                .map(|f| ColumnBuilder::for_type(f.data_type()))
+                .collect();
+            let writer = ArrowWriter::try_new(File::create(path)?, Arc::clone(&schema), None)
+                .map_err(|e| EngramError::Serialization(format!("Create Parquet writer: {}", e)))?;
+            Ok(Self {
+                writer,
+                schema,
+                builders,
+                buffered: 0,
+            })
+        }
+
+        fn flush_batch(&mut self) -> Result<(), EngramError> {
+            if self.buffered == 0 {
+                return Ok(());
+            }
+            let arrays: Vec<ArrayRef> = self.builders.iter_mut().map(|b| b.finish()).collect();
+            let batch = RecordBatch::try_new(Arc::clone(&self.schema), arrays)
+                .map_err(|e| EngramError::Serialization(format!("Build record batch: {}", e)))?;
+            self.writer
+                .write(&batch)
+                .map_err(|e| EngramError::Serialization(format!("Write Parquet: {}", e)))?;
+            self.buffered = 0;
+            Ok(())
+        }
+    }
+
+    impl TableWriter for ParquetWriter {
+        fn write_row(&mut self, row: &[Value]) -> Result<(), EngramError> {
+            for (builder, value) in self.builders.iter_mut().zip(row) {
+                builder.append(value);
+            }
+            self.buffered += 1;
+            if self.buffered >= BATCH_ROWS {
+                self.flush_batch()?;
+            }
+            Ok(())
+        }
+
+        fn finish(mut self: Box<Self>) -> Result<(), EngramError> {
+            self.flush_batch()?;
+            self.writer
+                .close()
+                .map_err(|e| EngramError::Serialization(format!("Close Parquet: {}", e)))?;
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_arrow_type_follows_sqlite_affinity() {
+            assert_eq!(arrow_type("INTEGER"), DataType::Int64);
+            assert_eq!(arrow_type("TEXT"), DataType::Utf8);
+            assert_eq!(arrow_type("varchar(20)"), DataType::Utf8);
+            assert_eq!(arrow_type("REAL"), DataType::Float64);
+            assert_eq!(arrow_type("BLOB"), DataType::Binary);
+            assert_eq!(arrow_type(""), DataType::Binary);
+        }
+
+        #[test]
+        fn test_builder_converts_dynamic_values() {
+            let mut b = ColumnBuilder::for_type(&DataType::Int64);
+            b.append(&Value::Integer(5));
+            b.append(&Value::Text("7".to_string()));
+            b.append(&Value::Text("seven".to_string()));
+            let array = b.finish();
+            assert_eq!(array.len(), 3);
+            assert_eq!(array.null_count(), 1);
+        }
+    }
+}
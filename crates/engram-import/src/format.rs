@@ -0,0 +1,509 @@
+//! Import formats and the mapping of their records to Engram captures.
+//!
+//! Every format is line-delimited JSON, one record per line:
+//!
+//! - `engram`: Engram's generic archive. `type` is `screen`, `audio` or
+//!   `dictation` and the remaining fields use Engram's names (`text`,
+//!   `app_name`, `window_title`, `speaker`, ...).
+//! - `screenpipe`: items of a screenpipe search export, e.g.
+//!   `{"type": "OCR", "content": {"text": ..., "timestamp": ..., "app_name": ...}}`.
+//!   `OCR` and `UI` items become screen frames, `Audio` items audio chunks.
+//! - `rewind`: flat Rewind-style records with `application`, `window`,
+//!   `text` and, for meetings, `transcript`.
+//!
+//! Timestamps may be RFC 3339 strings, `YYYY-MM-DD HH:MM:SS` (UTC) or
+//! epoch seconds / milliseconds. Records without one are rejected rather
+//! than stamped with the import time.
+
+use std::fmt;
+
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use engram_core::types::{AudioChunk, ContentType, DictationEntry, DictationMode, ScreenFrame};
+
+/// Epoch values above this are taken as milliseconds (it is the year 5138
+/// in seconds).
+const EPOCH_MILLIS_THRESHOLD: f64 = 1e11;
+
+/// A supported import format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    Engram,
+    Screenpipe,
+    Rewind,
+}
+
+impl ImportFormat {
+    /// Every supported format.
+    pub const ALL: [ImportFormat; 3] = [
+        ImportFormat::Engram,
+        ImportFormat::Screenpipe,
+        ImportFormat::Rewind,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportFormat::Engram => "engram",
+            ImportFormat::Screenpipe => "screenpipe",
+            ImportFormat::Rewind => "rewind",
+        }
+    }
+}
+
+impl fmt::Display for ImportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ImportFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "engram" | "jsonl" => Ok(ImportFormat::Engram),
+            "screenpipe" => Ok(ImportFormat::Screenpipe),
+            "rewind" => Ok(ImportFormat::Rewind),
+            _ => Err(format!(
+                "Unknown import format: {} (expected engram, screenpipe or rewind)",
+                s
+            )),
+        }
+    }
+}
+
+/// A record mapped to the capture type it is ingested as.
+#[derive(Debug, Clone)]
+pub enum ImportItem {
+    Screen(ScreenFrame),
+    Audio(AudioChunk),
+    Dictation(DictationEntry),
+}
+
+impl ImportItem {
+    /// Content type name as stored in the `captures` table.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ImportItem::Screen(_) => "screen",
+            ImportItem::Audio(_) => "audio",
+            ImportItem::Dictation(_) => "dictation",
+        }
+    }
+
+    /// Original capture time.
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            ImportItem::Screen(f) => f.timestamp,
+            ImportItem::Audio(c) => c.timestamp,
+            ImportItem::Dictation(e) => e.timestamp,
+        }
+    }
+
+    /// The text that is embedded and stored.
+    pub fn text(&self) -> &str {
+        match self {
+            ImportItem::Screen(f) => &f.text,
+            ImportItem::Audio(c) => &c.transcription,
+            ImportItem::Dictation(e) => &e.text,
+        }
+    }
+}
+
+/// Map one parsed record to a capture.
+///
+/// Returns `Ok(None)` for records that carry nothing to import, such as
+/// blank text or a screenpipe item type Engram does not store.
+pub fn map_record(format: ImportFormat, record: &Value) -> Result<Option<ImportItem>, String> {
+    if !record.is_object() {
+        return Err("record is not a JSON object".to_string());
+    }
+    let item = match format {
+        ImportFormat::Engram => map_engram(record)?,
+        ImportFormat::Screenpipe => map_screenpipe(record)?,
+        ImportFormat::Rewind => map_rewind(record)?,
+    };
+    Ok(item.filter(|item| !item.text().trim().is_empty()))
+}
+
+fn map_engram(record: &Value) -> Result<Option<ImportItem>, String> {
+    let kind =
+        str_field(record, &["type", "content_type"]).ok_or_else(|| "missing 'type'".to_string())?;
+    let timestamp = timestamp_field(record, &["timestamp"])?;
+    let id = str_field(record, &["id"])
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .unwrap_or_else(Uuid::new_v4);
+    let app_name = string_field(record, &["app_name", "app_in_focus", "target_app"]);
+    let window_title = string_field(record, &["window_title", "target_window"]);
+
+    let item = match kind {
+        "screen" => ImportItem::Screen(ScreenFrame {
+            id,
+            content_type: ContentType::Screen,
+            timestamp,
+            app_name,
+            window_title,
+            monitor_id: str_field(record, &["monitor_id"])
+                .unwrap_or("import")
+                .to_string(),
+            text: string_field(record, &["text"]),
+            focused: record
+                .get("focused")
+                .and_then(Value::as_bool)
+                .unwrap_or(true),
+            image_data: Vec::new(),
+        }),
+        "audio" => ImportItem::Audio(AudioChunk {
+            id,
+            content_type: ContentType::Audio,
+            timestamp,
+            duration_secs: f32_field(record, &["duration_secs"]).unwrap_or(0.0),
+            transcription: string_field(record, &["text", "transcription"]),
+            speaker: string_field(record, &["speaker"]),
+            source_device: str_field(record, &["source_device"])
+                .unwrap_or("import")
+                .to_string(),
+            app_in_focus: app_name,
+            confidence: f32_field(record, &["confidence"]).unwrap_or(1.0),
+        }),
+        "dictation" => ImportItem::Dictation(DictationEntry {
+            id,
+            content_type: ContentType::Dictation,
+            timestamp,
+            text: string_field(record, &["text"]),
+            target_app: app_name,
+            target_window: window_title,
+            duration_secs: f32_field(record, &["duration_secs"]).unwrap_or(0.0),
+            mode: DictationMode::StoreOnly,
+        }),
+        other => return Err(format!("unsupported type '{}'", other)),
+    };
+    Ok(Some(item))
+}
+
+fn map_screenpipe(record: &Value) -> Result<Option<ImportItem>, String> {
+    let kind = str_field(record, &["type"]).ok_or_else(|| "missing 'type'".to_string())?;
+    let content = record
+        .get("content")
+        .filter(|c| c.is_object())
+        .ok_or_else(|| "missing 'content' object".to_string())?;
+
+    let item = match kind.to_ascii_lowercase().as_str() {
+        "ocr" | "ui" => ImportItem::Screen(ScreenFrame {
+            id: Uuid::new_v4(),
+            content_type: ContentType::Screen,
+            timestamp: timestamp_field(content, &["timestamp"])?,
+            app_name: string_field(content, &["app_name"]),
+            window_title: string_field(content, &["window_name"]),
+            monitor_id: "screenpipe".to_string(),
+            text: string_field(content, &["text"]),
+            focused: content
+                .get("focused")
+                .and_then(Value::as_bool)
+                .unwrap_or(true),
+            image_data: Vec::new(),
+        }),
+        "audio" => {
+            // Newer exports carry the speaker as an object.
+            let speaker = match content.get("speaker") {
+                Some(Value::Object(s)) => s
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                _ => string_field(content, &["speaker"]),
+            };
+            ImportItem::Audio(AudioChunk {
+                id: Uuid::new_v4(),
+                content_type: ContentType::Audio,
+                timestamp: timestamp_field(content, &["timestamp"])?,
+                duration_secs: span_secs(content),
+                transcription: string_field(content, &["transcription"]),
+                speaker,
+                source_device: str_field(content, &["device_name"])
+                    .unwrap_or("screenpipe")
+                    .to_string(),
+                app_in_focus: String::new(),
+                confidence: 1.0,
+            })
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(item))
+}
+
+fn map_rewind(record: &Value) -> Result<Option<ImportItem>, String> {
+    let timestamp = timestamp_field(record, &["timestamp", "start_time", "date"])?;
+    let app_name = string_field(record, &["application", "app", "app_name"]);
+    let kind = str_field(record, &["kind", "type"]).unwrap_or_default();
+    let transcript = str_field(record, &["transcript", "transcription"]);
+
+    let item = if transcript.is_some() || matches!(kind, "audio" | "meeting" | "transcript") {
+        ImportItem::Audio(AudioChunk {
+            id: Uuid::new_v4(),
+            content_type: ContentType::Audio,
+            timestamp,
+            duration_secs: f32_field(record, &["duration", "duration_secs"])
+                .unwrap_or_else(|| span_secs(record)),
+            transcription: transcript
+                .or_else(|| str_field(record, &["text"]))
+                .unwrap_or_default()
+                .to_string(),
+            speaker: string_field(record, &["speaker"]),
+            source_device: "rewind".to_string(),
+            app_in_focus: app_name,
+            confidence: 1.0,
+        })
+    } else {
+        ImportItem::Screen(ScreenFrame {
+            id: Uuid::new_v4(),
+            content_type: ContentType::Screen,
+            timestamp,
+            app_name,
+            window_title: string_field(record, &["window", "window_title", "title"]),
+            monitor_id: "rewind".to_string(),
+            text: string_field(record, &["text", "ocr_text"]),
+            focused: true,
+            image_data: Vec::new(),
+        })
+    };
+    Ok(Some(item))
+}
+
+/// The first of `keys` present as a string.
+fn str_field<'a>(record: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .find_map(|k| record.get(*k).and_then(Value::as_str))
+}
+
+fn string_field(record: &Value, keys: &[&str]) -> String {
+    str_field(record, keys).unwrap_or_default().to_string()
+}
+
+fn f32_field(record: &Value, keys: &[&str]) -> Option<f32> {
+    keys.iter()
+        .find_map(|k| record.get(*k).and_then(Value::as_f64))
+        .map(|v| v as f32)
+}
+
+/// Duration from numeric `start_time` / `end_time` offsets, or 0.
+fn span_secs(record: &Value) -> f32 {
+    match (
+        record.get("start_time").and_then(Value::as_f64),
+        record.get("end_time").and_then(Value::as_f64),
+    ) {
+        (Some(start), Some(end)) if end > start => (end - start) as f32,
+        _ => 0.0,
+    }
+}
+
+fn timestamp_field(record: &Value, keys: &[&str]) -> Result<DateTime<Utc>, String> {
+    let value = keys
+        .iter()
+        .find_map(|k| record.get(*k).filter(|v| !v.is_null()))
+        .ok_or_else(|| format!("missing '{}'", keys[0]))?;
+    parse_timestamp(value).ok_or_else(|| format!("invalid timestamp {}", value))
+}
+
+/// Parse an RFC 3339 / naive UTC string or epoch seconds / milliseconds.
+fn parse_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::String(s) => {
+            let s = s.trim();
+            if let Ok(ts) = DateTime::parse_from_rfc3339(s) {
+                return Some(ts.with_timezone(&Utc));
+            }
+            if let Ok(n) = s.parse::<f64>() {
+                return parse_epoch(n);
+            }
+            ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"]
+                .iter()
+                .find_map(|fmt| NaiveDateTime::parse_from_str(s, fmt).ok())
+                .map(|naive| naive.and_utc())
+        }
+        Value::Number(n) => n.as_f64().and_then(parse_epoch),
+        _ => None,
+    }
+}
+
+fn parse_epoch(n: f64) -> Option<DateTime<Utc>> {
+    if !n.is_finite() || n < 0.0 {
+        return None;
+    }
+    let millis = if n > EPOCH_MILLIS_THRESHOLD {
+        n
+    } else {
+        n * 1000.0
+    };
+    Utc.timestamp_millis_opt(millis as i64).single()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn ts(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_format_round_trips_through_str() {
+        for format in ImportFormat::ALL {
+            assert_eq!(format.as_str().parse::<ImportFormat>().unwrap(), format);
+        }
+        assert_eq!(
+            "jsonl".parse::<ImportFormat>().unwrap(),
+            ImportFormat::Engram
+        );
+        assert!("notion".parse::<ImportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_parse_timestamp_variants() {
+        let expected = ts("2026-01-05T10:00:00Z");
+        for value in [
+            json!("2026-01-05T10:00:00Z"),
+            json!("2026-01-05T11:00:00+01:00"),
+            json!("2026-01-05 10:00:00"),
+            json!("2026-01-05T10:00:00.000"),
+            json!(1767607200),
+            json!(1767607200000_i64),
+            json!("1767607200"),
+        ] {
+            assert_eq!(parse_timestamp(&value), Some(expected), "{}", value);
+        }
+        assert_eq!(parse_timestamp(&json!("yesterday")), None);
+        assert_eq!(parse_timestamp(&json!(-5)), None);
+    }
+
+    #[test]
+    fn test_map_engram_records() {
+        let id = Uuid::new_v4();
+        let item = map_record(
+            ImportFormat::Engram,
+            &json!({"type": "audio", "id": id.to_string(), "timestamp": "2026-01-05T10:00:00Z",
+                    "text": "Ship it Friday", "speaker": "Dana", "app_name": "Zoom",
+                    "duration_secs": 4.5}),
+        )
+        .unwrap()
+        .unwrap();
+        let ImportItem::Audio(chunk) = item else {
+            panic!("expected audio");
+        };
+        assert_eq!(chunk.id, id);
+        assert_eq!(chunk.timestamp, ts("2026-01-05T10:00:00Z"));
+        assert_eq!(chunk.speaker, "Dana");
+        assert_eq!(chunk.app_in_focus, "Zoom");
+        assert_eq!(chunk.duration_secs, 4.5);
+        assert_eq!(chunk.source_device, "import");
+
+        let item = map_record(
+            ImportFormat::Engram,
+            &json!({"type": "dictation", "timestamp": 1767607200, "text": "note to self",
+                    "target_app": "Notes"}),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(item.content_type(), "dictation");
+        let ImportItem::Dictation(entry) = item else {
+            panic!("expected dictation");
+        };
+        assert_eq!(entry.target_app, "Notes");
+        assert_eq!(entry.mode, DictationMode::StoreOnly);
+
+        let err = map_record(
+            ImportFormat::Engram,
+            &json!({"type": "video", "timestamp": 1767607200, "text": "x"}),
+        )
+        .unwrap_err();
+        assert!(err.contains("video"), "{}", err);
+    }
+
+    #[test]
+    fn test_map_screenpipe_records() {
+        let item = map_record(
+            ImportFormat::Screenpipe,
+            &json!({"type": "OCR", "content": {"frame_id": 12, "text": "cargo build",
+                    "timestamp": "2026-01-05T10:00:00Z", "app_name": "Terminal",
+                    "window_name": "zsh"}}),
+        )
+        .unwrap()
+        .unwrap();
+        let ImportItem::Screen(frame) = item else {
+            panic!("expected screen");
+        };
+        assert_eq!(frame.app_name, "Terminal");
+        assert_eq!(frame.window_title, "zsh");
+        assert_eq!(frame.monitor_id, "screenpipe");
+
+        let item = map_record(
+            ImportFormat::Screenpipe,
+            &json!({"type": "Audio", "content": {"chunk_id": 3, "transcription": "hello team",
+                    "timestamp": "2026-01-05T10:00:00Z", "device_name": "MacBook Mic",
+                    "speaker": {"id": 1, "name": "Dana"}, "start_time": 1.0, "end_time": 4.0}}),
+        )
+        .unwrap()
+        .unwrap();
+        let ImportItem::Audio(chunk) = item else {
+            panic!("expected audio");
+        };
+        assert_eq!(chunk.speaker, "Dana");
+        assert_eq!(chunk.source_device, "MacBook Mic");
+        assert_eq!(chunk.duration_secs, 3.0);
+
+        assert!(map_record(
+            ImportFormat::Screenpipe,
+            &json!({"type": "Input", "content": {"timestamp": "2026-01-05T10:00:00Z"}}),
+        )
+        .unwrap()
+        .is_none());
+        assert!(map_record(ImportFormat::Screenpipe, &json!({"type": "OCR"})).is_err());
+    }
+
+    #[test]
+    fn test_map_rewind_records() {
+        let item = map_record(
+            ImportFormat::Rewind,
+            &json!({"timestamp": 1767607200000_i64, "application": "Safari",
+                    "window": "Docs", "text": "quarterly plan"}),
+        )
+        .unwrap()
+        .unwrap();
+        let ImportItem::Screen(frame) = item else {
+            panic!("expected screen");
+        };
+        assert_eq!(frame.app_name, "Safari");
+        assert_eq!(frame.window_title, "Docs");
+        assert_eq!(frame.timestamp, ts("2026-01-05T10:00:00Z"));
+
+        let item = map_record(
+            ImportFormat::Rewind,
+            &json!({"start_time": "2026-01-05T10:00:00Z", "app": "Zoom",
+                    "transcript": "let's ship", "speaker": "Sam", "duration": 12}),
+        )
+        .unwrap()
+        .unwrap();
+        let ImportItem::Audio(chunk) = item else {
+            panic!("expected audio");
+        };
+        assert_eq!(chunk.app_in_focus, "Zoom");
+        assert_eq!(chunk.duration_secs, 12.0);
+        assert_eq!(chunk.source_device, "rewind");
+    }
+
+    #[test]
+    fn test_records_without_text_or_timestamp() {
+        assert!(map_record(
+            ImportFormat::Rewind,
+            &json!({"timestamp": 1767607200, "text": "   "}),
+        )
+        .unwrap()
+        .is_none());
+        let err = map_record(ImportFormat::Rewind, &json!({"text": "no time"})).unwrap_err();
+        assert!(err.contains("timestamp"), "{}", err);
+        assert!(map_record(ImportFormat::Engram, &json!([1, 2])).is_err());
+    }
+}
@@ -0,0 +1,316 @@
+//! Streaming import of a line-delimited JSON file through the pipeline.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::Serialize;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tracing::{debug, info};
+
+use engram_core::error::EngramError;
+use engram_storage::Database;
+use engram_vector::{EngramPipeline, IngestResult};
+
+use crate::format::{map_record, ImportFormat, ImportItem};
+
+/// Records processed between progress callbacks.
+pub const PROGRESS_INTERVAL: u64 = 100;
+
+/// Record errors kept in [`ImportProgress::errors`].
+const MAX_REPORTED_ERRORS: usize = 20;
+
+/// Running totals for one import.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct ImportProgress {
+    /// Non-blank lines read.
+    pub records: u64,
+    /// Records stored (including those stored with PII redacted).
+    pub imported: u64,
+    /// Records already stored, by exact match or embedding similarity.
+    pub duplicates: u64,
+    /// Records with nothing to import (blank text, unsupported item types).
+    pub skipped: u64,
+    /// Records the safety gate refused to store.
+    pub denied: u64,
+    /// Records that could not be parsed, mapped or stored.
+    pub failed: u64,
+    pub bytes_read: u64,
+    pub total_bytes: u64,
+    /// The first few record errors, prefixed with their line number.
+    pub errors: Vec<String>,
+}
+
+impl ImportProgress {
+    fn record_error(&mut self, line: u64, message: impl std::fmt::Display) {
+        self.failed += 1;
+        if self.errors.len() < MAX_REPORTED_ERRORS {
+            self.errors.push(format!("line {}: {}", line, message));
+        }
+    }
+}
+
+/// Imports exported captures into the pipeline.
+///
+/// Files are read line by line, so archives larger than memory can be
+/// imported. Each record is checked against the `captures` table by
+/// content type, original timestamp and (safety-checked) text before it is
+/// ingested, which makes re-running an import a no-op.
+pub struct Importer {
+    pipeline: Arc<EngramPipeline>,
+    db: Arc<Database>,
+}
+
+impl Importer {
+    /// Create an importer. `pipeline` should write through to `db`.
+    pub fn new(pipeline: Arc<EngramPipeline>, db: Arc<Database>) -> Self {
+        Self { pipeline, db }
+    }
+
+    /// Import every record in `path`.
+    ///
+    /// `on_progress` is called every [`PROGRESS_INTERVAL`] records and once
+    /// more when the file is done. Bad records are counted and reported in
+    /// the progress; only failing to read the file is an error.
+    pub async fn import_file(
+        &self,
+        path: &Path,
+        format: ImportFormat,
+        mut on_progress: impl FnMut(&ImportProgress),
+    ) -> Result<ImportProgress, EngramError> {
+        let file = tokio::fs::File::open(path).await?;
+        let mut progress = ImportProgress {
+            total_bytes: file.metadata().await?.len(),
+            ..ImportProgress::default()
+        };
+        let mut reader = BufReader::new(file);
+        let mut buf = Vec::new();
+        let mut line_no = 0u64;
+
+        loop {
+            buf.clear();
+            let read = reader.read_until(b'\n', &mut buf).await?;
+            if read == 0 {
+                break;
+            }
+            line_no += 1;
+            progress.bytes_read += read as u64;
+
+            let line = String::from_utf8_lossy(&buf);
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            progress.records += 1;
+            self.import_line(line, line_no, format, &mut progress).await;
+
+            if progress.records.is_multiple_of(PROGRESS_INTERVAL) {
+                on_progress(&progress);
+            }
+        }
+
+        on_progress(&progress);
+        info!(
+            path = %path.display(),
+            format = %format,
+            records = progress.records,
+            imported = progress.imported,
+            duplicates = progress.duplicates,
+            failed = progress.failed,
+            "Import finished"
+        );
+        Ok(progress)
+    }
+
+    async fn import_line(
+        &self,
+        line: &str,
+        line_no: u64,
+        format: ImportFormat,
+        progress: &mut ImportProgress,
+    ) {
+        let record: serde_json::Value = match serde_json::from_str(line) {
+            Ok(record) => record,
+            Err(e) => return progress.record_error(line_no, format!("invalid JSON: {}", e)),
+        };
+        let item = match map_record(format, &record) {
+            Ok(Some(item)) => item,
+            Ok(None) => {
+                progress.skipped += 1;
+                return;
+            }
+            Err(e) => return progress.record_error(line_no, e),
+        };
+
+        // Compare what would be stored, so redacted records dedupe too.
+        let Some(safe_text) = self.pipeline.safe_text(item.text()) else {
+            progress.denied += 1;
+            return;
+        };
+        let exists = self.db.with_conn(|conn| {
+            engram_storage::capture_exists(
+                conn,
+                item.content_type(),
+                item.timestamp().timestamp(),
+                &safe_text,
+            )
+        });
+        match exists {
+            Ok(true) => {
+                debug!(line = line_no, "Skipping record already stored");
+                progress.duplicates += 1;
+                return;
+            }
+            Ok(false) => {}
+            Err(e) => return progress.record_error(line_no, e),
+        }
+
+        let result = match item {
+            ImportItem::Screen(frame) => self.pipeline.ingest_screen(frame).await,
+            ImportItem::Audio(chunk) => self.pipeline.ingest_audio(chunk).await,
+            ImportItem::Dictation(entry) => self.pipeline.ingest_dictation(entry).await,
+        };
+        match result {
+            Ok(IngestResult::Stored { .. } | IngestResult::Redacted { .. }) => {
+                progress.imported += 1
+            }
+            Ok(IngestResult::Deduplicated { .. }) => progress.duplicates += 1,
+            Ok(IngestResult::Skipped { .. }) => progress.skipped += 1,
+            Ok(IngestResult::Denied { .. }) => progress.denied += 1,
+            Err(e) => progress.record_error(line_no, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engram_core::config::SafetyConfig;
+    use engram_storage::CaptureRepository;
+    use engram_vector::{MockEmbedding, VectorIndex};
+
+    fn make_importer() -> (Importer, Arc<Database>) {
+        let db = Arc::new(Database::in_memory().unwrap());
+        let pipeline = EngramPipeline::new(
+            Arc::new(VectorIndex::new()),
+            MockEmbedding::new(),
+            SafetyConfig {
+                custom_deny_patterns: vec!["TOP SECRET".to_string()],
+                ..Default::default()
+            },
+            0.95,
+        )
+        .with_database(Arc::clone(&db));
+        (Importer::new(Arc::new(pipeline), Arc::clone(&db)), db)
+    }
+
+    fn write_file(dir: &Path, lines: &[&str]) -> std::path::PathBuf {
+        let path = dir.join("export.jsonl");
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn test_import_counts_every_outcome() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_file(
+            dir.path(),
+            &[
+                r#"{"type": "OCR", "content": {"text": "cargo build --release", "timestamp": "2026-01-05T10:00:00Z", "app_name": "Terminal"}}"#,
+                r#"{"type": "Audio", "content": {"transcription": "mail dana@example.com the deck", "timestamp": "2026-01-05T10:01:00Z"}}"#,
+                "",
+                r#"{"type": "Input", "content": {"timestamp": "2026-01-05T10:02:00Z"}}"#,
+                r#"{"type": "OCR", "content": {"text": "TOP SECRET roadmap", "timestamp": "2026-01-05T10:03:00Z"}}"#,
+                r#"{"type": "OCR", "content": {"text": "no timestamp"}}"#,
+                "not json",
+            ],
+        );
+        let (importer, db) = make_importer();
+
+        let mut calls = 0;
+        let progress = importer
+            .import_file(&path, ImportFormat::Screenpipe, |_| calls += 1)
+            .await
+            .unwrap();
+
+        assert_eq!(progress.records, 6);
+        assert_eq!(progress.imported, 2);
+        assert_eq!(progress.skipped, 1);
+        assert_eq!(progress.denied, 1);
+        assert_eq!(progress.failed, 2);
+        assert_eq!(progress.errors.len(), 2);
+        assert!(
+            progress.errors[0].starts_with("line 6:"),
+            "{:?}",
+            progress.errors
+        );
+        assert!(progress.errors[1].starts_with("line 7: invalid JSON"));
+        assert_eq!(progress.bytes_read, progress.total_bytes);
+        assert_eq!(calls, 1);
+
+        let frames = CaptureRepository::new(db)
+            .find_by_app("Terminal", 10)
+            .unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(
+            frames[0].timestamp.to_rfc3339(),
+            "2026-01-05T10:00:00+00:00"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reimport_is_deduplicated() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_file(
+            dir.path(),
+            &[
+                r#"{"type": "screen", "timestamp": "2026-01-05T10:00:00Z", "text": "weekly sync notes", "app_name": "Notes"}"#,
+                r#"{"type": "dictation", "timestamp": "2026-01-05T10:05:00Z", "text": "call me at 555-123-4567"}"#,
+            ],
+        );
+        let (importer, _db) = make_importer();
+
+        let first = importer
+            .import_file(&path, ImportFormat::Engram, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(first.imported, 2);
+
+        let second = importer
+            .import_file(&path, ImportFormat::Engram, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(second.imported, 0);
+        assert_eq!(second.duplicates, 2);
+    }
+
+    #[tokio::test]
+    async fn test_progress_reported_every_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let lines: Vec<String> = (0..250)
+            .map(|i| format!(r#"{{"timestamp": {}, "text": "   "}}"#, 1767607200 + i))
+            .collect();
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let path = write_file(dir.path(), &refs);
+        let (importer, _db) = make_importer();
+
+        let mut seen = Vec::new();
+        importer
+            .import_file(&path, ImportFormat::Rewind, |p| seen.push(p.records))
+            .await
+            .unwrap();
+        assert_eq!(seen, vec![100, 200, 250]);
+    }
+
+    #[tokio::test]
+    async fn test_missing_file_is_an_error() {
+        let (importer, _db) = make_importer();
+        let result = importer
+            .import_file(
+                Path::new("/nonexistent/export.jsonl"),
+                ImportFormat::Engram,
+                |_| {},
+            )
+            .await;
+        assert!(matches!(result, Err(EngramError::Io(_))));
+    }
+}
@@ -0,0 +1,178 @@
+//! In-memory registry of imports started through the API.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::format::ImportFormat;
+use crate::importer::ImportProgress;
+
+/// Finished jobs kept for status queries; older ones are dropped first.
+const MAX_FINISHED_JOBS: usize = 50;
+
+/// Lifecycle of an import job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportJobStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+impl ImportJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportJobStatus::Running => "running",
+            ImportJobStatus::Completed => "completed",
+            ImportJobStatus::Failed => "failed",
+        }
+    }
+}
+
+/// An import and its latest progress.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportJob {
+    pub id: Uuid,
+    pub path: String,
+    pub format: ImportFormat,
+    pub status: ImportJobStatus,
+    pub progress: ImportProgress,
+    /// Why the import stopped, when it failed.
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Tracks running and recently finished imports.
+#[derive(Debug, Default)]
+pub struct ImportJobs {
+    jobs: Mutex<HashMap<Uuid, ImportJob>>,
+}
+
+impl ImportJobs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a running import of `path`.
+    pub fn start(&self, path: &str, format: ImportFormat) -> ImportJob {
+        let job = ImportJob {
+            id: Uuid::new_v4(),
+            path: path.to_string(),
+            format,
+            status: ImportJobStatus::Running,
+            progress: ImportProgress::default(),
+            error: None,
+            started_at: Utc::now(),
+            finished_at: None,
+        };
+        self.lock().insert(job.id, job.clone());
+        job
+    }
+
+    /// Record the latest progress of a running import.
+    pub fn update(&self, id: Uuid, progress: &ImportProgress) {
+        if let Some(job) = self.lock().get_mut(&id) {
+            job.progress = progress.clone();
+        }
+    }
+
+    /// Mark an import as finished with its final progress or error.
+    pub fn finish(&self, id: Uuid, result: Result<ImportProgress, String>) -> Option<ImportJob> {
+        let mut jobs = self.lock();
+        let job = jobs.get_mut(&id)?;
+        match result {
+            Ok(progress) => {
+                job.status = ImportJobStatus::Completed;
+                job.progress = progress;
+            }
+            Err(e) => {
+                job.status = ImportJobStatus::Failed;
+                job.error = Some(e);
+            }
+        }
+        job.finished_at = Some(Utc::now());
+        let finished = job.clone();
+
+        let mut done: Vec<(DateTime<Utc>, Uuid)> = jobs
+            .values()
+            .filter_map(|j| j.finished_at.map(|at| (at, j.id)))
+            .collect();
+        if done.len() > MAX_FINISHED_JOBS {
+            done.sort();
+            for (_, id) in &done[..done.len() - MAX_FINISHED_JOBS] {
+                jobs.remove(id);
+            }
+        }
+        Some(finished)
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<ImportJob> {
+        self.lock().get(&id).cloned()
+    }
+
+    /// All tracked jobs, newest first.
+    pub fn list(&self) -> Vec<ImportJob> {
+        let mut jobs: Vec<ImportJob> = self.lock().values().cloned().collect();
+        jobs.sort_by_key(|j| std::cmp::Reverse(j.started_at));
+        jobs
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Uuid, ImportJob>> {
+        self.jobs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_lifecycle() {
+        let jobs = ImportJobs::new();
+        let job = jobs.start("/tmp/export.jsonl", ImportFormat::Screenpipe);
+        assert_eq!(job.status, ImportJobStatus::Running);
+
+        let progress = ImportProgress {
+            records: 100,
+            imported: 90,
+            ..ImportProgress::default()
+        };
+        jobs.update(job.id, &progress);
+        assert_eq!(jobs.get(job.id).unwrap().progress.imported, 90);
+
+        let done = jobs.finish(job.id, Ok(progress)).unwrap();
+        assert_eq!(done.status, ImportJobStatus::Completed);
+        assert!(done.finished_at.is_some());
+
+        let failed = jobs.start("/tmp/missing.jsonl", ImportFormat::Engram);
+        jobs.finish(failed.id, Err("No such file".to_string()));
+        let failed = jobs.get(failed.id).unwrap();
+        assert_eq!(failed.status, ImportJobStatus::Failed);
+        assert_eq!(failed.error.as_deref(), Some("No such file"));
+
+        assert!(jobs.get(Uuid::new_v4()).is_none());
+        assert!(jobs
+            .finish(Uuid::new_v4(), Ok(ImportProgress::default()))
+            .is_none());
+    }
+
+    #[test]
+    fn test_old_finished_jobs_are_dropped() {
+        let jobs = ImportJobs::new();
+        let first = jobs.start("/tmp/0.jsonl", ImportFormat::Engram);
+        jobs.finish(first.id, Ok(ImportProgress::default()));
+        for i in 0..MAX_FINISHED_JOBS {
+            let job = jobs.start(&format!("/tmp/{}.jsonl", i + 1), ImportFormat::Engram);
+            jobs.finish(job.id, Ok(ImportProgress::default()));
+        }
+        let running = jobs.start("/tmp/running.jsonl", ImportFormat::Engram);
+
+        assert!(jobs.get(first.id).is_none());
+        assert!(jobs.get(running.id).is_some());
+        assert_eq!(jobs.list().len(), MAX_FINISHED_JOBS + 1);
+    }
+}
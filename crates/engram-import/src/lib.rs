@@ -0,0 +1,15 @@
+//! Engram Import crate - bring captures recorded by other tools into Engram.
+//!
+//! Reads line-delimited JSON exports from screenpipe, Rewind-style archives
+//! or Engram's own generic format, maps each record to a `ScreenFrame`,
+//! `AudioChunk` or `DictationEntry` with its original timestamp, and feeds
+//! it through `EngramPipeline` so the safety gate, deduplication and
+//! embedding apply exactly as they do to live captures.
+
+pub mod format;
+pub mod importer;
+pub mod jobs;
+
+pub use format::{map_record, ImportFormat, ImportItem};
+pub use importer::{ImportProgress, Importer};
+pub use jobs::{ImportJob, ImportJobStatus, ImportJobs};
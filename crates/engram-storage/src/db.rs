@@ -3,10 +3,10 @@
 //! Wraps a single rusqlite Connection in a Mutex for thread-safe access.
 //! Configures WAL mode and recommended PRAGMAs on initialization.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
-use rusqlite::Connection;
+use rusqlite::{Connection, OpenFlags};
 use tracing::info;
 
 use engram_core::error::EngramError;
@@ -19,6 +19,8 @@ use crate::migrations;
 /// wrapped in a Mutex since rusqlite Connection is not Sync.
 pub struct Database {
     conn: Mutex<Connection>,
+    /// File backing the database; `None` for in-memory databases.
+    path: Option<PathBuf>,
 }
 
 impl Database {
@@ -48,6 +50,7 @@ impl Database {
 
         let db = Self {
             conn: Mutex::new(conn),
+            path: Some(path.to_path_buf()),
         };
 
         // Run migrations.
@@ -70,6 +73,7 @@ impl Database {
 
         let db = Self {
             conn: Mutex::new(conn),
+            path: None,
         };
 
         db.with_conn(migrations::run_migrations)?;
@@ -91,6 +95,56 @@ impl Database {
             .map_err(|e| EngramError::Storage(format!("Database lock poisoned: {}", e)))?;
         f(&conn)
     }
+
+    /// Execute a closure inside a transaction.
+    ///
+    /// The transaction commits if the closure returns `Ok` and rolls back
+    /// otherwise, so every write made through the connection either lands
+    /// together or not at all.
+    pub fn with_transaction<F, T>(&self, f: F) -> Result<T, EngramError>
+    where
+        F: FnOnce(&Connection) -> Result<T, EngramError>,
+    {
+        let mut conn = self
+            .conn
+            .lock()
+            .map_err(|e| EngramError::Storage(format!("Database lock poisoned: {}", e)))?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| EngramError::Storage(format!("Begin transaction: {}", e)))?;
+        let value = f(&tx)?;
+        tx.commit()
+            .map_err(|e| EngramError::Storage(format!("Commit transaction: {}", e)))?;
+        Ok(value)
+    }
+
+    /// Execute a closure against a consistent read-only snapshot.
+    ///
+    /// File-backed databases open a separate read-only connection and run
+    /// the closure inside a read transaction, so WAL keeps every read at
+    /// the same point in time while writers carry on through the shared
+    /// connection. In-memory databases cannot be reopened and fall back to
+    /// a transaction on the shared connection.
+    pub fn with_snapshot<F, T>(&self, f: F) -> Result<T, EngramError>
+    where
+        F: FnOnce(&Connection) -> Result<T, EngramError>,
+    {
+        let Some(path) = &self.path else {
+            return self.with_transaction(f);
+        };
+        let mut conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .map_err(|e| EngramError::Storage(format!("Open read-only connection: {}", e)))?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| EngramError::Storage(format!("Begin snapshot: {}", e)))?;
+        let value = f(&tx)?;
+        tx.commit()
+            .map_err(|e| EngramError::Storage(format!("End snapshot: {}", e)))?;
+        Ok(value)
+    }
 }
 
 // SAFETY: Database is Send+Sync because:
@@ -159,4 +213,70 @@ mod tests {
         })
         .unwrap();
     }
+
+    #[test]
+    fn test_with_transaction_rolls_back_on_error() {
+        let db = Database::in_memory().unwrap();
+        let insert = |conn: &Connection, id: &str| {
+            conn.execute(
+                "INSERT INTO captures (id, content_type, timestamp, text) VALUES (?1, 'screen', 0, 'x')",
+                [id],
+            )
+            .map_err(|e| EngramError::Storage(e.to_string()))
+        };
+
+        let result: Result<(), EngramError> = db.with_transaction(|conn| {
+            insert(conn, "a")?;
+            Err(EngramError::Storage("boom".to_string()))
+        });
+        assert!(result.is_err());
+
+        db.with_transaction(|conn| insert(conn, "b")).unwrap();
+
+        let ids: Vec<String> = db
+            .with_conn(|conn| {
+                let mut stmt = conn
+                    .prepare("SELECT id FROM captures")
+                    .map_err(|e| EngramError::Storage(e.to_string()))?;
+                let rows = stmt
+                    .query_map([], |row| row.get(0))
+                    .map_err(|e| EngramError::Storage(e.to_string()))?;
+                Ok(rows.filter_map(Result::ok).collect())
+            })
+            .unwrap();
+        assert_eq!(ids, vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn test_with_snapshot_reads_one_point_in_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(&dir.path().join("test.db")).unwrap();
+        let count = |conn: &Connection| -> Result<i64, EngramError> {
+            conn.query_row("SELECT COUNT(*) FROM captures", [], |row| row.get(0))
+                .map_err(|e| EngramError::Storage(e.to_string()))
+        };
+
+        let (before, after) = db
+            .with_snapshot(|snapshot| {
+                let before = count(snapshot)?;
+                // The shared connection stays free for writers meanwhile.
+                db.with_conn(|conn| {
+                    conn.execute(
+                        "INSERT INTO captures (id, content_type, timestamp, text) VALUES ('a', 'screen', 0, 'x')",
+                        [],
+                    )
+                    .map_err(|e| EngramError::Storage(e.to_string()))
+                })?;
+                Ok((before, count(snapshot)?))
+            })
+            .unwrap();
+        assert_eq!((before, after), (0, 0));
+        assert_eq!(db.with_conn(count).unwrap(), 1);
+
+        let write = db.with_snapshot(|conn| {
+            conn.execute("DELETE FROM captures", [])
+                .map_err(|e| EngramError::Storage(e.to_string()))
+        });
+        assert!(write.is_err());
+    }
 }
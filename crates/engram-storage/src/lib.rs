@@ -13,14 +13,26 @@ pub mod tier;
 
 pub use db::Database;
 pub use queries::{
-    get_action_history, get_intents, get_task, list_tasks, store_action_history, store_intent,
-    store_task, update_task_status, ActionHistoryRow, AppSummary, CaptureRow, ClusterRow, DbStats,
-    DigestRow, EntityRow, HistoryFilters, IntentFilters, IntentRow, QueryService, SummaryRow,
-    TaskFilters, TaskRow,
+    adjust_source_boost, append_event_log, append_event_log_json, capture_exists,
+    delete_document_file, delete_saved_search, event_log_window_start, get_action_history,
+    get_api_token_by_hash, get_chat_feedback, get_chat_feedback_counts, get_document_file,
+    get_fusion_weight, get_ingest_key, get_intents, get_query_stats, get_saved_search,
+    get_search_latencies, get_search_mode_stats, get_source_boosts, get_task, list_api_tokens,
+    list_document_files, list_due_saved_searches, list_feedback_queries, list_saved_searches,
+    list_source_boosts, list_tasks, max_capture_rowid, max_event_log_id, purge_event_log,
+    purge_search_log, query_event_log, record_saved_search_run, record_search_click,
+    revoke_api_token, store_action_history, store_api_token, store_chat_feedback, store_digest,
+    store_entity, store_fusion_weight, store_ingest_key, store_intent, store_saved_search,
+    store_search_log, store_summary, store_task, touch_api_token, update_saved_search,
+    update_task_status, upsert_document_file, ActionHistoryRow, ApiTokenRow, AppSummary,
+    CaptureRow, ChatFeedbackRow, ClusterRow, DbStats, DigestRow, DocumentFileRow, EntityRow,
+    EventLogFilter, EventLogRow, FeedbackCountRow, FusionWeightRow, HistoryFilters, IngestKeyRow,
+    IntentFilters, IntentRow, QueryService, QueryStatRow, SavedSearchRow, SearchLogRow,
+    SearchModeStatRow, SourceBoostRow, SummaryRow, TaskFilters, TaskRow, LOG_ID_FIELD,
 };
 pub use repository::{
-    AudioRepository, CaptureRepository, DictationRepository, VectorMetadata,
+    AudioRepository, CaptureRepository, DictationRepository, DocumentRepository, VectorMetadata,
     VectorMetadataRepository,
 };
-pub use search::{sanitize_fts5_query, FtsResult, FtsSearch};
+pub use search::{sanitize_fts5_query, FtsFilters, FtsResult, FtsSearch};
 pub use tier::{PurgeResult, TierManager};
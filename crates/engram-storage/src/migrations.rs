@@ -61,6 +61,46 @@ pub fn run_migrations(conn: &Connection) -> Result<(), EngramError> {
         info!("Applied migration v6: chat_tables");
     }
 
+    if current_version < 7 {
+        apply_v7(conn)?;
+        info!("Applied migration v7: saved_searches");
+    }
+
+    if current_version < 8 {
+        apply_v8(conn)?;
+        info!("Applied migration v8: search_log");
+    }
+
+    if current_version < 9 {
+        apply_v9(conn)?;
+        info!("Applied migration v9: api_tokens");
+    }
+
+    if current_version < 10 {
+        apply_v10(conn)?;
+        info!("Applied migration v10: event_log");
+    }
+
+    if current_version < 11 {
+        apply_v11(conn)?;
+        info!("Applied migration v11: document_captures_and_ingest_keys");
+    }
+
+    if current_version < 12 {
+        apply_v12(conn)?;
+        info!("Applied migration v12: document_files");
+    }
+
+    if current_version < 13 {
+        apply_v13(conn)?;
+        info!("Applied migration v13: chat_session_titles_and_search");
+    }
+
+    if current_version < 14 {
+        apply_v14(conn)?;
+        info!("Applied migration v14: chat_feedback");
+    }
+
     Ok(())
 }
 
@@ -450,6 +490,379 @@ fn apply_v6(conn: &Connection) -> Result<(), EngramError> {
     Ok(())
 }
 
+/// Version 7: Saved searches.
+///
+/// Creates the table for persisted queries that are re-run on an interval.
+/// `last_capture_rowid` is the highest `captures` rowid seen by the previous
+/// run, so new matches go by insert order rather than capture timestamps,
+/// which clients may back-date. It is NULL until the first run.
+fn apply_v7(conn: &Connection) -> Result<(), EngramError> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS saved_searches (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            query TEXT NOT NULL,
+            mode TEXT NOT NULL DEFAULT 'keyword'
+                CHECK(mode IN ('keyword', 'semantic', 'hybrid')),
+            content_type TEXT,
+            app_name TEXT,
+            interval_secs INTEGER NOT NULL DEFAULT 900,
+            notify INTEGER NOT NULL DEFAULT 0,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            last_run_at INTEGER,
+            last_match_count INTEGER NOT NULL DEFAULT 0,
+            last_capture_rowid INTEGER,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_saved_searches_last_run ON saved_searches(last_run_at);
+
+        INSERT OR IGNORE INTO schema_migrations (version, name) VALUES (7, 'saved_searches');
+        ",
+    )
+    .map_err(|e| EngramError::Storage(format!("Failed to apply migration v7: {}", e)))?;
+
+    Ok(())
+}
+
+/// Version 8: Search query log.
+///
+/// Creates the opt-in local log of executed searches used for analytics.
+fn apply_v8(conn: &Connection) -> Result<(), EngramError> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS search_log (
+            id TEXT PRIMARY KEY,
+            query TEXT NOT NULL,
+            mode TEXT NOT NULL,
+            latency_ms INTEGER NOT NULL,
+            result_count INTEGER NOT NULL,
+            clicked_result_id TEXT,
+            created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_search_log_created ON search_log(created_at);
+        CREATE INDEX IF NOT EXISTS idx_search_log_query ON search_log(query);
+
+        INSERT OR IGNORE INTO schema_migrations (version, name) VALUES (8, 'search_log');
+        ",
+    )
+    .map_err(|e| EngramError::Storage(format!("Failed to apply migration v8: {}", e)))?;
+
+    Ok(())
+}
+
+/// Version 9: Scoped API tokens.
+///
+/// Stores named bearer tokens as SHA-256 hashes with their scopes, expiry
+/// and last-used time. Plaintext tokens are never persisted.
+fn apply_v9(conn: &Connection) -> Result<(), EngramError> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS api_tokens (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL UNIQUE,
+            token_hash TEXT NOT NULL UNIQUE,
+            scopes TEXT NOT NULL,
+            expires_at INTEGER,
+            last_used_at INTEGER,
+            revoked INTEGER NOT NULL DEFAULT 0,
+            created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+        );
+
+        INSERT OR IGNORE INTO schema_migrations (version, name) VALUES (9, 'api_tokens');
+        ",
+    )
+    .map_err(|e| EngramError::Storage(format!("Failed to apply migration v9: {}", e)))?;
+
+    Ok(())
+}
+
+/// Version 10: Durable domain event log.
+///
+/// Append-only record of domain events, written in the same transaction as
+/// the state change that produced them where possible. Consumers resume from
+/// the last `id` they processed.
+fn apply_v10(conn: &Connection) -> Result<(), EngramError> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS event_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            event_type TEXT NOT NULL,
+            payload TEXT NOT NULL,
+            occurred_at INTEGER NOT NULL,
+            recorded_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_event_log_type_time ON event_log(event_type, occurred_at);
+        CREATE INDEX IF NOT EXISTS idx_event_log_time ON event_log(occurred_at);
+
+        INSERT OR IGNORE INTO schema_migrations (version, name) VALUES (10, 'event_log');
+        ",
+    )
+    .map_err(|e| EngramError::Storage(format!("Failed to apply migration v10: {}", e)))?;
+
+    Ok(())
+}
+
+/// Version 11: Document captures and ingest idempotency keys.
+///
+/// SQLite cannot alter a CHECK constraint, so `captures` is rebuilt to
+/// accept `content_type = 'document'` and gains `speaker`, `source_path`
+/// and `passage_index` columns. Rowids are copied so `captures_fts` stays
+/// in sync; its triggers and the capture indexes are recreated. Foreign
+/// keys are suspended during the rebuild so dependent rows survive the
+/// table swap.
+fn apply_v11(conn: &Connection) -> Result<(), EngramError> {
+    let foreign_keys: i64 = conn
+        .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+        .map_err(|e| EngramError::Storage(format!("Failed to apply migration v11: {}", e)))?;
+    conn.execute_batch("PRAGMA foreign_keys = OFF;")
+        .map_err(|e| EngramError::Storage(format!("Failed to apply migration v11: {}", e)))?;
+
+    let result = conn.execute_batch(
+        "
+        BEGIN;
+
+        CREATE TABLE captures_v12 (
+            id              TEXT PRIMARY KEY NOT NULL,
+            content_type    TEXT NOT NULL
+                            CHECK (content_type IN ('screen', 'audio', 'dictation', 'document')),
+            timestamp       INTEGER NOT NULL,
+            text            TEXT NOT NULL DEFAULT '',
+            app_name        TEXT NOT NULL DEFAULT '',
+            window_title    TEXT NOT NULL DEFAULT '',
+            monitor_id      TEXT,
+            focused         INTEGER DEFAULT 1,
+            source_device   TEXT,
+            duration_secs   REAL,
+            confidence      REAL,
+            session_id      TEXT,
+            target_app      TEXT,
+            target_window   TEXT,
+            mode            TEXT,
+            tier            TEXT NOT NULL DEFAULT 'hot'
+                            CHECK (tier IN ('hot', 'warm', 'cold')),
+            vector_format   TEXT NOT NULL DEFAULT 'f32'
+                            CHECK (vector_format IN ('f32', 'int8', 'product', 'binary')),
+            screenshot_path TEXT,
+            audio_file_path TEXT,
+            created_at      INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+            speaker         TEXT,
+            source_path     TEXT,
+            passage_index   INTEGER
+        );
+
+        INSERT INTO captures_v12 (
+            rowid, id, content_type, timestamp, text, app_name, window_title,
+            monitor_id, focused, source_device, duration_secs, confidence,
+            session_id, target_app, target_window, mode, tier, vector_format,
+            screenshot_path, audio_file_path, created_at
+        )
+        SELECT
+            rowid, id, content_type, timestamp, text, app_name, window_title,
+            monitor_id, focused, source_device, duration_secs, confidence,
+            session_id, target_app, target_window, mode, tier, vector_format,
+            screenshot_path, audio_file_path, created_at
+        FROM captures;
+
+        DROP TABLE captures;
+        ALTER TABLE captures_v12 RENAME TO captures;
+
+        CREATE INDEX IF NOT EXISTS idx_captures_timestamp
+            ON captures (timestamp DESC);
+        CREATE INDEX IF NOT EXISTS idx_captures_content_type
+            ON captures (content_type, timestamp DESC);
+        CREATE INDEX IF NOT EXISTS idx_captures_app_name
+            ON captures (app_name, timestamp DESC);
+        CREATE INDEX IF NOT EXISTS idx_captures_tier
+            ON captures (tier, timestamp ASC);
+        CREATE INDEX IF NOT EXISTS idx_captures_session_id
+            ON captures (session_id)
+            WHERE session_id IS NOT NULL;
+        CREATE INDEX IF NOT EXISTS idx_captures_target_app
+            ON captures (target_app, timestamp DESC)
+            WHERE target_app IS NOT NULL;
+        CREATE INDEX IF NOT EXISTS idx_captures_source_path
+            ON captures (source_path, passage_index)
+            WHERE source_path IS NOT NULL;
+
+        CREATE TRIGGER IF NOT EXISTS captures_fts_insert
+        AFTER INSERT ON captures
+        BEGIN
+            INSERT INTO captures_fts(rowid, text, app_name, content_type)
+            VALUES (NEW.rowid, NEW.text, NEW.app_name, NEW.content_type);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS captures_fts_delete
+        AFTER DELETE ON captures
+        BEGIN
+            INSERT INTO captures_fts(captures_fts, rowid, text, app_name, content_type)
+            VALUES ('delete', OLD.rowid, OLD.text, OLD.app_name, OLD.content_type);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS captures_fts_update
+        AFTER UPDATE ON captures
+        BEGIN
+            INSERT INTO captures_fts(captures_fts, rowid, text, app_name, content_type)
+            VALUES ('delete', OLD.rowid, OLD.text, OLD.app_name, OLD.content_type);
+            INSERT INTO captures_fts(rowid, text, app_name, content_type)
+            VALUES (NEW.rowid, NEW.text, NEW.app_name, NEW.content_type);
+        END;
+
+        -- Idempotency keys for POST /ingest/batch.
+        CREATE TABLE IF NOT EXISTS ingest_keys (
+            key TEXT PRIMARY KEY NOT NULL,
+            capture_id TEXT,
+            status TEXT NOT NULL,
+            message TEXT NOT NULL,
+            created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+        );
+
+        INSERT OR IGNORE INTO schema_migrations (version, name)
+            VALUES (11, 'document_captures_and_ingest_keys');
+
+        COMMIT;
+        ",
+    );
+    if result.is_err() {
+        let _ = conn.execute_batch("ROLLBACK;");
+    }
+    if foreign_keys != 0 {
+        conn.execute_batch("PRAGMA foreign_keys = ON;")
+            .map_err(|e| EngramError::Storage(format!("Failed to apply migration v11: {}", e)))?;
+    }
+    result.map_err(|e| EngramError::Storage(format!("Failed to apply migration v11: {}", e)))?;
+
+    Ok(())
+}
+
+/// Version 12: Watched document files.
+///
+/// One row per file indexed from a watched folder. `mtime` and `size`
+/// decide whether a file needs re-indexing; its passages live in
+/// `captures` keyed by `source_path`.
+fn apply_v12(conn: &Connection) -> Result<(), EngramError> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS document_files (
+            path TEXT PRIMARY KEY NOT NULL,
+            mtime INTEGER NOT NULL,
+            size INTEGER NOT NULL,
+            passage_count INTEGER NOT NULL DEFAULT 0,
+            indexed_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+        );
+
+        INSERT OR IGNORE INTO schema_migrations (version, name) VALUES (12, 'document_files');
+        ",
+    )
+    .map_err(|e| EngramError::Storage(format!("Failed to apply migration v12: {}", e)))?;
+
+    Ok(())
+}
+
+/// Version 13: Chat session titles, pinning and message search.
+///
+/// Adds `title` and `pinned` to chat sessions and an FTS5 index over chat
+/// message content, kept in sync by triggers like `captures_fts`.
+fn apply_v13(conn: &Connection) -> Result<(), EngramError> {
+    conn.execute_batch(
+        "
+        ALTER TABLE chat_sessions ADD COLUMN title TEXT;
+        ALTER TABLE chat_sessions ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0;
+
+        CREATE INDEX IF NOT EXISTS idx_chat_sessions_pinned
+            ON chat_sessions(pinned DESC, last_message_at DESC);
+
+        CREATE VIRTUAL TABLE IF NOT EXISTS chat_messages_fts USING fts5(
+            content,
+            content='chat_messages',
+            content_rowid='rowid'
+        );
+
+        INSERT INTO chat_messages_fts(chat_messages_fts) VALUES('rebuild');
+
+        CREATE TRIGGER IF NOT EXISTS chat_messages_fts_insert
+        AFTER INSERT ON chat_messages
+        BEGIN
+            INSERT INTO chat_messages_fts(rowid, content) VALUES (NEW.rowid, NEW.content);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS chat_messages_fts_delete
+        AFTER DELETE ON chat_messages
+        BEGIN
+            INSERT INTO chat_messages_fts(chat_messages_fts, rowid, content)
+            VALUES ('delete', OLD.rowid, OLD.content);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS chat_messages_fts_update
+        AFTER UPDATE ON chat_messages
+        BEGIN
+            INSERT INTO chat_messages_fts(chat_messages_fts, rowid, content)
+            VALUES ('delete', OLD.rowid, OLD.content);
+            INSERT INTO chat_messages_fts(rowid, content) VALUES (NEW.rowid, NEW.content);
+        END;
+
+        INSERT OR IGNORE INTO schema_migrations (version, name)
+            VALUES (13, 'chat_session_titles_and_search');
+        ",
+    )
+    .map_err(|e| EngramError::Storage(format!("Failed to apply migration v13: {}", e)))?;
+
+    Ok(())
+}
+
+/// Version 14: Feedback on chat answers.
+///
+/// Records thumbs-up/down and irrelevant-source feedback (one rating per
+/// user, message and source), the per-user, per-term source boosts learned
+/// from it, and each user's tuned fusion weight.
+fn apply_v14(conn: &Connection) -> Result<(), EngramError> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS chat_feedback (
+            id TEXT PRIMARY KEY NOT NULL,
+            message_id TEXT NOT NULL,
+            session_id TEXT NOT NULL,
+            chunk_id TEXT,
+            rating TEXT NOT NULL CHECK (rating IN ('up', 'down', 'irrelevant')),
+            query TEXT NOT NULL DEFAULT '',
+            user_id TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            FOREIGN KEY (message_id) REFERENCES chat_messages(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_chat_feedback_message ON chat_feedback(message_id);
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_chat_feedback_target
+            ON chat_feedback(user_id, message_id, COALESCE(chunk_id, ''));
+        CREATE INDEX IF NOT EXISTS idx_chat_feedback_user
+            ON chat_feedback(user_id, created_at DESC);
+
+        CREATE TABLE IF NOT EXISTS chat_source_boosts (
+            user_id TEXT NOT NULL,
+            term TEXT NOT NULL,
+            chunk_id TEXT NOT NULL,
+            score REAL NOT NULL DEFAULT 0,
+            updated_at INTEGER NOT NULL,
+            PRIMARY KEY (user_id, term, chunk_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS chat_fusion_weights (
+            user_id TEXT PRIMARY KEY NOT NULL,
+            semantic_weight REAL NOT NULL,
+            feedback_count INTEGER NOT NULL DEFAULT 0,
+            updated_at INTEGER NOT NULL
+        );
+
+        INSERT OR IGNORE INTO schema_migrations (version, name) VALUES (14, 'chat_feedback');
+        ",
+    )
+    .map_err(|e| EngramError::Storage(format!("Failed to apply migration v14: {}", e)))?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -473,7 +886,7 @@ mod tests {
                 row.get(0)
             })
             .unwrap();
-        assert_eq!(version, 6);
+        assert_eq!(version, 14);
     }
 
     #[test]
@@ -1280,7 +1693,7 @@ mod tests {
     }
 
     #[test]
-    fn test_all_six_migrations_applied() {
+    fn test_all_migrations_applied() {
         let conn = open_test_conn();
         run_migrations(&conn).unwrap();
 
@@ -1289,9 +1702,9 @@ mod tests {
                 row.get(0)
             })
             .unwrap();
-        assert_eq!(count, 6);
+        assert_eq!(count, 14);
 
-        let versions: Vec<i64> = (1..=6).collect();
+        let versions: Vec<i64> = (1..=14).collect();
         for v in versions {
             let name: String = conn
                 .query_row(
@@ -1606,4 +2019,362 @@ mod tests {
             .unwrap();
         assert_eq!(count, 1);
     }
+
+    // =========================================================================
+    // V7: Saved searches
+    // =========================================================================
+
+    #[test]
+    fn test_v7_saved_searches_table() {
+        let conn = open_test_conn();
+        run_migrations(&conn).unwrap();
+
+        conn.execute(
+            "INSERT INTO saved_searches (id, name, query) VALUES ('ss-1', 'Incidents', 'INC-4521')",
+            [],
+        )
+        .unwrap();
+
+        let (mode, interval, notify, enabled): (String, i64, i64, i64) = conn
+            .query_row(
+                "SELECT mode, interval_secs, notify, enabled FROM saved_searches WHERE id = 'ss-1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+        assert_eq!(mode, "keyword");
+        assert_eq!(interval, 900);
+        assert_eq!(notify, 0);
+        assert_eq!(enabled, 1);
+
+        let watermark: Option<i64> = conn
+            .query_row(
+                "SELECT last_capture_rowid FROM saved_searches WHERE id = 'ss-1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(watermark, None);
+    }
+
+    #[test]
+    fn test_v7_saved_searches_mode_check() {
+        let conn = open_test_conn();
+        run_migrations(&conn).unwrap();
+
+        let result = conn.execute(
+            "INSERT INTO saved_searches (id, name, query, mode) VALUES ('ss-bad', 'x', 'y', 'fuzzy')",
+            [],
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_v7_migration_version_recorded() {
+        let conn = open_test_conn();
+        run_migrations(&conn).unwrap();
+
+        let name: String = conn
+            .query_row(
+                "SELECT name FROM schema_migrations WHERE version = 7",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(name, "saved_searches");
+    }
+
+    // =========================================================================
+    // V8: Search log
+    // =========================================================================
+
+    #[test]
+    fn test_v8_search_log_table() {
+        let conn = open_test_conn();
+        run_migrations(&conn).unwrap();
+
+        conn.execute(
+            "INSERT INTO search_log (id, query, mode, latency_ms, result_count)
+             VALUES ('log-1', 'release notes', 'hybrid', 12, 0)",
+            [],
+        )
+        .unwrap();
+
+        let (clicked, created_at): (Option<String>, i64) = conn
+            .query_row(
+                "SELECT clicked_result_id, created_at FROM search_log WHERE id = 'log-1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert!(clicked.is_none());
+        assert!(created_at > 0);
+    }
+
+    #[test]
+    fn test_v8_migration_version_recorded() {
+        let conn = open_test_conn();
+        run_migrations(&conn).unwrap();
+
+        let name: String = conn
+            .query_row(
+                "SELECT name FROM schema_migrations WHERE version = 8",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(name, "search_log");
+    }
+
+    // =========================================================================
+    // V9: API tokens
+    // =========================================================================
+
+    #[test]
+    fn test_v9_api_tokens_table() {
+        let conn = open_test_conn();
+        run_migrations(&conn).unwrap();
+
+        conn.execute(
+            "INSERT INTO api_tokens (id, name, token_hash, scopes)
+             VALUES ('tok-1', 'scripts', 'abc123', 'search:read')",
+            [],
+        )
+        .unwrap();
+
+        let (revoked, expires_at): (i64, Option<i64>) = conn
+            .query_row(
+                "SELECT revoked, expires_at FROM api_tokens WHERE id = 'tok-1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(revoked, 0);
+        assert!(expires_at.is_none());
+
+        // Names are unique.
+        let dup = conn.execute(
+            "INSERT INTO api_tokens (id, name, token_hash, scopes)
+             VALUES ('tok-2', 'scripts', 'def456', 'admin')",
+            [],
+        );
+        assert!(dup.is_err());
+    }
+
+    // =========================================================================
+    // V10: Event log
+    // =========================================================================
+
+    #[test]
+    fn test_v10_event_log_ids_never_reused() {
+        let conn = open_test_conn();
+        run_migrations(&conn).unwrap();
+
+        let insert = || {
+            conn.execute(
+                "INSERT INTO event_log (event_type, payload, occurred_at)
+                 VALUES ('task_created', '{}', 100)",
+                [],
+            )
+            .unwrap();
+            conn.last_insert_rowid()
+        };
+        let first = insert();
+        conn.execute("DELETE FROM event_log", []).unwrap();
+        let second = insert();
+        assert!(second > first, "AUTOINCREMENT must not reuse purged IDs");
+    }
+
+    // =========================================================================
+    // V11: Document captures and ingest keys
+    // =========================================================================
+
+    #[test]
+    fn test_v11_rebuild_keeps_captures_fts_and_foreign_keys() {
+        let conn = open_test_conn();
+        conn.execute_batch(
+            "CREATE TABLE schema_migrations (
+                version INTEGER PRIMARY KEY NOT NULL,
+                name TEXT NOT NULL,
+                applied_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+            );",
+        )
+        .unwrap();
+        apply_v1(&conn).unwrap();
+        apply_v2(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO captures (id, content_type, timestamp, text, app_name)
+                VALUES ('cap-1', 'audio', 100, 'quarterly roadmap review', 'Zoom');
+             INSERT INTO transcriptions (id, session_id, capture_id, timestamp, duration_secs, source_device)
+                VALUES ('tr-1', 'sess', 'cap-1', 100, 5.0, 'mic');",
+        )
+        .unwrap();
+
+        run_migrations(&conn).unwrap();
+
+        let fk: i64 = conn
+            .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(fk, 1);
+        let transcripts: i64 = conn
+            .query_row("SELECT COUNT(*) FROM transcriptions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(transcripts, 1);
+        let hit: String = conn
+            .query_row(
+                "SELECT c.id FROM captures_fts f JOIN captures c ON c.rowid = f.rowid
+                 WHERE captures_fts MATCH 'roadmap'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(hit, "cap-1");
+
+        conn.execute(
+            "INSERT INTO captures (id, content_type, timestamp, text, source_path, passage_index)
+             VALUES ('doc-1', 'document', 200, 'runbook step', '/notes/runbook.md', 0)",
+            [],
+        )
+        .unwrap();
+        let docs: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM captures_fts WHERE captures_fts MATCH 'runbook'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(docs, 1);
+
+        // Cascades still point at the rebuilt table.
+        conn.execute("DELETE FROM captures WHERE id = 'cap-1'", [])
+            .unwrap();
+        let transcripts: i64 = conn
+            .query_row("SELECT COUNT(*) FROM transcriptions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(transcripts, 0);
+    }
+
+    // =========================================================================
+    // V13: Chat session titles and message search
+    // =========================================================================
+
+    #[test]
+    fn test_v13_chat_messages_fts_follows_messages() {
+        let conn = open_test_conn();
+        conn.execute_batch(
+            "CREATE TABLE schema_migrations (
+                version INTEGER PRIMARY KEY NOT NULL,
+                name TEXT NOT NULL,
+                applied_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+            );",
+        )
+        .unwrap();
+        for apply in [
+            apply_v1, apply_v2, apply_v3, apply_v4, apply_v5, apply_v6, apply_v7, apply_v8,
+            apply_v9, apply_v10, apply_v11, apply_v12, apply_v13,
+        ] {
+            apply(&conn).unwrap();
+        }
+        conn.execute_batch(
+            "INSERT INTO chat_sessions (id) VALUES ('sess-old');
+             INSERT INTO chat_messages (id, session_id, role, content)
+                VALUES ('msg-old', 'sess-old', 'user', 'what did we decide about pricing');",
+        )
+        .unwrap();
+
+        run_migrations(&conn).unwrap();
+
+        let matches = |term: &str| -> i64 {
+            conn.query_row(
+                "SELECT COUNT(*) FROM chat_messages_fts WHERE chat_messages_fts MATCH ?1",
+                [term],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        // Existing messages are indexed by the rebuild.
+        assert_eq!(matches("pricing"), 1);
+
+        conn.execute(
+            "INSERT INTO chat_messages (id, session_id, role, content)
+             VALUES ('msg-new', 'sess-old', 'assistant', 'pricing stays flat')",
+            [],
+        )
+        .unwrap();
+        assert_eq!(matches("pricing"), 2);
+        conn.execute(
+            "UPDATE chat_messages SET content = 'renamed' WHERE id = 'msg-new'",
+            [],
+        )
+        .unwrap();
+        assert_eq!(matches("pricing"), 1);
+
+        // Cascaded deletes leave the index too.
+        conn.execute("DELETE FROM chat_sessions WHERE id = 'sess-old'", [])
+            .unwrap();
+        assert_eq!(matches("pricing"), 0);
+
+        let (title, pinned): (Option<String>, i64) = {
+            conn.execute("INSERT INTO chat_sessions (id) VALUES ('sess-new')", [])
+                .unwrap();
+            conn.query_row(
+                "SELECT title, pinned FROM chat_sessions WHERE id = 'sess-new'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap()
+        };
+        assert_eq!(title, None);
+        assert_eq!(pinned, 0);
+    }
+
+    // =========================================================================
+    // V14: Chat feedback
+    // =========================================================================
+
+    #[test]
+    fn test_v14_chat_feedback_tables() {
+        let conn = open_test_conn();
+        run_migrations(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO chat_sessions (id) VALUES ('sess-1');
+             INSERT INTO chat_messages (id, session_id, role, content)
+                VALUES ('msg-1', 'sess-1', 'assistant', 'pricing stays flat');
+             INSERT INTO chat_feedback (id, message_id, session_id, chunk_id, rating, query, user_id, created_at)
+                VALUES ('fb-1', 'msg-1', 'sess-1', 'chunk-1', 'irrelevant', 'pricing', 'primary', 0);
+             INSERT INTO chat_source_boosts (user_id, term, chunk_id, score, updated_at)
+                VALUES ('primary', 'pricing', 'chunk-1', -0.5, 0);
+             INSERT INTO chat_fusion_weights (user_id, semantic_weight, updated_at)
+                VALUES ('primary', 0.6, 0);",
+        )
+        .unwrap();
+
+        let bad_rating = conn.execute(
+            "INSERT INTO chat_feedback (id, message_id, session_id, rating, user_id, created_at)
+             VALUES ('fb-2', 'msg-1', 'sess-1', 'meh', 'primary', 0)",
+            [],
+        );
+        assert!(bad_rating.is_err());
+
+        // One rating per user, message and source.
+        let repeat = conn.execute(
+            "INSERT INTO chat_feedback (id, message_id, session_id, chunk_id, rating, user_id, created_at)
+             VALUES ('fb-3', 'msg-1', 'sess-1', 'chunk-1', 'up', 'primary', 0)",
+            [],
+        );
+        assert!(repeat.is_err());
+
+        // Feedback goes with its message; learned boosts stay.
+        conn.execute("DELETE FROM chat_sessions WHERE id = 'sess-1'", [])
+            .unwrap();
+        let count = |table: &str| -> i64 {
+            conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+                row.get(0)
+            })
+            .unwrap()
+        };
+        assert_eq!(count("chat_feedback"), 0);
+        assert_eq!(count("chat_source_boosts"), 1);
+        assert_eq!(count("chat_fusion_weights"), 1);
+    }
 }
@@ -314,13 +314,16 @@ impl QueryService {
         time_range_end: Option<&str>,
     ) -> Result<(), EngramError> {
         self.db.with_conn(|conn| {
-            conn.execute(
-                "INSERT INTO summaries (id, title, bullet_points, source_chunk_ids, source_app, time_range_start, time_range_end)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                rusqlite::params![id, title, bullet_points, source_chunk_ids, source_app, time_range_start, time_range_end],
+            store_summary(
+                conn,
+                id,
+                title,
+                bullet_points,
+                source_chunk_ids,
+                source_app,
+                time_range_start,
+                time_range_end,
             )
-            .map_err(|e| EngramError::Storage(format!("Store summary: {}", e)))?;
-            Ok(())
         })
     }
 
@@ -412,13 +415,15 @@ impl QueryService {
         confidence: f64,
     ) -> Result<(), EngramError> {
         self.db.with_conn(|conn| {
-            conn.execute(
-                "INSERT INTO entities (id, entity_type, value, source_chunk_id, source_summary_id, confidence)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                rusqlite::params![id, entity_type, value, source_chunk_id, source_summary_id, confidence],
+            store_entity(
+                conn,
+                id,
+                entity_type,
+                value,
+                source_chunk_id,
+                source_summary_id,
+                confidence,
             )
-            .map_err(|e| EngramError::Storage(format!("Store entity: {}", e)))?;
-            Ok(())
         })
     }
 
@@ -508,13 +513,15 @@ impl QueryService {
         chunk_count: u32,
     ) -> Result<(), EngramError> {
         self.db.with_conn(|conn| {
-            conn.execute(
-                "INSERT INTO daily_digests (id, digest_date, content, summary_count, entity_count, chunk_count)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                rusqlite::params![id, digest_date, content, summary_count, entity_count, chunk_count],
+            store_digest(
+                conn,
+                id,
+                digest_date,
+                content,
+                summary_count,
+                entity_count,
+                chunk_count,
             )
-            .map_err(|e| EngramError::Storage(format!("Store digest: {}", e)))?;
-            Ok(())
         })
     }
 
@@ -812,6 +819,108 @@ impl QueryService {
             Ok(results)
         })
     }
+
+    /// Fetch a single capture of any content type by ID.
+    ///
+    /// Dictation rows report their target app and window in `app_name` and
+    /// `window_title`.
+    pub fn get_capture(&self, id: Uuid) -> Result<Option<CaptureRow>, EngramError> {
+        self.db.with_conn(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT id, content_type, timestamp, text,
+                            COALESCE(NULLIF(app_name, ''), target_app, ''),
+                            COALESCE(NULLIF(window_title, ''), target_window, ''),
+                            COALESCE(monitor_id, ''), COALESCE(source_device, ''),
+                            COALESCE(duration_secs, 0.0), COALESCE(confidence, 0.0),
+                            COALESCE(mode, '')
+                     FROM captures
+                     WHERE id = ?1",
+                )
+                .map_err(|e| EngramError::Storage(format!("Get capture prepare: {}", e)))?;
+
+            let mut rows = stmt
+                .query_map(rusqlite::params![id.to_string()], |row| {
+                    Ok(map_capture_row(row))
+                })
+                .map_err(|e| EngramError::Storage(format!("Get capture: {}", e)))?;
+
+            match rows.next() {
+                Some(row) => Ok(Some(row.map_err(|e| EngramError::Storage(e.to_string()))??)),
+                None => Ok(None),
+            }
+        })
+    }
+
+    /// Fetch captures of all content types within `window_secs` either side of
+    /// `center_epoch`, interleaved in ascending time order.
+    ///
+    /// When the window holds more than `limit` rows, the nearest ones win:
+    /// half the budget goes to each side of the center, and a side with fewer
+    /// rows hands its share to the other. Rows at `center_epoch` count as
+    /// after it. Both range scans are served by `idx_captures_timestamp`.
+    pub fn captures_around(
+        &self,
+        center_epoch: i64,
+        window_secs: i64,
+        limit: u64,
+    ) -> Result<Vec<CaptureRow>, EngramError> {
+        const COLUMNS: &str = "SELECT id, content_type, timestamp, text,
+                            COALESCE(NULLIF(app_name, ''), target_app, ''),
+                            COALESCE(NULLIF(window_title, ''), target_window, ''),
+                            COALESCE(monitor_id, ''), COALESCE(source_device, ''),
+                            COALESCE(duration_secs, 0.0), COALESCE(confidence, 0.0),
+                            COALESCE(mode, '')
+                     FROM captures";
+
+        self.db.with_conn(|conn| {
+            let fetch = |sql: &str, from: i64, to: i64| -> Result<Vec<CaptureRow>, EngramError> {
+                let mut stmt = conn
+                    .prepare(sql)
+                    .map_err(|e| EngramError::Storage(format!("Captures around prepare: {}", e)))?;
+                let rows = stmt
+                    .query_map(rusqlite::params![from, to, limit as i64], |row| {
+                        Ok(map_capture_row(row))
+                    })
+                    .map_err(|e| EngramError::Storage(format!("Captures around: {}", e)))?;
+                let mut results = Vec::new();
+                for row in rows {
+                    results.push(row.map_err(|e| EngramError::Storage(e.to_string()))??);
+                }
+                Ok(results)
+            };
+
+            // Each side is ordered outward from the center.
+            let before = fetch(
+                &format!(
+                    "{} WHERE timestamp >= ?1 AND timestamp < ?2
+                     ORDER BY timestamp DESC, id DESC LIMIT ?3",
+                    COLUMNS
+                ),
+                center_epoch - window_secs,
+                center_epoch,
+            )?;
+            let after = fetch(
+                &format!(
+                    "{} WHERE timestamp >= ?1 AND timestamp <= ?2
+                     ORDER BY timestamp ASC, id ASC LIMIT ?3",
+                    COLUMNS
+                ),
+                center_epoch,
+                center_epoch + window_secs,
+            )?;
+
+            let limit = limit as usize;
+            let take_after = after.len().min(limit - limit / 2);
+            let take_before = before.len().min(limit - take_after);
+            let take_after = after.len().min(limit - take_before);
+
+            let mut results: Vec<CaptureRow> = before.into_iter().take(take_before).collect();
+            results.reverse();
+            results.extend(after.into_iter().take(take_after));
+            Ok(results)
+        })
+    }
 }
 
 // =============================================================================
@@ -883,6 +992,69 @@ pub struct HistoryFilters {
     pub limit: Option<u32>,
 }
 
+// =============================================================================
+// Insight Rows
+// =============================================================================
+
+/// Store a summary row.
+#[allow(clippy::too_many_arguments)]
+pub fn store_summary(
+    conn: &rusqlite::Connection,
+    id: &str,
+    title: &str,
+    bullet_points: &str,
+    source_chunk_ids: &str,
+    source_app: Option<&str>,
+    time_range_start: Option<&str>,
+    time_range_end: Option<&str>,
+) -> Result<(), EngramError> {
+    conn.execute(
+        "INSERT INTO summaries (id, title, bullet_points, source_chunk_ids, source_app, time_range_start, time_range_end)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![id, title, bullet_points, source_chunk_ids, source_app, time_range_start, time_range_end],
+    )
+    .map_err(|e| EngramError::Storage(format!("Store summary: {}", e)))?;
+    Ok(())
+}
+
+/// Store an entity row.
+pub fn store_entity(
+    conn: &rusqlite::Connection,
+    id: &str,
+    entity_type: &str,
+    value: &str,
+    source_chunk_id: Option<&str>,
+    source_summary_id: Option<&str>,
+    confidence: f64,
+) -> Result<(), EngramError> {
+    conn.execute(
+        "INSERT INTO entities (id, entity_type, value, source_chunk_id, source_summary_id, confidence)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![id, entity_type, value, source_chunk_id, source_summary_id, confidence],
+    )
+    .map_err(|e| EngramError::Storage(format!("Store entity: {}", e)))?;
+    Ok(())
+}
+
+/// Store a daily digest row.
+pub fn store_digest(
+    conn: &rusqlite::Connection,
+    id: &str,
+    digest_date: &str,
+    content: &str,
+    summary_count: u32,
+    entity_count: u32,
+    chunk_count: u32,
+) -> Result<(), EngramError> {
+    conn.execute(
+        "INSERT INTO daily_digests (id, digest_date, content, summary_count, entity_count, chunk_count)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![id, digest_date, content, summary_count, entity_count, chunk_count],
+    )
+    .map_err(|e| EngramError::Storage(format!("Store digest: {}", e)))?;
+    Ok(())
+}
+
 // =============================================================================
 // Action Engine Query Methods
 // =============================================================================
@@ -1184,13 +1356,1194 @@ pub fn get_action_history(
                 executed_at: row.get(5)?,
             })
         })
-        .map_err(|e| EngramError::Storage(format!("Get action history: {}", e)))?;
+        .map_err(|e| EngramError::Storage(format!("Get action history: {}", e)))?;
+
+    let mut results = Vec::new();
+    for row in rows {
+        results.push(row.map_err(|e| EngramError::Storage(e.to_string()))?);
+    }
+    Ok(results)
+}
+
+// =============================================================================
+// Saved Searches
+// =============================================================================
+
+/// A row from the saved_searches table.
+#[derive(Debug, Clone)]
+pub struct SavedSearchRow {
+    pub id: String,
+    pub name: String,
+    pub query: String,
+    pub mode: String,
+    pub content_type: Option<String>,
+    pub app_name: Option<String>,
+    pub interval_secs: i64,
+    pub notify: bool,
+    pub enabled: bool,
+    /// Unix epoch (seconds) of the last completed run, if any.
+    pub last_run_at: Option<i64>,
+    pub last_match_count: i64,
+    pub created_at: String,
+    /// Highest `captures` rowid when the search last ran; matches stored
+    /// after it are new. `None` until the first run.
+    pub last_capture_rowid: Option<i64>,
+}
+
+const SAVED_SEARCH_COLUMNS: &str = "id, name, query, mode, content_type, app_name, interval_secs, notify, enabled, last_run_at, last_match_count, created_at, last_capture_rowid";
+
+fn map_saved_search_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SavedSearchRow> {
+    let notify: i32 = row.get(7)?;
+    let enabled: i32 = row.get(8)?;
+    Ok(SavedSearchRow {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        query: row.get(2)?,
+        mode: row.get(3)?,
+        content_type: row.get(4)?,
+        app_name: row.get(5)?,
+        interval_secs: row.get(6)?,
+        notify: notify != 0,
+        enabled: enabled != 0,
+        last_run_at: row.get(9)?,
+        last_match_count: row.get(10)?,
+        created_at: row.get(11)?,
+        last_capture_rowid: row.get(12)?,
+    })
+}
+
+/// Store a saved search row.
+pub fn store_saved_search(
+    conn: &rusqlite::Connection,
+    search: &SavedSearchRow,
+) -> Result<(), EngramError> {
+    conn.execute(
+        "INSERT INTO saved_searches (id, name, query, mode, content_type, app_name, interval_secs, notify, enabled, last_run_at, last_match_count, created_at, last_capture_rowid)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        rusqlite::params![
+            search.id,
+            search.name,
+            search.query,
+            search.mode,
+            search.content_type,
+            search.app_name,
+            search.interval_secs,
+            search.notify as i32,
+            search.enabled as i32,
+            search.last_run_at,
+            search.last_match_count,
+            search.created_at,
+            search.last_capture_rowid,
+        ],
+    )
+    .map_err(|e| EngramError::Storage(format!("Store saved search: {}", e)))?;
+    Ok(())
+}
+
+/// Get a single saved search by ID.
+pub fn get_saved_search(
+    conn: &rusqlite::Connection,
+    id: &str,
+) -> Result<Option<SavedSearchRow>, EngramError> {
+    let sql = format!(
+        "SELECT {} FROM saved_searches WHERE id = ?1",
+        SAVED_SEARCH_COLUMNS
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| EngramError::Storage(format!("Get saved search prepare: {}", e)))?;
+
+    let mut rows = stmt
+        .query_map(rusqlite::params![id], map_saved_search_row)
+        .map_err(|e| EngramError::Storage(format!("Get saved search: {}", e)))?;
+
+    if let Some(row) = rows.next() {
+        Ok(Some(row.map_err(|e| EngramError::Storage(e.to_string()))?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// List all saved searches, newest first.
+pub fn list_saved_searches(
+    conn: &rusqlite::Connection,
+) -> Result<Vec<SavedSearchRow>, EngramError> {
+    let sql = format!(
+        "SELECT {} FROM saved_searches ORDER BY created_at DESC, id",
+        SAVED_SEARCH_COLUMNS
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| EngramError::Storage(format!("List saved searches prepare: {}", e)))?;
+
+    let rows = stmt
+        .query_map([], map_saved_search_row)
+        .map_err(|e| EngramError::Storage(format!("List saved searches: {}", e)))?;
+
+    let mut results = Vec::new();
+    for row in rows {
+        results.push(row.map_err(|e| EngramError::Storage(e.to_string()))?);
+    }
+    Ok(results)
+}
+
+/// List enabled saved searches whose interval has elapsed as of `now_epoch`.
+///
+/// Searches that have never run are always due.
+pub fn list_due_saved_searches(
+    conn: &rusqlite::Connection,
+    now_epoch: i64,
+) -> Result<Vec<SavedSearchRow>, EngramError> {
+    let sql = format!(
+        "SELECT {} FROM saved_searches
+         WHERE enabled = 1 AND (last_run_at IS NULL OR last_run_at + interval_secs <= ?1)
+         ORDER BY last_run_at ASC",
+        SAVED_SEARCH_COLUMNS
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| EngramError::Storage(format!("List due saved searches prepare: {}", e)))?;
+
+    let rows = stmt
+        .query_map(rusqlite::params![now_epoch], map_saved_search_row)
+        .map_err(|e| EngramError::Storage(format!("List due saved searches: {}", e)))?;
+
+    let mut results = Vec::new();
+    for row in rows {
+        results.push(row.map_err(|e| EngramError::Storage(e.to_string()))?);
+    }
+    Ok(results)
+}
+
+/// Update the user-editable fields of a saved search.
+///
+/// Run bookkeeping (`last_run_at`, `last_match_count`) is left untouched;
+/// use [`record_saved_search_run`] for that.
+pub fn update_saved_search(
+    conn: &rusqlite::Connection,
+    search: &SavedSearchRow,
+) -> Result<bool, EngramError> {
+    let rows_affected = conn
+        .execute(
+            "UPDATE saved_searches
+             SET name = ?1, query = ?2, mode = ?3, content_type = ?4, app_name = ?5,
+                 interval_secs = ?6, notify = ?7, enabled = ?8
+             WHERE id = ?9",
+            rusqlite::params![
+                search.name,
+                search.query,
+                search.mode,
+                search.content_type,
+                search.app_name,
+                search.interval_secs,
+                search.notify as i32,
+                search.enabled as i32,
+                search.id,
+            ],
+        )
+        .map_err(|e| EngramError::Storage(format!("Update saved search: {}", e)))?;
+    Ok(rows_affected > 0)
+}
+
+/// Record the completion of a saved search run that saw captures up to
+/// rowid `capture_rowid`.
+pub fn record_saved_search_run(
+    conn: &rusqlite::Connection,
+    id: &str,
+    run_at_epoch: i64,
+    match_count: i64,
+    capture_rowid: i64,
+) -> Result<bool, EngramError> {
+    let rows_affected = conn
+        .execute(
+            "UPDATE saved_searches SET last_run_at = ?1, last_match_count = ?2, last_capture_rowid = ?3
+             WHERE id = ?4",
+            rusqlite::params![run_at_epoch, match_count, capture_rowid, id],
+        )
+        .map_err(|e| EngramError::Storage(format!("Record saved search run: {}", e)))?;
+    Ok(rows_affected > 0)
+}
+
+/// Delete a saved search by ID.
+pub fn delete_saved_search(conn: &rusqlite::Connection, id: &str) -> Result<bool, EngramError> {
+    let rows_affected = conn
+        .execute(
+            "DELETE FROM saved_searches WHERE id = ?1",
+            rusqlite::params![id],
+        )
+        .map_err(|e| EngramError::Storage(format!("Delete saved search: {}", e)))?;
+    Ok(rows_affected > 0)
+}
+
+/// Highest `captures` rowid, or 0 when there are no captures. Rowids grow
+/// with each insert, so this marks how far ingestion has got.
+pub fn max_capture_rowid(conn: &rusqlite::Connection) -> Result<i64, EngramError> {
+    conn.query_row("SELECT COALESCE(MAX(rowid), 0) FROM captures", [], |row| {
+        row.get(0)
+    })
+    .map_err(|e| EngramError::Storage(format!("Max capture rowid: {}", e)))
+}
+
+// =============================================================================
+// Search Log
+// =============================================================================
+
+/// A row from the search_log table.
+#[derive(Debug, Clone)]
+pub struct SearchLogRow {
+    pub id: String,
+    pub query: String,
+    pub mode: String,
+    pub latency_ms: i64,
+    pub result_count: i64,
+    pub clicked_result_id: Option<String>,
+    /// Unix epoch (seconds).
+    pub created_at: i64,
+}
+
+/// A normalized query with its aggregate statistics.
+#[derive(Debug, Clone)]
+pub struct QueryStatRow {
+    /// Lower-cased, trimmed query text.
+    pub query: String,
+    pub count: i64,
+    pub avg_result_count: f64,
+    pub last_seen: i64,
+}
+
+/// Per-mode aggregate statistics over the search log.
+#[derive(Debug, Clone)]
+pub struct SearchModeStatRow {
+    pub mode: String,
+    pub count: i64,
+    pub zero_result_count: i64,
+    pub clicked_count: i64,
+    pub avg_latency_ms: f64,
+}
+
+/// Store a search log row.
+pub fn store_search_log(
+    conn: &rusqlite::Connection,
+    entry: &SearchLogRow,
+) -> Result<(), EngramError> {
+    conn.execute(
+        "INSERT INTO search_log (id, query, mode, latency_ms, result_count, clicked_result_id, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![
+            entry.id,
+            entry.query,
+            entry.mode,
+            entry.latency_ms,
+            entry.result_count,
+            entry.clicked_result_id,
+            entry.created_at,
+        ],
+    )
+    .map_err(|e| EngramError::Storage(format!("Store search log: {}", e)))?;
+    Ok(())
+}
+
+/// Record which result was clicked for a logged search.
+pub fn record_search_click(
+    conn: &rusqlite::Connection,
+    id: &str,
+    result_id: &str,
+) -> Result<bool, EngramError> {
+    let rows_affected = conn
+        .execute(
+            "UPDATE search_log SET clicked_result_id = ?1 WHERE id = ?2",
+            rusqlite::params![result_id, id],
+        )
+        .map_err(|e| EngramError::Storage(format!("Record search click: {}", e)))?;
+    Ok(rows_affected > 0)
+}
+
+/// Most frequent queries since `since_epoch`, grouped case-insensitively.
+///
+/// With `zero_results_only`, only searches that returned nothing are counted.
+pub fn get_query_stats(
+    conn: &rusqlite::Connection,
+    since_epoch: i64,
+    zero_results_only: bool,
+    limit: u32,
+) -> Result<Vec<QueryStatRow>, EngramError> {
+    let zero_clause = if zero_results_only {
+        "AND result_count = 0"
+    } else {
+        ""
+    };
+    let sql = format!(
+        "SELECT lower(trim(query)) AS q, COUNT(*) AS n, AVG(result_count), MAX(created_at)
+         FROM search_log
+         WHERE created_at >= ?1 {}
+         GROUP BY q
+         ORDER BY n DESC, MAX(created_at) DESC
+         LIMIT ?2",
+        zero_clause
+    );
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| EngramError::Storage(format!("Query stats prepare: {}", e)))?;
+
+    let rows = stmt
+        .query_map(rusqlite::params![since_epoch, limit as i64], |row| {
+            Ok(QueryStatRow {
+                query: row.get(0)?,
+                count: row.get(1)?,
+                avg_result_count: row.get(2)?,
+                last_seen: row.get(3)?,
+            })
+        })
+        .map_err(|e| EngramError::Storage(format!("Query stats: {}", e)))?;
+
+    let mut results = Vec::new();
+    for row in rows {
+        results.push(row.map_err(|e| EngramError::Storage(e.to_string()))?);
+    }
+    Ok(results)
+}
+
+/// Per-mode counts and mean latency since `since_epoch`.
+pub fn get_search_mode_stats(
+    conn: &rusqlite::Connection,
+    since_epoch: i64,
+) -> Result<Vec<SearchModeStatRow>, EngramError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT mode, COUNT(*),
+                    SUM(CASE WHEN result_count = 0 THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN clicked_result_id IS NOT NULL THEN 1 ELSE 0 END),
+                    AVG(latency_ms)
+             FROM search_log
+             WHERE created_at >= ?1
+             GROUP BY mode
+             ORDER BY COUNT(*) DESC",
+        )
+        .map_err(|e| EngramError::Storage(format!("Search mode stats prepare: {}", e)))?;
+
+    let rows = stmt
+        .query_map(rusqlite::params![since_epoch], |row| {
+            Ok(SearchModeStatRow {
+                mode: row.get(0)?,
+                count: row.get(1)?,
+                zero_result_count: row.get(2)?,
+                clicked_count: row.get(3)?,
+                avg_latency_ms: row.get(4)?,
+            })
+        })
+        .map_err(|e| EngramError::Storage(format!("Search mode stats: {}", e)))?;
+
+    let mut results = Vec::new();
+    for row in rows {
+        results.push(row.map_err(|e| EngramError::Storage(e.to_string()))?);
+    }
+    Ok(results)
+}
+
+/// All logged latencies since `since_epoch`, sorted ascending.
+pub fn get_search_latencies(
+    conn: &rusqlite::Connection,
+    since_epoch: i64,
+) -> Result<Vec<i64>, EngramError> {
+    let mut stmt = conn
+        .prepare("SELECT latency_ms FROM search_log WHERE created_at >= ?1 ORDER BY latency_ms ASC")
+        .map_err(|e| EngramError::Storage(format!("Search latencies prepare: {}", e)))?;
+
+    let rows = stmt
+        .query_map(rusqlite::params![since_epoch], |row| row.get::<_, i64>(0))
+        .map_err(|e| EngramError::Storage(format!("Search latencies: {}", e)))?;
+
+    let mut results = Vec::new();
+    for row in rows {
+        results.push(row.map_err(|e| EngramError::Storage(e.to_string()))?);
+    }
+    Ok(results)
+}
+
+/// Delete search log entries, optionally only those older than `before_epoch`.
+///
+/// Logged `search_performed` events carry the query text as well, so they
+/// are deleted from the event log and the stream replay buffer too.
+/// Run this inside `Database::with_transaction`.
+///
+/// Returns the number of search log rows deleted.
+pub fn purge_search_log(
+    conn: &rusqlite::Connection,
+    before_epoch: Option<i64>,
+) -> Result<usize, EngramError> {
+    let before = before_epoch.unwrap_or(i64::MAX);
+    let deleted = conn
+        .execute(
+            "DELETE FROM search_log WHERE created_at < ?1",
+            rusqlite::params![before],
+        )
+        .map_err(|e| EngramError::Storage(format!("Purge search log: {}", e)))?;
+    conn.execute(
+        "DELETE FROM event_log WHERE event_type = 'search_performed' AND occurred_at < ?1",
+        rusqlite::params![before],
+    )
+    .map_err(|e| EngramError::Storage(format!("Purge search events: {}", e)))?;
+    Ok(deleted)
+}
+
+// =============================================================================
+// API Tokens
+// =============================================================================
+
+/// A row from the api_tokens table.
+#[derive(Debug, Clone)]
+pub struct ApiTokenRow {
+    pub id: String,
+    pub name: String,
+    /// Hex-encoded SHA-256 of the bearer token.
+    pub token_hash: String,
+    pub scopes: Vec<String>,
+    /// Unix epoch (seconds) after which the token is rejected.
+    pub expires_at: Option<i64>,
+    pub last_used_at: Option<i64>,
+    pub revoked: bool,
+    pub created_at: i64,
+}
+
+const API_TOKEN_COLUMNS: &str =
+    "id, name, token_hash, scopes, expires_at, last_used_at, revoked, created_at";
+
+fn map_api_token_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ApiTokenRow> {
+    let scopes: String = row.get(3)?;
+    Ok(ApiTokenRow {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        token_hash: row.get(2)?,
+        scopes: scopes
+            .split(',')
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect(),
+        expires_at: row.get(4)?,
+        last_used_at: row.get(5)?,
+        revoked: row.get::<_, i64>(6)? != 0,
+        created_at: row.get(7)?,
+    })
+}
+
+/// Store a new API token.
+pub fn store_api_token(
+    conn: &rusqlite::Connection,
+    token: &ApiTokenRow,
+) -> Result<(), EngramError> {
+    conn.execute(
+        "INSERT INTO api_tokens (id, name, token_hash, scopes, expires_at, last_used_at, revoked, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        rusqlite::params![
+            token.id,
+            token.name,
+            token.token_hash,
+            token.scopes.join(","),
+            token.expires_at,
+            token.last_used_at,
+            token.revoked as i64,
+            token.created_at,
+        ],
+    )
+    .map_err(|e| EngramError::Storage(format!("Store API token: {}", e)))?;
+    Ok(())
+}
+
+/// Look up an API token by its hash.
+pub fn get_api_token_by_hash(
+    conn: &rusqlite::Connection,
+    token_hash: &str,
+) -> Result<Option<ApiTokenRow>, EngramError> {
+    let sql = format!(
+        "SELECT {} FROM api_tokens WHERE token_hash = ?1",
+        API_TOKEN_COLUMNS
+    );
+    match conn.query_row(&sql, rusqlite::params![token_hash], map_api_token_row) {
+        Ok(row) => Ok(Some(row)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(EngramError::Storage(format!("Get API token: {}", e))),
+    }
+}
+
+/// List all API tokens, oldest first.
+pub fn list_api_tokens(conn: &rusqlite::Connection) -> Result<Vec<ApiTokenRow>, EngramError> {
+    let sql = format!(
+        "SELECT {} FROM api_tokens ORDER BY created_at ASC, name ASC",
+        API_TOKEN_COLUMNS
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| EngramError::Storage(format!("List API tokens prepare: {}", e)))?;
+
+    let rows = stmt
+        .query_map([], map_api_token_row)
+        .map_err(|e| EngramError::Storage(format!("List API tokens: {}", e)))?;
+
+    let mut results = Vec::new();
+    for row in rows {
+        results.push(row.map_err(|e| EngramError::Storage(e.to_string()))?);
+    }
+    Ok(results)
+}
+
+/// Revoke an API token by ID or name.
+///
+/// Returns `false` if no active token matched.
+pub fn revoke_api_token(
+    conn: &rusqlite::Connection,
+    id_or_name: &str,
+) -> Result<bool, EngramError> {
+    let rows_affected = conn
+        .execute(
+            "UPDATE api_tokens SET revoked = 1 WHERE (id = ?1 OR name = ?1) AND revoked = 0",
+            rusqlite::params![id_or_name],
+        )
+        .map_err(|e| EngramError::Storage(format!("Revoke API token: {}", e)))?;
+    Ok(rows_affected > 0)
+}
+
+/// Record that a token was used at `now_epoch`.
+///
+/// Writes are coalesced to at most one per minute per token.
+pub fn touch_api_token(
+    conn: &rusqlite::Connection,
+    id: &str,
+    now_epoch: i64,
+) -> Result<(), EngramError> {
+    conn.execute(
+        "UPDATE api_tokens SET last_used_at = ?2
+         WHERE id = ?1 AND (last_used_at IS NULL OR last_used_at <= ?2 - 60)",
+        rusqlite::params![id, now_epoch],
+    )
+    .map_err(|e| EngramError::Storage(format!("Touch API token: {}", e)))?;
+    Ok(())
+}
+
+// =============================================================================
+// Event Log
+// =============================================================================
+
+/// Key under which a published event payload carries its event log ID.
+pub const LOG_ID_FIELD: &str = "log_id";
+
+/// A row from the durable event_log table.
+#[derive(Debug, Clone)]
+pub struct EventLogRow {
+    pub id: i64,
+    pub event_type: String,
+    /// Serialized event JSON as produced by `DomainEvent::to_json()`.
+    pub payload: String,
+    /// Event timestamp (Unix epoch seconds).
+    pub occurred_at: i64,
+    pub recorded_at: i64,
+}
+
+/// Filters for [`query_event_log`].
+#[derive(Debug, Clone, Default)]
+pub struct EventLogFilter {
+    /// Event names to include; empty includes every type.
+    pub event_types: Vec<String>,
+    /// Only events that occurred at or after this epoch.
+    pub since: Option<i64>,
+    /// Only events that occurred before this epoch.
+    pub until: Option<i64>,
+    /// Only events with an ID greater than this (consumer cursor).
+    pub after_id: Option<i64>,
+    pub limit: u32,
+}
+
+/// Append a domain event to the event log and return its ID.
+///
+/// Call this with the same connection (inside `Database::with_transaction`)
+/// as the state change that produced the event.
+pub fn append_event_log(
+    conn: &rusqlite::Connection,
+    event: &engram_core::events::DomainEvent,
+) -> Result<i64, EngramError> {
+    append_event_log_json(conn, &event.to_json())
+}
+
+/// Append an already-serialized domain event (`DomainEvent::to_json()`
+/// shape) to the event log and return its ID.
+pub fn append_event_log_json(
+    conn: &rusqlite::Connection,
+    payload: &serde_json::Value,
+) -> Result<i64, EngramError> {
+    let event_type = payload["event"]
+        .as_str()
+        .ok_or_else(|| EngramError::Storage("Event payload has no event name".to_string()))?;
+    let occurred_at = payload["timestamp"]
+        .as_i64()
+        .unwrap_or_else(|| chrono::Utc::now().timestamp());
+    conn.execute(
+        "INSERT INTO event_log (event_type, payload, occurred_at) VALUES (?1, ?2, ?3)",
+        rusqlite::params![event_type, payload.to_string(), occurred_at],
+    )
+    .map_err(|e| EngramError::Storage(format!("Append event log: {}", e)))?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Query the event log in ID order.
+pub fn query_event_log(
+    conn: &rusqlite::Connection,
+    filter: &EventLogFilter,
+) -> Result<Vec<EventLogRow>, EngramError> {
+    let mut sql = String::from(
+        "SELECT id, event_type, payload, occurred_at, recorded_at FROM event_log WHERE 1=1",
+    );
+    let mut params: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+    if !filter.event_types.is_empty() {
+        let placeholders: Vec<String> = filter
+            .event_types
+            .iter()
+            .map(|t| {
+                params.push(Box::new(t.clone()));
+                format!("?{}", params.len())
+            })
+            .collect();
+        sql.push_str(&format!(" AND event_type IN ({})", placeholders.join(", ")));
+    }
+    if let Some(since) = filter.since {
+        params.push(Box::new(since));
+        sql.push_str(&format!(" AND occurred_at >= ?{}", params.len()));
+    }
+    if let Some(until) = filter.until {
+        params.push(Box::new(until));
+        sql.push_str(&format!(" AND occurred_at < ?{}", params.len()));
+    }
+    if let Some(after_id) = filter.after_id {
+        params.push(Box::new(after_id));
+        sql.push_str(&format!(" AND id > ?{}", params.len()));
+    }
+    params.push(Box::new(filter.limit as i64));
+    sql.push_str(&format!(" ORDER BY id ASC LIMIT ?{}", params.len()));
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| EngramError::Storage(format!("Event log prepare: {}", e)))?;
+
+    let param_refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+    let rows = stmt
+        .query_map(param_refs.as_slice(), |row| {
+            Ok(EventLogRow {
+                id: row.get(0)?,
+                event_type: row.get(1)?,
+                payload: row.get(2)?,
+                occurred_at: row.get(3)?,
+                recorded_at: row.get(4)?,
+            })
+        })
+        .map_err(|e| EngramError::Storage(format!("Event log query: {}", e)))?;
+
+    let mut results = Vec::new();
+    for row in rows {
+        results.push(row.map_err(|e| EngramError::Storage(e.to_string()))?);
+    }
+    Ok(results)
+}
+
+/// Highest event log ID, or 0 if the log is empty.
+pub fn max_event_log_id(conn: &rusqlite::Connection) -> Result<i64, EngramError> {
+    conn.query_row("SELECT COALESCE(MAX(id), 0) FROM event_log", [], |row| {
+        row.get(0)
+    })
+    .map_err(|e| EngramError::Storage(format!("Max event log id: {}", e)))
+}
+
+/// ID of the oldest of the newest `count` event log entries, or `None` if
+/// the log is empty.
+pub fn event_log_window_start(
+    conn: &rusqlite::Connection,
+    count: usize,
+) -> Result<Option<i64>, EngramError> {
+    conn.query_row(
+        "SELECT MIN(id) FROM (SELECT id FROM event_log ORDER BY id DESC LIMIT ?1)",
+        rusqlite::params![count as i64],
+        |row| row.get(0),
+    )
+    .map_err(|e| EngramError::Storage(format!("Event log window: {}", e)))
+}
+
+/// Delete event log entries that occurred before `before_epoch`.
+///
+/// Returns the number of rows deleted.
+pub fn purge_event_log(
+    conn: &rusqlite::Connection,
+    before_epoch: i64,
+) -> Result<usize, EngramError> {
+    conn.execute(
+        "DELETE FROM event_log WHERE occurred_at < ?1",
+        rusqlite::params![before_epoch],
+    )
+    .map_err(|e| EngramError::Storage(format!("Purge event log: {}", e)))
+}
+
+// =============================================================================
+// Ingest Keys
+// =============================================================================
+
+/// The recorded outcome of an ingest item submitted with an idempotency key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IngestKeyRow {
+    pub key: String,
+    /// Capture ID when the item was stored.
+    pub capture_id: Option<String>,
+    /// Outcome name, e.g. `stored` or `deduplicated`.
+    pub status: String,
+    pub message: String,
+    pub created_at: i64,
+}
+
+/// Look up the outcome recorded for an idempotency key.
+pub fn get_ingest_key(
+    conn: &rusqlite::Connection,
+    key: &str,
+) -> Result<Option<IngestKeyRow>, EngramError> {
+    let result = conn.query_row(
+        "SELECT key, capture_id, status, message, created_at FROM ingest_keys WHERE key = ?1",
+        rusqlite::params![key],
+        |row| {
+            Ok(IngestKeyRow {
+                key: row.get(0)?,
+                capture_id: row.get(1)?,
+                status: row.get(2)?,
+                message: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        },
+    );
+    match result {
+        Ok(row) => Ok(Some(row)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(EngramError::Storage(format!("Get ingest key: {}", e))),
+    }
+}
+
+/// Record the outcome for an idempotency key.
+///
+/// An existing key is kept, except that the key's own capture may update
+/// its status and message. Returns `false` when the key was already
+/// recorded for something else, so running this in the transaction that
+/// stores a capture reserves the key for it.
+pub fn store_ingest_key(
+    conn: &rusqlite::Connection,
+    key: &str,
+    capture_id: Option<&str>,
+    status: &str,
+    message: &str,
+) -> Result<bool, EngramError> {
+    let changed = conn
+        .execute(
+            "INSERT INTO ingest_keys (key, capture_id, status, message) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(key) DO UPDATE SET status = excluded.status, message = excluded.message
+             WHERE ingest_keys.capture_id = excluded.capture_id",
+            rusqlite::params![key, capture_id, status, message],
+        )
+        .map_err(|e| EngramError::Storage(format!("Store ingest key: {}", e)))?;
+    Ok(changed > 0)
+}
+
+// =============================================================================
+// Document Files
+// =============================================================================
+
+/// A file indexed from a watched document folder.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentFileRow {
+    pub path: String,
+    /// Modification time (unix milliseconds) when the file was indexed.
+    pub mtime: i64,
+    /// File size in bytes when the file was indexed.
+    pub size: i64,
+    pub passage_count: i64,
+    pub indexed_at: i64,
+}
+
+fn map_document_file_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<DocumentFileRow> {
+    Ok(DocumentFileRow {
+        path: row.get(0)?,
+        mtime: row.get(1)?,
+        size: row.get(2)?,
+        passage_count: row.get(3)?,
+        indexed_at: row.get(4)?,
+    })
+}
+
+/// Look up the tracking row for a document file.
+pub fn get_document_file(
+    conn: &rusqlite::Connection,
+    path: &str,
+) -> Result<Option<DocumentFileRow>, EngramError> {
+    let result = conn.query_row(
+        "SELECT path, mtime, size, passage_count, indexed_at FROM document_files WHERE path = ?1",
+        rusqlite::params![path],
+        map_document_file_row,
+    );
+    match result {
+        Ok(row) => Ok(Some(row)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(EngramError::Storage(format!("Get document file: {}", e))),
+    }
+}
+
+/// All tracked document files, ordered by path.
+pub fn list_document_files(
+    conn: &rusqlite::Connection,
+) -> Result<Vec<DocumentFileRow>, EngramError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT path, mtime, size, passage_count, indexed_at FROM document_files ORDER BY path",
+        )
+        .map_err(|e| EngramError::Storage(format!("List document files: {}", e)))?;
+    let rows = stmt
+        .query_map([], map_document_file_row)
+        .map_err(|e| EngramError::Storage(format!("List document files: {}", e)))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| EngramError::Storage(format!("List document files: {}", e)))
+}
+
+/// Insert or replace the tracking row for a document file.
+pub fn upsert_document_file(
+    conn: &rusqlite::Connection,
+    path: &str,
+    mtime: i64,
+    size: i64,
+    passage_count: i64,
+) -> Result<(), EngramError> {
+    conn.execute(
+        "INSERT INTO document_files (path, mtime, size, passage_count, indexed_at)
+         VALUES (?1, ?2, ?3, ?4, strftime('%s', 'now'))
+         ON CONFLICT(path) DO UPDATE SET
+             mtime = excluded.mtime,
+             size = excluded.size,
+             passage_count = excluded.passage_count,
+             indexed_at = excluded.indexed_at",
+        rusqlite::params![path, mtime, size, passage_count],
+    )
+    .map_err(|e| EngramError::Storage(format!("Upsert document file: {}", e)))?;
+    Ok(())
+}
+
+/// Stop tracking a document file. Returns whether a row was removed.
+pub fn delete_document_file(conn: &rusqlite::Connection, path: &str) -> Result<bool, EngramError> {
+    let deleted = conn
+        .execute(
+            "DELETE FROM document_files WHERE path = ?1",
+            rusqlite::params![path],
+        )
+        .map_err(|e| EngramError::Storage(format!("Delete document file: {}", e)))?;
+    Ok(deleted > 0)
+}
+
+// =============================================================================
+// Chat Feedback
+// =============================================================================
+
+/// Feedback on a chat answer, or on one of its sources when `chunk_id` is set.
+#[derive(Debug, Clone)]
+pub struct ChatFeedbackRow {
+    pub id: String,
+    pub message_id: String,
+    pub session_id: String,
+    pub chunk_id: Option<String>,
+    /// `up`, `down` or `irrelevant`.
+    pub rating: String,
+    /// The question the answer was for.
+    pub query: String,
+    pub user_id: String,
+    /// Unix epoch (seconds).
+    pub created_at: i64,
+}
+
+/// Learned boost (positive) or penalty (negative) for a source under a
+/// query term, for one user.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceBoostRow {
+    pub user_id: String,
+    pub term: String,
+    pub chunk_id: String,
+    pub score: f64,
+    pub updated_at: i64,
+}
+
+/// A user's tuned weight of semantic similarity in chat retrieval.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FusionWeightRow {
+    pub user_id: String,
+    pub semantic_weight: f64,
+    pub feedback_count: i64,
+    pub updated_at: i64,
+}
+
+/// Number of feedback entries with a rating.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedbackCountRow {
+    pub rating: String,
+    pub count: i64,
+}
+
+fn map_source_boost_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SourceBoostRow> {
+    Ok(SourceBoostRow {
+        user_id: row.get(0)?,
+        term: row.get(1)?,
+        chunk_id: row.get(2)?,
+        score: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+/// A user's earlier rating of a message, or of one of its sources when
+/// `chunk_id` is set.
+pub fn get_chat_feedback(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    message_id: &str,
+    chunk_id: Option<&str>,
+) -> Result<Option<ChatFeedbackRow>, EngramError> {
+    let result = conn.query_row(
+        "SELECT id, message_id, session_id, chunk_id, rating, query, user_id, created_at
+         FROM chat_feedback
+         WHERE user_id = ?1 AND message_id = ?2 AND COALESCE(chunk_id, '') = COALESCE(?3, '')",
+        rusqlite::params![user_id, message_id, chunk_id],
+        |row| {
+            Ok(ChatFeedbackRow {
+                id: row.get(0)?,
+                message_id: row.get(1)?,
+                session_id: row.get(2)?,
+                chunk_id: row.get(3)?,
+                rating: row.get(4)?,
+                query: row.get(5)?,
+                user_id: row.get(6)?,
+                created_at: row.get(7)?,
+            })
+        },
+    );
+    match result {
+        Ok(row) => Ok(Some(row)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(EngramError::Storage(format!("Get chat feedback: {}", e))),
+    }
+}
+
+/// Store a feedback entry, replacing the user's earlier rating of the same
+/// message and source.
+pub fn store_chat_feedback(
+    conn: &rusqlite::Connection,
+    row: &ChatFeedbackRow,
+) -> Result<(), EngramError> {
+    conn.execute(
+        "INSERT OR REPLACE INTO chat_feedback (id, message_id, session_id, chunk_id, rating, query, user_id, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        rusqlite::params![
+            row.id,
+            row.message_id,
+            row.session_id,
+            row.chunk_id,
+            row.rating,
+            row.query,
+            row.user_id,
+            row.created_at,
+        ],
+    )
+    .map_err(|e| EngramError::Storage(format!("Store chat feedback: {}", e)))?;
+    Ok(())
+}
+
+/// Add `delta` to `user_id`'s boost of `chunk_id` under `term`, keeping it
+/// within -1..1. Returns the new score.
+pub fn adjust_source_boost(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    term: &str,
+    chunk_id: &str,
+    delta: f64,
+    now: i64,
+) -> Result<f64, EngramError> {
+    conn.query_row(
+        "INSERT INTO chat_source_boosts (user_id, term, chunk_id, score, updated_at)
+         VALUES (?1, ?2, ?3, max(-1.0, min(1.0, ?4)), ?5)
+         ON CONFLICT(user_id, term, chunk_id) DO UPDATE SET
+             score = max(-1.0, min(1.0, score + ?4)),
+             updated_at = ?5
+         RETURNING score",
+        rusqlite::params![user_id, term, chunk_id, delta, now],
+        |row| row.get(0),
+    )
+    .map_err(|e| EngramError::Storage(format!("Adjust source boost: {}", e)))
+}
+
+/// Boosts `user_id` has taught for any of `terms`.
+pub fn get_source_boosts(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    terms: &[String],
+) -> Result<Vec<SourceBoostRow>, EngramError> {
+    if terms.is_empty() {
+        return Ok(vec![]);
+    }
+    let placeholders = vec!["?"; terms.len()].join(", ");
+    let sql = format!(
+        "SELECT user_id, term, chunk_id, score, updated_at FROM chat_source_boosts
+         WHERE user_id = ? AND term IN ({}) AND score != 0",
+        placeholders
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| EngramError::Storage(format!("Get source boosts: {}", e)))?;
+    let rows = stmt
+        .query_map(
+            rusqlite::params_from_iter(
+                std::iter::once(user_id).chain(terms.iter().map(|t| t.as_str())),
+            ),
+            map_source_boost_row,
+        )
+        .map_err(|e| EngramError::Storage(format!("Get source boosts: {}", e)))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| EngramError::Storage(format!("Get source boosts: {}", e)))
+}
+
+/// `user_id`'s strongest boosts and penalties, largest magnitude first.
+pub fn list_source_boosts(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    limit: u32,
+) -> Result<Vec<SourceBoostRow>, EngramError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT user_id, term, chunk_id, score, updated_at FROM chat_source_boosts
+             WHERE user_id = ?1 AND score != 0
+             ORDER BY abs(score) DESC, updated_at DESC
+             LIMIT ?2",
+        )
+        .map_err(|e| EngramError::Storage(format!("List source boosts: {}", e)))?;
+    let rows = stmt
+        .query_map(
+            rusqlite::params![user_id, limit as i64],
+            map_source_boost_row,
+        )
+        .map_err(|e| EngramError::Storage(format!("List source boosts: {}", e)))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| EngramError::Storage(format!("List source boosts: {}", e)))
+}
+
+/// Look up a user's tuned fusion weight.
+pub fn get_fusion_weight(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+) -> Result<Option<FusionWeightRow>, EngramError> {
+    let result = conn.query_row(
+        "SELECT user_id, semantic_weight, feedback_count, updated_at
+         FROM chat_fusion_weights WHERE user_id = ?1",
+        rusqlite::params![user_id],
+        |row| {
+            Ok(FusionWeightRow {
+                user_id: row.get(0)?,
+                semantic_weight: row.get(1)?,
+                feedback_count: row.get(2)?,
+                updated_at: row.get(3)?,
+            })
+        },
+    );
+    match result {
+        Ok(row) => Ok(Some(row)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(EngramError::Storage(format!("Get fusion weight: {}", e))),
+    }
+}
+
+/// Insert or replace a user's fusion weight.
+pub fn store_fusion_weight(
+    conn: &rusqlite::Connection,
+    row: &FusionWeightRow,
+) -> Result<(), EngramError> {
+    conn.execute(
+        "INSERT INTO chat_fusion_weights (user_id, semantic_weight, feedback_count, updated_at)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(user_id) DO UPDATE SET
+             semantic_weight = excluded.semantic_weight,
+             feedback_count = excluded.feedback_count,
+             updated_at = excluded.updated_at",
+        rusqlite::params![
+            row.user_id,
+            row.semantic_weight,
+            row.feedback_count,
+            row.updated_at
+        ],
+    )
+    .map_err(|e| EngramError::Storage(format!("Store fusion weight: {}", e)))?;
+    Ok(())
+}
+
+/// Feedback counts per rating for a user.
+pub fn get_chat_feedback_counts(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+) -> Result<Vec<FeedbackCountRow>, EngramError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT rating, COUNT(*) FROM chat_feedback
+             WHERE user_id = ?1
+             GROUP BY rating
+             ORDER BY rating",
+        )
+        .map_err(|e| EngramError::Storage(format!("Chat feedback counts: {}", e)))?;
+    let rows = stmt
+        .query_map(rusqlite::params![user_id], |row| {
+            Ok(FeedbackCountRow {
+                rating: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })
+        .map_err(|e| EngramError::Storage(format!("Chat feedback counts: {}", e)))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| EngramError::Storage(format!("Chat feedback counts: {}", e)))
+}
 
-    let mut results = Vec::new();
-    for row in rows {
-        results.push(row.map_err(|e| EngramError::Storage(e.to_string()))?);
-    }
-    Ok(results)
+/// The questions a user most recently gave feedback on, newest first.
+pub fn list_feedback_queries(
+    conn: &rusqlite::Connection,
+    user_id: &str,
+    limit: u32,
+) -> Result<Vec<String>, EngramError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT query FROM chat_feedback
+             WHERE user_id = ?1 AND query != ''
+             GROUP BY query
+             ORDER BY MAX(created_at) DESC, MAX(rowid) DESC
+             LIMIT ?2",
+        )
+        .map_err(|e| EngramError::Storage(format!("List feedback queries: {}", e)))?;
+    let rows = stmt
+        .query_map(rusqlite::params![user_id, limit as i64], |row| row.get(0))
+        .map_err(|e| EngramError::Storage(format!("List feedback queries: {}", e)))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| EngramError::Storage(format!("List feedback queries: {}", e)))
+}
+
+// =============================================================================
+// Capture Lookup
+// =============================================================================
+
+/// Whether a capture of `content_type` with exactly this timestamp (unix
+/// seconds) and text is already stored.
+pub fn capture_exists(
+    conn: &rusqlite::Connection,
+    content_type: &str,
+    timestamp: i64,
+    text: &str,
+) -> Result<bool, EngramError> {
+    conn.query_row(
+        "SELECT EXISTS(
+             SELECT 1 FROM captures
+             WHERE content_type = ?1 AND timestamp = ?2 AND text = ?3
+         )",
+        rusqlite::params![content_type, timestamp, text],
+        |row| row.get(0),
+    )
+    .map_err(|e| EngramError::Storage(format!("Capture exists: {}", e)))
 }
 
 fn map_capture_row(row: &rusqlite::Row<'_>) -> Result<CaptureRow, EngramError> {
@@ -1600,6 +2953,79 @@ mod tests {
         assert!(chunks.is_empty());
     }
 
+    fn insert_at(db: &Database, ct: &str, text: &str, ts: i64) -> Uuid {
+        let id = Uuid::new_v4();
+        db.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO captures (id, content_type, timestamp, text, app_name, target_app)
+                 VALUES (?1, ?2, ?3, ?4, CASE WHEN ?2 = 'dictation' THEN '' ELSE 'Chrome' END, 'Notepad')",
+                rusqlite::params![id.to_string(), ct, ts, text],
+            )
+            .map_err(|e| EngramError::Storage(e.to_string()))?;
+            Ok(())
+        })
+        .unwrap();
+        id
+    }
+
+    #[test]
+    fn test_get_capture_any_type() {
+        let db = make_db();
+        let audio = insert_at(&db, "audio", "standup call", 1_000);
+        let dictation = insert_at(&db, "dictation", "dear team", 1_010);
+
+        let qs = QueryService::new(db);
+        let row = qs.get_capture(audio).unwrap().unwrap();
+        assert_eq!(row.content_type, "audio");
+        assert_eq!(row.app_name, "Chrome");
+
+        // Dictation rows fall back to target_app.
+        let row = qs.get_capture(dictation).unwrap().unwrap();
+        assert_eq!(row.app_name, "Notepad");
+
+        assert!(qs.get_capture(Uuid::new_v4()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_captures_around_interleaves_types() {
+        let db = make_db();
+        insert_at(&db, "screen", "too early", 600);
+        let a = insert_at(&db, "audio", "a", 800);
+        let s = insert_at(&db, "screen", "s", 1_000);
+        let d = insert_at(&db, "dictation", "d", 1_200);
+        insert_at(&db, "audio", "too late", 1_400);
+
+        let qs = QueryService::new(db);
+        let rows = qs.captures_around(1_000, 300, 100).unwrap();
+        let ids: Vec<Uuid> = rows.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![a, s, d]);
+
+        let limited = qs.captures_around(1_000, 300, 2).unwrap();
+        let ids: Vec<Uuid> = limited.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![a, s]);
+    }
+
+    #[test]
+    fn test_captures_around_keeps_nearest_when_window_is_full() {
+        let db = make_db();
+        for offset in 1..=10 {
+            insert_at(&db, "screen", "before", 1_000 - offset);
+            insert_at(&db, "screen", "after", 1_000 + offset);
+        }
+        let anchor = insert_at(&db, "audio", "anchor", 1_000);
+
+        let qs = QueryService::new(db);
+        let rows = qs.captures_around(1_000, 300, 5).unwrap();
+        let times: Vec<i64> = rows.iter().map(|r| r.timestamp.timestamp()).collect();
+        assert_eq!(times, vec![998, 999, 1_000, 1_001, 1_002]);
+        assert_eq!(rows[2].id, anchor);
+
+        // A sparse side hands its share to the other.
+        let rows = qs.captures_around(1_011, 300, 4).unwrap();
+        let times: Vec<i64> = rows.iter().map(|r| r.timestamp.timestamp()).collect();
+        assert_eq!(times, vec![1_007, 1_008, 1_009, 1_010]);
+    }
+
     // =========================================================================
     // Action Engine Query Tests
     // =========================================================================
@@ -2304,4 +3730,610 @@ mod tests {
             .unwrap();
         assert_eq!(results.len(), 2);
     }
+
+    // =========================================================================
+    // Saved Search Query Tests
+    // =========================================================================
+
+    fn saved_search(id: &str) -> SavedSearchRow {
+        SavedSearchRow {
+            id: id.to_string(),
+            name: "Incident".to_string(),
+            query: "INC-4521".to_string(),
+            mode: "keyword".to_string(),
+            content_type: None,
+            app_name: None,
+            interval_secs: 600,
+            notify: true,
+            enabled: true,
+            last_run_at: None,
+            last_match_count: 0,
+            created_at: "2026-02-18T10:00:00".to_string(),
+            last_capture_rowid: None,
+        }
+    }
+
+    #[test]
+    fn test_store_and_get_saved_search() {
+        let conn = make_conn();
+        store_saved_search(&conn, &saved_search("ss-1")).unwrap();
+
+        let found = get_saved_search(&conn, "ss-1").unwrap().unwrap();
+        assert_eq!(found.query, "INC-4521");
+        assert!(found.notify);
+        assert!(found.last_run_at.is_none());
+        assert!(get_saved_search(&conn, "missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_update_and_delete_saved_search() {
+        let conn = make_conn();
+        store_saved_search(&conn, &saved_search("ss-upd")).unwrap();
+
+        let mut row = saved_search("ss-upd");
+        row.name = "Release notes".to_string();
+        row.enabled = false;
+        assert!(update_saved_search(&conn, &row).unwrap());
+
+        let found = get_saved_search(&conn, "ss-upd").unwrap().unwrap();
+        assert_eq!(found.name, "Release notes");
+        assert!(!found.enabled);
+
+        assert!(delete_saved_search(&conn, "ss-upd").unwrap());
+        assert!(!delete_saved_search(&conn, "ss-upd").unwrap());
+        assert!(list_saved_searches(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_list_due_saved_searches() {
+        let conn = make_conn();
+        store_saved_search(&conn, &saved_search("ss-new")).unwrap();
+        store_saved_search(&conn, &saved_search("ss-ran")).unwrap();
+        let mut disabled = saved_search("ss-off");
+        disabled.enabled = false;
+        store_saved_search(&conn, &disabled).unwrap();
+
+        record_saved_search_run(&conn, "ss-ran", 1_000, 3, 42).unwrap();
+
+        // Interval is 600s: "ss-ran" is not due until 1600.
+        let due: Vec<String> = list_due_saved_searches(&conn, 1_500)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(due, vec!["ss-new".to_string()]);
+
+        let due = list_due_saved_searches(&conn, 1_600).unwrap();
+        assert_eq!(due.len(), 2);
+
+        let ran = get_saved_search(&conn, "ss-ran").unwrap().unwrap();
+        assert_eq!(ran.last_run_at, Some(1_000));
+        assert_eq!(ran.last_match_count, 3);
+        assert_eq!(ran.last_capture_rowid, Some(42));
+    }
+
+    #[test]
+    fn test_max_capture_rowid_follows_inserts() {
+        let db = make_db();
+        assert_eq!(db.with_conn(max_capture_rowid).unwrap(), 0);
+        insert(&db, "screen", "INC-4521 opened", "Chrome");
+        let first = db.with_conn(max_capture_rowid).unwrap();
+        let new = insert(&db, "audio", "INC-4521 resolved", "Teams");
+        // A back-dated capture still gets the next rowid.
+        db.with_conn(|conn| {
+            conn.execute(
+                "UPDATE captures SET timestamp = 1000, created_at = 1000 WHERE id = ?1",
+                rusqlite::params![new.to_string()],
+            )
+            .map_err(|e| EngramError::Storage(e.to_string()))?;
+            Ok(())
+        })
+        .unwrap();
+        assert!(db.with_conn(max_capture_rowid).unwrap() > first);
+    }
+
+    // =========================================================================
+    // Search Log Query Tests
+    // =========================================================================
+
+    fn log_search(
+        conn: &rusqlite::Connection,
+        id: &str,
+        query: &str,
+        mode: &str,
+        latency: i64,
+        results: i64,
+        at: i64,
+    ) {
+        store_search_log(
+            conn,
+            &SearchLogRow {
+                id: id.to_string(),
+                query: query.to_string(),
+                mode: mode.to_string(),
+                latency_ms: latency,
+                result_count: results,
+                clicked_result_id: None,
+                created_at: at,
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_query_stats_groups_case_insensitively() {
+        let conn = make_conn();
+        log_search(&conn, "l1", "Release Notes", "hybrid", 10, 4, 1_000);
+        log_search(&conn, "l2", "release notes ", "raw", 20, 2, 1_100);
+        log_search(&conn, "l3", "INC-4521", "raw", 30, 0, 1_200);
+        log_search(&conn, "l4", "inc-4521", "semantic", 40, 0, 1_300);
+        log_search(&conn, "l5", "inc-4521", "semantic", 50, 0, 1_400);
+        log_search(&conn, "l6", "ancient", "raw", 5, 0, 10);
+
+        let top = get_query_stats(&conn, 500, false, 10).unwrap();
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].query, "inc-4521");
+        assert_eq!(top[0].count, 3);
+        assert_eq!(top[1].query, "release notes");
+        assert!((top[1].avg_result_count - 3.0).abs() < f64::EPSILON);
+
+        let zero = get_query_stats(&conn, 0, true, 10).unwrap();
+        let queries: Vec<&str> = zero.iter().map(|q| q.query.as_str()).collect();
+        assert_eq!(queries, vec!["inc-4521", "ancient"]);
+        assert_eq!(zero[0].last_seen, 1_400);
+    }
+
+    #[test]
+    fn test_search_mode_stats_and_latencies() {
+        let conn = make_conn();
+        log_search(&conn, "m1", "a", "raw", 30, 0, 1_000);
+        log_search(&conn, "m2", "b", "raw", 10, 3, 1_000);
+        log_search(&conn, "m3", "c", "hybrid", 20, 1, 1_000);
+        assert!(record_search_click(&conn, "m2", "capture-1").unwrap());
+        assert!(!record_search_click(&conn, "missing", "capture-1").unwrap());
+
+        let modes = get_search_mode_stats(&conn, 0).unwrap();
+        assert_eq!(modes[0].mode, "raw");
+        assert_eq!(modes[0].count, 2);
+        assert_eq!(modes[0].zero_result_count, 1);
+        assert_eq!(modes[0].clicked_count, 1);
+        assert!((modes[0].avg_latency_ms - 20.0).abs() < f64::EPSILON);
+
+        assert_eq!(get_search_latencies(&conn, 0).unwrap(), vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn test_purge_search_log() {
+        let conn = make_conn();
+        log_search(&conn, "p1", "old", "raw", 1, 0, 100);
+        log_search(&conn, "p2", "new", "raw", 1, 0, 2_000);
+
+        assert_eq!(purge_search_log(&conn, Some(1_000)).unwrap(), 1);
+        assert_eq!(get_search_latencies(&conn, 0).unwrap().len(), 1);
+        assert_eq!(purge_search_log(&conn, None).unwrap(), 1);
+        assert!(get_search_latencies(&conn, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_purge_search_log_deletes_search_events() {
+        let conn = make_conn();
+        for (event, timestamp) in [
+            ("search_performed", 100),
+            ("search_performed", 2_000),
+            ("task_created", 100),
+        ] {
+            let payload = serde_json::json!({ "event": event, "timestamp": timestamp });
+            append_event_log_json(&conn, &payload).unwrap();
+        }
+        let logged = |conn: &rusqlite::Connection| {
+            query_event_log(
+                conn,
+                &EventLogFilter {
+                    limit: 10,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .into_iter()
+            .map(|row| row.event_type)
+            .collect::<Vec<_>>()
+        };
+
+        purge_search_log(&conn, Some(1_000)).unwrap();
+        assert_eq!(
+            logged(&conn),
+            vec!["search_performed".to_string(), "task_created".to_string()]
+        );
+
+        purge_search_log(&conn, None).unwrap();
+        assert_eq!(logged(&conn), vec!["task_created".to_string()]);
+    }
+
+    // =========================================================================
+    // API Token Query Tests
+    // =========================================================================
+
+    fn api_token(name: &str, hash: &str) -> ApiTokenRow {
+        ApiTokenRow {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            token_hash: hash.to_string(),
+            scopes: vec!["search:read".to_string(), "ingest:write".to_string()],
+            expires_at: Some(5_000),
+            last_used_at: None,
+            revoked: false,
+            created_at: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_api_token_roundtrip() {
+        let conn = make_conn();
+        let token = api_token("scripts", "hash-1");
+        store_api_token(&conn, &token).unwrap();
+
+        let found = get_api_token_by_hash(&conn, "hash-1").unwrap().unwrap();
+        assert_eq!(found.id, token.id);
+        assert_eq!(found.scopes, token.scopes);
+        assert_eq!(found.expires_at, Some(5_000));
+        assert!(!found.revoked);
+        assert!(get_api_token_by_hash(&conn, "other").unwrap().is_none());
+
+        // Duplicate names are rejected.
+        assert!(store_api_token(&conn, &api_token("scripts", "hash-2")).is_err());
+        assert_eq!(list_api_tokens(&conn).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_revoke_api_token_by_id_or_name() {
+        let conn = make_conn();
+        let a = api_token("a", "hash-a");
+        store_api_token(&conn, &a).unwrap();
+        store_api_token(&conn, &api_token("b", "hash-b")).unwrap();
+
+        assert!(revoke_api_token(&conn, &a.id).unwrap());
+        assert!(!revoke_api_token(&conn, &a.id).unwrap());
+        assert!(revoke_api_token(&conn, "b").unwrap());
+        assert!(!revoke_api_token(&conn, "missing").unwrap());
+        assert!(list_api_tokens(&conn).unwrap().iter().all(|t| t.revoked));
+    }
+
+    #[test]
+    fn test_touch_api_token_coalesces_writes() {
+        let conn = make_conn();
+        let token = api_token("t", "hash-t");
+        store_api_token(&conn, &token).unwrap();
+
+        touch_api_token(&conn, &token.id, 2_000).unwrap();
+        touch_api_token(&conn, &token.id, 2_030).unwrap();
+        let used = get_api_token_by_hash(&conn, "hash-t").unwrap().unwrap();
+        assert_eq!(used.last_used_at, Some(2_000));
+
+        touch_api_token(&conn, &token.id, 2_100).unwrap();
+        let used = get_api_token_by_hash(&conn, "hash-t").unwrap().unwrap();
+        assert_eq!(used.last_used_at, Some(2_100));
+    }
+
+    // =========================================================================
+    // Event Log Query Tests
+    // =========================================================================
+
+    fn log_event(conn: &rusqlite::Connection, at: i64, task: bool) -> i64 {
+        let event = if task {
+            engram_core::events::DomainEvent::TaskCreated {
+                task_id: Uuid::new_v4(),
+                action_type: "reminder".to_string(),
+                source: "test".to_string(),
+                timestamp: engram_core::types::Timestamp(at),
+            }
+        } else {
+            engram_core::events::DomainEvent::ApplicationStarted {
+                version: "0.1.0".to_string(),
+                config_path: "config.toml".to_string(),
+                timestamp: engram_core::types::Timestamp(at),
+            }
+        };
+        append_event_log(conn, &event).unwrap()
+    }
+
+    #[test]
+    fn test_event_log_query_by_type_and_time() {
+        let conn = make_conn();
+        let first = log_event(&conn, 100, true);
+        log_event(&conn, 200, false);
+        let third = log_event(&conn, 300, true);
+
+        let all = query_event_log(
+            &conn,
+            &EventLogFilter {
+                limit: 10,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].event_type, "task_created");
+        assert_eq!(all[1].event_type, "application_started");
+
+        let tasks = query_event_log(
+            &conn,
+            &EventLogFilter {
+                event_types: vec!["task_created".to_string()],
+                since: Some(150),
+                limit: 10,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id, third);
+        assert_eq!(tasks[0].occurred_at, 300);
+        let payload: serde_json::Value = serde_json::from_str(&tasks[0].payload).unwrap();
+        assert_eq!(payload["event"], "task_created");
+
+        let resumed = query_event_log(
+            &conn,
+            &EventLogFilter {
+                after_id: Some(first),
+                until: Some(300),
+                limit: 10,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed[0].event_type, "application_started");
+    }
+
+    #[test]
+    fn test_purge_event_log() {
+        let conn = make_conn();
+        assert_eq!(max_event_log_id(&conn).unwrap(), 0);
+        log_event(&conn, 100, true);
+        let last = log_event(&conn, 200, true);
+        assert_eq!(purge_event_log(&conn, 150).unwrap(), 1);
+        assert_eq!(max_event_log_id(&conn).unwrap(), last);
+        let left = query_event_log(
+            &conn,
+            &EventLogFilter {
+                limit: 10,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].occurred_at, 200);
+    }
+
+    #[test]
+    fn test_event_log_window_start() {
+        let conn = make_conn();
+        assert_eq!(event_log_window_start(&conn, 2).unwrap(), None);
+        let ids: Vec<i64> = (0..4).map(|at| log_event(&conn, at, true)).collect();
+        assert_eq!(event_log_window_start(&conn, 2).unwrap(), Some(ids[2]));
+        assert_eq!(event_log_window_start(&conn, 10).unwrap(), Some(ids[0]));
+    }
+
+    #[test]
+    fn test_ingest_key_first_outcome_wins() {
+        let conn = make_conn();
+        assert!(get_ingest_key(&conn, "rec-1").unwrap().is_none());
+
+        assert!(store_ingest_key(&conn, "rec-1", Some("cap-1"), "stored", "Stored").unwrap());
+        assert!(!store_ingest_key(&conn, "rec-1", None, "skipped", "Skipped").unwrap());
+        assert!(!store_ingest_key(&conn, "rec-1", Some("cap-2"), "stored", "Stored").unwrap());
+
+        let row = get_ingest_key(&conn, "rec-1").unwrap().unwrap();
+        assert_eq!(row.capture_id.as_deref(), Some("cap-1"));
+        assert_eq!(row.status, "stored");
+
+        // The key's own capture can refine the outcome.
+        assert!(store_ingest_key(&conn, "rec-1", Some("cap-1"), "redacted", "Redacted").unwrap());
+        assert_eq!(
+            get_ingest_key(&conn, "rec-1").unwrap().unwrap().status,
+            "redacted"
+        );
+
+        assert!(store_ingest_key(&conn, "rec-2", None, "skipped", "Skipped").unwrap());
+        assert!(!store_ingest_key(&conn, "rec-2", None, "denied", "Denied").unwrap());
+        assert_eq!(
+            get_ingest_key(&conn, "rec-2").unwrap().unwrap().status,
+            "skipped"
+        );
+    }
+
+    #[test]
+    fn test_document_file_upsert_and_delete() {
+        let conn = make_conn();
+        assert!(get_document_file(&conn, "/notes/a.md").unwrap().is_none());
+
+        upsert_document_file(&conn, "/notes/a.md", 100, 2048, 3).unwrap();
+        upsert_document_file(&conn, "/notes/b.txt", 50, 10, 1).unwrap();
+        upsert_document_file(&conn, "/notes/a.md", 200, 4096, 5).unwrap();
+
+        let row = get_document_file(&conn, "/notes/a.md").unwrap().unwrap();
+        assert_eq!((row.mtime, row.size, row.passage_count), (200, 4096, 5));
+        let paths: Vec<String> = list_document_files(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.path)
+            .collect();
+        assert_eq!(paths, vec!["/notes/a.md", "/notes/b.txt"]);
+
+        assert!(delete_document_file(&conn, "/notes/a.md").unwrap());
+        assert!(!delete_document_file(&conn, "/notes/a.md").unwrap());
+        assert_eq!(list_document_files(&conn).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_capture_exists_matches_type_timestamp_and_text() {
+        let conn = make_conn();
+        conn.execute(
+            "INSERT INTO captures (id, content_type, timestamp, text) VALUES ('a', 'audio', 1700000000, 'Ship it Friday')",
+            [],
+        )
+        .unwrap();
+
+        assert!(capture_exists(&conn, "audio", 1700000000, "Ship it Friday").unwrap());
+        assert!(!capture_exists(&conn, "screen", 1700000000, "Ship it Friday").unwrap());
+        assert!(!capture_exists(&conn, "audio", 1700000001, "Ship it Friday").unwrap());
+        assert!(!capture_exists(&conn, "audio", 1700000000, "Ship it Monday").unwrap());
+    }
+
+    #[test]
+    fn test_source_boosts_accumulate_within_bounds() {
+        let conn = make_conn();
+        assert_eq!(
+            adjust_source_boost(&conn, "primary", "pricing", "chunk-1", 0.75, 10).unwrap(),
+            0.75
+        );
+        assert_eq!(
+            adjust_source_boost(&conn, "primary", "pricing", "chunk-1", 0.75, 20).unwrap(),
+            1.0
+        );
+        assert_eq!(
+            adjust_source_boost(&conn, "primary", "launch", "chunk-2", -0.5, 30).unwrap(),
+            -0.5
+        );
+        adjust_source_boost(&conn, "primary", "launch", "chunk-3", 0.25, 40).unwrap();
+        adjust_source_boost(&conn, "primary", "launch", "chunk-3", -0.25, 50).unwrap();
+        // Another user's boosts are their own.
+        assert_eq!(
+            adjust_source_boost(&conn, "laptop", "pricing", "chunk-1", -0.5, 60).unwrap(),
+            -0.5
+        );
+
+        let terms = vec!["pricing".to_string(), "launch".to_string()];
+        let mut found = get_source_boosts(&conn, "primary", &terms).unwrap();
+        found.sort_by(|a, b| a.chunk_id.cmp(&b.chunk_id));
+        // Boosts back at zero are not returned.
+        assert_eq!(found.len(), 2);
+        assert_eq!(
+            (found[0].chunk_id.as_str(), found[0].score),
+            ("chunk-1", 1.0)
+        );
+        assert_eq!(found[0].updated_at, 20);
+        assert!(get_source_boosts(&conn, "primary", &[]).unwrap().is_empty());
+        assert!(get_source_boosts(&conn, "tablet", &terms)
+            .unwrap()
+            .is_empty());
+
+        let strongest = list_source_boosts(&conn, "primary", 1).unwrap();
+        assert_eq!(strongest[0].chunk_id, "chunk-1");
+        let theirs = list_source_boosts(&conn, "laptop", 10).unwrap();
+        assert_eq!(theirs.len(), 1);
+        assert_eq!(theirs[0].score, -0.5);
+    }
+
+    #[test]
+    fn test_chat_feedback_counts_weights_and_queries() {
+        let conn = make_conn();
+        conn.execute_batch(
+            "INSERT INTO chat_sessions (id) VALUES ('sess-1');
+             INSERT INTO chat_messages (id, session_id, role, content)
+                VALUES ('msg-1', 'sess-1', 'assistant', 'answer');",
+        )
+        .unwrap();
+        for (i, (rating, query, user)) in [
+            ("up", "pricing", "primary"),
+            ("irrelevant", "launch", "primary"),
+            ("irrelevant", "pricing", "primary"),
+            ("down", "budget", "laptop"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            store_chat_feedback(
+                &conn,
+                &ChatFeedbackRow {
+                    id: format!("fb-{}", i),
+                    message_id: "msg-1".to_string(),
+                    session_id: "sess-1".to_string(),
+                    chunk_id: (rating == "irrelevant").then(|| format!("chunk-{}", i)),
+                    rating: rating.to_string(),
+                    query: query.to_string(),
+                    user_id: user.to_string(),
+                    created_at: 100 + i as i64,
+                },
+            )
+            .unwrap();
+        }
+
+        let counts = get_chat_feedback_counts(&conn, "primary").unwrap();
+        assert_eq!(
+            counts,
+            vec![
+                FeedbackCountRow {
+                    rating: "irrelevant".to_string(),
+                    count: 2
+                },
+                FeedbackCountRow {
+                    rating: "up".to_string(),
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!(
+            list_feedback_queries(&conn, "primary", 10).unwrap(),
+            vec!["pricing", "launch"]
+        );
+
+        assert!(get_fusion_weight(&conn, "primary").unwrap().is_none());
+        let mut weight = FusionWeightRow {
+            user_id: "primary".to_string(),
+            semantic_weight: 0.62,
+            feedback_count: 1,
+            updated_at: 100,
+        };
+        store_fusion_weight(&conn, &weight).unwrap();
+        weight.semantic_weight = 0.64;
+        weight.feedback_count = 2;
+        store_fusion_weight(&conn, &weight).unwrap();
+        assert_eq!(get_fusion_weight(&conn, "primary").unwrap(), Some(weight));
+    }
+
+    #[test]
+    fn test_chat_feedback_replaces_earlier_rating() {
+        let conn = make_conn();
+        conn.execute_batch(
+            "INSERT INTO chat_sessions (id) VALUES ('sess-1');
+             INSERT INTO chat_messages (id, session_id, role, content)
+                VALUES ('msg-1', 'sess-1', 'assistant', 'answer');",
+        )
+        .unwrap();
+        let feedback =
+            |id: &str, chunk_id: Option<&str>, rating: &str, user: &str| ChatFeedbackRow {
+                id: id.to_string(),
+                message_id: "msg-1".to_string(),
+                session_id: "sess-1".to_string(),
+                chunk_id: chunk_id.map(str::to_string),
+                rating: rating.to_string(),
+                query: "pricing".to_string(),
+                user_id: user.to_string(),
+                created_at: 100,
+            };
+        store_chat_feedback(&conn, &feedback("fb-1", None, "up", "primary")).unwrap();
+        store_chat_feedback(&conn, &feedback("fb-2", Some("chunk-1"), "up", "primary")).unwrap();
+        store_chat_feedback(&conn, &feedback("fb-3", None, "up", "laptop")).unwrap();
+        assert!(
+            get_chat_feedback(&conn, "primary", "msg-1", Some("chunk-2"))
+                .unwrap()
+                .is_none()
+        );
+
+        // Rating the whole answer again replaces the earlier rating only.
+        store_chat_feedback(&conn, &feedback("fb-1", None, "down", "primary")).unwrap();
+        let whole = get_chat_feedback(&conn, "primary", "msg-1", None)
+            .unwrap()
+            .unwrap();
+        assert_eq!((whole.id.as_str(), whole.rating.as_str()), ("fb-1", "down"));
+        let source = get_chat_feedback(&conn, "primary", "msg-1", Some("chunk-1"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(source.rating, "up");
+
+        let counts = get_chat_feedback_counts(&conn, "primary").unwrap();
+        let total: i64 = counts.iter().map(|c| c.count).sum();
+        assert_eq!(total, 2);
+    }
 }
@@ -1,7 +1,7 @@
 //! Repository implementations for SQLite-backed persistence.
 //!
-//! Provides CaptureRepository, AudioRepository, and DictationRepository
-//! that operate on the Database struct using raw SQL.
+//! Provides CaptureRepository, AudioRepository, DictationRepository and
+//! DocumentRepository that operate on the Database struct using raw SQL.
 
 use std::sync::Arc;
 
@@ -9,7 +9,9 @@ use chrono::{DateTime, TimeZone, Utc};
 use uuid::Uuid;
 
 use engram_core::error::EngramError;
-use engram_core::types::{AudioChunk, ContentType, DictationEntry, DictationMode, ScreenFrame};
+use engram_core::types::{
+    AudioChunk, ContentType, DictationEntry, DictationMode, DocumentEntry, ScreenFrame,
+};
 
 use crate::db::Database;
 
@@ -25,24 +27,28 @@ impl CaptureRepository {
 
     /// Store a new screen frame.
     pub fn save(&self, frame: &ScreenFrame) -> Result<(), EngramError> {
-        self.db.with_conn(|conn| {
-            conn.execute(
-                "INSERT INTO captures (id, content_type, timestamp, text, app_name, window_title, monitor_id, focused)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-                rusqlite::params![
-                    frame.id.to_string(),
-                    "screen",
-                    frame.timestamp.timestamp(),
-                    frame.text,
-                    frame.app_name,
-                    frame.window_title,
-                    frame.monitor_id,
-                    frame.focused as i32,
-                ],
-            )
-            .map_err(|e| EngramError::Storage(format!("Failed to save capture: {}", e)))?;
-            Ok(())
-        })
+        self.db.with_conn(|conn| Self::insert(conn, frame))
+    }
+
+    /// Insert a new screen frame using `conn`, which may be inside a
+    /// transaction with related writes.
+    pub fn insert(conn: &rusqlite::Connection, frame: &ScreenFrame) -> Result<(), EngramError> {
+        conn.execute(
+            "INSERT INTO captures (id, content_type, timestamp, text, app_name, window_title, monitor_id, focused)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                frame.id.to_string(),
+                "screen",
+                frame.timestamp.timestamp(),
+                frame.text,
+                frame.app_name,
+                frame.window_title,
+                frame.monitor_id,
+                frame.focused as i32,
+            ],
+        )
+        .map_err(|e| EngramError::Storage(format!("Failed to save capture: {}", e)))?;
+        Ok(())
     }
 
     /// Find a screen frame by ID.
@@ -170,24 +176,28 @@ impl AudioRepository {
 
     /// Store an audio chunk in the captures table.
     pub fn save(&self, chunk: &AudioChunk) -> Result<(), EngramError> {
-        self.db.with_conn(|conn| {
-            conn.execute(
-                "INSERT INTO captures (id, content_type, timestamp, text, app_name, source_device, duration_secs, confidence)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-                rusqlite::params![
-                    chunk.id.to_string(),
-                    "audio",
-                    chunk.timestamp.timestamp(),
-                    chunk.transcription,
-                    chunk.app_in_focus,
-                    chunk.source_device,
-                    chunk.duration_secs as f64,
-                    chunk.confidence as f64,
-                ],
-            )
-            .map_err(|e| EngramError::Storage(format!("Failed to save audio: {}", e)))?;
-            Ok(())
-        })
+        self.db.with_conn(|conn| Self::insert(conn, chunk))
+    }
+
+    /// Like [`Self::save`], using `conn`.
+    pub fn insert(conn: &rusqlite::Connection, chunk: &AudioChunk) -> Result<(), EngramError> {
+        conn.execute(
+            "INSERT INTO captures (id, content_type, timestamp, text, app_name, source_device, duration_secs, confidence, speaker)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, NULLIF(?9, ''))",
+            rusqlite::params![
+                chunk.id.to_string(),
+                "audio",
+                chunk.timestamp.timestamp(),
+                chunk.transcription,
+                chunk.app_in_focus,
+                chunk.source_device,
+                chunk.duration_secs as f64,
+                chunk.confidence as f64,
+                chunk.speaker,
+            ],
+        )
+        .map_err(|e| EngramError::Storage(format!("Failed to save audio: {}", e)))?;
+        Ok(())
     }
 
     /// Find an audio chunk by ID.
@@ -195,7 +205,7 @@ impl AudioRepository {
         self.db.with_conn(|conn| {
             let mut stmt = conn
                 .prepare(
-                    "SELECT id, content_type, timestamp, text, app_name, source_device, duration_secs, confidence
+                    "SELECT id, content_type, timestamp, text, app_name, source_device, duration_secs, confidence, speaker
                      FROM captures WHERE id = ?1 AND content_type = 'audio'",
                 )
                 .map_err(|e| EngramError::Storage(e.to_string()))?;
@@ -224,7 +234,7 @@ impl AudioRepository {
         self.db.with_conn(|conn| {
             let mut stmt = conn
                 .prepare(
-                    "SELECT id, content_type, timestamp, text, app_name, source_device, duration_secs, confidence
+                    "SELECT id, content_type, timestamp, text, app_name, source_device, duration_secs, confidence, speaker
                      FROM captures
                      WHERE content_type = 'audio' AND timestamp >= ?1 AND timestamp <= ?2
                      ORDER BY timestamp DESC
@@ -287,6 +297,11 @@ impl DictationRepository {
 
     /// Store a dictation entry in the captures table.
     pub fn save(&self, entry: &DictationEntry) -> Result<(), EngramError> {
+        self.db.with_conn(|conn| Self::insert(conn, entry))
+    }
+
+    /// Like [`Self::save`], using `conn`.
+    pub fn insert(conn: &rusqlite::Connection, entry: &DictationEntry) -> Result<(), EngramError> {
         let mode_str = match entry.mode {
             DictationMode::Type => "type",
             DictationMode::StoreOnly => "store_only",
@@ -294,24 +309,22 @@ impl DictationRepository {
             DictationMode::Clipboard => "clipboard",
         };
 
-        self.db.with_conn(|conn| {
-            conn.execute(
-                "INSERT INTO captures (id, content_type, timestamp, text, target_app, target_window, duration_secs, mode)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-                rusqlite::params![
-                    entry.id.to_string(),
-                    "dictation",
-                    entry.timestamp.timestamp(),
-                    entry.text,
-                    entry.target_app,
-                    entry.target_window,
-                    entry.duration_secs as f64,
-                    mode_str,
-                ],
-            )
-            .map_err(|e| EngramError::Storage(format!("Failed to save dictation: {}", e)))?;
-            Ok(())
-        })
+        conn.execute(
+            "INSERT INTO captures (id, content_type, timestamp, text, target_app, target_window, duration_secs, mode)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                entry.id.to_string(),
+                "dictation",
+                entry.timestamp.timestamp(),
+                entry.text,
+                entry.target_app,
+                entry.target_window,
+                entry.duration_secs as f64,
+                mode_str,
+            ],
+        )
+        .map_err(|e| EngramError::Storage(format!("Failed to save dictation: {}", e)))?;
+        Ok(())
     }
 
     /// Find a dictation entry by ID.
@@ -397,6 +410,133 @@ impl DictationRepository {
     }
 }
 
+/// Repository for document passages.
+pub struct DocumentRepository {
+    db: Arc<Database>,
+}
+
+impl DocumentRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Store a document passage in the captures table.
+    pub fn save(&self, entry: &DocumentEntry) -> Result<(), EngramError> {
+        self.db.with_conn(|conn| Self::insert(conn, entry))
+    }
+
+    /// Like [`Self::save`], using `conn`.
+    pub fn insert(conn: &rusqlite::Connection, entry: &DocumentEntry) -> Result<(), EngramError> {
+        conn.execute(
+            "INSERT INTO captures (id, content_type, timestamp, text, app_name, window_title, source_path, passage_index)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                entry.id.to_string(),
+                "document",
+                entry.timestamp.timestamp(),
+                entry.text,
+                entry.app_name,
+                entry.title,
+                entry.source_path,
+                entry.passage_index,
+            ],
+        )
+        .map_err(|e| EngramError::Storage(format!("Failed to save document: {}", e)))?;
+        Ok(())
+    }
+
+    /// Find a document passage by ID.
+    pub fn find_by_id(&self, id: Uuid) -> Result<Option<DocumentEntry>, EngramError> {
+        self.db.with_conn(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT id, content_type, timestamp, text, app_name, window_title, source_path, passage_index
+                     FROM captures WHERE id = ?1 AND content_type = 'document'",
+                )
+                .map_err(|e| EngramError::Storage(e.to_string()))?;
+
+            let result = stmt
+                .query_row(rusqlite::params![id.to_string()], |row| {
+                    Ok(row_to_document_entry(row))
+                })
+                .optional()
+                .map_err(|e| EngramError::Storage(e.to_string()))?;
+
+            match result {
+                Some(entry) => Ok(Some(entry?)),
+                None => Ok(None),
+            }
+        })
+    }
+
+    /// All passages of one document, in passage order.
+    pub fn find_by_source(&self, source_path: &str) -> Result<Vec<DocumentEntry>, EngramError> {
+        self.db.with_conn(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT id, content_type, timestamp, text, app_name, window_title, source_path, passage_index
+                     FROM captures
+                     WHERE content_type = 'document' AND source_path = ?1
+                     ORDER BY passage_index ASC",
+                )
+                .map_err(|e| EngramError::Storage(e.to_string()))?;
+
+            let rows = stmt
+                .query_map(rusqlite::params![source_path], |row| {
+                    Ok(row_to_document_entry(row))
+                })
+                .map_err(|e| EngramError::Storage(e.to_string()))?;
+
+            let mut entries = Vec::new();
+            for row in rows {
+                let entry = row.map_err(|e| EngramError::Storage(e.to_string()))??;
+                entries.push(entry);
+            }
+            Ok(entries)
+        })
+    }
+
+    /// Delete all passages of one document, returning the deleted IDs.
+    pub fn delete_by_source(&self, source_path: &str) -> Result<Vec<Uuid>, EngramError> {
+        self.db.with_conn(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "DELETE FROM captures
+                     WHERE content_type = 'document' AND source_path = ?1
+                     RETURNING id",
+                )
+                .map_err(|e| EngramError::Storage(e.to_string()))?;
+
+            let rows = stmt
+                .query_map(rusqlite::params![source_path], |row| {
+                    row.get::<_, String>(0)
+                })
+                .map_err(|e| EngramError::Storage(format!("Failed to delete document: {}", e)))?;
+
+            let mut ids = Vec::new();
+            for row in rows {
+                let id = row.map_err(|e| EngramError::Storage(e.to_string()))?;
+                ids.push(Uuid::parse_str(&id).map_err(|e| EngramError::Storage(e.to_string()))?);
+            }
+            Ok(ids)
+        })
+    }
+
+    /// Count total document passages.
+    pub fn count(&self) -> Result<u64, EngramError> {
+        self.db.with_conn(|conn| {
+            let count: i64 = conn
+                .query_row(
+                    "SELECT COUNT(*) FROM captures WHERE content_type = 'document'",
+                    [],
+                    |row| row.get(0),
+                )
+                .map_err(|e| EngramError::Storage(e.to_string()))?;
+            Ok(count as u64)
+        })
+    }
+}
+
 /// Metadata record for a stored vector embedding.
 #[derive(Debug, Clone)]
 pub struct VectorMetadata {
@@ -557,6 +697,9 @@ fn row_to_audio_chunk(row: &rusqlite::Row<'_>) -> Result<AudioChunk, EngramError
     let confidence: Option<f64> = row
         .get(7)
         .map_err(|e| EngramError::Storage(e.to_string()))?;
+    let speaker: Option<String> = row
+        .get(8)
+        .map_err(|e| EngramError::Storage(e.to_string()))?;
 
     Ok(AudioChunk {
         id: Uuid::parse_str(&id_str)
@@ -568,7 +711,7 @@ fn row_to_audio_chunk(row: &rusqlite::Row<'_>) -> Result<AudioChunk, EngramError
             .unwrap_or_default(),
         transcription: text,
         duration_secs: duration_secs.unwrap_or(0.0) as f32,
-        speaker: String::new(),
+        speaker: speaker.unwrap_or_default(),
         source_device: source_device.unwrap_or_default(),
         app_in_focus,
         confidence: confidence.unwrap_or(0.0) as f32,
@@ -622,6 +765,45 @@ fn row_to_dictation_entry(row: &rusqlite::Row<'_>) -> Result<DictationEntry, Eng
     })
 }
 
+fn row_to_document_entry(row: &rusqlite::Row<'_>) -> Result<DocumentEntry, EngramError> {
+    let id_str: String = row
+        .get(0)
+        .map_err(|e| EngramError::Storage(e.to_string()))?;
+    let timestamp_i64: i64 = row
+        .get(2)
+        .map_err(|e| EngramError::Storage(e.to_string()))?;
+    let text: String = row
+        .get(3)
+        .map_err(|e| EngramError::Storage(e.to_string()))?;
+    let app_name: String = row
+        .get(4)
+        .map_err(|e| EngramError::Storage(e.to_string()))?;
+    let title: String = row
+        .get(5)
+        .map_err(|e| EngramError::Storage(e.to_string()))?;
+    let source_path: Option<String> = row
+        .get(6)
+        .map_err(|e| EngramError::Storage(e.to_string()))?;
+    let passage_index: Option<u32> = row
+        .get(7)
+        .map_err(|e| EngramError::Storage(e.to_string()))?;
+
+    Ok(DocumentEntry {
+        id: Uuid::parse_str(&id_str)
+            .map_err(|e| EngramError::Storage(format!("Invalid UUID: {}", e)))?,
+        content_type: ContentType::Document,
+        timestamp: Utc
+            .timestamp_opt(timestamp_i64, 0)
+            .single()
+            .unwrap_or_default(),
+        title,
+        source_path: source_path.unwrap_or_default(),
+        passage_index: passage_index.unwrap_or(0),
+        app_name,
+        text,
+    })
+}
+
 fn row_to_vector_metadata(row: &rusqlite::Row<'_>) -> Result<VectorMetadata, EngramError> {
     let id_str: String = row
         .get(0)
@@ -860,6 +1042,63 @@ mod tests {
         assert_eq!(repo.count().unwrap(), 0);
     }
 
+    #[test]
+    fn test_audio_speaker_round_trips() {
+        let db = make_db();
+        let repo = AudioRepository::new(db);
+
+        let chunk = make_chunk();
+        repo.save(&chunk).unwrap();
+
+        let found = repo.find_by_id(chunk.id).unwrap().unwrap();
+        assert_eq!(found.speaker, "Speaker 1");
+    }
+
+    // ========================================================================
+    // DocumentRepository tests
+    // ========================================================================
+
+    #[test]
+    fn test_document_save_and_find_by_source() {
+        let db = make_db();
+        let repo = DocumentRepository::new(db);
+
+        for (i, text) in ["Step one", "Step two"].iter().enumerate().rev() {
+            repo.save(&DocumentEntry {
+                id: Uuid::new_v4(),
+                content_type: ContentType::Document,
+                timestamp: Utc::now(),
+                title: "Runbook".to_string(),
+                source_path: "/notes/runbook.md".to_string(),
+                passage_index: i as u32,
+                app_name: "Obsidian".to_string(),
+                text: text.to_string(),
+            })
+            .unwrap();
+        }
+
+        let passages = repo.find_by_source("/notes/runbook.md").unwrap();
+        assert_eq!(passages.len(), 2);
+        assert_eq!(passages[0].text, "Step one");
+        assert_eq!(passages[1].passage_index, 1);
+        assert_eq!(passages[0].title, "Runbook");
+
+        let found = repo.find_by_id(passages[1].id).unwrap().unwrap();
+        assert_eq!(found.content_type, ContentType::Document);
+        assert_eq!(repo.count().unwrap(), 2);
+
+        let mut deleted = repo.delete_by_source("/notes/runbook.md").unwrap();
+        deleted.sort();
+        let mut expected: Vec<Uuid> = passages.iter().map(|p| p.id).collect();
+        expected.sort();
+        assert_eq!(deleted, expected);
+        assert_eq!(repo.count().unwrap(), 0);
+        assert!(repo
+            .delete_by_source("/notes/runbook.md")
+            .unwrap()
+            .is_empty());
+    }
+
     // ========================================================================
     // DictationRepository tests
     // ========================================================================
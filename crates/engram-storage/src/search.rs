@@ -56,6 +56,35 @@ pub struct FtsResult {
     pub rank: f64,
 }
 
+/// Metadata filters for [`FtsSearch::search_filtered`].
+///
+/// Every set field must match; an empty filter matches every capture.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FtsFilters {
+    /// Content type (screen, audio, dictation, document).
+    pub content_type: Option<String>,
+    /// Case-insensitive substring of the application name, so `zoom`
+    /// matches `Zoom.exe` and `Zoom Meetings`.
+    pub app_name: Option<String>,
+    /// Start time (inclusive).
+    pub start: Option<DateTime<Utc>>,
+    /// End time (inclusive).
+    pub end: Option<DateTime<Utc>>,
+    /// Captures spoken by, or mentioning, any of these people.
+    pub people: Vec<String>,
+    /// Only captures stored after this `captures` rowid.
+    pub stored_after: Option<i64>,
+    /// Only captures stored at or before this `captures` rowid.
+    pub stored_up_to: Option<i64>,
+}
+
+impl FtsFilters {
+    /// Whether no filter is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Full-text search engine backed by FTS5.
 pub struct FtsSearch {
     db: Arc<Database>,
@@ -213,6 +242,135 @@ impl FtsSearch {
         })
     }
 
+    /// Search captures with metadata filters applied in SQL.
+    ///
+    /// Filters run before the limit, so a narrow filter still returns up to
+    /// `limit` results instead of whatever survived from the global top
+    /// matches. An empty `query` lists the filtered captures, newest first.
+    pub fn search_filtered(
+        &self,
+        query: &str,
+        filters: &FtsFilters,
+        limit: u64,
+    ) -> Result<Vec<FtsResult>, EngramError> {
+        let sanitized = sanitize_fts5_query(query);
+        if sanitized.is_empty() && filters.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let has_match = !sanitized.is_empty();
+        let mut conditions: Vec<String> = Vec::new();
+        let mut params: Vec<rusqlite::types::Value> = Vec::new();
+        if has_match {
+            conditions.push("captures_fts MATCH ?".to_string());
+            params.push(sanitized.into());
+        }
+        push_filter_conditions(filters, &mut conditions, &mut params);
+        params.push((limit as i64).into());
+
+        // Without a MATCH there is no BM25 rank; list newest first.
+        let (from, rank, order) = if has_match {
+            (
+                "captures_fts JOIN captures c ON c.rowid = captures_fts.rowid",
+                "rank",
+                "rank",
+            )
+        } else {
+            ("captures c", "0.0", "c.timestamp DESC")
+        };
+        let sql = format!(
+            "SELECT c.id, c.content_type, c.timestamp, c.text, c.app_name, {}
+             FROM {}
+             WHERE {}
+             ORDER BY {}
+             LIMIT ?",
+            rank,
+            from,
+            conditions.join(" AND "),
+            order
+        );
+        self.query_results(&sql, params)
+    }
+
+    /// Load the captures among `ids` that pass `filters`.
+    ///
+    /// Used to apply the same filters to candidates found by other means,
+    /// such as vector search. Results are in no particular order and have a
+    /// rank of zero.
+    pub fn find_filtered(
+        &self,
+        ids: &[Uuid],
+        filters: &FtsFilters,
+    ) -> Result<Vec<FtsResult>, EngramError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conditions = vec![format!("c.id IN ({})", vec!["?"; ids.len()].join(", "))];
+        let mut params: Vec<rusqlite::types::Value> =
+            ids.iter().map(|id| id.to_string().into()).collect();
+        push_filter_conditions(filters, &mut conditions, &mut params);
+
+        let sql = format!(
+            "SELECT c.id, c.content_type, c.timestamp, c.text, c.app_name, 0.0
+             FROM captures c
+             WHERE {}",
+            conditions.join(" AND ")
+        );
+        self.query_results(&sql, params)
+    }
+
+    /// Run a capture query selecting id, content type, timestamp, text, app
+    /// name and FTS5 rank, in that order.
+    fn query_results(
+        &self,
+        sql: &str,
+        params: Vec<rusqlite::types::Value>,
+    ) -> Result<Vec<FtsResult>, EngramError> {
+        self.db.with_conn(|conn| {
+            let mut stmt = conn
+                .prepare(sql)
+                .map_err(|e| EngramError::Storage(format!("FTS5 query prepare failed: {}", e)))?;
+
+            let rows = stmt
+                .query_map(rusqlite::params_from_iter(params), |row| {
+                    let id_str: String = row.get(0)?;
+                    let content_type: String = row.get(1)?;
+                    let timestamp_i64: i64 = row.get(2)?;
+                    let text: String = row.get(3)?;
+                    let app_name: String = row.get(4)?;
+                    let rank: f64 = row.get(5)?;
+                    Ok((id_str, content_type, timestamp_i64, text, app_name, rank))
+                })
+                .map_err(|e| EngramError::Storage(format!("FTS5 query failed: {}", e)))?;
+
+            let mut results = Vec::new();
+            for row in rows {
+                let (id_str, content_type, timestamp_i64, text, app_name, rank) =
+                    row.map_err(|e| EngramError::Storage(e.to_string()))?;
+
+                let id = Uuid::parse_str(&id_str)
+                    .map_err(|e| EngramError::Storage(format!("Invalid UUID: {}", e)))?;
+
+                let timestamp = Utc
+                    .timestamp_opt(timestamp_i64, 0)
+                    .single()
+                    .unwrap_or_default();
+
+                results.push(FtsResult {
+                    id,
+                    content_type,
+                    timestamp,
+                    text,
+                    app_name,
+                    rank: -rank,
+                });
+            }
+
+            Ok(results)
+        })
+    }
+
     /// Count total matches for a query.
     pub fn count_matches(&self, query: &str) -> Result<u64, EngramError> {
         if query.trim().is_empty() {
@@ -237,6 +395,56 @@ impl FtsSearch {
     }
 }
 
+/// Append SQL conditions (over the `captures c` alias) for `filters`.
+fn push_filter_conditions(
+    filters: &FtsFilters,
+    conditions: &mut Vec<String>,
+    params: &mut Vec<rusqlite::types::Value>,
+) {
+    if let Some(ref ct) = filters.content_type {
+        conditions.push("c.content_type = ?".to_string());
+        params.push(ct.clone().into());
+    }
+    if let Some(ref app) = filters.app_name {
+        conditions.push("c.app_name LIKE ? ESCAPE '\\'".to_string());
+        params.push(format!("%{}%", escape_like(app)).into());
+    }
+    if let Some(start) = filters.start {
+        conditions.push("c.timestamp >= ?".to_string());
+        params.push(start.timestamp().into());
+    }
+    if let Some(end) = filters.end {
+        conditions.push("c.timestamp <= ?".to_string());
+        params.push(end.timestamp().into());
+    }
+    if let Some(after) = filters.stored_after {
+        conditions.push("c.rowid > ?".to_string());
+        params.push(after.into());
+    }
+    if let Some(up_to) = filters.stored_up_to {
+        conditions.push("c.rowid <= ?".to_string());
+        params.push(up_to.into());
+    }
+    if !filters.people.is_empty() {
+        let mut alternatives = Vec::new();
+        for person in &filters.people {
+            alternatives
+                .push("c.speaker LIKE ? ESCAPE '\\' OR c.text LIKE ? ESCAPE '\\'".to_string());
+            params.push(escape_like(person).into());
+            params.push(format!("%{}%", escape_like(person)).into());
+        }
+        conditions.push(format!("({})", alternatives.join(" OR ")));
+    }
+}
+
+/// Escape `%`, `_` and the escape character for a `LIKE ... ESCAPE '\'` pattern.
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -492,4 +700,186 @@ mod tests {
         );
         assert_eq!(sanitize_fts5_query("test*"), "\"test*\"");
     }
+
+    /// Insert a capture at `timestamp` with an optional speaker.
+    fn insert_at(
+        db: &Database,
+        ct: &str,
+        text: &str,
+        app: &str,
+        timestamp: i64,
+        speaker: Option<&str>,
+    ) -> Uuid {
+        let id = Uuid::new_v4();
+        db.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO captures (id, content_type, timestamp, text, app_name, speaker)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                rusqlite::params![id.to_string(), ct, timestamp, text, app, speaker],
+            )
+            .map_err(|e| EngramError::Storage(e.to_string()))?;
+            Ok(())
+        })
+        .unwrap();
+        id
+    }
+
+    #[test]
+    fn test_fts_search_filtered_applies_each_filter() {
+        let db = make_db();
+        let zoom = insert_at(
+            &db,
+            "audio",
+            "budget review for the launch",
+            "Zoom.exe",
+            1_000,
+            Some("Alice"),
+        );
+        let slack = insert_at(
+            &db,
+            "screen",
+            "budget thread, ask bob_smith",
+            "Slack",
+            2_000,
+            None,
+        );
+        let old = insert_at(&db, "screen", "budget draft", "Slack", 100, None);
+        let search = FtsSearch::new(db);
+        let ids = |filters: FtsFilters| -> Vec<Uuid> {
+            let mut ids: Vec<Uuid> = search
+                .search_filtered("budget", &filters, 10)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+        let sorted = |mut v: Vec<Uuid>| {
+            v.sort();
+            v
+        };
+
+        assert_eq!(ids(FtsFilters::default()), sorted(vec![zoom, slack, old]));
+        assert_eq!(
+            ids(FtsFilters {
+                app_name: Some("zoom".to_string()),
+                ..Default::default()
+            }),
+            vec![zoom]
+        );
+        assert_eq!(
+            ids(FtsFilters {
+                content_type: Some("screen".to_string()),
+                start: Utc.timestamp_opt(500, 0).single(),
+                ..Default::default()
+            }),
+            vec![slack]
+        );
+        assert_eq!(
+            ids(FtsFilters {
+                end: Utc.timestamp_opt(1_500, 0).single(),
+                ..Default::default()
+            }),
+            sorted(vec![zoom, old])
+        );
+        // By speaker, and by mention in the text ("_" is not a wildcard).
+        assert_eq!(
+            ids(FtsFilters {
+                people: vec!["alice".to_string()],
+                ..Default::default()
+            }),
+            vec![zoom]
+        );
+        assert_eq!(
+            ids(FtsFilters {
+                people: vec!["Bob_Smith".to_string(), "Carol".to_string()],
+                ..Default::default()
+            }),
+            vec![slack]
+        );
+        assert!(ids(FtsFilters {
+            people: vec!["Bob%".to_string()],
+            ..Default::default()
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn test_fts_search_filtered_by_stored_range() {
+        let db = make_db();
+        insert_capture(&db, "screen", "budget one", "Slack");
+        let second = insert_capture(&db, "screen", "budget two", "Slack");
+        let third = insert_capture(&db, "screen", "budget three", "Slack");
+        insert_capture(&db, "screen", "budget four", "Slack");
+        let search = FtsSearch::new(db);
+
+        // Rowids follow insert order: 1 through 4.
+        let filters = FtsFilters {
+            stored_after: Some(1),
+            stored_up_to: Some(3),
+            ..Default::default()
+        };
+        let mut ids: Vec<Uuid> = search
+            .search_filtered("budget", &filters, 10)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        ids.sort();
+        let mut expected = vec![second, third];
+        expected.sort();
+        assert_eq!(ids, expected);
+    }
+
+    #[test]
+    fn test_find_filtered_keeps_matching_ids() {
+        let db = make_db();
+        let zoom = insert_at(&db, "audio", "cloud costs", "Zoom", 1_000, None);
+        let slack = insert_at(&db, "screen", "cloud costs", "Slack", 2_000, None);
+        insert_at(&db, "screen", "not a candidate", "Zoom", 3_000, None);
+        let search = FtsSearch::new(db);
+
+        let filters = FtsFilters {
+            app_name: Some("zoom".to_string()),
+            ..Default::default()
+        };
+        let results = search
+            .find_filtered(&[zoom, slack, Uuid::new_v4()], &filters)
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, zoom);
+        assert_eq!(results[0].text, "cloud costs");
+
+        let all = search
+            .find_filtered(&[zoom, slack], &FtsFilters::default())
+            .unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(search
+            .find_filtered(&[], &FtsFilters::default())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_fts_search_filtered_without_query_lists_newest_first() {
+        let db = make_db();
+        let first = insert_at(&db, "screen", "standup notes", "Slack", 1_000, None);
+        let second = insert_at(&db, "screen", "lunch order", "Slack", 2_000, None);
+        insert_at(&db, "screen", "compiler output", "Terminal", 3_000, None);
+        let search = FtsSearch::new(db);
+
+        let filters = FtsFilters {
+            app_name: Some("slack".to_string()),
+            ..Default::default()
+        };
+        let results = search.search_filtered("", &filters, 10).unwrap();
+        let ids: Vec<Uuid> = results.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![second, first]);
+
+        assert!(search
+            .search_filtered("  ", &FtsFilters::default(), 10)
+            .unwrap()
+            .is_empty());
+    }
 }
@@ -100,11 +100,19 @@ mod tests {
     #[test]
     fn tray_panel_has_action_buttons() {
         assert!(TRAY_PANEL_HTML.contains("tray-dictate-btn"));
+        assert!(TRAY_PANEL_HTML.contains("tray-ask-btn"));
         assert!(TRAY_PANEL_HTML.contains("tray-pause-btn"));
         assert!(TRAY_PANEL_HTML.contains("tray-settings-btn"));
         assert!(TRAY_PANEL_HTML.contains("tray-fullui-btn"));
     }
 
+    #[test]
+    fn tray_panel_shows_voice_answers() {
+        assert!(TRAY_PANEL_HTML.contains("tray-voice-answer"));
+        assert!(TRAY_PANEL_HTML.contains("/chat/voice/start"));
+        assert!(TRAY_PANEL_HTML.contains("/chat/voice/stop"));
+    }
+
     #[test]
     fn tray_panel_uses_compact_dimensions() {
         assert!(TRAY_PANEL_HTML.contains("400px"));
@@ -327,6 +327,22 @@ impl VectorIndex {
         Ok(hits)
     }
 
+    /// Fetch the stored (L2-normalized) embedding for an entry, if present.
+    ///
+    /// Lets callers run "more like this" queries without re-embedding text.
+    pub fn get_embedding(&self, id: Uuid) -> Result<Option<Vec<f32>>, EngramError> {
+        let db = self
+            .db
+            .read()
+            .map_err(|e| EngramError::Storage(format!("VectorDB lock poisoned: {}", e)))?;
+
+        let entry = db
+            .get(&id.to_string())
+            .map_err(|e| EngramError::Storage(format!("HNSW get failed: {}", e)))?;
+
+        Ok(entry.map(|e| e.vector))
+    }
+
     /// Delete an entry from the index by ID.
     pub fn delete(&self, id: Uuid) -> Result<(), EngramError> {
         let id_str = id.to_string();
@@ -588,4 +604,20 @@ mod tests {
         let bad = index.insert(Uuid::new_v4(), vec![1.0f32; 128], serde_json::json!({}));
         assert!(bad.is_err());
     }
+
+    #[test]
+    fn test_get_embedding() {
+        let index = VectorIndex::with_dimensions(4);
+        let id = Uuid::new_v4();
+        index
+            .insert(id, vec![3.0, 0.0, 4.0, 0.0], serde_json::json!({}))
+            .unwrap();
+
+        // Stored vectors are normalized on insert.
+        let stored = index.get_embedding(id).unwrap().unwrap();
+        assert!((stored[0] - 0.6).abs() < 1e-6);
+        assert!((stored[2] - 0.8).abs() < 1e-6);
+
+        assert!(index.get_embedding(Uuid::new_v4()).unwrap().is_none());
+    }
 }
@@ -11,5 +11,5 @@ pub mod search;
 
 pub use embedding::{DynEmbeddingService, EmbeddingService, MockEmbedding, OnnxEmbeddingService};
 pub use index::{SearchHit, VectorIndex};
-pub use pipeline::{EngramPipeline, IngestResult};
+pub use pipeline::{CommitHook, EngramPipeline, IngestResult};
 pub use search::{SearchEngine, SearchFilters, SearchResult};
@@ -1,9 +1,9 @@
 //! Engram ingestion pipeline.
 //!
 //! The EngramPipeline processes incoming data (screen frames, audio chunks,
-//! dictation entries) through deduplication, embedding, and storage stages.
+//! dictation entries, document passages) through deduplication, embedding, and storage stages.
 
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
@@ -12,11 +12,11 @@ use uuid::Uuid;
 use engram_core::config::SafetyConfig;
 use engram_core::error::EngramError;
 use engram_core::safety::{SafetyDecision, SafetyGate};
-use engram_core::types::{AudioChunk, DictationEntry, ScreenFrame};
+use engram_core::types::{AudioChunk, DictationEntry, DocumentEntry, ScreenFrame};
 
 use engram_storage::{
-    AudioRepository, CaptureRepository, Database, DictationRepository, VectorMetadata,
-    VectorMetadataRepository,
+    AudioRepository, CaptureRepository, Database, DictationRepository, DocumentRepository,
+    VectorMetadata, VectorMetadataRepository,
 };
 
 use crate::embedding::{DynEmbeddingService, EmbeddingService};
@@ -37,6 +37,11 @@ pub enum IngestResult {
     Denied { reason: String },
 }
 
+/// Extra writes committed in the same SQLite transaction as an ingested
+/// entry, such as its domain event. An error rolls the entry back.
+pub type CommitHook<'a> =
+    &'a mut (dyn FnMut(&rusqlite::Connection) -> Result<(), EngramError> + Send);
+
 /// The main Engram ingestion pipeline.
 ///
 /// Processes incoming data through:
@@ -51,7 +56,7 @@ pub enum IngestResult {
 pub struct EngramPipeline {
     index: Arc<VectorIndex>,
     embedder: Box<dyn DynEmbeddingService>,
-    safety_gate: SafetyGate,
+    safety_gate: RwLock<SafetyGate>,
     dedup_threshold: f64,
     database: Option<Arc<Database>>,
 }
@@ -70,7 +75,7 @@ impl EngramPipeline {
         Self {
             index,
             embedder: Box::new(embedder),
-            safety_gate: SafetyGate::new(safety_config),
+            safety_gate: RwLock::new(SafetyGate::new(safety_config)),
             dedup_threshold,
             database: None,
         }
@@ -86,7 +91,7 @@ impl EngramPipeline {
         Self {
             index,
             embedder,
-            safety_gate: SafetyGate::new(safety_config),
+            safety_gate: RwLock::new(SafetyGate::new(safety_config)),
             dedup_threshold,
             database: None,
         }
@@ -110,7 +115,18 @@ impl EngramPipeline {
     }
 
     /// Ingest a screen frame through the pipeline.
-    pub async fn ingest_screen(&self, mut frame: ScreenFrame) -> Result<IngestResult, EngramError> {
+    pub async fn ingest_screen(&self, frame: ScreenFrame) -> Result<IngestResult, EngramError> {
+        self.ingest_screen_with(frame, &mut |_| Ok(())).await
+    }
+
+    /// Ingest a screen frame, running `on_commit` in the transaction that
+    /// stores it. `on_commit` does not run when nothing is stored or no
+    /// database is attached.
+    pub async fn ingest_screen_with(
+        &self,
+        mut frame: ScreenFrame,
+        on_commit: CommitHook<'_>,
+    ) -> Result<IngestResult, EngramError> {
         if frame.text.trim().is_empty() {
             debug!(frame_id = %frame.id, "Skipping frame with empty text");
             return Ok(IngestResult::Skipped {
@@ -136,7 +152,10 @@ impl EngramPipeline {
                 IngestResult::Stored { .. } | IngestResult::Redacted { .. }
             ) {
                 frame.text = safe_text;
-                CaptureRepository::new(Arc::clone(db)).save(&frame)?;
+                self.commit_entry(db, frame.id, |conn| {
+                    CaptureRepository::insert(conn, &frame)?;
+                    on_commit(conn)
+                })?;
             }
         }
 
@@ -144,7 +163,18 @@ impl EngramPipeline {
     }
 
     /// Ingest an audio chunk through the pipeline.
-    pub async fn ingest_audio(&self, mut chunk: AudioChunk) -> Result<IngestResult, EngramError> {
+    pub async fn ingest_audio(&self, chunk: AudioChunk) -> Result<IngestResult, EngramError> {
+        self.ingest_audio_with(chunk, &mut |_| Ok(())).await
+    }
+
+    /// Ingest an audio chunk, running `on_commit` in the transaction that
+    /// stores it. `on_commit` does not run when nothing is stored or no
+    /// database is attached.
+    pub async fn ingest_audio_with(
+        &self,
+        mut chunk: AudioChunk,
+        on_commit: CommitHook<'_>,
+    ) -> Result<IngestResult, EngramError> {
         if chunk.transcription.trim().is_empty() {
             debug!(chunk_id = %chunk.id, "Skipping audio chunk with empty transcription");
             return Ok(IngestResult::Skipped {
@@ -156,6 +186,7 @@ impl EngramPipeline {
             "content_type": "audio",
             "source_device": &chunk.source_device,
             "app_in_focus": &chunk.app_in_focus,
+            "speaker": &chunk.speaker,
             "timestamp": chunk.timestamp.to_rfc3339(),
             "duration_secs": chunk.duration_secs,
             "confidence": chunk.confidence,
@@ -171,7 +202,10 @@ impl EngramPipeline {
                 IngestResult::Stored { .. } | IngestResult::Redacted { .. }
             ) {
                 chunk.transcription = safe_text;
-                AudioRepository::new(Arc::clone(db)).save(&chunk)?;
+                self.commit_entry(db, chunk.id, |conn| {
+                    AudioRepository::insert(conn, &chunk)?;
+                    on_commit(conn)
+                })?;
             }
         }
 
@@ -180,8 +214,19 @@ impl EngramPipeline {
 
     /// Ingest a dictation entry through the pipeline.
     pub async fn ingest_dictation(
+        &self,
+        entry: DictationEntry,
+    ) -> Result<IngestResult, EngramError> {
+        self.ingest_dictation_with(entry, &mut |_| Ok(())).await
+    }
+
+    /// Ingest a dictation entry, running `on_commit` in the transaction that
+    /// stores it. `on_commit` does not run when nothing is stored or no
+    /// database is attached.
+    pub async fn ingest_dictation_with(
         &self,
         mut entry: DictationEntry,
+        on_commit: CommitHook<'_>,
     ) -> Result<IngestResult, EngramError> {
         if entry.text.trim().is_empty() {
             debug!(entry_id = %entry.id, "Skipping dictation with empty text");
@@ -207,13 +252,100 @@ impl EngramPipeline {
                 IngestResult::Stored { .. } | IngestResult::Redacted { .. }
             ) {
                 entry.text = safe_text;
-                DictationRepository::new(Arc::clone(db)).save(&entry)?;
+                self.commit_entry(db, entry.id, |conn| {
+                    DictationRepository::insert(conn, &entry)?;
+                    on_commit(conn)
+                })?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Ingest a document passage through the pipeline.
+    pub async fn ingest_document(&self, entry: DocumentEntry) -> Result<IngestResult, EngramError> {
+        self.ingest_document_with(entry, &mut |_| Ok(())).await
+    }
+
+    /// Ingest a document passage, running `on_commit` in the transaction that
+    /// stores it. `on_commit` does not run when nothing is stored or no
+    /// database is attached.
+    pub async fn ingest_document_with(
+        &self,
+        mut entry: DocumentEntry,
+        on_commit: CommitHook<'_>,
+    ) -> Result<IngestResult, EngramError> {
+        if entry.text.trim().is_empty() {
+            debug!(entry_id = %entry.id, "Skipping document passage with empty text");
+            return Ok(IngestResult::Skipped {
+                reason: "Empty document text".to_string(),
+            });
+        }
+
+        let metadata = serde_json::json!({
+            "content_type": "document",
+            "app_name": &entry.app_name,
+            "title": &entry.title,
+            "source_path": &entry.source_path,
+            "passage_index": entry.passage_index,
+            "timestamp": entry.timestamp.to_rfc3339(),
+        });
+
+        let (result, safe_text) = self.ingest_text(entry.id, &entry.text, metadata).await?;
+
+        if let Some(db) = &self.database {
+            if matches!(
+                result,
+                IngestResult::Stored { .. } | IngestResult::Redacted { .. }
+            ) {
+                entry.text = safe_text;
+                self.commit_entry(db, entry.id, |conn| {
+                    DocumentRepository::insert(conn, &entry)?;
+                    on_commit(conn)
+                })?;
             }
         }
 
         Ok(result)
     }
 
+    /// Run an entry's SQLite writes in one transaction, removing the entry
+    /// from the vector index again if they fail.
+    fn commit_entry(
+        &self,
+        db: &Database,
+        id: Uuid,
+        write: impl FnOnce(&rusqlite::Connection) -> Result<(), EngramError>,
+    ) -> Result<(), EngramError> {
+        let committed = db.with_transaction(write);
+        if committed.is_err() {
+            if let Err(e) = self.index.delete(id) {
+                debug!(id = %id, error = %e, "Failed to remove uncommitted entry from index");
+            }
+        }
+        committed
+    }
+
+    /// Remove every passage of a document from SQLite and the vector index.
+    ///
+    /// Returns the number of passages removed. Without a database the
+    /// passages cannot be looked up, so nothing is removed.
+    pub fn remove_document(&self, source_path: &str) -> Result<usize, EngramError> {
+        let Some(db) = &self.database else {
+            return Ok(0);
+        };
+        let ids = DocumentRepository::new(Arc::clone(db)).delete_by_source(source_path)?;
+        for id in &ids {
+            self.index.delete(*id)?;
+        }
+        debug!(
+            source_path,
+            removed = ids.len(),
+            "Removed document passages"
+        );
+        Ok(ids.len())
+    }
+
     /// Core ingestion logic: safety check, embed, dedup, and store.
     ///
     /// Returns the result and the safety-checked text (used by callers
@@ -225,7 +357,12 @@ impl EngramPipeline {
         metadata: serde_json::Value,
     ) -> Result<(IngestResult, String), EngramError> {
         // Step 1: Safety gate — redact PII or deny.
-        let (safe_text, redaction_count) = match self.safety_gate.check(text) {
+        let decision = self
+            .safety_gate
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .check(text);
+        let (safe_text, redaction_count) = match decision {
             SafetyDecision::Allow => (text.to_string(), 0),
             SafetyDecision::Redacted {
                 text: redacted,
@@ -305,6 +442,23 @@ impl EngramPipeline {
         Ok((result, safe_text))
     }
 
+    /// The text that would be stored for `text` after the safety gate.
+    ///
+    /// Returns `None` when the gate would deny it. Lets callers compare
+    /// incoming text against what is already stored.
+    pub fn safe_text(&self, text: &str) -> Option<String> {
+        match self
+            .safety_gate
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .check(text)
+        {
+            SafetyDecision::Allow => Some(text.to_string()),
+            SafetyDecision::Redacted { text, .. } => Some(text),
+            SafetyDecision::Deny { .. } => None,
+        }
+    }
+
     /// Get a reference to the underlying vector index.
     pub fn index(&self) -> &VectorIndex {
         &self.index
@@ -314,6 +468,13 @@ impl EngramPipeline {
     pub fn dedup_threshold(&self) -> f64 {
         self.dedup_threshold
     }
+
+    /// Replace the safety gate configuration.
+    ///
+    /// Applies to every entry ingested after the call; used for config hot-reload.
+    pub fn set_safety_config(&self, config: SafetyConfig) {
+        *self.safety_gate.write().unwrap_or_else(|e| e.into_inner()) = SafetyGate::new(config);
+    }
 }
 
 #[cfg(test)]
@@ -377,6 +538,19 @@ mod tests {
         }
     }
 
+    fn make_document_entry(text: &str) -> DocumentEntry {
+        DocumentEntry {
+            id: Uuid::new_v4(),
+            content_type: ContentType::Document,
+            timestamp: Utc::now(),
+            title: "Runbook".to_string(),
+            source_path: "/notes/runbook.md".to_string(),
+            passage_index: 0,
+            app_name: "Obsidian".to_string(),
+            text: text.to_string(),
+        }
+    }
+
     #[tokio::test]
     async fn test_ingest_screen_stores() {
         let pipeline = make_pipeline();
@@ -449,6 +623,24 @@ mod tests {
         assert!(matches!(result, IngestResult::Skipped { .. }));
     }
 
+    #[tokio::test]
+    async fn test_ingest_document_stores() {
+        let pipeline = make_pipeline();
+        let entry = make_document_entry("Restart the worker before rotating keys");
+
+        let result = pipeline.ingest_document(entry).await.unwrap();
+        assert!(matches!(result, IngestResult::Stored { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_ingest_document_skips_empty() {
+        let pipeline = make_pipeline();
+        let entry = make_document_entry("\n\n");
+
+        let result = pipeline.ingest_document(entry).await.unwrap();
+        assert!(matches!(result, IngestResult::Skipped { .. }));
+    }
+
     #[tokio::test]
     async fn test_different_texts_not_deduplicated() {
         let pipeline = make_pipeline();
@@ -575,6 +767,19 @@ mod tests {
         assert!(matches!(result, IngestResult::Stored { .. }));
     }
 
+    #[tokio::test]
+    async fn test_set_safety_config_applies_to_next_ingest() {
+        let pipeline = make_pipeline();
+        pipeline.set_safety_config(SafetyConfig {
+            custom_deny_patterns: vec!["TOP SECRET".to_string()],
+            ..SafetyConfig::default()
+        });
+        let frame = make_screen_frame("TOP SECRET launch plans");
+
+        let result = pipeline.ingest_screen(frame).await.unwrap();
+        assert!(matches!(result, IngestResult::Denied { .. }));
+    }
+
     // -- Dual-write (vector + SQLite) tests --
 
     fn make_pipeline_with_db() -> (EngramPipeline, Arc<Database>) {
@@ -637,6 +842,33 @@ mod tests {
         assert_eq!(found.unwrap().text, "Take a note about the project");
     }
 
+    #[tokio::test]
+    async fn test_dual_write_document_persists_to_sqlite() {
+        let (pipeline, db) = make_pipeline_with_db();
+        let entry = make_document_entry("Runbook: rotate the signing keys quarterly");
+        let id = entry.id;
+
+        let result = pipeline.ingest_document(entry).await.unwrap();
+        assert!(matches!(result, IngestResult::Stored { .. }));
+
+        let found = DocumentRepository::new(db).find_by_id(id).unwrap().unwrap();
+        assert_eq!(found.source_path, "/notes/runbook.md");
+        assert_eq!(found.text, "Runbook: rotate the signing keys quarterly");
+    }
+
+    #[tokio::test]
+    async fn test_remove_document_clears_sqlite_and_index() {
+        let (pipeline, db) = make_pipeline_with_db();
+        let entry = make_document_entry("Runbook: rotate the signing keys quarterly");
+        pipeline.ingest_document(entry).await.unwrap();
+        assert_eq!(pipeline.index().len(), 1);
+
+        assert_eq!(pipeline.remove_document("/notes/runbook.md").unwrap(), 1);
+        assert_eq!(pipeline.index().len(), 0);
+        assert_eq!(DocumentRepository::new(db).count().unwrap(), 0);
+        assert_eq!(pipeline.remove_document("/notes/runbook.md").unwrap(), 0);
+    }
+
     #[tokio::test]
     async fn test_dual_write_redacted_text_in_sqlite() {
         let (pipeline, db) = make_pipeline_with_db();
@@ -653,6 +885,55 @@ mod tests {
         assert!(!found.text.contains("user@example.com"));
     }
 
+    #[tokio::test]
+    async fn test_commit_hook_shares_the_capture_transaction() {
+        let (pipeline, db) = make_pipeline_with_db();
+
+        let frame = make_screen_frame("hook writes land with the capture");
+        let id = frame.id;
+        let mut hooked = 0;
+        pipeline
+            .ingest_screen_with(frame, &mut |conn| {
+                hooked += 1;
+                engram_storage::store_ingest_key(conn, "hooked", None, "stored", "Stored")
+                    .map(|_| ())
+            })
+            .await
+            .unwrap();
+        assert_eq!(hooked, 1);
+        assert!(CaptureRepository::new(Arc::clone(&db))
+            .find_by_id(id)
+            .unwrap()
+            .is_some());
+
+        // A failing hook rolls the capture back and unindexes it.
+        let frame = make_screen_frame("completely different rolled back content");
+        let id = frame.id;
+        let failed = pipeline
+            .ingest_screen_with(frame, &mut |_| {
+                Err(EngramError::Storage("hook failed".to_string()))
+            })
+            .await;
+        assert!(failed.is_err());
+        assert!(CaptureRepository::new(db).find_by_id(id).unwrap().is_none());
+        assert_eq!(pipeline.index().len(), 1);
+    }
+
+    #[test]
+    fn test_safe_text_matches_stored_text() {
+        let pipeline = make_pipeline_with_safety(SafetyConfig {
+            custom_deny_patterns: vec!["TOP SECRET".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(
+            pipeline.safe_text("plain notes").as_deref(),
+            Some("plain notes")
+        );
+        let redacted = pipeline.safe_text("mail user@example.com").unwrap();
+        assert!(redacted.contains("[EMAIL_REDACTED]"));
+        assert!(pipeline.safe_text("TOP SECRET plans").is_none());
+    }
+
     #[tokio::test]
     async fn test_dual_write_denied_not_in_sqlite() {
         let config = SafetyConfig {
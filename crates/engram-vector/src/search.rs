@@ -82,81 +82,127 @@ impl SearchEngine {
         let fetch_count = k * 3;
         let hits = self.index.search(&query_vec, fetch_count)?;
 
-        let mut results: Vec<SearchResult> = Vec::new();
-
-        for hit in hits {
-            // Apply metadata filters.
-            let meta = &hit.metadata;
-
-            if let Some(ref ct_filter) = filters.content_type {
-                if let Some(ct_val) = meta.get("content_type").and_then(|v| v.as_str()) {
-                    let ct_str = serde_json::to_string(ct_filter)
-                        .unwrap_or_default()
-                        .trim_matches('"')
-                        .to_string();
-                    if ct_val != ct_str {
-                        continue;
-                    }
+        Ok(filter_hits(hits, &filters, None, k))
+    }
+
+    /// Find entries similar to an already-indexed entry.
+    ///
+    /// Reuses the stored vector for `id` instead of re-embedding its text, and
+    /// excludes the entry itself from the results. Returns `None` when `id`
+    /// has no vector in the index.
+    pub fn find_similar(
+        &self,
+        id: Uuid,
+        filters: SearchFilters,
+        k: usize,
+    ) -> Result<Option<Vec<SearchResult>>, EngramError> {
+        let Some(vector) = self.index.get_embedding(id)? else {
+            return Ok(None);
+        };
+
+        // One extra candidate for the entry itself.
+        let fetch_count = (k + 1) * 3;
+        let hits = self.index.search(&vector, fetch_count)?;
+
+        Ok(Some(filter_hits(hits, &filters, Some(id), k)))
+    }
+
+    /// Embed `query` with the engine's embedding service.
+    ///
+    /// Lets callers search the index directly or score candidates found
+    /// elsewhere (e.g. by keyword search) against the same query vector.
+    pub async fn embed_query(&self, query: &str) -> Result<Vec<f32>, EngramError> {
+        self.embedder.embed_boxed(query).await
+    }
+
+    /// Get a reference to the underlying vector index.
+    pub fn index(&self) -> &VectorIndex {
+        &self.index
+    }
+}
+
+/// Apply metadata filters to raw index hits and convert them to results.
+///
+/// Stops after `k` results; `exclude` drops a single ID (e.g. the query entry).
+fn filter_hits(
+    hits: Vec<crate::index::SearchHit>,
+    filters: &SearchFilters,
+    exclude: Option<Uuid>,
+    k: usize,
+) -> Vec<SearchResult> {
+    let mut results: Vec<SearchResult> = Vec::new();
+
+    for hit in hits {
+        if Some(hit.id) == exclude {
+            continue;
+        }
+
+        // Apply metadata filters.
+        let meta = &hit.metadata;
+
+        if let Some(ref ct_filter) = filters.content_type {
+            if let Some(ct_val) = meta.get("content_type").and_then(|v| v.as_str()) {
+                let ct_str = serde_json::to_string(ct_filter)
+                    .unwrap_or_default()
+                    .trim_matches('"')
+                    .to_string();
+                if ct_val != ct_str {
+                    continue;
                 }
             }
+        }
 
-            if let Some(ref app_filter) = filters.app_name {
-                if let Some(app_val) = meta.get("app_name").and_then(|v| v.as_str()) {
-                    if app_val != app_filter {
-                        continue;
-                    }
+        if let Some(ref app_filter) = filters.app_name {
+            if let Some(app_val) = meta.get("app_name").and_then(|v| v.as_str()) {
+                if app_val != app_filter {
+                    continue;
                 }
             }
+        }
 
-            if let Some(ref start) = filters.start {
-                if let Some(ts_str) = meta.get("timestamp").and_then(|v| v.as_str()) {
-                    if let Ok(ts) = ts_str.parse::<DateTime<Utc>>() {
-                        if ts < *start {
-                            continue;
-                        }
+        if let Some(ref start) = filters.start {
+            if let Some(ts_str) = meta.get("timestamp").and_then(|v| v.as_str()) {
+                if let Ok(ts) = ts_str.parse::<DateTime<Utc>>() {
+                    if ts < *start {
+                        continue;
                     }
                 }
             }
+        }
 
-            if let Some(ref end) = filters.end {
-                if let Some(ts_str) = meta.get("timestamp").and_then(|v| v.as_str()) {
-                    if let Ok(ts) = ts_str.parse::<DateTime<Utc>>() {
-                        if ts > *end {
-                            continue;
-                        }
+        if let Some(ref end) = filters.end {
+            if let Some(ts_str) = meta.get("timestamp").and_then(|v| v.as_str()) {
+                if let Ok(ts) = ts_str.parse::<DateTime<Utc>>() {
+                    if ts > *end {
+                        continue;
                     }
                 }
             }
-
-            results.push(SearchResult {
-                id: hit.id,
-                score: hit.score,
-                content_type: meta
-                    .get("content_type")
-                    .and_then(|v| v.as_str())
-                    .map(String::from),
-                app_name: meta
-                    .get("app_name")
-                    .and_then(|v| v.as_str())
-                    .map(String::from),
-                timestamp: meta
-                    .get("timestamp")
-                    .and_then(|v| v.as_str())
-                    .map(String::from),
-            });
-
-            if results.len() >= k {
-                break;
-            }
         }
 
-        Ok(results)
+        results.push(SearchResult {
+            id: hit.id,
+            score: hit.score,
+            content_type: meta
+                .get("content_type")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            app_name: meta
+                .get("app_name")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            timestamp: meta
+                .get("timestamp")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        });
+
+        if results.len() >= k {
+            break;
+        }
     }
 
-    /// Get a reference to the underlying vector index.
-    pub fn index(&self) -> &VectorIndex {
-        &self.index
-    }
+    results
 }
 
 #[cfg(test)]
@@ -178,6 +224,13 @@ mod tests {
         assert!(results.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_embed_query_matches_embedder() {
+        let engine = make_engine();
+        let expected = MockEmbedding::new().embed("cloud costs").await.unwrap();
+        assert_eq!(engine.embed_query("cloud costs").await.unwrap(), expected);
+    }
+
     #[tokio::test]
     async fn test_hybrid_search_finds_results() {
         let engine = make_engine();
@@ -266,4 +319,55 @@ mod tests {
 
         assert!(results.len() <= 3);
     }
+
+    #[tokio::test]
+    async fn test_find_similar_excludes_self() {
+        let engine = make_engine();
+        let embedder = MockEmbedding::new();
+
+        let source = Uuid::new_v4();
+        let neighbour = Uuid::new_v4();
+        engine
+            .index()
+            .insert(
+                source,
+                embedder.embed("quarterly release notes").await.unwrap(),
+                serde_json::json!({"content_type": "screen"}),
+            )
+            .unwrap();
+        engine
+            .index()
+            .insert(
+                neighbour,
+                embedder.embed("release notes draft").await.unwrap(),
+                serde_json::json!({"content_type": "audio"}),
+            )
+            .unwrap();
+
+        let results = engine
+            .find_similar(source, SearchFilters::default(), 10)
+            .unwrap()
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, neighbour);
+
+        let screen_only = SearchFilters {
+            content_type: Some(ContentType::Screen),
+            ..Default::default()
+        };
+        let results = engine
+            .find_similar(source, screen_only, 10)
+            .unwrap()
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_find_similar_unknown_id() {
+        let engine = make_engine();
+        let results = engine
+            .find_similar(Uuid::new_v4(), SearchFilters::default(), 10)
+            .unwrap();
+        assert!(results.is_none());
+    }
 }
@@ -88,6 +88,31 @@ pub trait TranscriptionService: Send + Sync {
     ) -> impl Future<Output = Result<TranscriptionResult, EngramError>> + Send;
 }
 
+/// Object-safe version of [`TranscriptionService`] for dynamic dispatch.
+///
+/// `TranscriptionService::transcribe` returns `impl Future`, so it cannot be
+/// used as `dyn TranscriptionService`. This trait boxes the future instead;
+/// every `TranscriptionService` implements it through a blanket impl.
+pub trait DynTranscriptionService: Send + Sync {
+    /// Transcribe audio data into text (boxed future).
+    fn transcribe_boxed<'a>(
+        &'a self,
+        audio_data: &'a [f32],
+        sample_rate: u32,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TranscriptionResult, EngramError>> + Send + 'a>>;
+}
+
+impl<T: TranscriptionService> DynTranscriptionService for T {
+    fn transcribe_boxed<'a>(
+        &'a self,
+        audio_data: &'a [f32],
+        sample_rate: u32,
+    ) -> std::pin::Pin<Box<dyn Future<Output = Result<TranscriptionResult, EngramError>> + Send + 'a>>
+    {
+        Box::pin(self.transcribe(audio_data, sample_rate))
+    }
+}
+
 // =============================================================================
 // Mock implementation
 // =============================================================================
@@ -202,6 +227,14 @@ mod tests {
         assert!((result.segments[0].end - 2.0).abs() < 0.01);
     }
 
+    #[tokio::test]
+    async fn test_dyn_transcription_service() {
+        let service: Box<dyn DynTranscriptionService> = Box::new(MockTranscriptionService::new());
+        let audio = vec![0.0f32; 16000];
+        let result = service.transcribe_boxed(&audio, 16000).await.unwrap();
+        assert_eq!(result.text, "[mock transcription]");
+    }
+
     #[test]
     fn test_whisper_config_default() {
         let config = WhisperConfig::default();